@@ -96,6 +96,16 @@ image_segmentation_init() {
   popd
 }
 
+interactive_segmentation_init() {
+  interactive_segmentation_dir="${model_path}/interactive_segmentation"
+  mkdir -p "${interactive_segmentation_dir}"
+  pushd "${interactive_segmentation_dir}"
+
+  curl -sLO "https://storage.googleapis.com/mediapipe-models/interactive_segmenter/magic_touch/float32/latest/magic_touch.tflite"
+
+  popd
+}
+
 image_embedding_init() {
   image_embedding_dir="${model_path}/image_embedding"
   mkdir -p "${image_embedding_dir}"
@@ -146,6 +156,48 @@ face_landmark_init() {
     popd
 }
 
+face_stylization_init() {
+    face_stylization_dir="${model_path}/face_stylization"
+    mkdir -p "${face_stylization_dir}"
+    pushd "${face_stylization_dir}"
+
+    curl -sLO "https://storage.googleapis.com/mediapipe-models/face_stylizer/blaze_face_stylizer/float32/latest/face_stylizer_color_sketch.task"
+    # the stylization model is also used as a generic image to image model
+    unzip -o face_stylizer_color_sketch.task face_stylizer.tflite
+
+    popd
+}
+
+pose_landmark_init() {
+    pose_landmark_dir="${model_path}/pose_landmark"
+    mkdir -p "${pose_landmark_dir}"
+    pushd "${pose_landmark_dir}"
+
+    model_urls=("https://storage.googleapis.com/mediapipe-models/pose_landmarker/pose_landmarker_lite/float16/latest/pose_landmarker_lite.task"
+    )
+
+    for url in "${model_urls[@]}"; do
+      curl -sLO "${url}"
+    done
+
+    popd
+}
+
+holistic_landmark_init() {
+    holistic_landmark_dir="${model_path}/holistic_landmark"
+    mkdir -p "${holistic_landmark_dir}"
+    pushd "${holistic_landmark_dir}"
+
+    model_urls=("https://storage.googleapis.com/mediapipe-models/holistic_landmarker/holistic_landmarker/float16/latest/holistic_landmarker.task"
+    )
+
+    for url in "${model_urls[@]}"; do
+      curl -sLO "${url}"
+    done
+
+    popd
+}
+
 
 audio_classification_init() {
   audio_classification_dir="${model_path}/audio_classification"
@@ -162,6 +214,16 @@ audio_classification_init() {
   popd
 }
 
+audio_embedding_init() {
+  audio_embedding_dir="${model_path}/audio_embedding"
+  mkdir -p "${audio_embedding_dir}"
+  pushd "${audio_embedding_dir}"
+
+  curl -sLO "https://storage.googleapis.com/mediapipe-models/audio_embedder/yamnet_embedder/float32/latest/yamnet_embedder.tflite"
+
+  popd
+}
+
 text_classification_init() {
   text_classification_dir="${model_path}/text_classification"
   mkdir -p "${text_classification_dir}"
@@ -195,14 +257,56 @@ text_embedding_init() {
     popd
 }
 
+language_detection_init() {
+    language_detection_dir="${model_path}/language_detection"
+    mkdir -p "${language_detection_dir}"
+    pushd "${language_detection_dir}"
+
+    curl -sLO "https://storage.googleapis.com/mediapipe-models/language_detector/language_detector/float32/latest/language_detector.tflite"
+
+    popd
+}
+
+text_question_answering_init() {
+    text_question_answering_dir="${model_path}/text_question_answering"
+    mkdir -p "${text_question_answering_dir}"
+    pushd "${text_question_answering_dir}"
+
+    curl -sLO "https://storage.googleapis.com/mediapipe-assets/mobilebert_with_metadata.tflite"
+
+    popd
+}
+
+token_classification_init() {
+    token_classification_dir="${model_path}/token_classification"
+    mkdir -p "${token_classification_dir}"
+    pushd "${token_classification_dir}"
+
+    # there is no released MediaPipe NER model, so set `NER_MODEL_URL` to a BERT NER model
+    # with TfLite metadata, which has the vocab file and the label file.
+    if [ -n "${NER_MODEL_URL:-}" ]; then
+      curl -sL "${NER_MODEL_URL}" -o "bert_ner.tflite"
+    fi
+
+    popd
+}
+
 object_detection_init
 image_classification_init
 gesture_recognition_init
 hand_landmark_detection_init
 image_segmentation_init
+interactive_segmentation_init
 image_embedding_init
 face_detection_init
 face_landmark_init
+face_stylization_init
+pose_landmark_init
+holistic_landmark_init
 audio_classification_init
+audio_embedding_init
 text_classification_init
 text_embedding_init
+language_detection_init
+text_question_answering_init
+token_classification_init
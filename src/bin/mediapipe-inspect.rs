@@ -0,0 +1,390 @@
+//! Print a human-readable or JSON report for a model file, such as `.tflite`, `.onnx` and `.task` files.
+//!
+//! Usage: ```mediapipe-inspect [--json] <model_path>```
+
+use mediapipe_rs::model::{ModelInfo, TensorInfo};
+use mediapipe_rs::preprocess::ToTensorInfo;
+
+const USAGE: &str = "Usage: mediapipe-inspect [--json] <model_path>";
+
+/// The report tree, which can be rendered as JSON or human-readable text.
+enum Value {
+    Null,
+    // formatted number
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(&'static str, Value)>),
+}
+
+impl Value {
+    #[inline(always)]
+    fn str(s: impl ToString) -> Self {
+        Self::String(s.to_string())
+    }
+
+    #[inline(always)]
+    fn num(n: impl ToString) -> Self {
+        Self::Number(n.to_string())
+    }
+
+    #[inline(always)]
+    fn array<T>(v: impl IntoIterator<Item = T>, f: impl Fn(T) -> Value) -> Self {
+        Self::Array(v.into_iter().map(f).collect())
+    }
+
+    #[inline(always)]
+    fn is_scalar(&self) -> bool {
+        !matches!(self, Self::Array(_) | Self::Object(_))
+    }
+
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Value::Null => out.push_str("null"),
+            Value::Number(n) => {
+                if n.parse::<f64>().map(|f| f.is_finite()).unwrap_or(false) {
+                    out.push_str(n);
+                } else {
+                    out.push_str("null");
+                }
+            }
+            Value::String(s) => {
+                out.push('"');
+                for c in s.chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\r' => out.push_str("\\r"),
+                        '\t' => out.push_str("\\t"),
+                        c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+            }
+            Value::Array(a) => {
+                out.push('[');
+                for (i, v) in a.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    v.write_json(out);
+                }
+                out.push(']');
+            }
+            Value::Object(o) => {
+                out.push('{');
+                for (i, (k, v)) in o.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    Value::str(k).write_json(out);
+                    out.push(':');
+                    v.write_json(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    fn write_scalar_text(&self, out: &mut String) {
+        match self {
+            Value::Null => out.push('-'),
+            Value::Number(s) | Value::String(s) => out.push_str(s),
+            _ => unreachable!(),
+        }
+    }
+
+    fn write_text(&self, indent: usize, out: &mut String) {
+        match self {
+            Value::Array(a) if a.iter().all(|v| v.is_scalar()) => {
+                out.push('[');
+                for (i, v) in a.iter().enumerate() {
+                    if i != 0 {
+                        out.push_str(", ");
+                    }
+                    v.write_scalar_text(out);
+                }
+                out.push_str("]\n");
+            }
+            Value::Array(a) => {
+                out.push('\n');
+                for v in a {
+                    out.push_str(&" ".repeat(indent));
+                    out.push_str("- ");
+                    match v {
+                        Value::Object(o) => Self::write_object_text(o, indent + 2, true, out),
+                        v => v.write_text(indent + 2, out),
+                    }
+                }
+            }
+            Value::Object(o) => {
+                out.push('\n');
+                Self::write_object_text(o, indent, false, out);
+            }
+            v => {
+                v.write_scalar_text(out);
+                out.push('\n');
+            }
+        }
+    }
+
+    fn write_object_text(
+        o: &[(&'static str, Value)],
+        indent: usize,
+        skip_first_indent: bool,
+        out: &mut String,
+    ) {
+        for (i, (k, v)) in o.iter().enumerate() {
+            if i != 0 || !skip_first_indent {
+                out.push_str(&" ".repeat(indent));
+            }
+            out.push_str(k);
+            match v {
+                Value::Array(a) if a.iter().all(|v| v.is_scalar()) => out.push_str(": "),
+                Value::Array(_) | Value::Object(_) => out.push(':'),
+                _ => out.push_str(": "),
+            }
+            v.write_text(indent + 2, out);
+        }
+    }
+}
+
+fn tensor_report(t: TensorInfo, process_units: &[&'static str]) -> Vec<(&'static str, Value)> {
+    vec![
+        ("name", Value::str(t.name)),
+        ("type", Value::str(format!("{:?}", t.tensor_type))),
+        ("shape", Value::array(t.shape, Value::num)),
+        (
+            "quantization",
+            match t.quantization_parameters {
+                Some(q) => Value::Object(vec![
+                    ("scale", Value::num(q.scale)),
+                    ("zero_point", Value::num(q.zero_point)),
+                ]),
+                None => Value::Null,
+            },
+        ),
+        ("process_units", Value::array(process_units, Value::str)),
+    ]
+}
+
+fn to_tensor_info_report(info: Option<&ToTensorInfo>) -> Value {
+    match info {
+        #[cfg(feature = "vision")]
+        Some(ToTensorInfo::Image(i)) => Value::Object(vec![
+            ("kind", Value::str("image")),
+            ("layout", Value::str(format!("{:?}", i.image_data_layout))),
+            ("color_space", Value::str(format!("{:?}", i.color_space))),
+            ("width", Value::num(i.tensor_shape.width)),
+            ("height", Value::num(i.tensor_shape.height)),
+            ("channels", Value::num(i.tensor_shape.channels)),
+            (
+                "normalization",
+                Value::Object(vec![
+                    ("mean", Value::array(&i.normalization_options.0, Value::num)),
+                    ("std", Value::array(&i.normalization_options.1, Value::num)),
+                ]),
+            ),
+            ("stats_min", Value::array(&i.stats_min, Value::num)),
+            ("stats_max", Value::array(&i.stats_max, Value::num)),
+        ]),
+        #[cfg(feature = "audio")]
+        Some(ToTensorInfo::Audio(a)) => Value::Object(vec![
+            ("kind", Value::str("audio")),
+            ("sample_rate", Value::num(a.sample_rate)),
+            ("channels", Value::num(a.num_channels)),
+            ("samples", Value::num(a.num_samples)),
+        ]),
+        #[cfg(feature = "text")]
+        Some(ToTensorInfo::Text(t)) => {
+            use mediapipe_rs::preprocess::text::TextToTensorInfo;
+            let (tokenizer, max_seq_len, vocab_size) = match t {
+                TextToTensorInfo::BertModel {
+                    max_seq_len,
+                    token_index_map,
+                    ..
+                } => ("bert", Some(*max_seq_len), Some(token_index_map.len())),
+                TextToTensorInfo::RegexModel {
+                    max_seq_len,
+                    token_index_map,
+                    ..
+                } => ("regex", Some(*max_seq_len), Some(token_index_map.len())),
+                TextToTensorInfo::SentencePieceModel {
+                    max_seq_len,
+                    tokenizer,
+                    ..
+                } => (
+                    "sentence_piece",
+                    Some(*max_seq_len),
+                    Some(tokenizer.vocab_size()),
+                ),
+                TextToTensorInfo::StringModel => ("string", None, None),
+                TextToTensorInfo::UseModel | TextToTensorInfo::UseEncoderModel { .. } => {
+                    ("universal_sentence_encoder", None, None)
+                }
+            };
+            Value::Object(vec![
+                ("kind", Value::str("text")),
+                ("tokenizer", Value::str(tokenizer)),
+                ("max_seq_len", max_seq_len.map_or(Value::Null, Value::num)),
+                ("vocab_size", vocab_size.map_or(Value::Null, Value::num)),
+            ])
+        }
+        _ => Value::Null,
+    }
+}
+
+fn model_report(info: &ModelInfo) -> Value {
+    if info.is_bundle() {
+        return Value::Object(vec![
+            ("kind", Value::str("bundle")),
+            ("files", Value::array(info.associated_files(), Value::str)),
+            (
+                "models",
+                Value::array(info.sub_models(), |(name, m)| {
+                    Value::Object(vec![("file", Value::str(name)), ("model", model_report(m))])
+                }),
+            ),
+        ]);
+    }
+
+    let inputs = (0..info.input_count()).filter_map(|i| {
+        let mut r = tensor_report(info.input(i)?, info.input_process_units(i));
+        r.push(("preprocess", to_tensor_info_report(info.to_tensor_info(i))));
+        Some(Value::Object(r))
+    });
+    let outputs = (0..info.output_count()).filter_map(|i| {
+        let mut r = tensor_report(info.output(i)?, info.output_process_units(i));
+        let labels = match info.output_label_files(i) {
+            Some((file, locales)) => Value::Object(vec![
+                ("file", Value::str(file)),
+                (
+                    "count",
+                    info.output_labels(i, None)
+                        .map_or(Value::Null, |l| Value::num(l.len())),
+                ),
+                (
+                    "locales",
+                    Value::array(locales, |(locale, file)| {
+                        Value::Object(vec![
+                            ("locale", Value::str(locale)),
+                            ("file", Value::str(file)),
+                        ])
+                    }),
+                ),
+            ]),
+            None => Value::Null,
+        };
+        r.push(("labels", labels));
+        #[cfg(feature = "vision")]
+        r.push((
+            "bounding_box",
+            match info.output_bounding_box_properties(i) {
+                Some(b) => Value::array(b, Value::num),
+                None => Value::Null,
+            },
+        ));
+        Some(Value::Object(r))
+    });
+
+    Value::Object(vec![
+        ("kind", Value::str("model")),
+        (
+            "backend",
+            Value::str(format!("{:?}", info.model_backend().unwrap())),
+        ),
+        ("inputs", Value::Array(inputs.collect())),
+        ("outputs", Value::Array(outputs.collect())),
+        (
+            "output_activation",
+            Value::str(format!("{:?}", info.output_activation())),
+        ),
+        (
+            "associated_files",
+            Value::array(info.associated_files(), Value::str),
+        ),
+        ("vocab_files", Value::array(info.vocab_files(), Value::str)),
+    ])
+}
+
+fn main() {
+    let mut json = false;
+    let mut model_path = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => model_path = Some(arg),
+        }
+    }
+    let model_path = match model_path {
+        Some(p) => p,
+        None => {
+            eprintln!("{}", USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    let info = match ModelInfo::from_file(&model_path) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("Cannot parse model `{}`: {}", model_path, e);
+            std::process::exit(1);
+        }
+    };
+
+    let report = Value::Object(vec![
+        ("path", Value::str(&model_path)),
+        ("model", model_report(&info)),
+    ]);
+    let mut out = String::new();
+    if json {
+        report.write_json(&mut out);
+        out.push('\n');
+    } else if let Value::Object(o) = &report {
+        Value::write_object_text(o, 0, false, &mut out);
+    }
+    print!("{}", out);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn json(v: &Value) -> String {
+        let mut out = String::new();
+        v.write_json(&mut out);
+        out
+    }
+
+    #[test]
+    fn test_json_string_escape() {
+        assert_eq!(json(&Value::str("a\"b\\c")), r#""a\"b\\c""#);
+        assert_eq!(json(&Value::str("\n\r\t")), r#""\n\r\t""#);
+        assert_eq!(
+            json(&Value::str("\u{0}\u{1}\u{1f} \u{7f}")),
+            "\"\\u0000\\u0001\\u001f \u{7f}\""
+        );
+        assert_eq!(json(&Value::str("标签/é")), "\"标签/é\"");
+    }
+
+    #[test]
+    fn test_json_value() {
+        let v = Value::Object(vec![
+            ("name", Value::str("in\"put")),
+            ("shape", Value::array([1, 224], Value::num)),
+            ("scale", Value::num(f32::NAN)),
+            ("zero_point", Value::Null),
+            ("units", Value::Array(vec![])),
+        ]);
+        assert_eq!(
+            json(&v),
+            r#"{"name":"in\"put","shape":[1,224],"scale":null,"zero_point":null,"units":[]}"#
+        );
+    }
+}
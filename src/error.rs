@@ -21,6 +21,9 @@ pub enum Error {
     #[error("Model Inconsistent Error: {0}")]
     ModelInconsistentError(String),
 
+    #[error("Inference Backend Error: {0}")]
+    BackendError(String),
+
     #[error("FlatBuffer Error: {0}")]
     FlatBufferError(#[from] flatbuffers::InvalidFlatbuffer),
 
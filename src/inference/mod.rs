@@ -0,0 +1,113 @@
+mod record_replay;
+#[cfg(feature = "native-tflite")]
+mod tflite;
+mod wasi_nn_backend;
+
+pub use record_replay::{RecordBackend, ReplayBackend};
+#[cfg(feature = "native-tflite")]
+pub use tflite::TfLiteNativeBackend;
+pub use wasi_nn::{GraphEncoding, TensorType};
+pub use wasi_nn_backend::WasiNNBackend;
+
+use crate::{Device, Error};
+
+/// Abstraction for inference backends.
+///
+/// A backend loads model bytes into an [`InferenceGraph`], which can create many [`ExecutionContext`].
+/// The default backend is [`WasiNNBackend`], and users can use the builder's ```backend``` option to choose another one.
+/// If the feature ```native-tflite``` is enabled and the target is not ```wasm32```, the default backend is
+/// ```TfLiteNativeBackend```.
+pub trait InferenceBackend: Send + Sync {
+    /// Load the model buffers to a new graph.
+    fn build_graph(
+        &self,
+        encoding: GraphEncoding,
+        device: Device,
+        model_buffers: &[&[u8]],
+    ) -> Result<Box<dyn InferenceGraph>, Error>;
+}
+
+/// A loaded model, which is used to create execution contexts.
+pub trait InferenceGraph {
+    /// Create a new execution context for this graph.
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error>;
+}
+
+/// A running context for a graph, which stores the input and output tensors.
+pub trait ExecutionContext {
+    /// Set the input tensor data, ```data``` is the raw bytes of tensor.
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error>;
+
+    /// Do inference.
+    fn compute(&mut self) -> Result<(), Error>;
+
+    /// Copy the output tensor data to ```out_buffer```, return the bytes size of output tensor.
+    fn get_output(&mut self, index: usize, out_buffer: &mut [u8]) -> Result<usize, Error>;
+}
+
+/// Graph used by tasks, wrap the backend graph.
+pub(crate) struct Graph {
+    inner: Box<dyn InferenceGraph>,
+}
+
+impl Graph {
+    #[inline(always)]
+    pub(crate) fn new(inner: Box<dyn InferenceGraph>) -> Self {
+        Self { inner }
+    }
+
+    #[inline(always)]
+    pub(crate) fn init_execution_context(&self) -> Result<GraphExecutionContext<'_>, Error> {
+        Ok(GraphExecutionContext {
+            inner: self.inner.init_execution_context()?,
+        })
+    }
+}
+
+/// Execution context used by task sessions, wrap the backend context and accept typed buffers.
+pub(crate) struct GraphExecutionContext<'a> {
+    inner: Box<dyn ExecutionContext + 'a>,
+}
+
+impl<'a> GraphExecutionContext<'a> {
+    #[inline(always)]
+    pub(crate) fn set_input<T: Sized>(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: impl AsRef<[T]>,
+    ) -> Result<(), Error> {
+        let data = data.as_ref();
+        let bytes = unsafe {
+            core::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
+        };
+        self.inner.set_input(index, tensor_type, dimensions, bytes)
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute(&mut self) -> Result<(), Error> {
+        self.inner.compute()
+    }
+
+    #[inline(always)]
+    pub(crate) fn get_output<T: Sized>(
+        &mut self,
+        index: usize,
+        out_buffer: &mut [T],
+    ) -> Result<usize, Error> {
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(
+                out_buffer.as_mut_ptr() as *mut u8,
+                std::mem::size_of_val(out_buffer),
+            )
+        };
+        self.inner.get_output(index, bytes)
+    }
+}
@@ -0,0 +1,390 @@
+//! Record and replay backends, used to test the pre-process and post-process without a real inference engine.
+//!
+//! The recorded directory layout is:
+//! ```text
+//! <dir>/graph_<g>/context_<c>/run_<r>/input_<i>.bin    // input tensor bytes
+//! <dir>/graph_<g>/context_<c>/run_<r>/input_<i>.txt    // input tensor type and dimensions, such as `F32 1,224,224,3`
+//! <dir>/graph_<g>/context_<c>/run_<r>/output_<i>.bin   // output tensor bytes
+//! ```
+//! ```g```, ```c```, ```r``` are the orders of graph building, execution context creation and computing.
+
+use super::{ExecutionContext, GraphEncoding, InferenceBackend, InferenceGraph, TensorType};
+use crate::{Device, Error};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[inline(always)]
+fn run_dir(context_dir: &Path, run: usize) -> PathBuf {
+    context_dir.join(format!("run_{}", run))
+}
+
+/// A backend wraps another backend, and dumps every input and output tensor to a directory.
+pub struct RecordBackend<B: InferenceBackend> {
+    inner: B,
+    dir: PathBuf,
+    graph_count: AtomicUsize,
+}
+
+impl<B: InferenceBackend> RecordBackend<B> {
+    /// Create a new record backend, the tensors will be saved to ```dir```.
+    #[inline(always)]
+    pub fn new(inner: B, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            dir: dir.into(),
+            graph_count: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<B: InferenceBackend> InferenceBackend for RecordBackend<B> {
+    fn build_graph(
+        &self,
+        encoding: GraphEncoding,
+        device: Device,
+        model_buffers: &[&[u8]],
+    ) -> Result<Box<dyn InferenceGraph>, Error> {
+        let inner = self.inner.build_graph(encoding, device, model_buffers)?;
+        let id = self.graph_count.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(RecordGraph {
+            inner,
+            dir: self.dir.join(format!("graph_{}", id)),
+            context_count: AtomicUsize::new(0),
+        }))
+    }
+}
+
+struct RecordGraph {
+    inner: Box<dyn InferenceGraph>,
+    dir: PathBuf,
+    context_count: AtomicUsize,
+}
+
+impl InferenceGraph for RecordGraph {
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+        let id = self.context_count.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(RecordExecutionContext {
+            inner: self.inner.init_execution_context()?,
+            dir: self.dir.join(format!("context_{}", id)),
+            run: 0,
+        }))
+    }
+}
+
+struct RecordExecutionContext<'a> {
+    inner: Box<dyn ExecutionContext + 'a>,
+    dir: PathBuf,
+    run: usize,
+}
+
+impl<'a> ExecutionContext for RecordExecutionContext<'a> {
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error> {
+        let dir = run_dir(&self.dir, self.run);
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join(format!("input_{}.bin", index)), data)?;
+        let dims: Vec<String> = dimensions.iter().map(|d| d.to_string()).collect();
+        std::fs::write(
+            dir.join(format!("input_{}.txt", index)),
+            format!("{:?} {}", tensor_type, dims.join(",")),
+        )?;
+        self.inner.set_input(index, tensor_type, dimensions, data)
+    }
+
+    fn compute(&mut self) -> Result<(), Error> {
+        self.inner.compute()?;
+        self.run += 1;
+        Ok(())
+    }
+
+    fn get_output(&mut self, index: usize, out_buffer: &mut [u8]) -> Result<usize, Error> {
+        let size = self.inner.get_output(index, out_buffer)?;
+        let dir = run_dir(&self.dir, self.run.saturating_sub(1));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(
+            dir.join(format!("output_{}.bin", index)),
+            &out_buffer[..size.min(out_buffer.len())],
+        )?;
+        Ok(size)
+    }
+}
+
+/// A backend which serves the outputs recorded by [`RecordBackend`], it does not need any model or inference engine.
+pub struct ReplayBackend {
+    dir: PathBuf,
+    check_inputs: bool,
+    graph_count: AtomicUsize,
+}
+
+impl ReplayBackend {
+    /// Create a new replay backend, the tensors are read from ```dir```.
+    #[inline(always)]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            check_inputs: false,
+            graph_count: AtomicUsize::new(0),
+        }
+    }
+
+    /// If set to true, the input tensors must have the same types, dimensions and bytes as the recorded inputs.
+    /// (Default is false)
+    /// It can be used to check the pre-process results.
+    #[inline(always)]
+    pub fn check_inputs(mut self, check_inputs: bool) -> Self {
+        self.check_inputs = check_inputs;
+        self
+    }
+}
+
+impl InferenceBackend for ReplayBackend {
+    fn build_graph(
+        &self,
+        _encoding: GraphEncoding,
+        _device: Device,
+        _model_buffers: &[&[u8]],
+    ) -> Result<Box<dyn InferenceGraph>, Error> {
+        let id = self.graph_count.fetch_add(1, Ordering::SeqCst);
+        let dir = self.dir.join(format!("graph_{}", id));
+        if !dir.is_dir() {
+            return Err(Error::BackendError(format!(
+                "Cannot find recorded graph directory `{}`",
+                dir.display()
+            )));
+        }
+        Ok(Box::new(ReplayGraph {
+            dir,
+            check_inputs: self.check_inputs,
+            context_count: AtomicUsize::new(0),
+        }))
+    }
+}
+
+struct ReplayGraph {
+    dir: PathBuf,
+    check_inputs: bool,
+    context_count: AtomicUsize,
+}
+
+impl InferenceGraph for ReplayGraph {
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+        let id = self.context_count.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(ReplayExecutionContext {
+            dir: self.dir.join(format!("context_{}", id)),
+            check_inputs: self.check_inputs,
+            run: 0,
+        }))
+    }
+}
+
+struct ReplayExecutionContext {
+    dir: PathBuf,
+    check_inputs: bool,
+    run: usize,
+}
+
+impl ExecutionContext for ReplayExecutionContext {
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error> {
+        if self.check_inputs {
+            let dir = run_dir(&self.dir, self.run);
+            let info_path = dir.join(format!("input_{}.txt", index));
+            let info = std::fs::read_to_string(&info_path)?;
+            let (recorded_type, recorded_dims) = info.split_once(' ').ok_or_else(|| {
+                Error::BackendError(format!(
+                    "Invalid recorded tensor information `{}` in `{}`",
+                    info,
+                    info_path.display()
+                ))
+            })?;
+
+            let type_str = format!("{:?}", tensor_type);
+            if type_str != recorded_type {
+                return Err(Error::BackendError(format!(
+                    "Input tensor `{}` type is `{}`, but the recorded type is `{}` in `{}`",
+                    index,
+                    type_str,
+                    recorded_type,
+                    info_path.display()
+                )));
+            }
+            let dims: Vec<String> = dimensions.iter().map(|d| d.to_string()).collect();
+            let dims = dims.join(",");
+            if dims != recorded_dims.trim_end() {
+                return Err(Error::BackendError(format!(
+                    "Input tensor `{}` dimensions are `[{}]`, but the recorded dimensions are `[{}]` in `{}`",
+                    index,
+                    dims,
+                    recorded_dims,
+                    info_path.display()
+                )));
+            }
+
+            let data_path = dir.join(format!("input_{}.bin", index));
+            let recorded = std::fs::read(&data_path)?;
+            if recorded.len() != data.len() {
+                return Err(Error::BackendError(format!(
+                    "Input tensor `{}` has `{}` bytes, but the recorded tensor has `{}` bytes in `{}`",
+                    index,
+                    data.len(),
+                    recorded.len(),
+                    data_path.display()
+                )));
+            }
+            if let Some(offset) = recorded.iter().zip(data).position(|(a, b)| a != b) {
+                return Err(Error::BackendError(format!(
+                    "Input tensor `{}` is different from the recorded tensor at byte `{}` in `{}`",
+                    index,
+                    offset,
+                    data_path.display()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn compute(&mut self) -> Result<(), Error> {
+        let dir = run_dir(&self.dir, self.run);
+        if !dir.is_dir() {
+            return Err(Error::BackendError(format!(
+                "Cannot find recorded run directory `{}`",
+                dir.display()
+            )));
+        }
+        self.run += 1;
+        Ok(())
+    }
+
+    fn get_output(&mut self, index: usize, out_buffer: &mut [u8]) -> Result<usize, Error> {
+        let path =
+            run_dir(&self.dir, self.run.saturating_sub(1)).join(format!("output_{}.bin", index));
+        let data = std::fs::read(&path)?;
+        if data.len() > out_buffer.len() {
+            return Err(Error::BackendError(format!(
+                "Output buffer is too small, expect `{}` bytes, but got `{}`",
+                data.len(),
+                out_buffer.len()
+            )));
+        }
+        out_buffer[..data.len()].copy_from_slice(&data);
+        Ok(data.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // A backend which outputs the sum of inputs bytes.
+    struct SumBackend;
+
+    struct SumGraph;
+
+    struct SumExecutionContext(Vec<u8>, u8);
+
+    impl InferenceBackend for SumBackend {
+        fn build_graph(
+            &self,
+            _: GraphEncoding,
+            _: Device,
+            _: &[&[u8]],
+        ) -> Result<Box<dyn InferenceGraph>, Error> {
+            Ok(Box::new(SumGraph))
+        }
+    }
+
+    impl InferenceGraph for SumGraph {
+        fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+            Ok(Box::new(SumExecutionContext(Vec::new(), 0)))
+        }
+    }
+
+    impl ExecutionContext for SumExecutionContext {
+        fn set_input(
+            &mut self,
+            _: usize,
+            _: TensorType,
+            _: &[usize],
+            d: &[u8],
+        ) -> Result<(), Error> {
+            self.0 = d.to_vec();
+            Ok(())
+        }
+
+        fn compute(&mut self) -> Result<(), Error> {
+            self.1 = self.0.iter().fold(0u8, |a, b| a.wrapping_add(*b));
+            Ok(())
+        }
+
+        fn get_output(&mut self, _: usize, out_buffer: &mut [u8]) -> Result<usize, Error> {
+            out_buffer[0] = self.1;
+            Ok(1)
+        }
+    }
+
+    fn run(backend: &dyn InferenceBackend, inputs: &[&[u8]]) -> Result<Vec<u8>, Error> {
+        let graph = backend.build_graph(GraphEncoding::TensorflowLite, Device::CPU, &[&[]])?;
+        let mut ctx = graph.init_execution_context()?;
+        let mut res = Vec::new();
+        for input in inputs {
+            ctx.set_input(0, TensorType::U8, &[input.len()], input)?;
+            ctx.compute()?;
+            let mut out = [0u8; 4];
+            let size = ctx.get_output(0, &mut out)?;
+            res.extend_from_slice(&out[..size]);
+        }
+        Ok(res)
+    }
+
+    #[test]
+    fn test_record_replay() {
+        let dir = std::env::temp_dir().join(format!("mediapipe-rs-record-{}", std::process::id()));
+        let inputs: [&[u8]; 2] = [&[1, 2, 3], &[4, 5]];
+
+        let recorded = run(&RecordBackend::new(SumBackend, &dir), &inputs).unwrap();
+        assert_eq!(recorded, vec![6, 9]);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("graph_0/context_0/run_1/input_0.txt")).unwrap(),
+            "U8 2"
+        );
+
+        let replayed = run(&ReplayBackend::new(&dir).check_inputs(true), &inputs).unwrap();
+        assert_eq!(replayed, recorded);
+
+        // inputs are different from the recorded
+        assert!(run(&ReplayBackend::new(&dir).check_inputs(true), &[&[1, 2]]).is_err());
+        assert_eq!(run(&ReplayBackend::new(&dir), &[&[1, 2]]).unwrap(), vec![6]);
+        let replay = ReplayBackend::new(&dir).check_inputs(true);
+        let graph = replay
+            .build_graph(GraphEncoding::TensorflowLite, Device::CPU, &[&[]])
+            .unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+        let err = ctx
+            .set_input(0, TensorType::I32, &[3], &[1, 2, 3])
+            .unwrap_err();
+        assert!(err.to_string().contains("type is `I32`"));
+        let err = ctx
+            .set_input(0, TensorType::U8, &[1, 3], &[1, 2, 3])
+            .unwrap_err();
+        assert!(err.to_string().contains("dimensions are `[1,3]`"));
+        let err = ctx
+            .set_input(0, TensorType::U8, &[3], &[1, 0, 3])
+            .unwrap_err();
+        assert!(err.to_string().contains("at byte `1`"));
+        ctx.set_input(0, TensorType::U8, &[3], &[1, 2, 3]).unwrap();
+        // no more recorded runs
+        assert!(run(&ReplayBackend::new(&dir), &[&[0], &[0], &[0]]).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
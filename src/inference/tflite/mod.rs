@@ -0,0 +1,920 @@
+mod ops;
+
+use super::{ExecutionContext, GraphEncoding, InferenceBackend, InferenceGraph, TensorType};
+use crate::model::tflite_schema as schema;
+use crate::{Device, Error};
+use ops::{Operator, TensorRef};
+
+/// A pure Rust TensorFlow Lite CPU backend, which can run the tasks outside WasmEdge.
+///
+/// All the tensors are computed as ```f32```, and the quantized tensors are dequantized when loading model
+/// and quantized again when getting the outputs, so the results of quantized models may have a little difference
+/// from the TensorFlow Lite runtime.
+/// The integer tensors must be in the range that ```f32``` can represent exactly (```[-2^24, 2^24]```),
+/// otherwise an error is returned instead of losing the precision.
+/// The string tensors are passed as the serialized TfLite string buffer with ```U8``` type.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct TfLiteNativeBackend;
+
+impl InferenceBackend for TfLiteNativeBackend {
+    fn build_graph(
+        &self,
+        encoding: GraphEncoding,
+        device: Device,
+        model_buffers: &[&[u8]],
+    ) -> Result<Box<dyn InferenceGraph>, Error> {
+        if encoding != GraphEncoding::TensorflowLite && encoding != GraphEncoding::Autodetect {
+            return Err(Error::BackendError(format!(
+                "Native TfLite backend cannot load `{:?}` models",
+                encoding
+            )));
+        }
+        if device != Device::CPU {
+            return Err(Error::BackendError(format!(
+                "Native TfLite backend only supports CPU device, but got `{:?}`",
+                device
+            )));
+        }
+        if model_buffers.len() != 1 {
+            return Err(Error::BackendError(format!(
+                "Native TfLite backend expect `1` model buffer, but got `{}`",
+                model_buffers.len()
+            )));
+        }
+        Ok(Box::new(TfLiteGraph::new(model_buffers[0])?))
+    }
+}
+
+/// The max absolute integer value which can be represented exactly by ```f32```.
+const MAX_EXACT_INT: i64 = 1 << 24;
+
+#[inline(always)]
+fn int_to_f32(v: i64) -> Option<f32> {
+    if v.unsigned_abs() <= MAX_EXACT_INT as u64 {
+        Some(v as f32)
+    } else {
+        None
+    }
+}
+
+/// Quantization parameters for a tensor, the ```scale``` and ```zero_point``` have the same length.
+#[derive(Debug, Clone)]
+struct Quantization {
+    scale: Vec<f32>,
+    zero_point: Vec<i64>,
+    axis: usize,
+}
+
+impl Quantization {
+    #[inline(always)]
+    fn channel(&self, index: usize, shape: &[usize]) -> usize {
+        if self.scale.len() == 1 {
+            return 0;
+        }
+        let inner = shape[self.axis + 1..].iter().product::<usize>();
+        (index / inner) % shape[self.axis]
+    }
+}
+
+struct Tensor {
+    shape: Vec<usize>,
+    data_type: schema::TensorType,
+    quantization: Option<Quantization>,
+    constant: Option<Vec<f32>>,
+}
+
+struct TfLiteGraph {
+    tensors: Vec<Tensor>,
+    operators: Vec<(Operator, Vec<Option<usize>>, Vec<usize>)>,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+}
+
+impl TfLiteGraph {
+    fn new(buf: &[u8]) -> Result<Self, Error> {
+        let model = schema::root_as_model(buf)?;
+        let subgraph = match model.subgraphs() {
+            Some(s) if !s.is_empty() => s.get(0),
+            _ => return Err(Error::ModelParseError("Model has no subgraph".into())),
+        };
+        let buffers = model.buffers();
+        let op_codes = model.operator_codes();
+
+        let mut tensors = Vec::new();
+        if let Some(ts) = subgraph.tensors() {
+            tensors.reserve(ts.len());
+            for t in ts {
+                let shape: Vec<usize> = match t.shape() {
+                    Some(s) => s.iter().map(|d| d.max(1) as usize).collect(),
+                    None => Vec::new(),
+                };
+                let quantization = t.quantization().and_then(|q| {
+                    let scale: Vec<f32> = q.scale()?.iter().collect();
+                    if scale.is_empty() {
+                        return None;
+                    }
+                    let mut zero_point: Vec<i64> = match q.zero_point() {
+                        Some(z) => z.iter().collect(),
+                        None => Vec::new(),
+                    };
+                    zero_point.resize(scale.len(), 0);
+                    Some(Quantization {
+                        scale,
+                        zero_point,
+                        axis: q.quantized_dimension().max(0) as usize,
+                    })
+                });
+                let data = buffers
+                    .and_then(|b| {
+                        let index = t.buffer() as usize;
+                        if index < b.len() {
+                            b.get(index).data()
+                        } else {
+                            None
+                        }
+                    })
+                    .map(|d| d.bytes())
+                    .filter(|d| !d.is_empty());
+                let constant = match data {
+                    Some(d) => Some(decode_constant(
+                        d,
+                        t.type_(),
+                        &shape,
+                        quantization.as_ref(),
+                    )?),
+                    None => None,
+                };
+                tensors.push(Tensor {
+                    shape,
+                    data_type: t.type_(),
+                    quantization,
+                    constant,
+                });
+            }
+        }
+
+        let mut operators = Vec::new();
+        if let Some(os) = subgraph.operators() {
+            operators.reserve(os.len());
+            for op in os {
+                let op_code = op_codes
+                    .and_then(|c| {
+                        let index = op.opcode_index() as usize;
+                        if index < c.len() {
+                            Some(c.get(index))
+                        } else {
+                            None
+                        }
+                    })
+                    .ok_or_else(|| {
+                        Error::ModelParseError(format!(
+                            "Invalid operator code index `{}`",
+                            op.opcode_index()
+                        ))
+                    })?;
+                let inputs: Vec<Option<usize>> = match op.inputs() {
+                    Some(i) => i
+                        .iter()
+                        .map(|i| if i < 0 { None } else { Some(i as usize) })
+                        .collect(),
+                    None => Vec::new(),
+                };
+                let outputs: Vec<usize> = match op.outputs() {
+                    Some(o) => o.iter().map(|o| o as usize).collect(),
+                    None => Vec::new(),
+                };
+                for i in inputs.iter().flatten().chain(outputs.iter()) {
+                    if *i >= tensors.len() {
+                        return Err(Error::ModelParseError(format!(
+                            "Invalid tensor index `{}`",
+                            i
+                        )));
+                    }
+                }
+                let operator = Operator::parse(&op_code, &op)?;
+                operators.push((operator, inputs, outputs));
+            }
+        }
+
+        let inputs: Vec<usize> = match subgraph.inputs() {
+            Some(i) => i.iter().map(|i| i as usize).collect(),
+            None => Vec::new(),
+        };
+        let outputs: Vec<usize> = match subgraph.outputs() {
+            Some(o) => o.iter().map(|o| o as usize).collect(),
+            None => Vec::new(),
+        };
+        for i in inputs.iter().chain(outputs.iter()) {
+            if *i >= tensors.len() {
+                return Err(Error::ModelParseError(format!(
+                    "Invalid tensor index `{}`",
+                    i
+                )));
+            }
+        }
+
+        Ok(Self {
+            tensors,
+            operators,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+impl InferenceGraph for TfLiteGraph {
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+        let values = self
+            .tensors
+            .iter()
+            .map(|t| (t.shape.clone(), Vec::new()))
+            .collect();
+        Ok(Box::new(TfLiteExecutionContext {
+            graph: self,
+            values,
+        }))
+    }
+}
+
+struct TfLiteExecutionContext<'a> {
+    graph: &'a TfLiteGraph,
+    // (shape, data) for every non-constant tensor
+    values: Vec<(Vec<usize>, Vec<f32>)>,
+}
+
+impl<'a> TfLiteExecutionContext<'a> {
+    #[inline(always)]
+    fn tensor_ref(&self, index: usize) -> TensorRef<'_> {
+        let tensor = &self.graph.tensors[index];
+        match tensor.constant {
+            Some(ref c) => TensorRef {
+                shape: &tensor.shape,
+                data: c.as_slice(),
+            },
+            None => TensorRef {
+                shape: &self.values[index].0,
+                data: self.values[index].1.as_slice(),
+            },
+        }
+    }
+}
+
+impl<'a> ExecutionContext for TfLiteExecutionContext<'a> {
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error> {
+        let tensor_index = *self.graph.inputs.get(index).ok_or_else(|| {
+            Error::BackendError(format!("Invalid input tensor index `{}`", index))
+        })?;
+        let elem_size = match tensor_type {
+            TensorType::F32 | TensorType::I32 => 4,
+            TensorType::F16 => 2,
+            TensorType::U8 => 1,
+        };
+        let num = dimensions.iter().product::<usize>();
+        if num * elem_size != data.len() {
+            return Err(Error::BackendError(format!(
+                "Input tensor `{}` expect `{}` bytes, but got `{}`",
+                index,
+                num * elem_size,
+                data.len()
+            )));
+        }
+
+        let tensor = &self.graph.tensors[tensor_index];
+        if tensor.data_type == schema::TensorType::STRING {
+            if tensor_type != TensorType::U8 {
+                return Err(Error::BackendError(format!(
+                    "String input tensor `{}` expect `U8` type, but got `{:?}`",
+                    index, tensor_type
+                )));
+            }
+            check_string_buffer(data)?;
+        } else if tensor_type == TensorType::I32 {
+            if let Some(v) = data
+                .chunks_exact(4)
+                .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                .find(|v| int_to_f32(*v as i64).is_none())
+            {
+                return Err(Error::BackendError(format!(
+                    "Input tensor `{}` value `{}` is out of the supported integer range `[-{2}, {2}]`",
+                    index, v, MAX_EXACT_INT
+                )));
+            }
+        }
+        let quantization = tensor.quantization.as_ref();
+        let (shape, values) = &mut self.values[tensor_index];
+        shape.clear();
+        shape.extend_from_slice(dimensions);
+        values.clear();
+        values.reserve(num);
+        match tensor_type {
+            TensorType::F32 => values.extend(
+                data.chunks_exact(4)
+                    .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])),
+            ),
+            TensorType::I32 => values.extend(
+                data.chunks_exact(4)
+                    .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]) as f32),
+            ),
+            TensorType::F16 => values.extend(
+                data.chunks_exact(2)
+                    .map(|c| f16_to_f32(u16::from_le_bytes([c[0], c[1]]))),
+            ),
+            TensorType::U8 => match quantization {
+                Some(q) if tensor.data_type == schema::TensorType::UINT8 => values.extend(
+                    data.iter()
+                        .map(|v| (*v as i64 - q.zero_point[0]) as f32 * q.scale[0]),
+                ),
+                Some(q) if tensor.data_type == schema::TensorType::INT8 => values.extend(
+                    data.iter()
+                        .map(|v| (*v as i8 as i64 - q.zero_point[0]) as f32 * q.scale[0]),
+                ),
+                _ => values.extend(data.iter().map(|v| *v as f32)),
+            },
+        }
+        Ok(())
+    }
+
+    fn compute(&mut self) -> Result<(), Error> {
+        let mut outputs = Vec::new();
+        for (operator, inputs, output_indices) in self.graph.operators.iter() {
+            outputs.clear();
+            for i in output_indices {
+                outputs.push(std::mem::take(&mut self.values[*i]));
+            }
+            let res = {
+                let input_refs: Vec<Option<TensorRef>> = inputs
+                    .iter()
+                    .map(|i| i.map(|i| self.tensor_ref(i)))
+                    .collect();
+                let output_static_shapes: Vec<&[usize]> = output_indices
+                    .iter()
+                    .map(|i| self.graph.tensors[*i].shape.as_slice())
+                    .collect();
+                operator.eval(&input_refs, &output_static_shapes, &mut outputs)
+            };
+            for (i, o) in output_indices.iter().zip(outputs.drain(..)) {
+                self.values[*i] = o;
+            }
+            res?;
+        }
+        Ok(())
+    }
+
+    fn get_output(&mut self, index: usize, out_buffer: &mut [u8]) -> Result<usize, Error> {
+        let tensor_index = *self.graph.outputs.get(index).ok_or_else(|| {
+            Error::BackendError(format!("Invalid output tensor index `{}`", index))
+        })?;
+        let tensor = &self.graph.tensors[tensor_index];
+        let data = self.tensor_ref(tensor_index).data;
+        let elem_size = match tensor.data_type {
+            schema::TensorType::FLOAT32 | schema::TensorType::INT32 => 4,
+            schema::TensorType::INT64 => 8,
+            schema::TensorType::UINT8
+            | schema::TensorType::INT8
+            | schema::TensorType::BOOL
+            | schema::TensorType::STRING => 1,
+            t => {
+                return Err(Error::BackendError(format!(
+                    "Unsupported output tensor type `{:?}`",
+                    t
+                )));
+            }
+        };
+        let bytes = data.len() * elem_size;
+        if out_buffer.len() < bytes {
+            return Err(Error::BackendError(format!(
+                "Output buffer is too small, expect `{}` bytes, but got `{}`",
+                bytes,
+                out_buffer.len()
+            )));
+        }
+
+        if tensor.data_type == schema::TensorType::INT32
+            || tensor.data_type == schema::TensorType::INT64
+        {
+            if let Some(v) = data.iter().find(|v| v.abs() > MAX_EXACT_INT as f32) {
+                return Err(Error::BackendError(format!(
+                    "Output tensor `{}` value `{}` is out of the supported integer range `[-{2}, {2}]`",
+                    index, v, MAX_EXACT_INT
+                )));
+            }
+        }
+
+        let quantization = tensor.quantization.as_ref();
+        let out = &mut out_buffer[..bytes];
+        match tensor.data_type {
+            schema::TensorType::FLOAT32 => {
+                for (o, v) in out.chunks_exact_mut(4).zip(data) {
+                    o.copy_from_slice(&v.to_le_bytes());
+                }
+            }
+            schema::TensorType::INT32 => {
+                for (o, v) in out.chunks_exact_mut(4).zip(data) {
+                    o.copy_from_slice(&(*v as i32).to_le_bytes());
+                }
+            }
+            schema::TensorType::INT64 => {
+                for (o, v) in out.chunks_exact_mut(8).zip(data) {
+                    o.copy_from_slice(&(*v as i64).to_le_bytes());
+                }
+            }
+            schema::TensorType::UINT8 => {
+                for (o, v) in out.iter_mut().zip(data) {
+                    *o = quantize(*v, quantization, 0., 255.) as u8;
+                }
+            }
+            schema::TensorType::INT8 => {
+                for (o, v) in out.iter_mut().zip(data) {
+                    *o = quantize(*v, quantization, -128., 127.) as i8 as u8;
+                }
+            }
+            schema::TensorType::STRING => {
+                for (o, v) in out.iter_mut().zip(data) {
+                    *o = *v as u8;
+                }
+            }
+            _ => {
+                for (o, v) in out.iter_mut().zip(data) {
+                    *o = (*v != 0.) as u8;
+                }
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+#[inline(always)]
+fn quantize(v: f32, quantization: Option<&Quantization>, min: f32, max: f32) -> f32 {
+    match quantization {
+        Some(q) => (v / q.scale[0] + q.zero_point[0] as f32)
+            .round()
+            .clamp(min, max),
+        None => v.round().clamp(min, max),
+    }
+}
+
+fn decode_constant(
+    data: &[u8],
+    data_type: schema::TensorType,
+    shape: &[usize],
+    quantization: Option<&Quantization>,
+) -> Result<Vec<f32>, Error> {
+    if let Some(q) = quantization {
+        if q.scale.len() > 1 && shape.get(q.axis) != Some(&q.scale.len()) {
+            return Err(Error::ModelParseError(format!(
+                "Constant tensor has `{}` quantization channels, but its shape is `{:?}`",
+                q.scale.len(),
+                shape
+            )));
+        }
+    }
+    macro_rules! decode {
+        ( $size:expr, $f:expr ) => {{
+            let f = $f;
+            data.chunks_exact($size)
+                .enumerate()
+                .map(|(i, c)| {
+                    let v = f(c);
+                    match quantization {
+                        Some(q) => {
+                            let channel = q.channel(i, shape);
+                            (v - q.zero_point[channel] as f32) * q.scale[channel]
+                        }
+                        None => v,
+                    }
+                })
+                .collect()
+        }};
+    }
+
+    Ok(match data_type {
+        schema::TensorType::FLOAT32 => {
+            decode!(4, |c: &[u8]| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        }
+        schema::TensorType::FLOAT16 => {
+            decode!(2, |c: &[u8]| f16_to_f32(u16::from_le_bytes([c[0], c[1]])))
+        }
+        // the quantized integer constants, such as the int32 bias of the quantized convolution,
+        // are dequantized like other quantized tensors.
+        schema::TensorType::INT32 if quantization.is_some() => {
+            decode!(4, |c: &[u8]| i32::from_le_bytes([c[0], c[1], c[2], c[3]])
+                as f32)
+        }
+        schema::TensorType::INT64 if quantization.is_some() => {
+            decode!(8, |c: &[u8]| i64::from_le_bytes([
+                c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]
+            ]) as f32)
+        }
+        schema::TensorType::INT32 => {
+            let values = data
+                .chunks_exact(4)
+                .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]) as i64);
+            return decode_int_constant(values, i32::MIN as i64, i32::MAX as i64);
+        }
+        schema::TensorType::INT64 => {
+            let values = data
+                .chunks_exact(8)
+                .map(|c| i64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]));
+            return decode_int_constant(values, i64::MIN, i64::MAX);
+        }
+        schema::TensorType::INT16 => {
+            decode!(2, |c: &[u8]| i16::from_le_bytes([c[0], c[1]]) as f32)
+        }
+        schema::TensorType::UINT8 | schema::TensorType::BOOL => decode!(1, |c: &[u8]| c[0] as f32),
+        schema::TensorType::INT8 => decode!(1, |c: &[u8]| c[0] as i8 as f32),
+        t => {
+            return Err(Error::ModelParseError(format!(
+                "Unsupported constant tensor type `{:?}`",
+                t
+            )));
+        }
+    })
+}
+
+/// Decode the integer constant tensor, which is not quantized.
+/// The type min and max values are the common "unbounded" markers (such as the strided slice end),
+/// they are kept as the out of range ```f32``` values and the operators clamp them.
+/// Other values out of the exact range of ```f32``` are rejected.
+fn decode_int_constant(
+    values: impl Iterator<Item = i64>,
+    type_min: i64,
+    type_max: i64,
+) -> Result<Vec<f32>, Error> {
+    values
+        .map(|v| match int_to_f32(v) {
+            Some(f) => Ok(f),
+            None if v == type_min || v == type_max => Ok(v as f32),
+            None => Err(Error::ModelParseError(format!(
+                "Constant tensor value `{}` is out of the supported integer range `[-{1}, {1}]`",
+                v, MAX_EXACT_INT
+            ))),
+        })
+        .collect()
+}
+
+/// Check the serialized TfLite string buffer: ```[count: i32][offsets: i32 * (count + 1)][string bytes]```.
+fn check_string_buffer(data: &[u8]) -> Result<(), Error> {
+    let read_i32 = |i: usize| {
+        let c = &data[i * 4..i * 4 + 4];
+        i32::from_le_bytes([c[0], c[1], c[2], c[3]])
+    };
+    let words = data.len() / 4;
+    let count = if words > 0 { read_i32(0) } else { -1 };
+    if count < 0 || count as usize + 2 > words {
+        return Err(Error::BackendError(format!(
+            "Invalid string tensor buffer: cannot read the header from `{}` bytes",
+            data.len()
+        )));
+    }
+    // the offsets start from the header end, and the last offset is the buffer end
+    let mut expect_min = (count as usize + 2) * 4;
+    for i in 1..count as usize + 2 {
+        let offset = read_i32(i);
+        if offset < 0 || (offset as usize) < expect_min || offset as usize > data.len() {
+            return Err(Error::BackendError(format!(
+                "Invalid string tensor buffer: bad offset `{}` at `{}`",
+                offset,
+                i - 1
+            )));
+        }
+        expect_min = offset as usize;
+    }
+    if read_i32(1) as usize != (count as usize + 2) * 4 || expect_min != data.len() {
+        return Err(Error::BackendError(format!(
+            "Invalid string tensor buffer: the offsets mismatch buffer size `{}`",
+            data.len()
+        )));
+    }
+    Ok(())
+}
+
+#[inline]
+fn f16_to_f32(h: u16) -> f32 {
+    let sign = ((h >> 15) as u32) << 31;
+    let exp = ((h >> 10) & 0x1f) as u32;
+    let mantissa = (h & 0x3ff) as u32;
+    let bits = if exp == 0 {
+        if mantissa == 0 {
+            sign
+        } else {
+            // subnormal number
+            let mut e = 127 - 15 + 1;
+            let mut m = mantissa;
+            while m & 0x400 == 0 {
+                m <<= 1;
+                e -= 1;
+            }
+            sign | (e << 23) | ((m & 0x3ff) << 13)
+        }
+    } else if exp == 0x1f {
+        sign | 0x7f80_0000 | (mantissa << 13)
+    } else {
+        sign | ((exp + 127 - 15) << 23) | (mantissa << 13)
+    };
+    f32::from_bits(bits)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::test_model::{
+        BuiltinOperator, BuiltinOptions, TensorType as ModelTensorType, TestModelBuilder,
+    };
+
+    fn build_graph(model: &[u8]) -> Result<Box<dyn InferenceGraph>, Error> {
+        TfLiteNativeBackend.build_graph(GraphEncoding::TensorflowLite, Device::CPU, &[model])
+    }
+
+    fn f32_bytes(v: &[f32]) -> Vec<u8> {
+        v.iter().flat_map(|f| f.to_le_bytes()).collect()
+    }
+
+    // input[1, 2] -> FULLY_CONNECTED(weights[3, 2], bias[3], RELU) -> SOFTMAX -> output[1, 3]
+    fn build_test_model() -> Vec<u8> {
+        let mut builder = TestModelBuilder::new();
+        let input = builder.tensor("input", &[1, 2], ModelTensorType::FLOAT32, None, None);
+        let weights = builder.f32_tensor("weights", &[3, 2], &[1., 2., -1., -2., 0.5, 0.5]);
+        let bias = builder.f32_tensor("bias", &[3], &[0., 1., 0.]);
+        let fc = builder.tensor("fc", &[1, 3], ModelTensorType::FLOAT32, None, None);
+        let output = builder.tensor("output", &[1, 3], ModelTensorType::FLOAT32, None, None);
+        builder.operator(
+            BuiltinOperator::FULLY_CONNECTED,
+            &[input, weights, bias],
+            &[fc],
+            |fbb| {
+                let o = schema::FullyConnectedOptions::create(
+                    fbb,
+                    &schema::FullyConnectedOptionsArgs {
+                        fused_activation_function: schema::ActivationFunctionType::RELU,
+                        ..Default::default()
+                    },
+                );
+                Some((BuiltinOptions::FullyConnectedOptions, o.as_union_value()))
+            },
+        );
+        builder.operator(BuiltinOperator::SOFTMAX, &[fc], &[output], |fbb| {
+            let o = schema::SoftmaxOptions::create(fbb, &schema::SoftmaxOptionsArgs { beta: 1. });
+            Some((BuiltinOptions::SoftmaxOptions, o.as_union_value()))
+        });
+        builder.finish(&[input], &[output])
+    }
+
+    #[test]
+    fn test_native_tflite_backend() {
+        let model = build_test_model();
+        let graph = build_graph(&model).unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+
+        let input = f32_bytes(&[1., 1.]);
+        ctx.set_input(0, TensorType::F32, &[1, 2], &input).unwrap();
+        ctx.compute().unwrap();
+        let mut output = [0u8; 12];
+        assert_eq!(ctx.get_output(0, &mut output).unwrap(), 12);
+        let output: Vec<f32> = output
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+
+        // fc: [3, -2, 1] -> relu: [3, 0, 1] -> softmax
+        let sum = 3f32.exp() + 1. + 1f32.exp();
+        let expect = [3f32.exp() / sum, 1. / sum, 1f32.exp() / sum];
+        for (o, e) in output.iter().zip(expect.iter()) {
+            assert!((o - e).abs() < 1e-6);
+        }
+
+        assert!(ctx.get_output(1, &mut [0u8; 12]).is_err());
+        assert!(ctx.set_input(0, TensorType::F32, &[1, 3], &input).is_err());
+        assert!(TfLiteNativeBackend
+            .build_graph(GraphEncoding::TensorflowLite, Device::GPU, &[&model])
+            .is_err());
+    }
+
+    // uint8 input[1, 2] -> DEQUANTIZE -> FULLY_CONNECTED(per-channel int8 weights[2, 2])
+    // -> QUANTIZE -> int8 output[1, 2]
+    #[test]
+    fn test_quantized_model() {
+        let mut builder = TestModelBuilder::new();
+        let input = builder.tensor(
+            "input",
+            &[1, 2],
+            ModelTensorType::UINT8,
+            None,
+            Some((&[0.5], &[128], 0)),
+        );
+        let dequantized =
+            builder.tensor("dequantized", &[1, 2], ModelTensorType::FLOAT32, None, None);
+        let weights = builder.tensor(
+            "weights",
+            &[2, 2],
+            ModelTensorType::INT8,
+            Some(&[2, 4, -8i8 as u8, 4]),
+            Some((&[0.5, 0.25], &[0, 0], 0)),
+        );
+        let fc = builder.tensor("fc", &[1, 2], ModelTensorType::FLOAT32, None, None);
+        let output = builder.tensor(
+            "output",
+            &[1, 2],
+            ModelTensorType::INT8,
+            None,
+            Some((&[0.1], &[-10], 0)),
+        );
+        builder.operator(
+            BuiltinOperator::DEQUANTIZE,
+            &[input],
+            &[dequantized],
+            |_| None,
+        );
+        builder.operator(
+            BuiltinOperator::FULLY_CONNECTED,
+            &[dequantized, weights],
+            &[fc],
+            |fbb| {
+                let o = schema::FullyConnectedOptions::create(fbb, &Default::default());
+                Some((BuiltinOptions::FullyConnectedOptions, o.as_union_value()))
+            },
+        );
+        builder.operator(BuiltinOperator::QUANTIZE, &[fc], &[output], |_| None);
+        let model = builder.finish(&[input], &[output]);
+
+        let graph = build_graph(&model).unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+        // input: [1, -2], weights: [[1, 2], [-2, 1]] -> fc: [-3, -4] -> output: [-40, -50]
+        ctx.set_input(0, TensorType::U8, &[1, 2], &[130, 124])
+            .unwrap();
+        ctx.compute().unwrap();
+        let mut output = [0u8; 2];
+        assert_eq!(ctx.get_output(0, &mut output).unwrap(), 2);
+        assert_eq!(output.map(|o| o as i8), [-40, -50]);
+
+        // input: [-64, 63.5] -> fc: [63, 191.5] -> the output is clamped to 127
+        ctx.set_input(0, TensorType::U8, &[1, 2], &[0, 255])
+            .unwrap();
+        ctx.compute().unwrap();
+        ctx.get_output(0, &mut output).unwrap();
+        assert_eq!(output.map(|o| o as i8), [127, 127]);
+    }
+
+    // uint8 input[1, 2] -> FULLY_CONNECTED(per-channel int8 weights[2, 2], int32 bias[2]) -> output[1, 2]
+    #[test]
+    fn test_quantized_bias() {
+        let mut builder = TestModelBuilder::new();
+        let input = builder.tensor(
+            "input",
+            &[1, 2],
+            ModelTensorType::UINT8,
+            None,
+            Some((&[0.5], &[128], 0)),
+        );
+        let weights = builder.tensor(
+            "weights",
+            &[2, 2],
+            ModelTensorType::INT8,
+            Some(&[2, 4, -8i8 as u8, 4]),
+            Some((&[0.5, 0.25], &[0, 0], 0)),
+        );
+        // the bias scale is the input scale multiplied by the weights scale of every channel
+        let bias = builder.tensor(
+            "bias",
+            &[2],
+            ModelTensorType::INT32,
+            Some(&[4i32.to_le_bytes(), 16i32.to_le_bytes()].concat()),
+            Some((&[0.25, 0.125], &[0, 0], 0)),
+        );
+        let output = builder.tensor("output", &[1, 2], ModelTensorType::FLOAT32, None, None);
+        builder.operator(
+            BuiltinOperator::FULLY_CONNECTED,
+            &[input, weights, bias],
+            &[output],
+            |fbb| {
+                let o = schema::FullyConnectedOptions::create(fbb, &Default::default());
+                Some((BuiltinOptions::FullyConnectedOptions, o.as_union_value()))
+            },
+        );
+        let model = builder.finish(&[input], &[output]);
+
+        let graph = build_graph(&model).unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+        // input: [1, -2], weights: [[1, 2], [-2, 1]], bias: [1, 2] -> output: [-2, -2]
+        ctx.set_input(0, TensorType::U8, &[1, 2], &[130, 124])
+            .unwrap();
+        ctx.compute().unwrap();
+        let mut output = [0u8; 8];
+        ctx.get_output(0, &mut output).unwrap();
+        assert_eq!(output.to_vec(), f32_bytes(&[-2., -2.]));
+
+        // the channels of the quantization mismatch the bias shape
+        let mut builder = TestModelBuilder::new();
+        let bias = builder.tensor(
+            "bias",
+            &[3],
+            ModelTensorType::INT32,
+            Some(&[0u8; 12]),
+            Some((&[0.25, 0.125], &[0, 0], 0)),
+        );
+        let model = builder.finish(&[bias], &[bias]);
+        assert!(build_graph(&model).is_err());
+    }
+
+    // input[2] -> ADD(constant[2]) -> int32 output[2]
+    fn build_int_model(constant: i32) -> Vec<u8> {
+        let mut builder = TestModelBuilder::new();
+        let input = builder.tensor("input", &[2], ModelTensorType::INT32, None, None);
+        let constant = builder.tensor(
+            "constant",
+            &[2],
+            ModelTensorType::INT32,
+            Some(&[constant.to_le_bytes(), 1i32.to_le_bytes()].concat()),
+            None,
+        );
+        let output = builder.tensor("output", &[2], ModelTensorType::INT32, None, None);
+        builder.operator(BuiltinOperator::ADD, &[input, constant], &[output], |_| {
+            None
+        });
+        builder.finish(&[input], &[output])
+    }
+
+    #[test]
+    fn test_integer_range() {
+        let model = build_int_model(2);
+        let graph = build_graph(&model).unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+        let max = MAX_EXACT_INT as i32;
+        let input = [max - 2, -max]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<u8>>();
+        ctx.set_input(0, TensorType::I32, &[2], &input).unwrap();
+        ctx.compute().unwrap();
+        let mut output = [0u8; 8];
+        ctx.get_output(0, &mut output).unwrap();
+        assert_eq!(&output[..4], &max.to_le_bytes());
+        assert_eq!(&output[4..], &(1 - max).to_le_bytes());
+
+        // the input is out of range
+        let input = [max + 1, 0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<u8>>();
+        assert!(ctx.set_input(0, TensorType::I32, &[2], &input).is_err());
+        // the output is out of range
+        let input = [max, 0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<u8>>();
+        ctx.set_input(0, TensorType::I32, &[2], &input).unwrap();
+        ctx.compute().unwrap();
+        assert!(ctx.get_output(0, &mut output).is_err());
+
+        // the constant is out of range, but the type max value is allowed
+        assert!(build_graph(&build_int_model(max + 1)).is_err());
+        assert!(build_graph(&build_int_model(i32::MAX)).is_ok());
+    }
+
+    #[test]
+    fn test_string_input() {
+        // the string input is the model output
+        let mut builder = TestModelBuilder::new();
+        let input = builder.tensor("input", &[1], ModelTensorType::STRING, None, None);
+        let model = builder.finish(&[input], &[input]);
+        let graph = build_graph(&model).unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+
+        let mut buf = [1i32, 12, 17]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<u8>>();
+        buf.extend_from_slice(b"hello");
+        ctx.set_input(0, TensorType::U8, &[buf.len()], &buf)
+            .unwrap();
+        ctx.compute().unwrap();
+        let mut output = [0u8; 32];
+        assert_eq!(ctx.get_output(0, &mut output).unwrap(), buf.len());
+        assert_eq!(&output[..buf.len()], buf.as_slice());
+
+        // the string tensor must be u8 serialized buffer
+        assert!(ctx
+            .set_input(0, TensorType::F32, &[buf.len() / 4], &buf[..16])
+            .is_err());
+        assert!(ctx.set_input(0, TensorType::U8, &[16], &buf[..16]).is_err());
+        assert!(ctx.set_input(0, TensorType::U8, &[2], &buf[..2]).is_err());
+        buf[4] = 8;
+        assert!(ctx
+            .set_input(0, TensorType::U8, &[buf.len()], &buf)
+            .is_err());
+    }
+
+    #[test]
+    fn test_f16_to_f32() {
+        assert_eq!(f16_to_f32(0x3c00), 1.);
+        assert_eq!(f16_to_f32(0xc000), -2.);
+        assert_eq!(f16_to_f32(0x3555), 0.33325195);
+        assert_eq!(f16_to_f32(0x0001), 5.9604645e-8);
+        assert!(f16_to_f32(0x7c00).is_infinite());
+    }
+}
@@ -0,0 +1,2061 @@
+use super::schema;
+use crate::Error;
+
+mod custom;
+
+/// Tensor data used by operators, the quantized tensors have been dequantized.
+pub(super) struct TensorRef<'a> {
+    pub shape: &'a [usize],
+    pub data: &'a [f32],
+}
+
+type Output = (Vec<usize>, Vec<f32>);
+
+#[derive(Debug, Copy, Clone)]
+pub(super) enum FusedActivation {
+    None,
+    Relu,
+    ReluN1To1,
+    Relu6,
+    Tanh,
+    SignBit,
+}
+
+impl FusedActivation {
+    fn parse(a: schema::ActivationFunctionType) -> Result<Self, Error> {
+        Ok(match a {
+            schema::ActivationFunctionType::NONE => Self::None,
+            schema::ActivationFunctionType::RELU => Self::Relu,
+            schema::ActivationFunctionType::RELU_N1_TO_1 => Self::ReluN1To1,
+            schema::ActivationFunctionType::RELU6 => Self::Relu6,
+            schema::ActivationFunctionType::TANH => Self::Tanh,
+            schema::ActivationFunctionType::SIGN_BIT => Self::SignBit,
+            a => {
+                return Err(Error::ModelParseError(format!(
+                    "Unsupported fused activation function `{:?}`",
+                    a
+                )));
+            }
+        })
+    }
+
+    #[inline(always)]
+    fn apply(self, data: &mut [f32]) {
+        match self {
+            Self::None => {}
+            Self::Relu => data.iter_mut().for_each(|v| *v = v.max(0.)),
+            Self::ReluN1To1 => data.iter_mut().for_each(|v| *v = v.clamp(-1., 1.)),
+            Self::Relu6 => data.iter_mut().for_each(|v| *v = v.clamp(0., 6.)),
+            Self::Tanh => data.iter_mut().for_each(|v| *v = v.tanh()),
+            Self::SignBit => data
+                .iter_mut()
+                .for_each(|v| *v = if v.is_sign_negative() { 1. } else { 0. }),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum Padding {
+    Same,
+    Valid,
+}
+
+impl Padding {
+    #[inline(always)]
+    fn parse(p: schema::Padding) -> Self {
+        if p == schema::Padding::VALID {
+            Self::Valid
+        } else {
+            Self::Same
+        }
+    }
+
+    /// return (output_size, padding_before)
+    #[inline(always)]
+    fn compute(
+        self,
+        in_size: usize,
+        kernel: usize,
+        stride: usize,
+        dilation: usize,
+    ) -> (usize, usize) {
+        let effective_kernel = (kernel - 1) * dilation + 1;
+        match self {
+            Self::Same => {
+                let out = in_size.div_ceil(stride);
+                let total = ((out.max(1) - 1) * stride + effective_kernel).saturating_sub(in_size);
+                (out, total / 2)
+            }
+            Self::Valid => (
+                (in_size + stride).saturating_sub(effective_kernel) / stride,
+                0,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(super) struct ConvParams {
+    padding: Padding,
+    stride_h: usize,
+    stride_w: usize,
+    dilation_h: usize,
+    dilation_w: usize,
+    activation: FusedActivation,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(super) struct PoolParams {
+    padding: Padding,
+    stride_h: usize,
+    stride_w: usize,
+    filter_h: usize,
+    filter_w: usize,
+    activation: FusedActivation,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(super) enum BinaryKind {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Maximum,
+    Minimum,
+    SquaredDifference,
+    Pow,
+    Prelu,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(super) enum UnaryKind {
+    Identity,
+    Logistic,
+    Tanh,
+    Relu,
+    Relu6,
+    ReluN1To1,
+    LeakyRelu(f32),
+    HardSwish,
+    Exp,
+    Log,
+    Sqrt,
+    Rsqrt,
+    Abs,
+    Neg,
+    Square,
+    Floor,
+    Elu,
+    Sin,
+    Cos,
+    Gelu(bool),
+    Cast(bool),
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(super) enum ReduceKind {
+    Mean,
+    Sum,
+    Max,
+    Min,
+    Prod,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Operator {
+    Unary(UnaryKind),
+    Binary(BinaryKind, FusedActivation),
+    Conv2D(ConvParams),
+    DepthwiseConv2D(ConvParams),
+    TransposeConv(ConvParams),
+    // mediapipe custom operator: Convolution2DTransposeBias
+    TransposeConvBias(ConvParams),
+    // mediapipe custom operator: MaxPoolingWithArgmax2D
+    MaxPoolWithArgmax(PoolParams),
+    // mediapipe custom operator: MaxUnpooling2D
+    MaxUnpooling(PoolParams),
+    // tflite custom operator: TFLite_Detection_PostProcess
+    DetectionPostProcess(custom::DetectionPostProcessParams),
+    FullyConnected {
+        activation: FusedActivation,
+        keep_num_dims: bool,
+    },
+    AveragePool2D(PoolParams),
+    MaxPool2D(PoolParams),
+    Reshape(Vec<i32>),
+    Squeeze(Vec<i32>),
+    ExpandDims,
+    Concatenation {
+        axis: i32,
+        activation: FusedActivation,
+    },
+    Softmax(f32),
+    LogSoftmax,
+    Pad,
+    Reduce(ReduceKind, bool),
+    Resize {
+        bilinear: bool,
+        align_corners: bool,
+        half_pixel_centers: bool,
+    },
+    StridedSlice {
+        begin_mask: i32,
+        end_mask: i32,
+        shrink_axis_mask: i32,
+    },
+    Slice,
+    Gather(i32),
+    Transpose,
+    BatchMatMul {
+        adj_x: bool,
+        adj_y: bool,
+    },
+    Split,
+    SplitV,
+    Pack(i32),
+    Unpack(i32),
+    Tile,
+    ArgMax,
+    ArgMin,
+    L2Normalization(FusedActivation),
+    Shape,
+}
+
+macro_rules! options {
+    ( $op:ident, $getter:ident ) => {
+        $op.$getter().ok_or_else(|| {
+            Error::ModelParseError(format!(
+                "Operator options `{}` is missing",
+                stringify!($getter)
+            ))
+        })?
+    };
+}
+
+macro_rules! conv_params {
+    ( $options:ident ) => {
+        ConvParams {
+            dilation_h: $options.dilation_h_factor().max(1) as usize,
+            dilation_w: $options.dilation_w_factor().max(1) as usize,
+            ..conv_params!($options, no_dilation)
+        }
+    };
+    ( $options:ident, no_dilation ) => {
+        ConvParams {
+            padding: Padding::parse($options.padding()),
+            stride_h: $options.stride_h().max(1) as usize,
+            stride_w: $options.stride_w().max(1) as usize,
+            dilation_h: 1,
+            dilation_w: 1,
+            activation: FusedActivation::parse($options.fused_activation_function())?,
+        }
+    };
+}
+
+macro_rules! pool_params {
+    ( $options:ident ) => {
+        PoolParams {
+            padding: Padding::parse($options.padding()),
+            stride_h: $options.stride_h().max(1) as usize,
+            stride_w: $options.stride_w().max(1) as usize,
+            filter_h: $options.filter_height().max(1) as usize,
+            filter_w: $options.filter_width().max(1) as usize,
+            activation: FusedActivation::parse($options.fused_activation_function())?,
+        }
+    };
+}
+
+macro_rules! binary_op {
+    ( $op:ident, $kind:ident, $getter:ident ) => {
+        Operator::Binary(
+            BinaryKind::$kind,
+            match $op.$getter() {
+                Some(o) => FusedActivation::parse(o.fused_activation_function())?,
+                None => FusedActivation::None,
+            },
+        )
+    };
+}
+
+impl Operator {
+    pub(super) fn parse(code: &schema::OperatorCode, op: &schema::Operator) -> Result<Self, Error> {
+        use schema::BuiltinOperator as B;
+
+        // the builtin code is stored in `deprecated_builtin_code` for old models.
+        let builtin = B((code.deprecated_builtin_code() as i32).max(code.builtin_code().0));
+        Ok(match builtin {
+            B::DEQUANTIZE | B::QUANTIZE => Self::Unary(UnaryKind::Identity),
+            B::LOGISTIC => Self::Unary(UnaryKind::Logistic),
+            B::TANH => Self::Unary(UnaryKind::Tanh),
+            B::RELU => Self::Unary(UnaryKind::Relu),
+            B::RELU6 => Self::Unary(UnaryKind::Relu6),
+            B::RELU_N1_TO_1 => Self::Unary(UnaryKind::ReluN1To1),
+            B::LEAKY_RELU => Self::Unary(UnaryKind::LeakyRelu(
+                options!(op, builtin_options_as_leaky_relu_options).alpha(),
+            )),
+            B::HARD_SWISH => Self::Unary(UnaryKind::HardSwish),
+            B::EXP => Self::Unary(UnaryKind::Exp),
+            B::LOG => Self::Unary(UnaryKind::Log),
+            B::SQRT => Self::Unary(UnaryKind::Sqrt),
+            B::RSQRT => Self::Unary(UnaryKind::Rsqrt),
+            B::ABS => Self::Unary(UnaryKind::Abs),
+            B::NEG => Self::Unary(UnaryKind::Neg),
+            B::SQUARE => Self::Unary(UnaryKind::Square),
+            B::FLOOR => Self::Unary(UnaryKind::Floor),
+            B::ELU => Self::Unary(UnaryKind::Elu),
+            B::SIN => Self::Unary(UnaryKind::Sin),
+            B::COS => Self::Unary(UnaryKind::Cos),
+            B::GELU => Self::Unary(UnaryKind::Gelu(
+                op.builtin_options_as_gelu_options()
+                    .map(|o| o.approximate())
+                    .unwrap_or(false),
+            )),
+            B::CAST => Self::Unary(UnaryKind::Cast(
+                op.builtin_options_as_cast_options()
+                    .map(|o| {
+                        let t = o.out_data_type();
+                        t != schema::TensorType::FLOAT32 && t != schema::TensorType::FLOAT16
+                    })
+                    .unwrap_or(false),
+            )),
+
+            B::ADD => binary_op!(op, Add, builtin_options_as_add_options),
+            B::SUB => binary_op!(op, Sub, builtin_options_as_sub_options),
+            B::MUL => binary_op!(op, Mul, builtin_options_as_mul_options),
+            B::DIV => binary_op!(op, Div, builtin_options_as_div_options),
+            B::MAXIMUM => Self::Binary(BinaryKind::Maximum, FusedActivation::None),
+            B::MINIMUM => Self::Binary(BinaryKind::Minimum, FusedActivation::None),
+            B::SQUARED_DIFFERENCE => {
+                Self::Binary(BinaryKind::SquaredDifference, FusedActivation::None)
+            }
+            B::POW => Self::Binary(BinaryKind::Pow, FusedActivation::None),
+            B::PRELU => Self::Binary(BinaryKind::Prelu, FusedActivation::None),
+
+            B::CONV_2D => {
+                let o = options!(op, builtin_options_as_conv_2_doptions);
+                Self::Conv2D(conv_params!(o))
+            }
+            B::DEPTHWISE_CONV_2D => {
+                let o = options!(op, builtin_options_as_depthwise_conv_2_doptions);
+                Self::DepthwiseConv2D(conv_params!(o))
+            }
+            B::TRANSPOSE_CONV => {
+                let o = options!(op, builtin_options_as_transpose_conv_options);
+                Self::TransposeConv(conv_params!(o, no_dilation))
+            }
+            B::FULLY_CONNECTED => {
+                let o = options!(op, builtin_options_as_fully_connected_options);
+                if o.weights_format() != schema::FullyConnectedOptionsWeightsFormat::DEFAULT {
+                    return Err(Error::ModelParseError(
+                        "Unsupported fully connected weights format".into(),
+                    ));
+                }
+                Self::FullyConnected {
+                    activation: FusedActivation::parse(o.fused_activation_function())?,
+                    keep_num_dims: o.keep_num_dims(),
+                }
+            }
+            B::AVERAGE_POOL_2D => {
+                let o = options!(op, builtin_options_as_pool_2_doptions);
+                Self::AveragePool2D(pool_params!(o))
+            }
+            B::MAX_POOL_2D => {
+                let o = options!(op, builtin_options_as_pool_2_doptions);
+                Self::MaxPool2D(pool_params!(o))
+            }
+
+            B::RESHAPE => Self::Reshape(
+                op.builtin_options_as_reshape_options()
+                    .and_then(|o| o.new_shape())
+                    .map(|s| s.iter().collect())
+                    .unwrap_or_default(),
+            ),
+            B::SQUEEZE => Self::Squeeze(
+                op.builtin_options_as_squeeze_options()
+                    .and_then(|o| o.squeeze_dims())
+                    .map(|s| s.iter().collect())
+                    .unwrap_or_default(),
+            ),
+            B::EXPAND_DIMS => Self::ExpandDims,
+            B::CONCATENATION => {
+                let o = options!(op, builtin_options_as_concatenation_options);
+                Self::Concatenation {
+                    axis: o.axis(),
+                    activation: FusedActivation::parse(o.fused_activation_function())?,
+                }
+            }
+            B::SOFTMAX => Self::Softmax(
+                op.builtin_options_as_softmax_options()
+                    .map(|o| o.beta())
+                    .unwrap_or(1.),
+            ),
+            B::LOG_SOFTMAX => Self::LogSoftmax,
+            B::PAD | B::PADV2 => Self::Pad,
+            B::MEAN | B::SUM | B::REDUCE_MAX | B::REDUCE_MIN | B::REDUCE_PROD => {
+                let kind = match builtin {
+                    B::MEAN => ReduceKind::Mean,
+                    B::SUM => ReduceKind::Sum,
+                    B::REDUCE_MAX => ReduceKind::Max,
+                    B::REDUCE_MIN => ReduceKind::Min,
+                    _ => ReduceKind::Prod,
+                };
+                Self::Reduce(
+                    kind,
+                    op.builtin_options_as_reducer_options()
+                        .map(|o| o.keep_dims())
+                        .unwrap_or(false),
+                )
+            }
+            B::RESIZE_BILINEAR => {
+                let o = options!(op, builtin_options_as_resize_bilinear_options);
+                Self::Resize {
+                    bilinear: true,
+                    align_corners: o.align_corners(),
+                    half_pixel_centers: o.half_pixel_centers(),
+                }
+            }
+            B::RESIZE_NEAREST_NEIGHBOR => {
+                let o = options!(op, builtin_options_as_resize_nearest_neighbor_options);
+                Self::Resize {
+                    bilinear: false,
+                    align_corners: o.align_corners(),
+                    half_pixel_centers: o.half_pixel_centers(),
+                }
+            }
+            B::STRIDED_SLICE => {
+                let o = options!(op, builtin_options_as_strided_slice_options);
+                if o.ellipsis_mask() != 0 || o.new_axis_mask() != 0 {
+                    return Err(Error::ModelParseError(
+                        "Unsupported strided slice with ellipsis mask or new axis mask".into(),
+                    ));
+                }
+                Self::StridedSlice {
+                    begin_mask: o.begin_mask(),
+                    end_mask: o.end_mask(),
+                    shrink_axis_mask: o.shrink_axis_mask(),
+                }
+            }
+            B::SLICE => Self::Slice,
+            B::GATHER => {
+                let o = options!(op, builtin_options_as_gather_options);
+                if o.batch_dims() != 0 {
+                    return Err(Error::ModelParseError(
+                        "Unsupported gather with batch dims".into(),
+                    ));
+                }
+                Self::Gather(o.axis())
+            }
+            B::TRANSPOSE => Self::Transpose,
+            B::BATCH_MATMUL => {
+                let o = options!(op, builtin_options_as_batch_mat_mul_options);
+                Self::BatchMatMul {
+                    adj_x: o.adj_x(),
+                    adj_y: o.adj_y(),
+                }
+            }
+            B::SPLIT => Self::Split,
+            B::SPLIT_V => Self::SplitV,
+            B::PACK => Self::Pack(options!(op, builtin_options_as_pack_options).axis()),
+            B::UNPACK => Self::Unpack(options!(op, builtin_options_as_unpack_options).axis()),
+            B::TILE => Self::Tile,
+            B::ARG_MAX => Self::ArgMax,
+            B::ARG_MIN => Self::ArgMin,
+            B::L2_NORMALIZATION => {
+                Self::L2Normalization(match op.builtin_options_as_l2_norm_options() {
+                    Some(o) => FusedActivation::parse(o.fused_activation_function())?,
+                    None => FusedActivation::None,
+                })
+            }
+            B::SHAPE => Self::Shape,
+            B::CUSTOM => custom::parse(
+                code.custom_code(),
+                op.custom_options().map(|o| o.bytes()).unwrap_or_default(),
+            )?,
+            b => {
+                return Err(Error::ModelParseError(format!(
+                    "Unsupported operator `{}`",
+                    b.variant_name().unwrap_or("UNKNOWN")
+                )));
+            }
+        })
+    }
+
+    /// Run the operator, ```static_shapes``` are the output shapes defined in model.
+    pub(super) fn eval(
+        &self,
+        inputs: &[Option<TensorRef>],
+        static_shapes: &[&[usize]],
+        outputs: &mut [Output],
+    ) -> Result<(), Error> {
+        if outputs.is_empty() {
+            return Ok(());
+        }
+        match self {
+            Self::Unary(kind) => unary(input(inputs, 0)?, *kind, &mut outputs[0]),
+            Self::Binary(kind, activation) => {
+                binary(input(inputs, 0)?, input(inputs, 1)?, *kind, &mut outputs[0])?;
+                activation.apply(&mut outputs[0].1);
+            }
+            Self::Conv2D(params) => conv_2d(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                optional_input(inputs, 2),
+                params,
+                &mut outputs[0],
+            )?,
+            Self::DepthwiseConv2D(params) => depthwise_conv_2d(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                optional_input(inputs, 2),
+                params,
+                &mut outputs[0],
+            )?,
+            Self::TransposeConv(params) => {
+                let output_shape = to_usize_vec(input(inputs, 0)?.data);
+                transpose_conv(
+                    input(inputs, 2)?,
+                    input(inputs, 1)?,
+                    optional_input(inputs, 3),
+                    &output_shape,
+                    params,
+                    &mut outputs[0],
+                )?
+            }
+            Self::TransposeConvBias(params) => transpose_conv(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                optional_input(inputs, 2),
+                static_shapes[0],
+                params,
+                &mut outputs[0],
+            )?,
+            Self::MaxPoolWithArgmax(params) => {
+                custom::max_pool_with_argmax(input(inputs, 0)?, params, outputs)?
+            }
+            Self::MaxUnpooling(params) => custom::max_unpooling(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                params,
+                static_shapes[0],
+                &mut outputs[0],
+            )?,
+            Self::DetectionPostProcess(params) => custom::detection_post_process(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                input(inputs, 2)?,
+                params,
+                outputs,
+            )?,
+            Self::FullyConnected {
+                activation,
+                keep_num_dims,
+            } => fully_connected(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                optional_input(inputs, 2),
+                *activation,
+                *keep_num_dims,
+                &mut outputs[0],
+            )?,
+            Self::AveragePool2D(params) => {
+                pool_2d(input(inputs, 0)?, params, false, &mut outputs[0])?
+            }
+            Self::MaxPool2D(params) => pool_2d(input(inputs, 0)?, params, true, &mut outputs[0])?,
+            Self::Reshape(new_shape) => {
+                let x = input(inputs, 0)?;
+                let shape: Vec<i64> = match optional_input(inputs, 1) {
+                    Some(s) => s.data.iter().map(|v| *v as i64).collect(),
+                    None if !new_shape.is_empty() => new_shape.iter().map(|v| *v as i64).collect(),
+                    None => static_shapes[0].iter().map(|v| *v as i64).collect(),
+                };
+                let shape = resolve_shape(&shape, x.data.len())?;
+                copy_to(x.data, shape, &mut outputs[0]);
+            }
+            Self::Squeeze(dims) => {
+                let x = input(inputs, 0)?;
+                let rank = x.shape.len();
+                let dims: Vec<usize> = dims
+                    .iter()
+                    .map(|d| normalize_axis(*d as i64, rank))
+                    .collect::<Result<_, _>>()?;
+                let shape = x
+                    .shape
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, d)| !(**d == 1 && (dims.is_empty() || dims.contains(i))))
+                    .map(|(_, d)| *d)
+                    .collect();
+                copy_to(x.data, shape, &mut outputs[0]);
+            }
+            Self::ExpandDims => {
+                let x = input(inputs, 0)?;
+                let axis = scalar(input(inputs, 1)?)? as i64;
+                let axis = normalize_axis(axis, x.shape.len() + 1)?;
+                let mut shape = x.shape.to_vec();
+                shape.insert(axis, 1);
+                copy_to(x.data, shape, &mut outputs[0]);
+            }
+            Self::Concatenation { axis, activation } => {
+                let xs: Vec<&TensorRef> = inputs.iter().flatten().collect();
+                concatenation(&xs, *axis, &mut outputs[0])?;
+                activation.apply(&mut outputs[0].1);
+            }
+            Self::Softmax(beta) => softmax(input(inputs, 0)?, *beta, false, &mut outputs[0]),
+            Self::LogSoftmax => softmax(input(inputs, 0)?, 1., true, &mut outputs[0]),
+            Self::Pad => {
+                let value = match optional_input(inputs, 2) {
+                    Some(v) => scalar(v)?,
+                    None => 0.,
+                };
+                pad(input(inputs, 0)?, input(inputs, 1)?, value, &mut outputs[0])?
+            }
+            Self::Reduce(kind, keep_dims) => reduce(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                *kind,
+                *keep_dims,
+                &mut outputs[0],
+            )?,
+            Self::Resize {
+                bilinear,
+                align_corners,
+                half_pixel_centers,
+            } => resize(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                *bilinear,
+                *align_corners,
+                *half_pixel_centers,
+                &mut outputs[0],
+            )?,
+            Self::StridedSlice {
+                begin_mask,
+                end_mask,
+                shrink_axis_mask,
+            } => strided_slice(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                input(inputs, 2)?,
+                optional_input(inputs, 3),
+                (*begin_mask, *end_mask, *shrink_axis_mask),
+                &mut outputs[0],
+            )?,
+            Self::Slice => slice(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                input(inputs, 2)?,
+                &mut outputs[0],
+            )?,
+            Self::Gather(axis) => {
+                gather(input(inputs, 0)?, input(inputs, 1)?, *axis, &mut outputs[0])?
+            }
+            Self::Transpose => transpose(input(inputs, 0)?, input(inputs, 1)?, &mut outputs[0])?,
+            Self::BatchMatMul { adj_x, adj_y } => batch_mat_mul(
+                input(inputs, 0)?,
+                input(inputs, 1)?,
+                *adj_x,
+                *adj_y,
+                &mut outputs[0],
+            )?,
+            Self::Split => {
+                let x = input(inputs, 1)?;
+                let axis = normalize_axis(scalar(input(inputs, 0)?)? as i64, x.shape.len())?;
+                let n = outputs.len();
+                if x.shape[axis] % n != 0 {
+                    return Err(Error::BackendError(format!(
+                        "Cannot split dimension `{}` to `{}` parts",
+                        x.shape[axis], n
+                    )));
+                }
+                let sizes = vec![x.shape[axis] / n; n];
+                split(x, axis, &sizes, outputs);
+            }
+            Self::SplitV => {
+                let x = input(inputs, 0)?;
+                let axis = normalize_axis(scalar(input(inputs, 2)?)? as i64, x.shape.len())?;
+                let sizes: Vec<i64> = input(inputs, 1)?.data.iter().map(|v| *v as i64).collect();
+                let known: i64 = sizes.iter().filter(|s| **s >= 0).sum();
+                let sizes: Vec<usize> = sizes
+                    .iter()
+                    .map(|s| {
+                        if *s < 0 {
+                            x.shape[axis] - known as usize
+                        } else {
+                            *s as usize
+                        }
+                    })
+                    .collect();
+                split(x, axis, &sizes, outputs);
+            }
+            Self::Pack(axis) => {
+                let xs: Vec<&TensorRef> = inputs.iter().flatten().collect();
+                let x = xs[0];
+                let axis = normalize_axis(*axis as i64, x.shape.len() + 1)?;
+                let mut shape = x.shape.to_vec();
+                shape.insert(axis, xs.len());
+                let outer = x.shape[..axis].iter().product::<usize>();
+                let inner = x.shape[axis..].iter().product::<usize>();
+                let out = &mut outputs[0];
+                out.1.clear();
+                for o in 0..outer {
+                    for x in xs.iter() {
+                        out.1.extend_from_slice(&x.data[o * inner..(o + 1) * inner]);
+                    }
+                }
+                out.0 = shape;
+            }
+            Self::Unpack(axis) => {
+                let x = input(inputs, 0)?;
+                let axis = normalize_axis(*axis as i64, x.shape.len())?;
+                let sizes = vec![1; x.shape[axis]];
+                split(x, axis, &sizes, outputs);
+                for o in outputs.iter_mut() {
+                    o.0.remove(axis);
+                }
+            }
+            Self::Tile => tile(input(inputs, 0)?, input(inputs, 1)?, &mut outputs[0])?,
+            Self::ArgMax => {
+                arg_reduce(input(inputs, 0)?, input(inputs, 1)?, true, &mut outputs[0])?
+            }
+            Self::ArgMin => {
+                arg_reduce(input(inputs, 0)?, input(inputs, 1)?, false, &mut outputs[0])?
+            }
+            Self::L2Normalization(activation) => {
+                let x = input(inputs, 0)?;
+                let depth = *x.shape.last().unwrap_or(&1);
+                let out = &mut outputs[0];
+                out.1.clear();
+                for c in x.data.chunks(depth.max(1)) {
+                    let norm = c.iter().map(|v| v * v).sum::<f32>().max(1e-6).sqrt();
+                    out.1.extend(c.iter().map(|v| v / norm));
+                }
+                out.0 = x.shape.to_vec();
+                activation.apply(&mut out.1);
+            }
+            Self::Shape => {
+                let x = input(inputs, 0)?;
+                let out = &mut outputs[0];
+                out.1.clear();
+                out.1.extend(x.shape.iter().map(|d| *d as f32));
+                out.0 = vec![x.shape.len()];
+            }
+        }
+        Ok(())
+    }
+}
+
+#[inline(always)]
+fn input<'a, 'b>(
+    inputs: &'a [Option<TensorRef<'b>>],
+    index: usize,
+) -> Result<&'a TensorRef<'b>, Error> {
+    inputs
+        .get(index)
+        .and_then(|i| i.as_ref())
+        .ok_or_else(|| Error::BackendError(format!("Operator input `{}` is missing", index)))
+}
+
+#[inline(always)]
+fn optional_input<'a, 'b>(
+    inputs: &'a [Option<TensorRef<'b>>],
+    index: usize,
+) -> Option<&'a TensorRef<'b>> {
+    inputs.get(index).and_then(|i| i.as_ref())
+}
+
+#[inline(always)]
+fn scalar(x: &TensorRef) -> Result<f32, Error> {
+    x.data
+        .first()
+        .cloned()
+        .ok_or_else(|| Error::BackendError("Expect a scalar tensor, but got empty".into()))
+}
+
+#[inline(always)]
+fn to_usize_vec(data: &[f32]) -> Vec<usize> {
+    data.iter().map(|v| *v as usize).collect()
+}
+
+#[inline(always)]
+fn normalize_axis(axis: i64, rank: usize) -> Result<usize, Error> {
+    let a = if axis < 0 { axis + rank as i64 } else { axis };
+    if a < 0 || a as usize >= rank.max(1) {
+        return Err(Error::BackendError(format!(
+            "Axis `{}` is out of range for rank `{}`",
+            axis, rank
+        )));
+    }
+    Ok(a as usize)
+}
+
+#[inline(always)]
+fn strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for i in (0..shape.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1];
+    }
+    strides
+}
+
+/// Call the function with every index of the shape in row-major order.
+#[inline(always)]
+fn for_each_index(shape: &[usize], mut f: impl FnMut(&[usize])) {
+    let num = shape.iter().product::<usize>();
+    let mut index = vec![0; shape.len()];
+    for _ in 0..num {
+        f(&index);
+        for d in (0..shape.len()).rev() {
+            index[d] += 1;
+            if index[d] < shape[d] {
+                break;
+            }
+            index[d] = 0;
+        }
+    }
+}
+
+#[inline(always)]
+fn offset(index: &[usize], strides: &[usize]) -> usize {
+    index.iter().zip(strides).map(|(i, s)| i * s).sum()
+}
+
+fn resolve_shape(shape: &[i64], num: usize) -> Result<Vec<usize>, Error> {
+    let known = shape.iter().filter(|d| **d >= 0).product::<i64>().max(1) as usize;
+    let res: Vec<usize> = shape
+        .iter()
+        .map(|d| if *d < 0 { num / known } else { *d as usize })
+        .collect();
+    if res.iter().product::<usize>() != num {
+        return Err(Error::BackendError(format!(
+            "Cannot reshape `{}` elements to shape `{:?}`",
+            num, shape
+        )));
+    }
+    Ok(res)
+}
+
+#[inline(always)]
+fn copy_to(data: &[f32], shape: Vec<usize>, out: &mut Output) {
+    out.1.clear();
+    out.1.extend_from_slice(data);
+    out.0 = shape;
+}
+
+#[inline]
+fn erf(x: f32) -> f32 {
+    // Abramowitz and Stegun formula 7.1.26
+    let t = 1. / (1. + 0.3275911 * x.abs());
+    let y = 1.
+        - (((((1.061_405_4 * t - 1.453_152) * t) + 1.421_413_8) * t - 0.284_496_74) * t
+            + 0.254_829_6)
+            * t
+            * (-x * x).exp();
+    y.copysign(x)
+}
+
+fn unary(x: &TensorRef, kind: UnaryKind, out: &mut Output) {
+    out.1.clear();
+    out.1.reserve(x.data.len());
+    let d = x.data.iter().cloned();
+    match kind {
+        UnaryKind::Identity => out.1.extend(d),
+        UnaryKind::Logistic => out.1.extend(d.map(|v| 1. / (1. + (-v).exp()))),
+        UnaryKind::Tanh => out.1.extend(d.map(|v| v.tanh())),
+        UnaryKind::Relu => out.1.extend(d.map(|v| v.max(0.))),
+        UnaryKind::Relu6 => out.1.extend(d.map(|v| v.clamp(0., 6.))),
+        UnaryKind::ReluN1To1 => out.1.extend(d.map(|v| v.clamp(-1., 1.))),
+        UnaryKind::LeakyRelu(alpha) => out.1.extend(d.map(|v| if v > 0. { v } else { v * alpha })),
+        UnaryKind::HardSwish => out.1.extend(d.map(|v| v * (v + 3.).clamp(0., 6.) / 6.)),
+        UnaryKind::Exp => out.1.extend(d.map(|v| v.exp())),
+        UnaryKind::Log => out.1.extend(d.map(|v| v.ln())),
+        UnaryKind::Sqrt => out.1.extend(d.map(|v| v.sqrt())),
+        UnaryKind::Rsqrt => out.1.extend(d.map(|v| 1. / v.sqrt())),
+        UnaryKind::Abs => out.1.extend(d.map(|v| v.abs())),
+        UnaryKind::Neg => out.1.extend(d.map(|v| -v)),
+        UnaryKind::Square => out.1.extend(d.map(|v| v * v)),
+        UnaryKind::Floor => out.1.extend(d.map(|v| v.floor())),
+        UnaryKind::Elu => out
+            .1
+            .extend(d.map(|v| if v > 0. { v } else { v.exp() - 1. })),
+        UnaryKind::Sin => out.1.extend(d.map(|v| v.sin())),
+        UnaryKind::Cos => out.1.extend(d.map(|v| v.cos())),
+        UnaryKind::Gelu(false) => out
+            .1
+            .extend(d.map(|v| 0.5 * v * (1. + erf(v * std::f32::consts::FRAC_1_SQRT_2)))),
+        UnaryKind::Gelu(true) => out
+            .1
+            .extend(d.map(|v| 0.5 * v * (1. + (0.797_884_6 * (v + 0.044715 * v * v * v)).tanh()))),
+        UnaryKind::Cast(to_int) => {
+            if to_int {
+                out.1.extend(d.map(|v| v.trunc()))
+            } else {
+                out.1.extend(d)
+            }
+        }
+    }
+    out.0 = x.shape.to_vec();
+}
+
+fn broadcast_shape(a: &[usize], b: &[usize]) -> Result<Vec<usize>, Error> {
+    let rank = a.len().max(b.len());
+    let mut shape = vec![1; rank];
+    for i in 0..rank {
+        let da = if i + a.len() >= rank {
+            a[i + a.len() - rank]
+        } else {
+            1
+        };
+        let db = if i + b.len() >= rank {
+            b[i + b.len() - rank]
+        } else {
+            1
+        };
+        shape[i] = if da == db || db == 1 {
+            da
+        } else if da == 1 {
+            db
+        } else {
+            return Err(Error::BackendError(format!(
+                "Cannot broadcast shape `{:?}` and `{:?}`",
+                a, b
+            )));
+        };
+    }
+    Ok(shape)
+}
+
+/// strides of `shape` in the broadcast `out_shape`, the broadcast dimension has stride `0`.
+fn broadcast_strides(shape: &[usize], out_shape: &[usize]) -> Vec<usize> {
+    let rank = out_shape.len();
+    let mut res = vec![0; rank];
+    let mut s = 1;
+    for i in (0..shape.len()).rev() {
+        res[i + rank - shape.len()] = if shape[i] == 1 { 0 } else { s };
+        s *= shape[i];
+    }
+    res
+}
+
+fn binary(a: &TensorRef, b: &TensorRef, kind: BinaryKind, out: &mut Output) -> Result<(), Error> {
+    match kind {
+        BinaryKind::Add => broadcast_binary(a, b, out, |x, y| x + y),
+        BinaryKind::Sub => broadcast_binary(a, b, out, |x, y| x - y),
+        BinaryKind::Mul => broadcast_binary(a, b, out, |x, y| x * y),
+        BinaryKind::Div => broadcast_binary(a, b, out, |x, y| x / y),
+        BinaryKind::Maximum => broadcast_binary(a, b, out, |x, y| x.max(y)),
+        BinaryKind::Minimum => broadcast_binary(a, b, out, |x, y| x.min(y)),
+        BinaryKind::SquaredDifference => broadcast_binary(a, b, out, |x, y| (x - y) * (x - y)),
+        BinaryKind::Pow => broadcast_binary(a, b, out, |x, y| x.powf(y)),
+        BinaryKind::Prelu => broadcast_binary(a, b, out, |x, y| if x >= 0. { x } else { x * y }),
+    }
+}
+
+#[inline(always)]
+fn broadcast_binary(
+    a: &TensorRef,
+    b: &TensorRef,
+    out: &mut Output,
+    f: impl Fn(f32, f32) -> f32,
+) -> Result<(), Error> {
+    let shape = broadcast_shape(a.shape, b.shape)?;
+    let num = shape.iter().product::<usize>();
+    let res = &mut out.1;
+    res.clear();
+    res.reserve(num);
+    if a.data.len() == num && b.data.len() == num {
+        res.extend(a.data.iter().zip(b.data).map(|(x, y)| f(*x, *y)));
+    } else if b.data.len() == 1 && a.data.len() == num {
+        let y = b.data[0];
+        res.extend(a.data.iter().map(|x| f(*x, y)));
+    } else if a.data.len() == 1 && b.data.len() == num {
+        let x = a.data[0];
+        res.extend(b.data.iter().map(|y| f(x, *y)));
+    } else {
+        let sa = broadcast_strides(a.shape, &shape);
+        let sb = broadcast_strides(b.shape, &shape);
+        for_each_index(&shape, |index| {
+            res.push(f(a.data[offset(index, &sa)], b.data[offset(index, &sb)]));
+        });
+    }
+    out.0 = shape;
+    Ok(())
+}
+
+#[inline(always)]
+fn check_rank(x: &TensorRef, rank: usize) -> Result<(), Error> {
+    if x.shape.len() != rank {
+        return Err(Error::BackendError(format!(
+            "Expect tensor rank `{}`, but got shape `{:?}`",
+            rank, x.shape
+        )));
+    }
+    Ok(())
+}
+
+#[inline(always)]
+fn init_with_bias(out: &mut Vec<f32>, num: usize, bias: Option<&TensorRef>, channels: usize) {
+    out.clear();
+    match bias {
+        Some(b) if b.data.len() == channels => {
+            for _ in 0..num / channels.max(1) {
+                out.extend_from_slice(b.data);
+            }
+        }
+        _ => out.resize(num, 0.),
+    }
+}
+
+fn conv_2d(
+    x: &TensorRef,
+    filter: &TensorRef,
+    bias: Option<&TensorRef>,
+    params: &ConvParams,
+    out: &mut Output,
+) -> Result<(), Error> {
+    check_rank(x, 4)?;
+    check_rank(filter, 4)?;
+    let (n, h, w, c) = (x.shape[0], x.shape[1], x.shape[2], x.shape[3]);
+    let (oc, kh, kw, ic) = (
+        filter.shape[0],
+        filter.shape[1],
+        filter.shape[2],
+        filter.shape[3],
+    );
+    if ic == 0 || c % ic != 0 || oc % (c / ic) != 0 {
+        return Err(Error::BackendError(format!(
+            "Conv2D input channels `{}` mismatch filter shape `{:?}`",
+            c, filter.shape
+        )));
+    }
+    let oc_per_group = oc / (c / ic);
+    let (oh, pad_t) = params
+        .padding
+        .compute(h, kh, params.stride_h, params.dilation_h);
+    let (ow, pad_l) = params
+        .padding
+        .compute(w, kw, params.stride_w, params.dilation_w);
+
+    let res = &mut out.1;
+    init_with_bias(res, n * oh * ow * oc, bias, oc);
+    for b in 0..n {
+        for oy in 0..oh {
+            for ox in 0..ow {
+                let base = ((b * oh + oy) * ow + ox) * oc;
+                let acc = &mut res[base..base + oc];
+                for ky in 0..kh {
+                    let iy =
+                        (oy * params.stride_h + ky * params.dilation_h) as isize - pad_t as isize;
+                    if iy < 0 || iy >= h as isize {
+                        continue;
+                    }
+                    for kx in 0..kw {
+                        let ix = (ox * params.stride_w + kx * params.dilation_w) as isize
+                            - pad_l as isize;
+                        if ix < 0 || ix >= w as isize {
+                            continue;
+                        }
+                        let in_base = ((b * h + iy as usize) * w + ix as usize) * c;
+                        for (o, a) in acc.iter_mut().enumerate() {
+                            let group_base = in_base + (o / oc_per_group) * ic;
+                            let in_slice = &x.data[group_base..group_base + ic];
+                            let w_base = ((o * kh + ky) * kw + kx) * ic;
+                            let w_slice = &filter.data[w_base..w_base + ic];
+                            *a += in_slice
+                                .iter()
+                                .zip(w_slice)
+                                .map(|(i, w)| i * w)
+                                .sum::<f32>();
+                        }
+                    }
+                }
+            }
+        }
+    }
+    params.activation.apply(res);
+    out.0 = vec![n, oh, ow, oc];
+    Ok(())
+}
+
+fn depthwise_conv_2d(
+    x: &TensorRef,
+    filter: &TensorRef,
+    bias: Option<&TensorRef>,
+    params: &ConvParams,
+    out: &mut Output,
+) -> Result<(), Error> {
+    check_rank(x, 4)?;
+    check_rank(filter, 4)?;
+    let (n, h, w, c) = (x.shape[0], x.shape[1], x.shape[2], x.shape[3]);
+    let (kh, kw, oc) = (filter.shape[1], filter.shape[2], filter.shape[3]);
+    if c == 0 || oc % c != 0 {
+        return Err(Error::BackendError(format!(
+            "DepthwiseConv2D input channels `{}` mismatch filter shape `{:?}`",
+            c, filter.shape
+        )));
+    }
+    let multiplier = oc / c;
+    let (oh, pad_t) = params
+        .padding
+        .compute(h, kh, params.stride_h, params.dilation_h);
+    let (ow, pad_l) = params
+        .padding
+        .compute(w, kw, params.stride_w, params.dilation_w);
+
+    let res = &mut out.1;
+    init_with_bias(res, n * oh * ow * oc, bias, oc);
+    for b in 0..n {
+        for oy in 0..oh {
+            for ox in 0..ow {
+                let base = ((b * oh + oy) * ow + ox) * oc;
+                let acc = &mut res[base..base + oc];
+                for ky in 0..kh {
+                    let iy =
+                        (oy * params.stride_h + ky * params.dilation_h) as isize - pad_t as isize;
+                    if iy < 0 || iy >= h as isize {
+                        continue;
+                    }
+                    for kx in 0..kw {
+                        let ix = (ox * params.stride_w + kx * params.dilation_w) as isize
+                            - pad_l as isize;
+                        if ix < 0 || ix >= w as isize {
+                            continue;
+                        }
+                        let in_base = ((b * h + iy as usize) * w + ix as usize) * c;
+                        let w_base = (ky * kw + kx) * oc;
+                        for (o, a) in acc.iter_mut().enumerate() {
+                            *a += x.data[in_base + o / multiplier] * filter.data[w_base + o];
+                        }
+                    }
+                }
+            }
+        }
+    }
+    params.activation.apply(res);
+    out.0 = vec![n, oh, ow, oc];
+    Ok(())
+}
+
+fn transpose_conv(
+    x: &TensorRef,
+    filter: &TensorRef,
+    bias: Option<&TensorRef>,
+    output_shape: &[usize],
+    params: &ConvParams,
+    out: &mut Output,
+) -> Result<(), Error> {
+    check_rank(x, 4)?;
+    check_rank(filter, 4)?;
+    if output_shape.len() != 4 {
+        return Err(Error::BackendError(format!(
+            "Invalid transpose conv output shape `{:?}`",
+            output_shape
+        )));
+    }
+    let (n, h, w, c) = (x.shape[0], x.shape[1], x.shape[2], x.shape[3]);
+    let (oc, kh, kw, ic) = (
+        filter.shape[0],
+        filter.shape[1],
+        filter.shape[2],
+        filter.shape[3],
+    );
+    let (oh, ow) = (output_shape[1], output_shape[2]);
+    if ic != c || output_shape[3] != oc {
+        return Err(Error::BackendError(format!(
+            "Transpose conv input shape `{:?}` mismatch filter shape `{:?}`",
+            x.shape, filter.shape
+        )));
+    }
+    let (pad_t, pad_l) = match params.padding {
+        Padding::Same => (
+            ((h - 1) * params.stride_h + kh).saturating_sub(oh) / 2,
+            ((w - 1) * params.stride_w + kw).saturating_sub(ow) / 2,
+        ),
+        Padding::Valid => (0, 0),
+    };
+
+    let res = &mut out.1;
+    init_with_bias(res, n * oh * ow * oc, bias, oc);
+    for b in 0..n {
+        for iy in 0..h {
+            for ix in 0..w {
+                let in_base = ((b * h + iy) * w + ix) * c;
+                let in_slice = &x.data[in_base..in_base + c];
+                for ky in 0..kh {
+                    let oy = (iy * params.stride_h + ky) as isize - pad_t as isize;
+                    if oy < 0 || oy >= oh as isize {
+                        continue;
+                    }
+                    for kx in 0..kw {
+                        let ox = (ix * params.stride_w + kx) as isize - pad_l as isize;
+                        if ox < 0 || ox >= ow as isize {
+                            continue;
+                        }
+                        let out_base = ((b * oh + oy as usize) * ow + ox as usize) * oc;
+                        for o in 0..oc {
+                            let w_base = ((o * kh + ky) * kw + kx) * ic;
+                            let w_slice = &filter.data[w_base..w_base + ic];
+                            res[out_base + o] += in_slice
+                                .iter()
+                                .zip(w_slice)
+                                .map(|(i, w)| i * w)
+                                .sum::<f32>();
+                        }
+                    }
+                }
+            }
+        }
+    }
+    params.activation.apply(res);
+    out.0 = output_shape.to_vec();
+    Ok(())
+}
+
+fn fully_connected(
+    x: &TensorRef,
+    weights: &TensorRef,
+    bias: Option<&TensorRef>,
+    activation: FusedActivation,
+    keep_num_dims: bool,
+    out: &mut Output,
+) -> Result<(), Error> {
+    check_rank(weights, 2)?;
+    let (units, depth) = (weights.shape[0], weights.shape[1]);
+    if depth == 0 || x.data.len() % depth != 0 {
+        return Err(Error::BackendError(format!(
+            "FullyConnected input shape `{:?}` mismatch weights shape `{:?}`",
+            x.shape, weights.shape
+        )));
+    }
+    let batch = x.data.len() / depth;
+    let res = &mut out.1;
+    init_with_bias(res, batch * units, bias, units);
+    for b in 0..batch {
+        let in_slice = &x.data[b * depth..(b + 1) * depth];
+        for u in 0..units {
+            let w_slice = &weights.data[u * depth..(u + 1) * depth];
+            res[b * units + u] += in_slice
+                .iter()
+                .zip(w_slice)
+                .map(|(i, w)| i * w)
+                .sum::<f32>();
+        }
+    }
+    activation.apply(res);
+    out.0 = if keep_num_dims && !x.shape.is_empty() {
+        let mut s = x.shape.to_vec();
+        *s.last_mut().unwrap() = units;
+        s
+    } else {
+        vec![batch, units]
+    };
+    Ok(())
+}
+
+fn pool_2d(x: &TensorRef, params: &PoolParams, max: bool, out: &mut Output) -> Result<(), Error> {
+    check_rank(x, 4)?;
+    let (n, h, w, c) = (x.shape[0], x.shape[1], x.shape[2], x.shape[3]);
+    let (oh, pad_t) = params
+        .padding
+        .compute(h, params.filter_h, params.stride_h, 1);
+    let (ow, pad_l) = params
+        .padding
+        .compute(w, params.filter_w, params.stride_w, 1);
+
+    let res = &mut out.1;
+    res.clear();
+    res.reserve(n * oh * ow * c);
+    for b in 0..n {
+        for oy in 0..oh {
+            let y_start = (oy * params.stride_h) as isize - pad_t as isize;
+            let y_range = y_start.max(0) as usize
+                ..((y_start + params.filter_h as isize).min(h as isize)).max(0) as usize;
+            for ox in 0..ow {
+                let x_start = (ox * params.stride_w) as isize - pad_l as isize;
+                let x_range = x_start.max(0) as usize
+                    ..((x_start + params.filter_w as isize).min(w as isize)).max(0) as usize;
+                let count = (y_range.len() * x_range.len()).max(1) as f32;
+                for ch in 0..c {
+                    let mut acc = if max { f32::MIN } else { 0. };
+                    for iy in y_range.clone() {
+                        for ix in x_range.clone() {
+                            let v = x.data[((b * h + iy) * w + ix) * c + ch];
+                            if max {
+                                acc = acc.max(v);
+                            } else {
+                                acc += v;
+                            }
+                        }
+                    }
+                    res.push(if max { acc } else { acc / count });
+                }
+            }
+        }
+    }
+    params.activation.apply(res);
+    out.0 = vec![n, oh, ow, c];
+    Ok(())
+}
+
+fn concatenation(xs: &[&TensorRef], axis: i32, out: &mut Output) -> Result<(), Error> {
+    let first = xs
+        .first()
+        .ok_or_else(|| Error::BackendError("Concatenation has no input".into()))?;
+    let rank = first.shape.len();
+    let axis = normalize_axis(axis as i64, rank)?;
+    let mut shape = first.shape.to_vec();
+    shape[axis] = 0;
+    for x in xs {
+        if x.shape.len() != rank {
+            return Err(Error::BackendError(format!(
+                "Concatenation input shape `{:?}` mismatch `{:?}`",
+                x.shape, first.shape
+            )));
+        }
+        shape[axis] += x.shape[axis];
+    }
+    let outer = shape[..axis].iter().product::<usize>();
+    let res = &mut out.1;
+    res.clear();
+    for o in 0..outer {
+        for x in xs {
+            let inner = x.shape[axis..].iter().product::<usize>();
+            res.extend_from_slice(&x.data[o * inner..(o + 1) * inner]);
+        }
+    }
+    out.0 = shape;
+    Ok(())
+}
+
+fn softmax(x: &TensorRef, beta: f32, log: bool, out: &mut Output) {
+    let depth = (*x.shape.last().unwrap_or(&1)).max(1);
+    let res = &mut out.1;
+    res.clear();
+    for c in x.data.chunks(depth) {
+        let max = c.iter().cloned().fold(f32::MIN, f32::max);
+        let sum = c.iter().map(|v| ((v - max) * beta).exp()).sum::<f32>();
+        if log {
+            let log_sum = sum.ln();
+            res.extend(c.iter().map(|v| (v - max) * beta - log_sum));
+        } else {
+            res.extend(c.iter().map(|v| ((v - max) * beta).exp() / sum));
+        }
+    }
+    out.0 = x.shape.to_vec();
+}
+
+fn pad(x: &TensorRef, paddings: &TensorRef, value: f32, out: &mut Output) -> Result<(), Error> {
+    let rank = x.shape.len();
+    if paddings.data.len() != rank * 2 {
+        return Err(Error::BackendError(format!(
+            "Pad paddings shape `{:?}` mismatch input rank `{}`",
+            paddings.shape, rank
+        )));
+    }
+    let before: Vec<usize> = (0..rank).map(|i| paddings.data[i * 2] as usize).collect();
+    let shape: Vec<usize> = (0..rank)
+        .map(|i| x.shape[i] + before[i] + paddings.data[i * 2 + 1] as usize)
+        .collect();
+    let out_strides = strides(&shape);
+    let res = &mut out.1;
+    res.clear();
+    res.resize(shape.iter().product(), value);
+    let mut i = 0;
+    for_each_index(x.shape, |index| {
+        let o: usize = index
+            .iter()
+            .zip(before.iter())
+            .zip(out_strides.iter())
+            .map(|((i, b), s)| (i + b) * s)
+            .sum();
+        res[o] = x.data[i];
+        i += 1;
+    });
+    out.0 = shape;
+    Ok(())
+}
+
+fn reduce(
+    x: &TensorRef,
+    axes: &TensorRef,
+    kind: ReduceKind,
+    keep_dims: bool,
+    out: &mut Output,
+) -> Result<(), Error> {
+    let rank = x.shape.len();
+    let mut reduced = vec![false; rank];
+    for a in axes.data {
+        reduced[normalize_axis(*a as i64, rank)?] = true;
+    }
+    let kept_shape: Vec<usize> = x
+        .shape
+        .iter()
+        .zip(reduced.iter())
+        .map(|(d, r)| if *r { 1 } else { *d })
+        .collect();
+    let kept_strides: Vec<usize> = strides(&kept_shape)
+        .into_iter()
+        .zip(reduced.iter())
+        .map(|(s, r)| if *r { 0 } else { s })
+        .collect();
+    let num = kept_shape.iter().product::<usize>();
+    let init = match kind {
+        ReduceKind::Mean | ReduceKind::Sum => 0.,
+        ReduceKind::Max => f32::MIN,
+        ReduceKind::Min => f32::MAX,
+        ReduceKind::Prod => 1.,
+    };
+    let res = &mut out.1;
+    res.clear();
+    res.resize(num, init);
+    let mut i = 0;
+    for_each_index(x.shape, |index| {
+        let o = offset(index, &kept_strides);
+        let v = x.data[i];
+        res[o] = match kind {
+            ReduceKind::Mean | ReduceKind::Sum => res[o] + v,
+            ReduceKind::Max => res[o].max(v),
+            ReduceKind::Min => res[o].min(v),
+            ReduceKind::Prod => res[o] * v,
+        };
+        i += 1;
+    });
+    if let ReduceKind::Mean = kind {
+        let count = (x.data.len() / num.max(1)).max(1) as f32;
+        res.iter_mut().for_each(|v| *v /= count);
+    }
+    out.0 = if keep_dims {
+        kept_shape
+    } else {
+        x.shape
+            .iter()
+            .zip(reduced.iter())
+            .filter(|(_, r)| !**r)
+            .map(|(d, _)| *d)
+            .collect()
+    };
+    Ok(())
+}
+
+fn resize(
+    x: &TensorRef,
+    size: &TensorRef,
+    bilinear: bool,
+    align_corners: bool,
+    half_pixel_centers: bool,
+    out: &mut Output,
+) -> Result<(), Error> {
+    check_rank(x, 4)?;
+    if size.data.len() != 2 {
+        return Err(Error::BackendError(
+            "Resize size must have 2 elements".into(),
+        ));
+    }
+    let (n, h, w, c) = (x.shape[0], x.shape[1], x.shape[2], x.shape[3]);
+    let (oh, ow) = (size.data[0] as usize, size.data[1] as usize);
+    let scale = |i: usize, o: usize| {
+        if align_corners && o > 1 {
+            (i - 1) as f32 / (o - 1) as f32
+        } else {
+            i as f32 / o as f32
+        }
+    };
+    let (scale_y, scale_x) = (scale(h, oh), scale(w, ow));
+
+    let res = &mut out.1;
+    res.clear();
+    res.reserve(n * oh * ow * c);
+    for b in 0..n {
+        for oy in 0..oh {
+            for ox in 0..ow {
+                if bilinear {
+                    let (in_y, in_x) = if half_pixel_centers {
+                        (
+                            ((oy as f32 + 0.5) * scale_y - 0.5).max(0.),
+                            ((ox as f32 + 0.5) * scale_x - 0.5).max(0.),
+                        )
+                    } else {
+                        (oy as f32 * scale_y, ox as f32 * scale_x)
+                    };
+                    let y0 = (in_y.floor() as usize).min(h - 1);
+                    let x0 = (in_x.floor() as usize).min(w - 1);
+                    let y1 = (y0 + 1).min(h - 1);
+                    let x1 = (x0 + 1).min(w - 1);
+                    let (dy, dx) = (in_y - y0 as f32, in_x - x0 as f32);
+                    for ch in 0..c {
+                        let p = |y: usize, x_: usize| x.data[((b * h + y) * w + x_) * c + ch];
+                        let top = p(y0, x0) * (1. - dx) + p(y0, x1) * dx;
+                        let bottom = p(y1, x0) * (1. - dx) + p(y1, x1) * dx;
+                        res.push(top * (1. - dy) + bottom * dy);
+                    }
+                } else {
+                    let nearest = |o: usize, s: f32, i: usize| {
+                        let offset = if half_pixel_centers { 0.5 } else { 0. };
+                        let v = (o as f32 + offset) * s;
+                        let v = if align_corners { v.round() } else { v.floor() };
+                        (v.max(0.) as usize).min(i - 1)
+                    };
+                    let (iy, ix) = (nearest(oy, scale_y, h), nearest(ox, scale_x, w));
+                    let base = ((b * h + iy) * w + ix) * c;
+                    res.extend_from_slice(&x.data[base..base + c]);
+                }
+            }
+        }
+    }
+    out.0 = vec![n, oh, ow, c];
+    Ok(())
+}
+
+/// Copy the elements selected by ```indices``` of every dimension.
+fn select(x: &TensorRef, indices: &[Vec<usize>], shape: Vec<usize>, out: &mut Output) {
+    let in_strides = strides(x.shape);
+    let select_shape: Vec<usize> = indices.iter().map(|i| i.len()).collect();
+    let res = &mut out.1;
+    res.clear();
+    res.reserve(select_shape.iter().product());
+    for_each_index(&select_shape, |index| {
+        let o: usize = index
+            .iter()
+            .enumerate()
+            .map(|(d, i)| indices[d][*i] * in_strides[d])
+            .sum();
+        res.push(x.data[o]);
+    });
+    out.0 = shape;
+}
+
+fn strided_slice(
+    x: &TensorRef,
+    begin: &TensorRef,
+    end: &TensorRef,
+    stride: Option<&TensorRef>,
+    (begin_mask, end_mask, shrink_axis_mask): (i32, i32, i32),
+    out: &mut Output,
+) -> Result<(), Error> {
+    let rank = x.shape.len();
+    let mut indices = Vec::with_capacity(rank);
+    let mut shape = Vec::with_capacity(rank);
+    for d in 0..rank {
+        let dim = x.shape[d] as i64;
+        let s = stride
+            .and_then(|s| s.data.get(d))
+            .map(|v| *v as i64)
+            .unwrap_or(1);
+        if s == 0 {
+            return Err(Error::BackendError(
+                "Strided slice stride cannot be 0".into(),
+            ));
+        }
+        let clamp = |v: i64| {
+            if s > 0 {
+                v.clamp(0, dim)
+            } else {
+                v.clamp(-1, dim - 1)
+            }
+        };
+        let normalize = |v: i64| if v < 0 { v + dim } else { v };
+        let b = begin.data.get(d).map(|v| *v as i64).unwrap_or(0);
+        let e = end.data.get(d).map(|v| *v as i64).unwrap_or(dim);
+        if shrink_axis_mask & (1 << d) != 0 {
+            let b = normalize(b).clamp(0, dim - 1) as usize;
+            indices.push(vec![b]);
+            continue;
+        }
+        let b = if begin_mask & (1 << d) != 0 {
+            if s > 0 {
+                0
+            } else {
+                dim - 1
+            }
+        } else {
+            clamp(normalize(b))
+        };
+        let e = if end_mask & (1 << d) != 0 {
+            if s > 0 {
+                dim
+            } else {
+                -1
+            }
+        } else {
+            clamp(normalize(e))
+        };
+        let mut index = Vec::new();
+        let mut i = b;
+        while (s > 0 && i < e) || (s < 0 && i > e) {
+            index.push(i as usize);
+            i += s;
+        }
+        shape.push(index.len());
+        indices.push(index);
+    }
+    select(x, &indices, shape, out);
+    Ok(())
+}
+
+fn slice(
+    x: &TensorRef,
+    begin: &TensorRef,
+    size: &TensorRef,
+    out: &mut Output,
+) -> Result<(), Error> {
+    let rank = x.shape.len();
+    if begin.data.len() != rank || size.data.len() != rank {
+        return Err(Error::BackendError(format!(
+            "Slice begin or size mismatch input rank `{}`",
+            rank
+        )));
+    }
+    let mut indices = Vec::with_capacity(rank);
+    for d in 0..rank {
+        let b = begin.data[d] as usize;
+        let s = if size.data[d] < 0. {
+            x.shape[d].saturating_sub(b)
+        } else {
+            size.data[d] as usize
+        };
+        if b + s > x.shape[d] {
+            return Err(Error::BackendError(format!(
+                "Slice is out of range for shape `{:?}`",
+                x.shape
+            )));
+        }
+        indices.push((b..b + s).collect::<Vec<_>>());
+    }
+    let shape = indices.iter().map(|i| i.len()).collect();
+    select(x, &indices, shape, out);
+    Ok(())
+}
+
+fn gather(
+    params: &TensorRef,
+    indices: &TensorRef,
+    axis: i32,
+    out: &mut Output,
+) -> Result<(), Error> {
+    let axis = normalize_axis(axis as i64, params.shape.len())?;
+    let dim = params.shape[axis];
+    let outer = params.shape[..axis].iter().product::<usize>();
+    let inner = params.shape[axis + 1..].iter().product::<usize>();
+    let res = &mut out.1;
+    res.clear();
+    res.reserve(outer * indices.data.len() * inner);
+    for o in 0..outer {
+        for i in indices.data {
+            let i = *i as i64;
+            if i < 0 || i as usize >= dim {
+                return Err(Error::BackendError(format!(
+                    "Gather index `{}` is out of range `{}`",
+                    i, dim
+                )));
+            }
+            let base = (o * dim + i as usize) * inner;
+            res.extend_from_slice(&params.data[base..base + inner]);
+        }
+    }
+    let mut shape = params.shape[..axis].to_vec();
+    shape.extend_from_slice(indices.shape);
+    shape.extend_from_slice(&params.shape[axis + 1..]);
+    out.0 = shape;
+    Ok(())
+}
+
+fn transpose(x: &TensorRef, perm: &TensorRef, out: &mut Output) -> Result<(), Error> {
+    let rank = x.shape.len();
+    let perm = perm
+        .data
+        .iter()
+        .map(|p| normalize_axis(*p as i64, rank))
+        .collect::<Result<Vec<_>, _>>()?;
+    if perm.len() != rank {
+        return Err(Error::BackendError(format!(
+            "Transpose perm `{:?}` mismatch input rank `{}`",
+            perm, rank
+        )));
+    }
+    let in_strides = strides(x.shape);
+    let shape: Vec<usize> = perm.iter().map(|p| x.shape[*p]).collect();
+    let permuted_strides: Vec<usize> = perm.iter().map(|p| in_strides[*p]).collect();
+    let res = &mut out.1;
+    res.clear();
+    res.reserve(x.data.len());
+    for_each_index(&shape, |index| {
+        res.push(x.data[offset(index, &permuted_strides)]);
+    });
+    out.0 = shape;
+    Ok(())
+}
+
+fn batch_mat_mul(
+    lhs: &TensorRef,
+    rhs: &TensorRef,
+    adj_x: bool,
+    adj_y: bool,
+    out: &mut Output,
+) -> Result<(), Error> {
+    if lhs.shape.len() < 2 || rhs.shape.len() < 2 {
+        return Err(Error::BackendError(
+            "BatchMatMul inputs rank must be at least 2".into(),
+        ));
+    }
+    let (lr, rr) = (lhs.shape.len(), rhs.shape.len());
+    let (m, k) = if adj_x {
+        (lhs.shape[lr - 1], lhs.shape[lr - 2])
+    } else {
+        (lhs.shape[lr - 2], lhs.shape[lr - 1])
+    };
+    let (k2, n) = if adj_y {
+        (rhs.shape[rr - 1], rhs.shape[rr - 2])
+    } else {
+        (rhs.shape[rr - 2], rhs.shape[rr - 1])
+    };
+    if k != k2 {
+        return Err(Error::BackendError(format!(
+            "BatchMatMul shape `{:?}` mismatch `{:?}`",
+            lhs.shape, rhs.shape
+        )));
+    }
+    let batch_shape = broadcast_shape(&lhs.shape[..lr - 2], &rhs.shape[..rr - 2])?;
+    let ls = broadcast_strides(&lhs.shape[..lr - 2], &batch_shape);
+    let rs = broadcast_strides(&rhs.shape[..rr - 2], &batch_shape);
+    let res = &mut out.1;
+    res.clear();
+    res.reserve(batch_shape.iter().product::<usize>() * m * n);
+    for_each_index(&batch_shape, |index| {
+        let l = &lhs.data[offset(index, &ls) * m * k..];
+        let r = &rhs.data[offset(index, &rs) * k * n..];
+        for i in 0..m {
+            for j in 0..n {
+                let mut acc = 0.;
+                for p in 0..k {
+                    let a = if adj_x { l[p * m + i] } else { l[i * k + p] };
+                    let b = if adj_y { r[j * k + p] } else { r[p * n + j] };
+                    acc += a * b;
+                }
+                res.push(acc);
+            }
+        }
+    });
+    let mut shape = batch_shape;
+    shape.push(m);
+    shape.push(n);
+    out.0 = shape;
+    Ok(())
+}
+
+fn split(x: &TensorRef, axis: usize, sizes: &[usize], outputs: &mut [Output]) {
+    let outer = x.shape[..axis].iter().product::<usize>();
+    let inner = x.shape[axis + 1..].iter().product::<usize>();
+    let dim = x.shape[axis];
+    let mut start = 0;
+    for (size, out) in sizes.iter().zip(outputs.iter_mut()) {
+        out.1.clear();
+        for o in 0..outer {
+            let base = (o * dim + start) * inner;
+            out.1.extend_from_slice(&x.data[base..base + size * inner]);
+        }
+        let mut shape = x.shape.to_vec();
+        shape[axis] = *size;
+        out.0 = shape;
+        start += size;
+    }
+}
+
+fn tile(x: &TensorRef, multiples: &TensorRef, out: &mut Output) -> Result<(), Error> {
+    let rank = x.shape.len();
+    if multiples.data.len() != rank {
+        return Err(Error::BackendError(format!(
+            "Tile multiples mismatch input rank `{}`",
+            rank
+        )));
+    }
+    let shape: Vec<usize> = x
+        .shape
+        .iter()
+        .zip(multiples.data)
+        .map(|(d, m)| d * (*m as usize))
+        .collect();
+    let in_strides = strides(x.shape);
+    let res = &mut out.1;
+    res.clear();
+    for_each_index(&shape, |index| {
+        let o: usize = index
+            .iter()
+            .zip(x.shape.iter())
+            .zip(in_strides.iter())
+            .map(|((i, d), s)| (i % d) * s)
+            .sum();
+        res.push(x.data[o]);
+    });
+    out.0 = shape;
+    Ok(())
+}
+
+fn arg_reduce(x: &TensorRef, axis: &TensorRef, max: bool, out: &mut Output) -> Result<(), Error> {
+    let axis = normalize_axis(scalar(axis)? as i64, x.shape.len())?;
+    let outer = x.shape[..axis].iter().product::<usize>();
+    let inner = x.shape[axis + 1..].iter().product::<usize>();
+    let dim = x.shape[axis];
+    let res = &mut out.1;
+    res.clear();
+    for o in 0..outer {
+        for i in 0..inner {
+            let mut best = 0;
+            for d in 1..dim {
+                let v = x.data[(o * dim + d) * inner + i];
+                let b = x.data[(o * dim + best) * inner + i];
+                if (max && v > b) || (!max && v < b) {
+                    best = d;
+                }
+            }
+            res.push(best as f32);
+        }
+    }
+    let mut shape = x.shape.to_vec();
+    shape.remove(axis);
+    out.0 = shape;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tensor<'a>(shape: &'a [usize], data: &'a [f32]) -> TensorRef<'a> {
+        TensorRef { shape, data }
+    }
+
+    fn conv_params(padding: Padding, stride: usize, dilation: usize) -> ConvParams {
+        ConvParams {
+            padding,
+            stride_h: stride,
+            stride_w: stride,
+            dilation_h: dilation,
+            dilation_w: dilation,
+            activation: FusedActivation::None,
+        }
+    }
+
+    fn pool_params(padding: Padding, filter: usize, stride: usize) -> PoolParams {
+        PoolParams {
+            padding,
+            stride_h: stride,
+            stride_w: stride,
+            filter_h: filter,
+            filter_w: filter,
+            activation: FusedActivation::None,
+        }
+    }
+
+    fn range(n: usize) -> Vec<f32> {
+        (1..=n).map(|v| v as f32).collect()
+    }
+
+    #[test]
+    fn test_padding() {
+        // (in_size, kernel, stride, dilation)
+        assert_eq!(Padding::Same.compute(4, 3, 1, 1), (4, 1));
+        assert_eq!(Padding::Same.compute(4, 3, 2, 1), (2, 0));
+        assert_eq!(Padding::Same.compute(5, 3, 2, 1), (3, 1));
+        assert_eq!(Padding::Same.compute(4, 3, 1, 2), (4, 2));
+        assert_eq!(Padding::Valid.compute(4, 3, 1, 1), (2, 0));
+        assert_eq!(Padding::Valid.compute(5, 3, 2, 1), (2, 0));
+        assert_eq!(Padding::Valid.compute(5, 3, 1, 2), (1, 0));
+    }
+
+    #[test]
+    fn test_conv_2d() {
+        let data = range(16);
+        let x = tensor(&[1, 4, 4, 1], &data);
+        let ones = [1.; 9];
+        let filter = tensor(&[1, 3, 3, 1], &ones);
+        let mut out = Output::default();
+
+        let params = conv_params(Padding::Same, 1, 1);
+        conv_2d(&x, &filter, None, &params, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 4, 4, 1]);
+        #[rustfmt::skip]
+        assert_eq!(out.1, vec![
+            14., 24., 30., 22.,
+            33., 54., 63., 45.,
+            57., 90., 99., 69.,
+            46., 72., 78., 54.,
+        ]);
+
+        // the extra padding is at the end
+        let params = conv_params(Padding::Same, 2, 1);
+        conv_2d(&x, &filter, None, &params, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 2, 2, 1]);
+        assert_eq!(out.1, vec![54., 45., 72., 54.]);
+
+        let params = conv_params(Padding::Valid, 1, 1);
+        conv_2d(&x, &filter, None, &params, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 2, 2, 1]);
+        assert_eq!(out.1, vec![54., 63., 90., 99.]);
+
+        let params = conv_params(Padding::Same, 1, 2);
+        conv_2d(&x, &filter, None, &params, &mut out).unwrap();
+        #[rustfmt::skip]
+        assert_eq!(out.1, vec![
+            24., 28., 24., 28.,
+            40., 44., 40., 44.,
+            24., 28., 24., 28.,
+            40., 44., 40., 44.,
+        ]);
+
+        let data = range(25);
+        let x5 = tensor(&[1, 5, 5, 1], &data);
+        let params = conv_params(Padding::Valid, 1, 2);
+        conv_2d(&x5, &filter, None, &params, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 1, 1, 1]);
+        assert_eq!(out.1, vec![117.]);
+
+        // 2 input channels and 2 output channels with bias and relu
+        let x = tensor(&[1, 1, 1, 2], &[1., 2.]);
+        let filter = tensor(&[2, 1, 1, 2], &[1., 1., 1., -1.]);
+        let bias = tensor(&[2], &[0.5, 0.]);
+        let mut params = conv_params(Padding::Valid, 1, 1);
+        params.activation = FusedActivation::Relu;
+        conv_2d(&x, &filter, Some(&bias), &params, &mut out).unwrap();
+        assert_eq!(out.1, vec![3.5, 0.]);
+
+        let filter = tensor(&[1, 1, 1, 3], &[1., 1., 1.]);
+        assert!(conv_2d(&x, &filter, None, &params, &mut out).is_err());
+    }
+
+    #[test]
+    fn test_depthwise_conv_2d() {
+        let data = range(9);
+        let x = tensor(&[1, 3, 3, 1], &data);
+        // depth multiplier is 2, the second channel is `x[0, 0] - x[1, 1]`
+        let filter = tensor(&[1, 2, 2, 2], &[1., 1., 1., 0., 1., 0., 1., -1.]);
+        let mut out = Output::default();
+
+        let params = conv_params(Padding::Valid, 1, 1);
+        depthwise_conv_2d(&x, &filter, None, &params, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 2, 2, 2]);
+        assert_eq!(out.1, vec![12., -4., 16., -4., 24., -4., 28., -4.]);
+
+        let params = conv_params(Padding::Same, 2, 1);
+        depthwise_conv_2d(&x, &filter, None, &params, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 2, 2, 2]);
+        assert_eq!(out.1, vec![12., -4., 9., 3., 15., 7., 9., 9.]);
+
+        let bias = tensor(&[2], &[1., 2.]);
+        let mut params = conv_params(Padding::Valid, 2, 1);
+        params.activation = FusedActivation::Relu6;
+        depthwise_conv_2d(&x, &filter, Some(&bias), &params, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 1, 1, 2]);
+        assert_eq!(out.1, vec![6., 0.]);
+
+        let filter = tensor(&[1, 1, 1, 3], &[1., 1., 1.]);
+        let x = tensor(&[1, 1, 1, 2], &[1., 2.]);
+        assert!(depthwise_conv_2d(&x, &filter, None, &params, &mut out).is_err());
+    }
+
+    #[test]
+    fn test_transpose_conv() {
+        let x = tensor(&[1, 2, 2, 1], &[1., 2., 3., 4.]);
+        let filter = tensor(&[1, 2, 2, 1], &[1.; 4]);
+        let mut out = Output::default();
+        let params = conv_params(Padding::Valid, 2, 1);
+        transpose_conv(&x, &filter, None, &[1, 4, 4, 1], &params, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 4, 4, 1]);
+        #[rustfmt::skip]
+        assert_eq!(out.1, vec![
+            1., 1., 2., 2.,
+            1., 1., 2., 2.,
+            3., 3., 4., 4.,
+            3., 3., 4., 4.,
+        ]);
+    }
+
+    #[test]
+    fn test_pool_2d() {
+        let data = range(16);
+        let x = tensor(&[1, 4, 4, 1], &data);
+        let mut out = Output::default();
+        let params = pool_params(Padding::Valid, 2, 2);
+        pool_2d(&x, &params, true, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 2, 2, 1]);
+        assert_eq!(out.1, vec![6., 8., 14., 16.]);
+        pool_2d(&x, &params, false, &mut out).unwrap();
+        assert_eq!(out.1, vec![3.5, 5.5, 11.5, 13.5]);
+
+        // the padding is not counted by the average
+        let data = range(9);
+        let x = tensor(&[1, 3, 3, 1], &data);
+        let params = pool_params(Padding::Same, 2, 2);
+        pool_2d(&x, &params, false, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 2, 2, 1]);
+        assert_eq!(out.1, vec![3., 4.5, 7.5, 9.]);
+        pool_2d(&x, &params, true, &mut out).unwrap();
+        assert_eq!(out.1, vec![5., 6., 8., 9.]);
+    }
+
+    #[test]
+    fn test_resize() {
+        let x = tensor(&[1, 2, 2, 1], &[0., 1., 2., 3.]);
+        let mut out = Output::default();
+
+        let size = tensor(&[2], &[3., 3.]);
+        resize(&x, &size, true, true, false, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 3, 3, 1]);
+        assert_eq!(out.1, vec![0., 0.5, 1., 1., 1.5, 2., 2., 2.5, 3.]);
+
+        let size = tensor(&[2], &[4., 4.]);
+        resize(&x, &size, true, false, true, &mut out).unwrap();
+        #[rustfmt::skip]
+        assert_eq!(out.1, vec![
+            0., 0.25, 0.75, 1.,
+            0.5, 0.75, 1.25, 1.5,
+            1.5, 1.75, 2.25, 2.5,
+            2., 2.25, 2.75, 3.,
+        ]);
+
+        resize(&x, &size, false, false, false, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 4, 4, 1]);
+        #[rustfmt::skip]
+        assert_eq!(out.1, vec![
+            0., 0., 1., 1.,
+            0., 0., 1., 1.,
+            2., 2., 3., 3.,
+            2., 2., 3., 3.,
+        ]);
+
+        assert!(resize(&x, &tensor(&[1], &[4.]), true, false, false, &mut out).is_err());
+    }
+
+    #[test]
+    fn test_concatenation() {
+        let a = tensor(&[1, 2, 1], &[1., 2.]);
+        let b = tensor(&[1, 2, 2], &[3., 4., 5., 6.]);
+        let mut out = Output::default();
+        concatenation(&[&a, &b], -1, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 2, 3]);
+        assert_eq!(out.1, vec![1., 3., 4., 2., 5., 6.]);
+
+        concatenation(&[&b, &b], 1, &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 4, 2]);
+        assert_eq!(out.1, vec![3., 4., 5., 6., 3., 4., 5., 6.]);
+
+        let c = tensor(&[2, 2], &[3., 4., 5., 6.]);
+        assert!(concatenation(&[&a, &c], 0, &mut out).is_err());
+    }
+
+    #[test]
+    fn test_strided_slice() {
+        let data = range(6);
+        let x = tensor(&[2, 3], &data);
+        let mut out = Output::default();
+        // the int32 max end value means to the end
+        let begin = tensor(&[2], &[1., 1.]);
+        let end = tensor(&[2], &[2., i32::MAX as f32]);
+        strided_slice(&x, &begin, &end, None, (0, 0, 0), &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 2]);
+        assert_eq!(out.1, vec![5., 6.]);
+
+        let stride = tensor(&[2], &[1., -2.]);
+        strided_slice(&x, &begin, &end, Some(&stride), (3, 2, 0), &mut out).unwrap();
+        assert_eq!(out.0, vec![2, 2]);
+        assert_eq!(out.1, vec![3., 1., 6., 4.]);
+    }
+}
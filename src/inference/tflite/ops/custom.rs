@@ -0,0 +1,684 @@
+//! The custom operators used by the MediaPipe task models:
+//! * ```Convolution2DTransposeBias```, ```MaxPoolingWithArgmax2D``` and ```MaxUnpooling2D```: MediaPipe
+//!   operators, used by the segmentation and stylization models.
+//! * ```TFLite_Detection_PostProcess```: TensorFlow Lite operator, used by the SSD and EfficientDet object
+//!   detection models.
+//!
+// These references files are licensed under Apache 2.0, and originally developed by Google:
+// * https://github.com/google/mediapipe/tree/master/mediapipe/util/tflite/operations
+// * https://github.com/tensorflow/tensorflow/blob/master/tensorflow/lite/kernels/detection_postprocess.cc
+
+use super::*;
+use std::collections::HashMap;
+
+/// Parameters of the ```TFLite_Detection_PostProcess``` operator.
+#[derive(Debug, Clone)]
+pub(in super::super) struct DetectionPostProcessParams {
+    max_detections: usize,
+    max_classes_per_detection: usize,
+    detections_per_class: usize,
+    use_regular_nms: bool,
+    score_threshold: f32,
+    iou_threshold: f32,
+    num_classes: usize,
+    /// ```(y_scale, x_scale, h_scale, w_scale)```
+    scales: [f32; 4],
+}
+
+pub(super) fn parse(custom_code: Option<&str>, options: &[u8]) -> Result<Operator, Error> {
+    Ok(match custom_code {
+        Some("Convolution2DTransposeBias") => {
+            // custom options is the struct `TfLiteTransposeConvParams`
+            let params = options_to_i32(options);
+            if params.len() < 3 {
+                return Err(Error::ModelParseError(
+                    "Invalid custom options for `Convolution2DTransposeBias`".into(),
+                ));
+            }
+            Operator::TransposeConvBias(ConvParams {
+                padding: parse_padding(params[0]),
+                stride_w: params[1].max(1) as usize,
+                stride_h: params[2].max(1) as usize,
+                dilation_h: 1,
+                dilation_w: 1,
+                activation: FusedActivation::None,
+            })
+        }
+        Some("MaxPoolingWithArgmax2D") => {
+            Operator::MaxPoolWithArgmax(parse_pool_params("MaxPoolingWithArgmax2D", options)?)
+        }
+        Some("MaxUnpooling2D") => {
+            Operator::MaxUnpooling(parse_pool_params("MaxUnpooling2D", options)?)
+        }
+        Some("TFLite_Detection_PostProcess") => {
+            let map = parse_flexbuffer_map(options)?;
+            let get = |key: &str, default: Option<f64>| {
+                map.get(key).cloned().or(default).ok_or_else(|| {
+                    Error::ModelParseError(format!(
+                        "Custom options `{}` is missing for `TFLite_Detection_PostProcess`",
+                        key
+                    ))
+                })
+            };
+            let params = DetectionPostProcessParams {
+                max_detections: get("max_detections", None)? as usize,
+                max_classes_per_detection: get("max_classes_per_detection", Some(1.))? as usize,
+                detections_per_class: get("detections_per_class", Some(100.))? as usize,
+                use_regular_nms: get("use_regular_nms", Some(0.))? != 0.,
+                score_threshold: get("nms_score_threshold", None)? as f32,
+                iou_threshold: get("nms_iou_threshold", None)? as f32,
+                num_classes: get("num_classes", None)? as usize,
+                scales: [
+                    get("y_scale", None)? as f32,
+                    get("x_scale", None)? as f32,
+                    get("h_scale", None)? as f32,
+                    get("w_scale", None)? as f32,
+                ],
+            };
+            if params.num_classes == 0 || params.max_classes_per_detection == 0 {
+                return Err(Error::ModelParseError(
+                    "Invalid custom options for `TFLite_Detection_PostProcess`".into(),
+                ));
+            }
+            Operator::DetectionPostProcess(params)
+        }
+        c => {
+            return Err(Error::ModelParseError(format!(
+                "Unsupported custom operator `{}`",
+                c.unwrap_or_default()
+            )));
+        }
+    })
+}
+
+#[inline(always)]
+fn options_to_i32(options: &[u8]) -> Vec<i32> {
+    options
+        .chunks_exact(4)
+        .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+/// ```TfLitePadding```: kTfLitePaddingValid = 2, other values are same padding.
+#[inline(always)]
+fn parse_padding(p: i32) -> Padding {
+    if p == 2 {
+        Padding::Valid
+    } else {
+        Padding::Same
+    }
+}
+
+/// The custom options is the struct ```TfLitePoolParams```:
+/// ```[padding, stride_width, stride_height, filter_width, filter_height, activation, ...]```.
+fn parse_pool_params(name: &str, options: &[u8]) -> Result<PoolParams, Error> {
+    let params = options_to_i32(options);
+    if params.len() < 6 {
+        return Err(Error::ModelParseError(format!(
+            "Invalid custom options for `{}`",
+            name
+        )));
+    }
+    // TfLiteFusedActivation
+    let activation = match params[5] {
+        0 => FusedActivation::None,
+        1 => FusedActivation::Relu,
+        2 => FusedActivation::ReluN1To1,
+        3 => FusedActivation::Relu6,
+        4 => FusedActivation::Tanh,
+        5 => FusedActivation::SignBit,
+        a => {
+            return Err(Error::ModelParseError(format!(
+                "Unsupported fused activation function `{}` for `{}`",
+                a, name
+            )));
+        }
+    };
+    Ok(PoolParams {
+        padding: parse_padding(params[0]),
+        stride_w: params[1].max(1) as usize,
+        stride_h: params[2].max(1) as usize,
+        filter_w: params[3].max(1) as usize,
+        filter_h: params[4].max(1) as usize,
+        activation,
+    })
+}
+
+/// Parse the FlexBuffers map whose values are numbers or booleans, other values are ignored.
+fn parse_flexbuffer_map(buf: &[u8]) -> Result<HashMap<String, f64>, Error> {
+    const INT: u8 = 1;
+    const UINT: u8 = 2;
+    const FLOAT: u8 = 3;
+    const INDIRECT_INT: u8 = 6;
+    const INDIRECT_UINT: u8 = 7;
+    const INDIRECT_FLOAT: u8 = 8;
+    const MAP: u8 = 9;
+    const BOOL: u8 = 26;
+
+    let err = || Error::ModelParseError("Invalid FlexBuffers custom options".into());
+    let bytes = |pos: usize, width: usize| {
+        pos.checked_add(width)
+            .and_then(|end| buf.get(pos..end))
+            .ok_or_else(err)
+    };
+    let read_uint = |pos: usize, width: usize| -> Result<u64, Error> {
+        let b = bytes(pos, width)?;
+        Ok(match width {
+            1 => b[0] as u64,
+            2 => u16::from_le_bytes([b[0], b[1]]) as u64,
+            4 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as u64,
+            _ => u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]),
+        })
+    };
+    let read_number = |t: u8, pos: usize, width: usize| -> Result<f64, Error> {
+        let b = bytes(pos, width)?;
+        Ok(match (t, width) {
+            (INT, 1) => b[0] as i8 as f64,
+            (INT, 2) => i16::from_le_bytes([b[0], b[1]]) as f64,
+            (INT, 4) => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            (INT, _) => read_uint(pos, width)? as i64 as f64,
+            (FLOAT, 4) => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            (FLOAT, 8) => f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]),
+            (FLOAT, _) => return Err(err()),
+            _ => read_uint(pos, width)? as f64,
+        })
+    };
+    // the offset is relative to its position, and points to the front
+    let indirect = |pos: usize, width: usize| -> Result<usize, Error> {
+        let offset = read_uint(pos, width)?;
+        pos.checked_sub(offset as usize).ok_or_else(err)
+    };
+
+    // the root: [root value][packed type][root byte width]
+    let len = buf.len();
+    if len < 3 {
+        return Err(err());
+    }
+    let root_width = buf[len - 1] as usize;
+    let root_type = buf[len - 2];
+    if root_type >> 2 != MAP {
+        return Err(Error::ModelParseError(
+            "FlexBuffers custom options must be a map".into(),
+        ));
+    }
+    let root_pos = (len - 2).checked_sub(root_width).ok_or_else(err)?;
+    // map: [keys offset][keys byte width][size][values][value packed types]
+    let width = 1usize << (root_type & 3);
+    let map_pos = indirect(root_pos, root_width)?;
+    let size = read_uint(map_pos.checked_sub(width).ok_or_else(err)?, width)? as usize;
+    let keys_width = read_uint(map_pos.checked_sub(width * 2).ok_or_else(err)?, width)? as usize;
+    let keys_pos = indirect(map_pos.checked_sub(width * 3).ok_or_else(err)?, width)?;
+    let types_pos = size
+        .checked_mul(width)
+        .and_then(|s| s.checked_add(map_pos))
+        .ok_or_else(err)?;
+
+    let mut map = HashMap::with_capacity(size);
+    for i in 0..size {
+        let key_pos = indirect(keys_pos + i * keys_width, keys_width)?;
+        let key_len = buf
+            .get(key_pos..)
+            .and_then(|k| k.iter().position(|b| *b == 0))
+            .ok_or_else(err)?;
+        let key = std::str::from_utf8(&buf[key_pos..key_pos + key_len]).map_err(|_| err())?;
+
+        let packed_type = *buf.get(types_pos + i).ok_or_else(err)?;
+        let value_pos = map_pos + i * width;
+        let value = match packed_type >> 2 {
+            t @ (INT | UINT | FLOAT | BOOL) => read_number(t, value_pos, width)?,
+            t @ (INDIRECT_INT | INDIRECT_UINT | INDIRECT_FLOAT) => read_number(
+                t - (INDIRECT_INT - INT),
+                indirect(value_pos, width)?,
+                1 << (packed_type & 3),
+            )?,
+            _ => continue,
+        };
+        map.insert(key.to_string(), value);
+    }
+    Ok(map)
+}
+
+/// Max pooling, and the second output is the index of the max value in every pooling window:
+/// ```filter_y * filter_width + filter_x```, which is used by [`max_unpooling`].
+pub(super) fn max_pool_with_argmax(
+    x: &TensorRef,
+    params: &PoolParams,
+    outputs: &mut [Output],
+) -> Result<(), Error> {
+    check_rank(x, 4)?;
+    if outputs.len() != 2 {
+        return Err(Error::BackendError(format!(
+            "`MaxPoolingWithArgmax2D` expect `2` outputs, but got `{}`",
+            outputs.len()
+        )));
+    }
+    let (n, h, w, c) = (x.shape[0], x.shape[1], x.shape[2], x.shape[3]);
+    let (oh, pad_t) = params
+        .padding
+        .compute(h, params.filter_h, params.stride_h, 1);
+    let (ow, pad_l) = params
+        .padding
+        .compute(w, params.filter_w, params.stride_w, 1);
+
+    let (res, indices) = outputs.split_at_mut(1);
+    let (res, indices) = (&mut res[0].1, &mut indices[0].1);
+    res.clear();
+    res.reserve(n * oh * ow * c);
+    indices.clear();
+    indices.reserve(n * oh * ow * c);
+    for b in 0..n {
+        for oy in 0..oh {
+            let y_start = (oy * params.stride_h) as isize - pad_t as isize;
+            for ox in 0..ow {
+                let x_start = (ox * params.stride_w) as isize - pad_l as isize;
+                for ch in 0..c {
+                    let mut max = f32::MIN;
+                    let mut max_index = 0;
+                    for fy in 0..params.filter_h {
+                        let iy = y_start + fy as isize;
+                        if iy < 0 || iy >= h as isize {
+                            continue;
+                        }
+                        for fx in 0..params.filter_w {
+                            let ix = x_start + fx as isize;
+                            if ix < 0 || ix >= w as isize {
+                                continue;
+                            }
+                            let v = x.data[((b * h + iy as usize) * w + ix as usize) * c + ch];
+                            if v > max {
+                                max = v;
+                                max_index = fy * params.filter_w + fx;
+                            }
+                        }
+                    }
+                    res.push(max);
+                    indices.push(max_index as f32);
+                }
+            }
+        }
+    }
+    params.activation.apply(res);
+    outputs[0].0 = vec![n, oh, ow, c];
+    outputs[1].0 = vec![n, oh, ow, c];
+    Ok(())
+}
+
+/// Write every input value to the position of the pooling window in the output, which is given by the
+/// indices of [`max_pool_with_argmax`], and other output values are zero.
+pub(super) fn max_unpooling(
+    x: &TensorRef,
+    indices: &TensorRef,
+    params: &PoolParams,
+    static_shape: &[usize],
+    out: &mut Output,
+) -> Result<(), Error> {
+    check_rank(x, 4)?;
+    if indices.shape != x.shape {
+        return Err(Error::BackendError(format!(
+            "`MaxUnpooling2D` indices shape `{:?}` mismatch input shape `{:?}`",
+            indices.shape, x.shape
+        )));
+    }
+    let (n, h, w, c) = (x.shape[0], x.shape[1], x.shape[2], x.shape[3]);
+    let (oh, ow) = if static_shape.len() == 4 && static_shape[0] == n && static_shape[3] == c {
+        (static_shape[1], static_shape[2])
+    } else {
+        (h * params.stride_h, w * params.stride_w)
+    };
+    let pad_t = params
+        .padding
+        .compute(oh, params.filter_h, params.stride_h, 1)
+        .1;
+    let pad_l = params
+        .padding
+        .compute(ow, params.filter_w, params.stride_w, 1)
+        .1;
+
+    let res = &mut out.1;
+    res.clear();
+    res.resize(n * oh * ow * c, 0.);
+    for b in 0..n {
+        for iy in 0..h {
+            for ix in 0..w {
+                for ch in 0..c {
+                    let i = ((b * h + iy) * w + ix) * c + ch;
+                    let index = indices.data[i].max(0.) as usize;
+                    let oy =
+                        (iy * params.stride_h + index / params.filter_w) as isize - pad_t as isize;
+                    let ox =
+                        (ix * params.stride_w + index % params.filter_w) as isize - pad_l as isize;
+                    if oy < 0 || oy >= oh as isize || ox < 0 || ox >= ow as isize {
+                        continue;
+                    }
+                    res[((b * oh + oy as usize) * ow + ox as usize) * c + ch] = x.data[i];
+                }
+            }
+        }
+    }
+    params.activation.apply(res);
+    out.0 = vec![n, oh, ow, c];
+    Ok(())
+}
+
+/// Decode the boxes by the anchors, and select the boxes using the non max suppression.
+/// The outputs are the boxes ```[1, num, 4]``` as ```(ymin, xmin, ymax, xmax)```, the classes ```[1, num]```,
+/// the scores ```[1, num]``` and the number of detections ```[1]```,
+/// where ```num``` is ```max_detections * max_classes_per_detection```.
+pub(super) fn detection_post_process(
+    box_encodings: &TensorRef,
+    class_predictions: &TensorRef,
+    anchors: &TensorRef,
+    params: &DetectionPostProcessParams,
+    outputs: &mut [Output],
+) -> Result<(), Error> {
+    check_rank(box_encodings, 3)?;
+    check_rank(class_predictions, 3)?;
+    check_rank(anchors, 2)?;
+    let num_boxes = box_encodings.shape[1];
+    let box_coords = box_encodings.shape[2];
+    let num_classes_with_background = class_predictions.shape[2];
+    if box_coords < 4
+        || class_predictions.shape[1] != num_boxes
+        || anchors.shape != [num_boxes, 4]
+        || num_classes_with_background < params.num_classes
+        || outputs.len() != 4
+    {
+        return Err(Error::BackendError(format!(
+            "`TFLite_Detection_PostProcess` got invalid inputs or outputs: boxes `{:?}`, scores `{:?}`, anchors `{:?}`",
+            box_encodings.shape, class_predictions.shape, anchors.shape
+        )));
+    }
+    let label_offset = num_classes_with_background - params.num_classes;
+
+    // decode the center-size boxes: (ycenter, xcenter, h, w)
+    let [y_scale, x_scale, h_scale, w_scale] = params.scales;
+    let boxes: Vec<[f32; 4]> = (0..num_boxes)
+        .map(|i| {
+            let e = &box_encodings.data[i * box_coords..i * box_coords + 4];
+            let a = &anchors.data[i * 4..i * 4 + 4];
+            let y_center = e[0] / y_scale * a[2] + a[0];
+            let x_center = e[1] / x_scale * a[3] + a[1];
+            let half_h = 0.5 * (e[2] / h_scale).exp() * a[2];
+            let half_w = 0.5 * (e[3] / w_scale).exp() * a[3];
+            [
+                y_center - half_h,
+                x_center - half_w,
+                y_center + half_h,
+                x_center + half_w,
+            ]
+        })
+        .collect();
+    let score = |b: usize, class: usize| {
+        class_predictions.data[b * num_classes_with_background + label_offset + class]
+    };
+
+    // (box index, class, score)
+    let mut detections: Vec<(usize, usize, f32)> = Vec::new();
+    let max_classes = params.max_classes_per_detection.min(params.num_classes);
+    if params.use_regular_nms {
+        let mut scores = Vec::with_capacity(num_boxes);
+        for class in 0..params.num_classes {
+            scores.clear();
+            scores.extend((0..num_boxes).map(|b| score(b, class)));
+            for b in non_max_suppression(&boxes, &scores, params, params.detections_per_class) {
+                detections.push((b, class, scores[b]));
+            }
+        }
+        detections.sort_by(|a, b| b.2.total_cmp(&a.2));
+        detections.truncate(params.max_detections);
+    } else {
+        // use the max class score of every box, and output the top classes of the selected boxes
+        let mut classes: Vec<usize> = (0..params.num_classes).collect();
+        let max_scores: Vec<f32> = (0..num_boxes)
+            .map(|b| {
+                (0..params.num_classes)
+                    .map(|c| score(b, c))
+                    .fold(f32::MIN, f32::max)
+            })
+            .collect();
+        for b in non_max_suppression(&boxes, &max_scores, params, params.max_detections) {
+            classes.sort_by(|x, y| score(b, *y).total_cmp(&score(b, *x)));
+            for c in classes.iter().take(max_classes) {
+                detections.push((b, *c, score(b, *c)));
+            }
+        }
+    }
+
+    let num = params.max_detections * params.max_classes_per_detection;
+    for (o, shape) in outputs
+        .iter_mut()
+        .zip([vec![1, num, 4], vec![1, num], vec![1, num], vec![1]])
+    {
+        o.1.clear();
+        o.1.resize(shape.iter().product(), 0.);
+        o.0 = shape;
+    }
+    for (i, (b, class, score)) in detections.iter().take(num).enumerate() {
+        outputs[0].1[i * 4..i * 4 + 4].copy_from_slice(&boxes[*b]);
+        outputs[1].1[i] = *class as f32;
+        outputs[2].1[i] = *score;
+    }
+    outputs[3].1[0] = detections.len().min(num) as f32;
+    Ok(())
+}
+
+/// Return the selected box indices in the descending order of the scores.
+fn non_max_suppression(
+    boxes: &[[f32; 4]],
+    scores: &[f32],
+    params: &DetectionPostProcessParams,
+    max_output: usize,
+) -> Vec<usize> {
+    let mut candidates: Vec<usize> = (0..boxes.len())
+        .filter(|i| scores[*i] >= params.score_threshold)
+        .collect();
+    candidates.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+
+    let mut selected: Vec<usize> = Vec::new();
+    for i in candidates {
+        if selected.len() >= max_output {
+            break;
+        }
+        if selected
+            .iter()
+            .all(|s| iou(&boxes[i], &boxes[*s]) <= params.iou_threshold)
+        {
+            selected.push(i);
+        }
+    }
+    selected
+}
+
+#[inline(always)]
+fn iou(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    let area_a = (a[2] - a[0]) * (a[3] - a[1]);
+    let area_b = (b[2] - b[0]) * (b[3] - b[1]);
+    if area_a <= 0. || area_b <= 0. {
+        return 0.;
+    }
+    let h = (a[2].min(b[2]) - a[0].max(b[0])).max(0.);
+    let w = (a[3].min(b[3]) - a[1].max(b[1])).max(0.);
+    let intersection = h * w;
+    intersection / (area_a + area_b - intersection)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tensor<'a>(shape: &'a [usize], data: &'a [f32]) -> TensorRef<'a> {
+        TensorRef { shape, data }
+    }
+
+    fn pool_params(filter: usize, stride: usize) -> PoolParams {
+        PoolParams {
+            padding: Padding::Same,
+            stride_h: stride,
+            stride_w: stride,
+            filter_h: filter,
+            filter_w: filter,
+            activation: FusedActivation::None,
+        }
+    }
+
+    /// Build a FlexBuffers map with the byte width `4`, the keys must be sorted.
+    fn flexbuffer_map(entries: &[(&str, u8, [u8; 4])]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let mut key_positions = Vec::new();
+        for (key, _, _) in entries {
+            key_positions.push(buf.len());
+            buf.extend_from_slice(key.as_bytes());
+            buf.push(0);
+        }
+        buf.resize(buf.len().div_ceil(4) * 4, 0);
+        // keys vector
+        buf.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+        let keys_pos = buf.len();
+        for p in key_positions {
+            let offset = buf.len() - p;
+            buf.extend_from_slice(&(offset as u32).to_le_bytes());
+        }
+        // map
+        let offset = buf.len() - keys_pos;
+        buf.extend_from_slice(&(offset as u32).to_le_bytes());
+        buf.extend_from_slice(&4u32.to_le_bytes());
+        buf.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+        let map_pos = buf.len();
+        for (_, _, value) in entries {
+            buf.extend_from_slice(value);
+        }
+        for (_, t, _) in entries {
+            buf.push(t << 2 | 2);
+        }
+        // root
+        let offset = buf.len() - map_pos;
+        buf.extend_from_slice(&(offset as u32).to_le_bytes());
+        buf.extend_from_slice(&[9 << 2 | 2, 4]);
+        buf
+    }
+
+    fn detection_options(use_regular_nms: bool) -> Vec<u8> {
+        let f = |v: f32| v.to_le_bytes();
+        let i = |v: i32| v.to_le_bytes();
+        flexbuffer_map(&[
+            ("h_scale", 3, f(5.)),
+            ("max_classes_per_detection", 1, i(1)),
+            ("max_detections", 1, i(3)),
+            ("nms_iou_threshold", 3, f(0.5)),
+            ("nms_score_threshold", 3, f(0.3)),
+            ("num_classes", 1, i(2)),
+            ("use_regular_nms", 26, i(use_regular_nms as i32)),
+            ("w_scale", 3, f(5.)),
+            ("x_scale", 3, f(10.)),
+            ("y_scale", 3, f(10.)),
+        ])
+    }
+
+    #[test]
+    fn test_parse_flexbuffer_map() {
+        let map = parse_flexbuffer_map(&detection_options(true)).unwrap();
+        assert_eq!(map.len(), 10);
+        assert_eq!(map["max_detections"], 3.);
+        assert_eq!(map["nms_iou_threshold"], 0.5);
+        assert_eq!(map["use_regular_nms"], 1.);
+
+        let options = detection_options(false);
+        assert!(parse_flexbuffer_map(&options[..options.len() - 8]).is_err());
+        assert!(parse_flexbuffer_map(&[]).is_err());
+    }
+
+    #[test]
+    fn test_max_pool_with_argmax_and_unpooling() {
+        // [1, 4, 4, 1]
+        let data = [
+            1., 5., 2., 0., //
+            3., 4., 8., 6., //
+            0., 0., 1., 0., //
+            9., 2., 0., 7., //
+        ];
+        let x = tensor(&[1, 4, 4, 1], &data);
+        let params = pool_params(2, 2);
+        let mut outputs = vec![(vec![], vec![]), (vec![], vec![])];
+        max_pool_with_argmax(&x, &params, &mut outputs).unwrap();
+        assert_eq!(outputs[0].0, vec![1, 2, 2, 1]);
+        assert_eq!(outputs[0].1, vec![5., 8., 9., 7.]);
+        assert_eq!(outputs[1].1, vec![1., 2., 2., 3.]);
+
+        let pooled = tensor(&outputs[0].0, &outputs[0].1);
+        let indices = tensor(&outputs[1].0, &outputs[1].1);
+        let mut out = (vec![], vec![]);
+        max_unpooling(&pooled, &indices, &params, &[1, 4, 4, 1], &mut out).unwrap();
+        assert_eq!(out.0, vec![1, 4, 4, 1]);
+        let expect: Vec<f32> = data
+            .iter()
+            .map(|v| if [5., 8., 9., 7.].contains(v) { *v } else { 0. })
+            .collect();
+        assert_eq!(out.1, expect);
+
+        let options: Vec<u8> = [1i32, 2, 2, 2, 2, 0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        assert!(parse(Some("MaxUnpooling2D"), &options).is_ok());
+        assert!(parse(Some("MaxUnpooling2D"), &options[..20]).is_err());
+    }
+
+    #[test]
+    fn test_detection_post_process() {
+        // anchors: (ycenter, xcenter, h, w)
+        let anchors = [
+            0.25, 0.25, 0.5, 0.5, //
+            0.25, 0.3, 0.5, 0.5, //
+            0.75, 0.75, 0.5, 0.5, //
+            0.5, 0.5, 1., 1.,
+        ];
+        // the second box is moved to the center of the first anchor
+        let boxes = [
+            0., 0., 0., 0., //
+            0., -1., 0., 0., //
+            0., 0., 0., 0., //
+            0., 0., 0., 0.,
+        ];
+        // background, class 0, class 1
+        let scores = [
+            0., 0.9, 0.1, //
+            0., 0.8, 0.2, //
+            0., 0.2, 0.6, //
+            0., 0.1, 0.2,
+        ];
+        let anchors = tensor(&[4, 4], &anchors);
+        let boxes = tensor(&[1, 4, 4], &boxes);
+        let scores = tensor(&[1, 4, 3], &scores);
+
+        let op = parse(
+            Some("TFLite_Detection_PostProcess"),
+            &detection_options(false),
+        )
+        .unwrap();
+        let params = match op {
+            Operator::DetectionPostProcess(p) => p,
+            _ => unreachable!(),
+        };
+        let mut outputs = vec![(vec![], vec![]); 4];
+        detection_post_process(&boxes, &scores, &anchors, &params, &mut outputs).unwrap();
+        assert_eq!(outputs[0].0, vec![1, 3, 4]);
+        // the second box is suppressed by the first box, and the last box is under the threshold
+        assert_eq!(outputs[3].1, vec![2.]);
+        assert_eq!(outputs[1].1, vec![0., 1., 0.]);
+        assert_eq!(outputs[2].1, vec![0.9, 0.6, 0.]);
+        assert_eq!(&outputs[0].1[..8], &[0., 0., 0.5, 0.5, 0.5, 0.5, 1., 1.]);
+
+        // the regular nms selects boxes for every class
+        let params = DetectionPostProcessParams {
+            use_regular_nms: true,
+            score_threshold: 0.15,
+            ..params
+        };
+        detection_post_process(&boxes, &scores, &anchors, &params, &mut outputs).unwrap();
+        assert_eq!(outputs[3].1, vec![3.]);
+        assert_eq!(outputs[1].1, vec![0., 1., 0.]);
+        assert_eq!(outputs[2].1, vec![0.9, 0.6, 0.2]);
+
+        assert!(parse(Some("TFLite_Detection_PostProcess"), &[]).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use super::{ExecutionContext, GraphEncoding, InferenceBackend, InferenceGraph, TensorType};
+use crate::{Device, Error};
+
+/// The WasmEdge WASI-NN backend, which is the default inference backend.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct WasiNNBackend;
+
+impl InferenceBackend for WasiNNBackend {
+    #[inline]
+    fn build_graph(
+        &self,
+        encoding: GraphEncoding,
+        device: Device,
+        model_buffers: &[&[u8]],
+    ) -> Result<Box<dyn InferenceGraph>, Error> {
+        let graph = wasi_nn::GraphBuilder::new(encoding, device).build_from_bytes(model_buffers)?;
+        Ok(Box::new(graph))
+    }
+}
+
+impl InferenceGraph for wasi_nn::Graph {
+    #[inline]
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+        Ok(Box::new(wasi_nn::Graph::init_execution_context(self)?))
+    }
+}
+
+impl<'a> ExecutionContext for wasi_nn::GraphExecutionContext<'a> {
+    #[inline(always)]
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error> {
+        wasi_nn::GraphExecutionContext::set_input(self, index, tensor_type, dimensions, data)?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn compute(&mut self) -> Result<(), Error> {
+        wasi_nn::GraphExecutionContext::compute(self)?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn get_output(&mut self, index: usize, out_buffer: &mut [u8]) -> Result<usize, Error> {
+        Ok(wasi_nn::GraphExecutionContext::get_output(
+            self, index, out_buffer,
+        )?)
+    }
+}
@@ -308,6 +308,34 @@
 //! }
 //! ```
 //!
+//! ## Inference backends
+//!
+//! The default inference backend is WasmEdge WASI-NN. Users can implement the [`inference::InferenceBackend`] trait
+//! to run the same task code with other inference engines:
+//! ```rust
+//! use mediapipe_rs::tasks::vision::ObjectDetectorBuilder;
+//!
+//! fn create_with_backend(model_blob: Vec<u8>, backend: impl mediapipe_rs::inference::InferenceBackend + 'static) {
+//!     let detector = ObjectDetectorBuilder::new()
+//!         .backend(backend)
+//!         .build_from_buffer(model_blob)
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! The ```native-tflite``` feature provides a pure Rust TfLite CPU backend
+//! ([`inference::TfLiteNativeBackend`]). When the target is not ```wasm32```, it is the default backend,
+//! so the tasks can run on the native host without any changes.
+//!
+//! [`inference::RecordBackend`] wraps another backend and dumps every input and output tensor to a directory, and
+//! [`inference::ReplayBackend`] serves the recorded outputs back, so the pre-process and post-process can be tested
+//! offline.
+//!
+//! ## Model information
+//!
+//! [`model::ModelInfo`] parses a model without building a task, such as tensor shapes, types, names, labels and
+//! associated files, which can be used to validate models before deploying them.
+//!
 //! ## Notice
 //! This work is made possible by **Google's work on [Mediapipe](https://github.com/google/mediapipe)**.
 //!
@@ -319,8 +347,13 @@
 compile_error!("Must select at least one task type: `vision`, `audio`, `text`");
 
 mod error;
+/// MediaPipe-rs model api, which can be used to get model information before building a task.
 #[macro_use]
-mod model;
+pub mod model;
+
+/// MediaPipe-rs inference backend api, which defines the interface to load models and do inference.
+/// The default backend is WasmEdge WASI-NN.
+pub mod inference;
 
 /// MediaPipe-rs postprocess api, which define the tasks results and implement tensors results to task results.
 /// The module also has utils to make use of results, such as drawing utils.
@@ -331,8 +364,9 @@ pub mod preprocess;
 pub mod tasks;
 
 pub use error::Error;
+use inference::{Graph, GraphExecutionContext};
 pub use wasi_nn::ExecutionTarget as Device;
-use wasi_nn::{Graph, GraphBuilder, GraphEncoding, GraphExecutionContext, TensorType};
+use wasi_nn::{GraphEncoding, TensorType};
 
 #[cfg(doc)]
 use tasks::{audio::*, text::*, vision::*};
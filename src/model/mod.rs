@@ -2,14 +2,23 @@
 
 use std::collections::HashMap;
 
+pub use model_info::{ModelInfo, TensorInfo};
+
 pub(crate) use memory_text_file::MemoryTextFile;
 pub(crate) use zip::ZipFiles;
 
+#[cfg(test)]
+pub(crate) use tflite::test_model;
+#[cfg(feature = "native-tflite")]
+pub(crate) use tflite::tflite_schema;
+
 use crate::postprocess::{Activation, QuantizationParameters};
+#[cfg(feature = "vision")]
+use crate::postprocess::{Anchor, TensorsDecodingOptions};
 #[cfg(feature = "audio")]
 use crate::preprocess::audio::AudioToTensorInfo;
 #[cfg(feature = "text")]
-use crate::preprocess::text::TextToTensorInfo;
+use crate::preprocess::text::{SentencePieceTokenizer, TextToTensorInfo};
 #[cfg(feature = "vision")]
 use crate::preprocess::vision::{ImageColorSpaceType, ImageDataLayout, ImageToTensorInfo};
 use crate::preprocess::ToTensorInfo;
@@ -17,10 +26,14 @@ use crate::{Error, GraphEncoding, TensorType};
 
 /// Abstraction for model resources.
 /// Users can use this trait to get information for models, such as data layout, model backend, etc.
-/// Now it supports ```TensorFlowLite``` backend.
+/// Now it supports ```TensorFlowLite``` and ```ONNX``` backends.
 pub(crate) trait ModelResourceTrait {
     fn model_backend(&self) -> GraphEncoding;
 
+    /// Get the model graph data which will be loaded by the inference backend.
+    /// A model buffer may carry other files, such as the ONNX model bundle.
+    fn model_graph_buffer<'a>(&'a self, model_buffer: &'a [u8]) -> &'a [u8];
+
     fn input_tensor_count(&self) -> usize;
 
     fn output_tensor_count(&self) -> usize;
@@ -33,8 +46,14 @@ pub(crate) trait ModelResourceTrait {
 
     fn output_tensor_shape(&self, index: usize) -> Option<&[usize]>;
 
+    fn input_tensor_name(&self, index: usize) -> Option<&str>;
+
+    fn output_tensor_name(&self, index: usize) -> Option<&str>;
+
     fn output_tensor_name_to_index(&self, name: &str) -> Option<usize>;
 
+    fn input_tensor_quantization_parameters(&self, index: usize) -> Option<QuantizationParameters>;
+
     fn output_tensor_quantization_parameters(&self, index: usize)
         -> Option<QuantizationParameters>;
 
@@ -44,14 +63,39 @@ pub(crate) trait ModelResourceTrait {
         locale: &str,
     ) -> Result<(&[u8], Option<&[u8]>), Error>;
 
+    /// Get the label file name and the locale label files: ```(label_file, [(locale, locale_label_file)])```
+    fn output_tensor_label_files(&self, index: usize) -> Option<(&str, Vec<(&str, &str)>)>;
+
+    fn associated_file_names(&self) -> Vec<&str>;
+
+    fn vocab_file_names(&self) -> Vec<&str>;
+
+    /// Get the model metadata process unit names, such as ```NormalizationOptions```.
+    fn input_process_units(&self, index: usize) -> &[&'static str];
+
+    fn output_process_units(&self, index: usize) -> &[&'static str];
+
     #[cfg(feature = "vision")]
     fn output_bounding_box_properties(&self, index: usize, slice: &mut [usize]) -> bool;
 
+    /// Get the SSD detector options in model metadata.
+    #[cfg(feature = "vision")]
+    fn detector_metadata(&self) -> Option<&DetectorMetadata>;
+
     fn to_tensor_info(&self, input_index: usize) -> Option<&ToTensorInfo>;
 
     fn output_activation(&self) -> Activation;
 }
 
+/// SSD detector options, such as the TfLite ```DETECTOR_METADATA``` custom metadata.
+#[cfg(feature = "vision")]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DetectorMetadata {
+    /// Fixed anchors, empty if the metadata has no anchors.
+    pub anchors: Vec<Anchor>,
+    pub tensors_decoding_options: Option<TensorsDecodingOptions>,
+}
+
 #[inline]
 pub(crate) fn parse_model(buf: &[u8]) -> Result<Box<dyn ModelResourceTrait + 'static>, Error> {
     if buf.len() < 8 {
@@ -65,10 +109,16 @@ pub(crate) fn parse_model(buf: &[u8]) -> Result<Box<dyn ModelResourceTrait + 'st
             let tf_model_resource = tflite::TfLiteModelResource::new(buf)?;
             Ok(Box::new(tf_model_resource))
         }
-        _ => Err(Error::ModelParseError(format!(
-            "Cannot parse this head magic `{:?}`",
-            &buf[..8]
-        ))),
+        _ => {
+            if onnx::OnnxModelResource::check_head(buf) {
+                let onnx_model_resource = onnx::OnnxModelResource::new(buf)?;
+                return Ok(Box::new(onnx_model_resource));
+            }
+            Err(Error::ModelParseError(format!(
+                "Cannot parse this head magic `{:?}`",
+                &buf[..8]
+            )))
+        }
     }
 }
 
@@ -126,16 +176,16 @@ macro_rules! get_type_and_quantization {
 }
 
 macro_rules! search_file_in_zip {
-    ( $zip_files:expr, $buf:expr, $candidate_list:expr, $task_name:expr ) => {{
+    ( $zip_files:expr, $candidate_list:expr, $task_name:expr ) => {{
         let mut search_result = None;
         for name in $candidate_list {
-            if let Some(r) = $zip_files.get_file_offset(*name) {
-                search_result = Some(r);
+            if let Some(f) = $zip_files.get_file(*name) {
+                search_result = Some(f);
                 break;
             }
         }
-        if let Some(r) = search_result {
-            &$buf[r]
+        if let Some(f) = search_result {
+            f
         } else {
             return Err(crate::Error::ModelInconsistentError(format!(
                 "Cannot find model asset file for `{}` task, candidate list is `{:?}`",
@@ -161,5 +211,8 @@ macro_rules! check_tensor_type {
 }
 
 mod memory_text_file;
+mod model_info;
+mod onnx;
+pub(crate) mod protobuf;
 mod tflite;
 mod zip;
@@ -0,0 +1,283 @@
+use super::*;
+use std::borrow::Cow;
+
+/// Information of a model input or output tensor.
+#[derive(Debug, Clone)]
+pub struct TensorInfo<'a> {
+    /// Tensor name, the name in metadata takes precedence over the name in model graph.
+    pub name: &'a str,
+    /// Tensor data type.
+    pub tensor_type: TensorType,
+    /// Tensor shape, dynamic dimensions are `1`.
+    pub shape: &'a [usize],
+    /// Quantization parameters, ```None``` if the tensor is not quantized.
+    pub quantization_parameters: Option<QuantizationParameters>,
+}
+
+/// Public view of a model, which can be used to check a model before building a task.
+///
+/// It supports TfLite models, ONNX models (with sidecar) and model bundles (such as `.task` files).
+/// A model bundle has no tensors itself, use [`ModelInfo::sub_models`] to get information for models in the bundle.
+///
+/// ```no_run
+/// use mediapipe_rs::model::ModelInfo;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let model_path = "assets/models/image_classification/efficientnet_lite0_fp32.tflite";
+/// let info = ModelInfo::from_file(model_path)?;
+/// for i in 0..info.output_count() {
+///     println!("{:?}", info.output(i));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ModelInfo {
+    model_resource: Option<Box<dyn ModelResourceTrait>>,
+    // only for model bundle
+    bundle_files: Vec<String>,
+    sub_models: Vec<(String, ModelInfo)>,
+}
+
+impl ModelInfo {
+    const SUB_MODEL_EXTENSIONS: &'static [&'static str] = &[".tflite", ".onnx", ".task"];
+
+    /// Parse model information from file.
+    #[inline(always)]
+    pub fn from_file(file_path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        Self::from_buffer(std::fs::read(file_path)?)
+    }
+
+    /// Parse model information from model buffer.
+    pub fn from_buffer(buffer: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let buf = buffer.as_ref();
+        let err = match parse_model(buf) {
+            Ok(model_resource) => {
+                return Ok(Self {
+                    model_resource: Some(model_resource),
+                    bundle_files: Vec::new(),
+                    sub_models: Vec::new(),
+                });
+            }
+            Err(e) => e,
+        };
+
+        // try to parse as model bundle, such as `.task` files.
+        let zip_files = match ZipFiles::try_new(buf) {
+            Ok(Some(z)) if buf.starts_with(&[0x50, 0x4b, 0x03, 0x04]) => z,
+            _ => return Err(err),
+        };
+        let mut bundle_files: Vec<String> = zip_files.file_names().map(|f| f.to_string()).collect();
+        bundle_files.sort();
+        let mut sub_models = Vec::new();
+        for name in bundle_files.iter() {
+            if Self::SUB_MODEL_EXTENSIONS
+                .iter()
+                .any(|e| name.ends_with(*e))
+            {
+                let sub_model = Self::from_buffer(zip_files.get_file(name).unwrap())?;
+                sub_models.push((name.clone(), sub_model));
+            }
+        }
+        Ok(Self {
+            model_resource: None,
+            bundle_files,
+            sub_models,
+        })
+    }
+
+    /// Return true if the model is a model bundle, which contains other models.
+    #[inline(always)]
+    pub fn is_bundle(&self) -> bool {
+        self.model_resource.is_none()
+    }
+
+    /// Get the models in the bundle: ```(file_name, model_info)```
+    #[inline(always)]
+    pub fn sub_models(&self) -> &[(String, ModelInfo)] {
+        self.sub_models.as_slice()
+    }
+
+    /// Get the model encoding, ```None``` for model bundle.
+    #[inline(always)]
+    pub fn model_backend(&self) -> Option<GraphEncoding> {
+        self.model_resource.as_ref().map(|m| m.model_backend())
+    }
+
+    /// Get the input tensor count.
+    #[inline(always)]
+    pub fn input_count(&self) -> usize {
+        self.model_resource
+            .as_ref()
+            .map(|m| m.input_tensor_count())
+            .unwrap_or(0)
+    }
+
+    /// Get the output tensor count.
+    #[inline(always)]
+    pub fn output_count(&self) -> usize {
+        self.model_resource
+            .as_ref()
+            .map(|m| m.output_tensor_count())
+            .unwrap_or(0)
+    }
+
+    /// Get the input tensor information at index.
+    pub fn input(&self, index: usize) -> Option<TensorInfo<'_>> {
+        let m = self.model_resource.as_ref()?;
+        Some(TensorInfo {
+            name: m.input_tensor_name(index).unwrap_or_default(),
+            tensor_type: m.input_tensor_type(index)?,
+            shape: m.input_tensor_shape(index)?,
+            quantization_parameters: m.input_tensor_quantization_parameters(index),
+        })
+    }
+
+    /// Get the output tensor information at index.
+    pub fn output(&self, index: usize) -> Option<TensorInfo<'_>> {
+        let m = self.model_resource.as_ref()?;
+        Some(TensorInfo {
+            name: m.output_tensor_name(index).unwrap_or_default(),
+            tensor_type: m.output_tensor_type(index)?,
+            shape: m.output_tensor_shape(index)?,
+            quantization_parameters: m.output_tensor_quantization_parameters(index),
+        })
+    }
+
+    /// Get the output tensor index by name.
+    #[inline(always)]
+    pub fn output_index(&self, name: &str) -> Option<usize> {
+        self.model_resource
+            .as_ref()?
+            .output_tensor_name_to_index(name)
+    }
+
+    /// Get the information to convert media input to the input tensor, such as image normalization options.
+    #[inline(always)]
+    pub fn to_tensor_info(&self, input_index: usize) -> Option<&ToTensorInfo> {
+        self.model_resource.as_ref()?.to_tensor_info(input_index)
+    }
+
+    /// Get the activation function for model outputs.
+    #[inline(always)]
+    pub fn output_activation(&self) -> Activation {
+        self.model_resource
+            .as_ref()
+            .map(|m| m.output_activation())
+            .unwrap_or_default()
+    }
+
+    /// Get the label locales of the output tensor, the default labels are not included.
+    #[inline]
+    pub fn output_label_locales(&self, index: usize) -> Vec<&str> {
+        match self.output_label_files(index) {
+            Some((_, locales)) => locales.into_iter().map(|(l, _)| l).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Get the label file names of the output tensor: ```(label_file, [(locale, locale_label_file)])```
+    #[inline]
+    pub fn output_label_files(&self, index: usize) -> Option<(&str, Vec<(&str, &str)>)> {
+        let (label_file, mut locales) = self
+            .model_resource
+            .as_ref()?
+            .output_tensor_label_files(index)?;
+        locales.sort();
+        Some((label_file, locales))
+    }
+
+    /// Get the labels of the output tensor. If ```locale``` is not ```None```, return the labels in this locale.
+    /// Return ```None``` if the output tensor has no labels or no labels in the locale.
+    pub fn output_labels(&self, index: usize, locale: Option<&str>) -> Option<Vec<Cow<'_, str>>> {
+        let (labels, locale_labels) = self
+            .model_resource
+            .as_ref()?
+            .output_tensor_labels_locale(index, locale.unwrap_or_default())
+            .ok()?;
+        let content = match locale {
+            Some(_) => locale_labels?,
+            None => labels,
+        };
+        let mut f = MemoryTextFile::new(content);
+        let mut res = Vec::new();
+        while let Some(l) = f.next_line() {
+            res.push(l);
+        }
+        Some(res)
+    }
+
+    /// Get the bounding box properties of the output tensor: indices of ```(left, top, right, bottom)```
+    #[cfg(feature = "vision")]
+    #[inline]
+    pub fn output_bounding_box_properties(&self, index: usize) -> Option<[usize; 4]> {
+        let mut res = [0; 4];
+        if self
+            .model_resource
+            .as_ref()?
+            .output_bounding_box_properties(index, &mut res)
+        {
+            Some(res)
+        } else {
+            None
+        }
+    }
+
+    /// Get the metadata process unit names of the input tensor, such as ```NormalizationOptions```.
+    /// The subgraph input process units are included.
+    #[inline(always)]
+    pub fn input_process_units(&self, index: usize) -> &[&'static str] {
+        match self.model_resource.as_ref() {
+            Some(m) => m.input_process_units(index),
+            None => &[],
+        }
+    }
+
+    /// Get the metadata process unit names of the output tensor, such as ```ScoreCalibrationOptions```.
+    /// The subgraph output process units are included.
+    #[inline(always)]
+    pub fn output_process_units(&self, index: usize) -> &[&'static str] {
+        match self.model_resource.as_ref() {
+            Some(m) => m.output_process_units(index),
+            None => &[],
+        }
+    }
+
+    /// Get the vocab file names used by text model tokenizers.
+    #[inline]
+    pub fn vocab_files(&self) -> Vec<&str> {
+        match self.model_resource.as_ref() {
+            Some(m) => m.vocab_file_names(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Get the associated file names, such as label files in TfLite metadata, or files in model bundle.
+    #[inline]
+    pub fn associated_files(&self) -> Vec<&str> {
+        let mut files = match self.model_resource.as_ref() {
+            Some(m) => m.associated_file_names(),
+            None => self.bundle_files.iter().map(|f| f.as_str()).collect(),
+        };
+        files.sort();
+        files
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_model_bundle_info() {
+        assert!(ModelInfo::from_buffer([]).is_err());
+        assert!(ModelInfo::from_file("").is_err());
+
+        let info = ModelInfo::from_file("assets/testdata/test.zip").unwrap();
+        assert!(info.is_bundle());
+        assert_eq!(info.model_backend(), None);
+        assert_eq!(info.input_count(), 0);
+        assert!(info.output(0).is_none());
+        assert!(info.sub_models().is_empty());
+        assert_eq!(info.associated_files(), vec!["1.txt", "2.txt"]);
+    }
+}
@@ -0,0 +1,905 @@
+//! ONNX model resource.
+//!
+//! ONNX models have no TfLite metadata, so the information needed by tasks, such as labels and
+//! normalization options, are read from a sidecar. The sidecar is a list of `key=value` pairs,
+//! which can be stored in the ONNX model `metadata_props`, or in a `metadata.txt` file of a
+//! model bundle (a zip archive contains a `.onnx` model file and other associated files, such as
+//! label files). The `metadata.txt` values take precedence over the `metadata_props`.
+//!
+//! Supported keys (```<i>``` is the tensor index):
+//! ```text
+//! input.<i>.layout = NHWC | NCHW                  // image data layout, guessed from shape if missing
+//! input.<i>.color_space = RGB | GRAYSCALE         // guessed from the channels if missing
+//! input.<i>.mean = 127.5,127.5,127.5              // normalization mean, default is `0`
+//! input.<i>.std = 127.5,127.5,127.5               // normalization std, default is `1`
+//! input.<i>.min = -1                              // input tensor stats min
+//! input.<i>.max = 1                               // input tensor stats max
+//! input.<i>.sample_rate = 16000                   // audio input sample rate
+//! input.<i>.channels = 1                          // audio input channels
+//! input.<i>.quantization = 0.0078125,128          // quantization scale and zero point
+//! output.<i>.labels = labels.txt                  // label file in the bundle
+//! output.<i>.labels.<locale> = labels_zh.txt      // locale label file in the bundle
+//! output.<i>.quantization = 0.00390625,0          // quantization scale and zero point
+//! output.<i>.bounding_box = 1,0,3,2               // bounding box indices of (left, top, right, bottom)
+//! output.activation = NONE | SIGMOID | SOFTMAX
+//! ```
+//! If a bundle has a `labels.txt` file and no labels are specified for output `0`, it is used
+//! as the labels of output `0`.
+
+use super::protobuf::ProtobufReader;
+use super::*;
+use std::collections::HashSet;
+use std::ops::Range;
+
+// (model file, associated files)
+type ModelBundle = (ModelGraph, HashMap<String, Vec<u8>>);
+
+enum ModelGraph {
+    // range of the model file in model buffer
+    Range(Range<usize>),
+    // model file is compressed in the model bundle
+    Owned(Vec<u8>),
+}
+
+impl ModelGraph {
+    #[inline(always)]
+    fn get<'a>(&'a self, model_buffer: &'a [u8]) -> &'a [u8] {
+        match self {
+            ModelGraph::Range(r) => &model_buffer[r.clone()],
+            ModelGraph::Owned(g) => g.as_slice(),
+        }
+    }
+}
+
+pub(crate) struct OnnxModelResource {
+    graph: ModelGraph,
+    input_names: Vec<String>,
+    output_names: Vec<String>,
+    input_shape: Vec<Vec<usize>>,
+    output_shape: Vec<Vec<usize>>,
+    input_types: Vec<TensorType>,
+    output_types: Vec<TensorType>,
+    input_quantization_parameters: Vec<Option<QuantizationParameters>>,
+    output_quantization_parameters: Vec<Option<QuantizationParameters>>,
+    to_tensor_info: Vec<ToTensorInfo>,
+    // Option<(label_filename, HashMap<label_locale, label_local_filename>)>
+    output_label_files: Vec<Option<(String, HashMap<String, String>)>>,
+    output_name_map: HashMap<String, usize>,
+    associated_files: HashMap<String, Vec<u8>>,
+    output_activation: Activation,
+
+    #[cfg(feature = "vision")]
+    output_bound_box_indices: Vec<Option<[usize; 4]>>,
+}
+
+impl OnnxModelResource {
+    // ONNX has no head magic, a serialized ModelProto starts with the `ir_version` field (field 1, varint).
+    const MODEL_PROTO_HEAD: u8 = 0x08;
+    const ZIP_HEAD_MAGIC: &'static [u8] = &[0x50, 0x4b, 0x03, 0x04];
+
+    const MODEL_FILE_EXTENSION: &'static str = ".onnx";
+    const SIDECAR_FILE_NAME: &'static str = "metadata.txt";
+    const DEFAULT_LABEL_FILE_NAME: &'static str = "labels.txt";
+
+    // ModelProto
+    const MODEL_IR_VERSION_FIELD: u32 = 1;
+    const MODEL_GRAPH_FIELD: u32 = 7;
+    const MODEL_METADATA_PROPS_FIELD: u32 = 14;
+    // all ModelProto fields, except the `ir_version`
+    const MODEL_FIELDS: &'static [u32] = &[2, 3, 4, 5, 6, 7, 8, 14, 20, 25, 26];
+    // the current ONNX IR version is 11, leave room for future versions
+    const MAX_IR_VERSION: u64 = 64;
+    // StringStringEntryProto
+    const ENTRY_KEY_FIELD: u32 = 1;
+    const ENTRY_VALUE_FIELD: u32 = 2;
+    // GraphProto
+    const GRAPH_INITIALIZER_FIELD: u32 = 5;
+    const GRAPH_INPUT_FIELD: u32 = 11;
+    const GRAPH_OUTPUT_FIELD: u32 = 12;
+    // TensorProto
+    const TENSOR_NAME_FIELD: u32 = 8;
+    // ValueInfoProto
+    const VALUE_INFO_NAME_FIELD: u32 = 1;
+    const VALUE_INFO_TYPE_FIELD: u32 = 2;
+    // TypeProto
+    const TYPE_TENSOR_TYPE_FIELD: u32 = 1;
+    // TypeProto.Tensor
+    const TENSOR_TYPE_ELEM_TYPE_FIELD: u32 = 1;
+    const TENSOR_TYPE_SHAPE_FIELD: u32 = 2;
+    // TensorShapeProto
+    const SHAPE_DIM_FIELD: u32 = 1;
+    // TensorShapeProto.Dimension
+    const DIM_VALUE_FIELD: u32 = 1;
+
+    // TensorProto.DataType
+    const DATA_TYPE_FLOAT: u64 = 1;
+    const DATA_TYPE_UINT8: u64 = 2;
+    const DATA_TYPE_INT32: u64 = 6;
+    const DATA_TYPE_FLOAT16: u64 = 10;
+
+    /// Check whether the buffer may be an ONNX model or an ONNX model bundle.
+    /// A zip archive is an ONNX model bundle only if it has a `.onnx` file, so other model bundles
+    /// (such as `.task` files) are not treated as ONNX models.
+    /// A ModelProto must start with a valid `ir_version`, followed by another ModelProto field.
+    pub(super) fn check_head(buf: &[u8]) -> bool {
+        if buf.starts_with(Self::ZIP_HEAD_MAGIC) {
+            return match ZipFiles::try_new(buf) {
+                Ok(Some(zip)) => zip
+                    .file_names()
+                    .any(|name| name.ends_with(Self::MODEL_FILE_EXTENSION)),
+                _ => false,
+            };
+        }
+        if buf.first() != Some(&Self::MODEL_PROTO_HEAD) {
+            return false;
+        }
+
+        let mut reader = ProtobufReader::new(buf);
+        match reader.next_field() {
+            Ok(Some((Self::MODEL_IR_VERSION_FIELD, v))) => {
+                if !matches!(v.as_varint(), Some(1..=Self::MAX_IR_VERSION)) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+        match reader.next_field() {
+            Ok(Some((field, _))) => Self::MODEL_FIELDS.contains(&field),
+            _ => false,
+        }
+    }
+
+    pub(super) fn new(buf: &[u8]) -> Result<Self, Error> {
+        let (graph, associated_files) = Self::parse_bundle(buf)?;
+        let mut _self = Self {
+            // set after parsing, the graph buffer may be owned by it
+            graph: ModelGraph::Range(0..0),
+            input_names: Vec::new(),
+            output_names: Vec::new(),
+            input_shape: Vec::new(),
+            output_shape: Vec::new(),
+            input_types: Vec::new(),
+            output_types: Vec::new(),
+            input_quantization_parameters: Vec::new(),
+            output_quantization_parameters: Vec::new(),
+            to_tensor_info: Vec::new(),
+            output_label_files: Vec::new(),
+            output_name_map: Default::default(),
+            associated_files,
+            output_activation: Default::default(),
+            #[cfg(feature = "vision")]
+            output_bound_box_indices: Vec::new(),
+        };
+
+        let mut metadata = HashMap::new();
+        let graph_proto = Self::parse_model_proto(graph.get(buf), &mut metadata)?;
+        _self.parse_graph(graph_proto)?;
+
+        if let Some(sidecar) = _self.associated_files.get(Self::SIDECAR_FILE_NAME) {
+            let mut f = MemoryTextFile::new(sidecar.as_slice());
+            while let Some(line) = f.next_line() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                match line.split_once('=') {
+                    Some((k, v)) => {
+                        metadata.insert(k.trim().to_string(), v.trim().to_string());
+                    }
+                    None => {
+                        return Err(Error::ModelParseError(format!(
+                            "Invalid line in `{}`: `{}`, expect `key=value`",
+                            Self::SIDECAR_FILE_NAME,
+                            line
+                        )));
+                    }
+                }
+            }
+        }
+        _self.parse_metadata(&metadata)?;
+        _self.graph = graph;
+        Ok(_self)
+    }
+
+    /// Return the model file and the associated files.
+    #[inline]
+    fn parse_bundle(buf: &[u8]) -> Result<ModelBundle, Error> {
+        if !buf.starts_with(Self::ZIP_HEAD_MAGIC) {
+            return Ok((ModelGraph::Range(0..buf.len()), Default::default()));
+        }
+
+        let zip = ZipFiles::new(buf)?;
+        let mut model_file = None;
+        let mut associated_files = HashMap::new();
+        for name in zip.file_names() {
+            if name.ends_with(Self::MODEL_FILE_EXTENSION) {
+                if model_file.is_some() {
+                    return Err(Error::ModelParseError(
+                        "ONNX model bundle has more than one `.onnx` model file".into(),
+                    ));
+                }
+                model_file = Some(name);
+            } else {
+                associated_files.insert(name.to_string(), zip.get_file(name).unwrap().to_vec());
+            }
+        }
+        match model_file {
+            Some(name) => {
+                let graph = match zip.get_file_offset(name) {
+                    Some(r) => ModelGraph::Range(r),
+                    None => ModelGraph::Owned(zip.get_file(name).unwrap().to_vec()),
+                };
+                Ok((graph, associated_files))
+            }
+            None => Err(Error::ModelParseError(
+                "Cannot find `.onnx` model file in the model bundle".into(),
+            )),
+        }
+    }
+
+    /// Return the GraphProto buffer, and put the `metadata_props` to ```metadata```.
+    #[inline]
+    fn parse_model_proto<'buf>(
+        buf: &'buf [u8],
+        metadata: &mut HashMap<String, String>,
+    ) -> Result<&'buf [u8], Error> {
+        let mut graph = None;
+        let mut reader = ProtobufReader::new(buf);
+        while let Some((field, value)) = reader.next_field()? {
+            match field {
+                Self::MODEL_GRAPH_FIELD => graph = value.as_bytes(),
+                Self::MODEL_METADATA_PROPS_FIELD => {
+                    let (mut key, mut val) = (None, None);
+                    let mut entry = ProtobufReader::new(value.as_bytes().unwrap_or_default());
+                    while let Some((f, v)) = entry.next_field()? {
+                        match f {
+                            Self::ENTRY_KEY_FIELD => key = Some(v.as_str()?),
+                            Self::ENTRY_VALUE_FIELD => val = Some(v.as_str()?),
+                            _ => {}
+                        }
+                    }
+                    if let Some(k) = key {
+                        metadata.insert(k.to_string(), val.unwrap_or_default().to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        graph.ok_or(Error::ModelParseError("ONNX model has no graph".into()))
+    }
+
+    #[inline]
+    fn parse_graph(&mut self, buf: &[u8]) -> Result<(), Error> {
+        let mut initializers = HashSet::new();
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        let mut reader = ProtobufReader::new(buf);
+        while let Some((field, value)) = reader.next_field()? {
+            match field {
+                Self::GRAPH_INITIALIZER_FIELD => {
+                    let mut tensor = ProtobufReader::new(value.as_bytes().unwrap_or_default());
+                    while let Some((f, v)) = tensor.next_field()? {
+                        if f == Self::TENSOR_NAME_FIELD {
+                            initializers.insert(v.as_str()?);
+                        }
+                    }
+                }
+                Self::GRAPH_INPUT_FIELD => inputs.push(value.as_bytes().unwrap_or_default()),
+                Self::GRAPH_OUTPUT_FIELD => outputs.push(value.as_bytes().unwrap_or_default()),
+                _ => {}
+            }
+        }
+
+        // old onnx models list the initializers in graph inputs too.
+        for i in inputs {
+            let (name, tensor_type, shape) = Self::parse_value_info(i)?;
+            if initializers.contains(name) {
+                continue;
+            }
+            self.input_names.push(name.to_string());
+            self.input_types.push(tensor_type);
+            self.input_shape.push(shape);
+        }
+        for (i, o) in outputs.into_iter().enumerate() {
+            let (name, tensor_type, shape) = Self::parse_value_info(o)?;
+            self.output_name_map.insert(name.to_string(), i);
+            self.output_names.push(name.to_string());
+            self.output_types.push(tensor_type);
+            self.output_shape.push(shape);
+        }
+        if self.input_types.is_empty() || self.output_types.is_empty() {
+            return Err(Error::ModelParseError(
+                "Model must has inputs and outputs information.".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parse ValueInfoProto, return the name, type and shape. Dynamic dimensions are set to `1`.
+    #[inline]
+    fn parse_value_info(buf: &[u8]) -> Result<(&str, TensorType, Vec<usize>), Error> {
+        let mut name = "";
+        let mut type_proto = None;
+        let mut reader = ProtobufReader::new(buf);
+        while let Some((field, value)) = reader.next_field()? {
+            match field {
+                Self::VALUE_INFO_NAME_FIELD => name = value.as_str()?,
+                Self::VALUE_INFO_TYPE_FIELD => type_proto = value.as_bytes(),
+                _ => {}
+            }
+        }
+
+        let mut tensor_type_proto = None;
+        let mut reader = ProtobufReader::new(type_proto.unwrap_or_default());
+        while let Some((field, value)) = reader.next_field()? {
+            if field == Self::TYPE_TENSOR_TYPE_FIELD {
+                tensor_type_proto = value.as_bytes();
+            }
+        }
+        let tensor_type_proto = tensor_type_proto.ok_or(Error::ModelParseError(format!(
+            "Tensor `{}` is not a tensor type",
+            name
+        )))?;
+
+        let mut elem_type = None;
+        let mut shape = None;
+        let mut reader = ProtobufReader::new(tensor_type_proto);
+        while let Some((field, value)) = reader.next_field()? {
+            match field {
+                Self::TENSOR_TYPE_ELEM_TYPE_FIELD => elem_type = value.as_varint(),
+                Self::TENSOR_TYPE_SHAPE_FIELD => {
+                    let mut dims = Vec::new();
+                    let mut shape_reader =
+                        ProtobufReader::new(value.as_bytes().unwrap_or_default());
+                    while let Some((f, v)) = shape_reader.next_field()? {
+                        if f != Self::SHAPE_DIM_FIELD {
+                            continue;
+                        }
+                        let mut dim = 1;
+                        let mut dim_reader = ProtobufReader::new(v.as_bytes().unwrap_or_default());
+                        while let Some((f, v)) = dim_reader.next_field()? {
+                            if f == Self::DIM_VALUE_FIELD {
+                                // int64, negative value is dynamic dimension
+                                let d = v.as_varint().unwrap_or_default() as i64;
+                                if d > 0 {
+                                    dim = d as usize;
+                                }
+                            }
+                        }
+                        dims.push(dim);
+                    }
+                    shape = Some(dims);
+                }
+                _ => {}
+            }
+        }
+
+        let tensor_type = match elem_type {
+            Some(Self::DATA_TYPE_FLOAT) => TensorType::F32,
+            Some(Self::DATA_TYPE_UINT8) => TensorType::U8,
+            Some(Self::DATA_TYPE_INT32) => TensorType::I32,
+            Some(Self::DATA_TYPE_FLOAT16) => TensorType::F16,
+            t => {
+                return Err(Error::ModelParseError(format!(
+                    "Unsupported tensor type `{:?}` for tensor `{}`",
+                    t, name
+                )));
+            }
+        };
+        let shape = shape.ok_or(Error::ModelParseError(format!(
+            "Missing tensor shape for tensor `{}`",
+            name
+        )))?;
+        Ok((name, tensor_type, shape))
+    }
+
+    #[inline]
+    fn parse_metadata(&mut self, metadata: &HashMap<String, String>) -> Result<(), Error> {
+        for i in 0..self.input_types.len() {
+            let q = Self::get_quantization_parameters(metadata, &format!("input.{}", i))?;
+            self.input_quantization_parameters.push(q);
+
+            #[cfg(feature = "audio")]
+            if metadata.contains_key(&format!("input.{}.sample_rate", i)) {
+                self.parse_audio_model_input_info(i, metadata)?;
+                continue;
+            }
+
+            #[cfg(feature = "vision")]
+            self.parse_vision_model_input_info(i, metadata)?;
+        }
+
+        for i in 0..self.output_types.len() {
+            // labels
+            let label_key = format!("output.{}.labels", i);
+            let mut label = metadata.get(&label_key).cloned();
+            if label.is_none()
+                && i == 0
+                && self
+                    .associated_files
+                    .contains_key(Self::DEFAULT_LABEL_FILE_NAME)
+            {
+                label = Some(Self::DEFAULT_LABEL_FILE_NAME.to_string());
+            }
+            let locale_prefix = format!("{}.", label_key);
+            let label_locales: HashMap<String, String> = metadata
+                .iter()
+                .filter_map(|(k, v)| {
+                    k.strip_prefix(locale_prefix.as_str())
+                        .map(|l| (l.to_string(), v.clone()))
+                })
+                .collect();
+            for f in label.iter().chain(label_locales.values()) {
+                self.get_file_content(f)?;
+            }
+            self.output_label_files
+                .push(label.map(|l| (l, label_locales)));
+
+            // quantization
+            let q = Self::get_quantization_parameters(metadata, &format!("output.{}", i))?;
+            self.output_quantization_parameters.push(q);
+
+            // bounding box
+            #[cfg(feature = "vision")]
+            {
+                let indices =
+                    match Self::get_f32_list(metadata, &format!("output.{}.bounding_box", i))? {
+                        Some(b) => {
+                            if b.len() != 4 {
+                                return Err(Error::ModelParseError(format!(
+                                "Output `{}` bounding box must have `4` indices, but got `{:?}`",
+                                i, b
+                            )));
+                            }
+                            Some([b[0] as usize, b[1] as usize, b[2] as usize, b[3] as usize])
+                        }
+                        None => None,
+                    };
+                self.output_bound_box_indices.push(indices);
+            }
+        }
+
+        if let Some(a) = metadata.get("output.activation") {
+            self.output_activation = match a.to_ascii_uppercase().as_str() {
+                "NONE" => Activation::None,
+                "SIGMOID" => Activation::SIGMOID,
+                "SOFTMAX" => Activation::SOFTMAX,
+                _ => {
+                    return Err(Error::ModelParseError(format!(
+                        "Invalid output activation type `{}`",
+                        a
+                    )));
+                }
+            };
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "vision")]
+    #[inline]
+    fn parse_vision_model_input_info(
+        &mut self,
+        i: usize,
+        metadata: &HashMap<String, String>,
+    ) -> Result<(), Error> {
+        let shape = self.input_shape.get(i).unwrap();
+        let layout = match metadata.get(&format!("input.{}.layout", i)) {
+            Some(l) => match l.to_ascii_uppercase().as_str() {
+                "NHWC" => ImageDataLayout::NHWC,
+                "NCHW" => ImageDataLayout::NCHW,
+                _ => {
+                    return Err(Error::ModelParseError(format!(
+                        "Invalid image data layout `{}` for input `{}`",
+                        l, i
+                    )));
+                }
+            },
+            None => {
+                if shape.len() != 4 {
+                    return Ok(());
+                }
+                // onnx models exported from pytorch are usually NCHW
+                if (shape[1] == 1 || shape[1] == 3) && shape[3] != 1 && shape[3] != 3 {
+                    ImageDataLayout::NCHW
+                } else {
+                    ImageDataLayout::NHWC
+                }
+            }
+        };
+        let tensor_shape =
+            match crate::preprocess::vision::ImageLikeTensorShape::parse(layout, shape) {
+                Ok(s) => s,
+                Err(_) => return Ok(()),
+            };
+
+        let color_space = match metadata.get(&format!("input.{}.color_space", i)) {
+            Some(c) => match c.to_ascii_uppercase().as_str() {
+                "RGB" => ImageColorSpaceType::RGB,
+                "GRAYSCALE" => ImageColorSpaceType::GRAYSCALE,
+                _ => ImageColorSpaceType::UNKNOWN,
+            },
+            None => match tensor_shape.channels {
+                3 => ImageColorSpaceType::RGB,
+                1 => ImageColorSpaceType::GRAYSCALE,
+                _ => ImageColorSpaceType::UNKNOWN,
+            },
+        };
+        let stats_min =
+            Self::get_f32_list(metadata, &format!("input.{}.min", i))?.unwrap_or_default();
+        let stats_max =
+            Self::get_f32_list(metadata, &format!("input.{}.max", i))?.unwrap_or_default();
+        let mean = Self::get_f32_list(metadata, &format!("input.{}.mean", i))?.unwrap_or(vec![0.]);
+        let std = Self::get_f32_list(metadata, &format!("input.{}.std", i))?.unwrap_or(vec![1.]);
+
+        let img_info = ImageToTensorInfo {
+            image_data_layout: layout,
+            color_space,
+            tensor_type: self.input_types[i],
+            tensor_shape,
+            stats_min,
+            stats_max,
+            normalization_options: (mean, std),
+        };
+
+        while self.to_tensor_info.len() < i {
+            self.to_tensor_info.push(ToTensorInfo::new_none());
+        }
+        self.to_tensor_info.push(ToTensorInfo::new_image(img_info));
+        Ok(())
+    }
+
+    #[cfg(feature = "audio")]
+    #[inline]
+    fn parse_audio_model_input_info(
+        &mut self,
+        i: usize,
+        metadata: &HashMap<String, String>,
+    ) -> Result<(), Error> {
+        let get_usize = |key: String| -> Result<Option<usize>, Error> {
+            match metadata.get(&key) {
+                Some(v) => v.parse().map(Some).map_err(|e| {
+                    Error::ModelParseError(format!("Cannot parse `{}` value `{}`: {}", key, v, e))
+                }),
+                None => Ok(None),
+            }
+        };
+        let sample_rate = get_usize(format!("input.{}.sample_rate", i))?.unwrap();
+        let num_channels = get_usize(format!("input.{}.channels", i))?.unwrap_or(1);
+        if num_channels == 0 {
+            return Err(Error::ModelParseError(format!(
+                "Audio input tensor `{}`, num channel cannot be zero",
+                i
+            )));
+        }
+        let input_shape = self.input_shape.get(i).unwrap();
+        let input_buffer_size = input_shape.iter().product::<usize>();
+        if input_buffer_size % num_channels != 0 {
+            return Err(Error::ModelParseError(format!(
+                "Input tensor size `{}` should be a multiplier of the number of channels `{}`",
+                input_buffer_size, num_channels
+            )));
+        }
+        let num_samples = *input_shape.last().unwrap() / num_channels;
+        let audio_info = AudioToTensorInfo {
+            num_channels,
+            num_samples,
+            sample_rate,
+            num_overlapping_samples: 0,
+            tensor_type: self.input_types[i],
+        };
+
+        while self.to_tensor_info.len() < i {
+            self.to_tensor_info.push(ToTensorInfo::new_none());
+        }
+        self.to_tensor_info
+            .push(ToTensorInfo::new_audio(audio_info));
+        Ok(())
+    }
+
+    #[inline]
+    fn get_quantization_parameters(
+        metadata: &HashMap<String, String>,
+        tensor: &str,
+    ) -> Result<Option<QuantizationParameters>, Error> {
+        let key = format!("{}.quantization", tensor);
+        match Self::get_f32_list(metadata, &key)? {
+            Some(q) => {
+                if q.len() != 2 {
+                    return Err(Error::ModelParseError(format!(
+                        "`{}` must be `scale,zero_point`, but got `{:?}`",
+                        key, q
+                    )));
+                }
+                Ok(Some(QuantizationParameters {
+                    scale: q[0],
+                    zero_point: q[1] as i32,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn get_f32_list(
+        metadata: &HashMap<String, String>,
+        key: &str,
+    ) -> Result<Option<Vec<f32>>, Error> {
+        match metadata.get(key) {
+            Some(v) => v
+                .split(',')
+                .map(|s| {
+                    s.trim().parse::<f32>().map_err(|e| {
+                        Error::ModelParseError(format!(
+                            "Cannot parse `{}` value `{}`: {}",
+                            key, v, e
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    #[inline(always)]
+    fn get_file_content(&self, filename: &str) -> Result<&[u8], Error> {
+        match self.associated_files.get(filename) {
+            Some(c) => Ok(c.as_slice()),
+            None => Err(Error::ModelParseError(format!(
+                "Cannot find associated file `{}`",
+                filename
+            ))),
+        }
+    }
+}
+
+impl ModelResourceTrait for OnnxModelResource {
+    fn model_backend(&self) -> GraphEncoding {
+        GraphEncoding::Onnx
+    }
+
+    fn model_graph_buffer<'a>(&'a self, model_buffer: &'a [u8]) -> &'a [u8] {
+        self.graph.get(model_buffer)
+    }
+
+    fn input_tensor_count(&self) -> usize {
+        self.input_shape.len()
+    }
+
+    fn output_tensor_count(&self) -> usize {
+        self.output_shape.len()
+    }
+
+    fn input_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.input_types.get(index).cloned()
+    }
+
+    fn output_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.output_types.get(index).cloned()
+    }
+
+    fn input_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.input_shape.get(index).map(|v| v.as_slice())
+    }
+
+    fn output_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.output_shape.get(index).map(|v| v.as_slice())
+    }
+
+    fn input_tensor_name(&self, index: usize) -> Option<&str> {
+        self.input_names.get(index).map(|n| n.as_str())
+    }
+
+    fn output_tensor_name(&self, index: usize) -> Option<&str> {
+        self.output_names.get(index).map(|n| n.as_str())
+    }
+
+    fn output_tensor_name_to_index(&self, name: &str) -> Option<usize> {
+        self.output_name_map.get(name).cloned()
+    }
+
+    fn input_tensor_quantization_parameters(&self, index: usize) -> Option<QuantizationParameters> {
+        self.input_quantization_parameters
+            .get(index)
+            .copied()
+            .flatten()
+    }
+
+    fn output_tensor_quantization_parameters(
+        &self,
+        index: usize,
+    ) -> Option<QuantizationParameters> {
+        self.output_quantization_parameters
+            .get(index)
+            .copied()
+            .flatten()
+    }
+
+    fn output_tensor_labels_locale(
+        &self,
+        index: usize,
+        locale_name: &str,
+    ) -> Result<(&[u8], Option<&[u8]>), Error> {
+        if let Some(Some((label_file, locales))) = self.output_label_files.get(index) {
+            let content = self.get_file_content(label_file)?;
+            let locale_content = if let Some(file) = locales.get(locale_name) {
+                Some(self.get_file_content(file)?)
+            } else {
+                None
+            };
+            return Ok((content, locale_content));
+        }
+        Err(Error::ModelInconsistentError(
+            "Missing model label file information.".into(),
+        ))
+    }
+
+    fn output_tensor_label_files(&self, index: usize) -> Option<(&str, Vec<(&str, &str)>)> {
+        match self.output_label_files.get(index) {
+            Some(Some((label_file, locales))) => Some((
+                label_file.as_str(),
+                locales
+                    .iter()
+                    .map(|(l, f)| (l.as_str(), f.as_str()))
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn associated_file_names(&self) -> Vec<&str> {
+        self.associated_files.keys().map(|f| f.as_str()).collect()
+    }
+
+    fn vocab_file_names(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    fn input_process_units(&self, _index: usize) -> &[&'static str] {
+        &[]
+    }
+
+    fn output_process_units(&self, _index: usize) -> &[&'static str] {
+        &[]
+    }
+
+    #[cfg(feature = "vision")]
+    fn output_bounding_box_properties(&self, index: usize, slice: &mut [usize]) -> bool {
+        if let Some(Some(s)) = self.output_bound_box_indices.get(index) {
+            slice.copy_from_slice(s);
+            return true;
+        }
+        false
+    }
+
+    #[cfg(feature = "vision")]
+    fn detector_metadata(&self) -> Option<&DetectorMetadata> {
+        None
+    }
+
+    fn to_tensor_info(&self, input_index: usize) -> Option<&ToTensorInfo> {
+        self.to_tensor_info.get(input_index)
+    }
+
+    fn output_activation(&self) -> Activation {
+        self.output_activation
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn varint(mut v: u64, out: &mut Vec<u8>) {
+        while v >= 0x80 {
+            out.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        out.push(v as u8);
+    }
+
+    fn field_varint(field: u32, v: u64, out: &mut Vec<u8>) {
+        varint((field as u64) << 3, out);
+        varint(v, out);
+    }
+
+    fn field_bytes(field: u32, b: &[u8], out: &mut Vec<u8>) {
+        varint(((field as u64) << 3) | 2, out);
+        varint(b.len() as u64, out);
+        out.extend_from_slice(b);
+    }
+
+    fn value_info(name: &str, elem_type: u64, dims: &[i64]) -> Vec<u8> {
+        let mut shape = Vec::new();
+        for d in dims {
+            let mut dim = Vec::new();
+            if *d > 0 {
+                field_varint(1, *d as u64, &mut dim);
+            } else {
+                field_bytes(2, b"batch", &mut dim);
+            }
+            field_bytes(1, &dim, &mut shape);
+        }
+        let mut tensor_type = Vec::new();
+        field_varint(1, elem_type, &mut tensor_type);
+        field_bytes(2, &shape, &mut tensor_type);
+        let mut type_proto = Vec::new();
+        field_bytes(1, &tensor_type, &mut type_proto);
+        let mut res = Vec::new();
+        field_bytes(1, name.as_bytes(), &mut res);
+        field_bytes(2, &type_proto, &mut res);
+        res
+    }
+
+    fn model(metadata: &[(&str, &str)]) -> Vec<u8> {
+        let mut initializer = Vec::new();
+        field_bytes(8, b"weight", &mut initializer);
+        let mut graph = Vec::new();
+        field_bytes(5, &initializer, &mut graph);
+        field_bytes(11, &value_info("image", 1, &[-1, 3, 224, 224]), &mut graph);
+        field_bytes(11, &value_info("weight", 1, &[1000, 512]), &mut graph);
+        field_bytes(12, &value_info("scores", 1, &[1, 1000]), &mut graph);
+        field_bytes(12, &value_info("ids", 6, &[1]), &mut graph);
+
+        let mut res = Vec::new();
+        field_varint(1, 8, &mut res);
+        field_bytes(7, &graph, &mut res);
+        for (k, v) in metadata {
+            let mut entry = Vec::new();
+            field_bytes(1, k.as_bytes(), &mut entry);
+            field_bytes(2, v.as_bytes(), &mut entry);
+            field_bytes(14, &entry, &mut res);
+        }
+        res
+    }
+
+    #[test]
+    fn test_onnx_model_resource() {
+        let buf = model(&[
+            ("input.0.mean", "123.675,116.28,103.53"),
+            ("input.0.std", "58.395,57.12,57.375"),
+            ("output.activation", "SOFTMAX"),
+        ]);
+        let m = parse_model(&buf).unwrap();
+        assert_eq!(m.model_backend(), GraphEncoding::Onnx);
+        assert_eq!(m.model_graph_buffer(&buf), buf.as_slice());
+        assert_eq!(m.input_tensor_count(), 1);
+        assert_eq!(m.input_tensor_shape(0).unwrap(), &[1, 3, 224, 224]);
+        assert_eq!(m.input_tensor_type(0), Some(TensorType::F32));
+        assert_eq!(m.output_tensor_count(), 2);
+        assert_eq!(m.output_tensor_shape(0).unwrap(), &[1, 1000]);
+        assert_eq!(m.output_tensor_type(1), Some(TensorType::I32));
+        assert_eq!(m.output_tensor_name_to_index("ids"), Some(1));
+        assert_eq!(m.output_activation(), Activation::SOFTMAX);
+        assert!(m.output_tensor_labels_locale(0, "").is_err());
+
+        #[cfg(feature = "vision")]
+        {
+            let info = m.to_tensor_info(0).unwrap().try_to_image().unwrap();
+            assert_eq!(info.image_data_layout, ImageDataLayout::NCHW);
+            assert_eq!(info.color_space, ImageColorSpaceType::RGB);
+            assert_eq!(info.width(), 224);
+            assert_eq!(info.normalization_options.1, vec![58.395, 57.12, 57.375]);
+        }
+
+        assert!(parse_model(&model(&[("input.0.mean", "a,b")])).is_err());
+        assert!(parse_model(&model(&[("output.0.labels", "labels.txt")])).is_err());
+        assert!(parse_model(&buf[..buf.len() - 3]).is_err());
+    }
+
+    #[test]
+    fn test_check_head() {
+        assert!(OnnxModelResource::check_head(&model(&[])));
+        // `ir_version` must be valid, and followed by a ModelProto field
+        assert!(!OnnxModelResource::check_head(&[0x08, 0x00, 0x3a, 0x00]));
+        assert!(!OnnxModelResource::check_head(&[0x08, 0x08]));
+        assert!(!OnnxModelResource::check_head(&[0x08, 0x08, 0x48, 0x00]));
+        assert!(!OnnxModelResource::check_head(&[0x08, 0x08, 0x3a, 0x10]));
+
+        // a zip archive without `.onnx` file is not an ONNX model bundle
+        let buf = std::fs::read("assets/testdata/test.zip").unwrap();
+        assert!(!OnnxModelResource::check_head(&buf));
+        let err = parse_model(&buf).err().unwrap().to_string();
+        assert!(!err.contains(".onnx"), "{}", err);
+    }
+}
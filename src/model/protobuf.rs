@@ -0,0 +1,224 @@
+// Protocol Buffers encoding spec: https://protobuf.dev/programming-guides/encoding/
+
+use crate::Error;
+
+/// The value of a protobuf field, only the wire format is decoded.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum FieldValue<'buf> {
+    Varint(u64),
+    Fixed64(u64),
+    LengthDelimited(&'buf [u8]),
+    Fixed32(u32),
+}
+
+impl<'buf> FieldValue<'buf> {
+    #[inline(always)]
+    pub(crate) fn as_varint(&self) -> Option<u64> {
+        match self {
+            FieldValue::Varint(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn as_bytes(&self) -> Option<&'buf [u8]> {
+        match self {
+            FieldValue::LengthDelimited(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn as_f32(&self) -> Option<f32> {
+        match self {
+            FieldValue::Fixed32(v) => Some(f32::from_bits(*v)),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn as_str(&self) -> Result<&'buf str, Error> {
+        let b = self.as_bytes().ok_or(Error::ModelParseError(
+            "Expect a length-delimited protobuf field for string".into(),
+        ))?;
+        std::str::from_utf8(b)
+            .map_err(|e| Error::ModelParseError(format!("Invalid utf-8 string: `{}`", e)))
+    }
+
+    /// Append the values of a repeated `float` field, which may be packed or not.
+    pub(crate) fn read_repeated_f32(&self, out: &mut Vec<f32>) -> Result<(), Error> {
+        match self {
+            FieldValue::Fixed32(v) => out.push(f32::from_bits(*v)),
+            FieldValue::LengthDelimited(b) if b.len() % 4 == 0 => out.extend(
+                b.chunks_exact(4)
+                    .map(|c| f32::from_le_bytes(c.try_into().unwrap())),
+            ),
+            _ => {
+                return Err(Error::ModelParseError(
+                    "Invalid protobuf repeated float field".into(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Append the values of a repeated varint field, which may be packed or not.
+    pub(crate) fn read_repeated_varint(&self, out: &mut Vec<u64>) -> Result<(), Error> {
+        match self {
+            FieldValue::Varint(v) => out.push(*v),
+            FieldValue::LengthDelimited(b) => {
+                let mut reader = ProtobufReader::new(b);
+                while reader.pos < reader.buf.len() {
+                    out.push(reader.read_varint()?);
+                }
+            }
+            _ => {
+                return Err(Error::ModelParseError(
+                    "Invalid protobuf repeated varint field".into(),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterate over the fields of a protobuf message: ```(field_number, value)```
+pub(crate) struct ProtobufReader<'buf> {
+    buf: &'buf [u8],
+    pos: usize,
+}
+
+impl<'buf> ProtobufReader<'buf> {
+    const WIRE_TYPE_VARINT: u64 = 0;
+    const WIRE_TYPE_I64: u64 = 1;
+    const WIRE_TYPE_LEN: u64 = 2;
+    const WIRE_TYPE_I32: u64 = 5;
+
+    #[inline(always)]
+    pub(crate) fn new(buf: &'buf [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    #[inline]
+    fn read_varint(&mut self) -> Result<u64, Error> {
+        let mut res = 0u64;
+        let mut shift = 0;
+        loop {
+            if self.pos >= self.buf.len() {
+                return Err(Error::ModelParseError(
+                    "Unexpected end of protobuf buffer while reading varint".into(),
+                ));
+            }
+            let b = self.buf[self.pos];
+            self.pos += 1;
+            if shift >= 64 {
+                return Err(Error::ModelParseError("Protobuf varint is too long".into()));
+            }
+            res |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(res);
+            }
+            shift += 7;
+        }
+    }
+
+    #[inline]
+    fn read_bytes(&mut self, len: usize) -> Result<&'buf [u8], Error> {
+        if len > self.buf.len() - self.pos {
+            return Err(Error::ModelParseError(format!(
+                "Protobuf field length `{}` is larger than the remaining buffer `{}`",
+                len,
+                self.buf.len() - self.pos
+            )));
+        }
+        let res = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(res)
+    }
+
+    /// Read next field, return ```None``` if reach the end of buffer.
+    pub(crate) fn next_field(&mut self) -> Result<Option<(u32, FieldValue<'buf>)>, Error> {
+        if self.pos >= self.buf.len() {
+            return Ok(None);
+        }
+        let key = self.read_varint()?;
+        let field_number = (key >> 3) as u32;
+        if field_number == 0 {
+            return Err(Error::ModelParseError(
+                "Invalid protobuf field number `0`".into(),
+            ));
+        }
+        let value = match key & 0x7 {
+            Self::WIRE_TYPE_VARINT => FieldValue::Varint(self.read_varint()?),
+            Self::WIRE_TYPE_I64 => {
+                let b = self.read_bytes(8)?;
+                FieldValue::Fixed64(u64::from_le_bytes(b.try_into().unwrap()))
+            }
+            Self::WIRE_TYPE_LEN => {
+                let len = self.read_varint()? as usize;
+                FieldValue::LengthDelimited(self.read_bytes(len)?)
+            }
+            Self::WIRE_TYPE_I32 => {
+                let b = self.read_bytes(4)?;
+                FieldValue::Fixed32(u32::from_le_bytes(b.try_into().unwrap()))
+            }
+            t => {
+                // groups are deprecated and not used by onnx
+                return Err(Error::ModelParseError(format!(
+                    "Unsupported protobuf wire type `{}`",
+                    t
+                )));
+            }
+        };
+        Ok(Some((field_number, value)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_protobuf_reader() {
+        // field 1: varint 150, field 2: string "ab", field 3: fixed32 1
+        let buf = [0x08, 0x96, 0x01, 0x12, 0x02, 0x61, 0x62, 0x1d, 1, 0, 0, 0];
+        let mut r = ProtobufReader::new(&buf);
+        let (n, v) = r.next_field().unwrap().unwrap();
+        assert_eq!((n, v.as_varint()), (1, Some(150)));
+        let (n, v) = r.next_field().unwrap().unwrap();
+        assert_eq!((n, v.as_str().unwrap()), (2, "ab"));
+        let (n, v) = r.next_field().unwrap().unwrap();
+        assert!(n == 3 && matches!(v, FieldValue::Fixed32(1)));
+        assert!(r.next_field().unwrap().is_none());
+
+        assert!(ProtobufReader::new(&[0x12, 0x05, 0x61])
+            .next_field()
+            .is_err());
+        assert!(ProtobufReader::new(&[0x08, 0x96]).next_field().is_err());
+    }
+
+    #[test]
+    fn test_repeated_fields() {
+        let mut floats = Vec::new();
+        FieldValue::Fixed32(1f32.to_bits())
+            .read_repeated_f32(&mut floats)
+            .unwrap();
+        let packed = [2f32.to_le_bytes(), 3f32.to_le_bytes()].concat();
+        FieldValue::LengthDelimited(&packed)
+            .read_repeated_f32(&mut floats)
+            .unwrap();
+        assert_eq!(floats, [1., 2., 3.]);
+        assert!(FieldValue::LengthDelimited(&[0, 0])
+            .read_repeated_f32(&mut floats)
+            .is_err());
+
+        let mut varints = Vec::new();
+        FieldValue::Varint(1)
+            .read_repeated_varint(&mut varints)
+            .unwrap();
+        FieldValue::LengthDelimited(&[0x02, 0x96, 0x01])
+            .read_repeated_varint(&mut varints)
+            .unwrap();
+        assert_eq!(varints, [1, 2, 150]);
+    }
+}
@@ -1,5 +1,5 @@
 // NOTE: the files `image_segmenter_metadata_schema_generated.rs`, `metadata_schema_generated.rs`,
-// `schema_generated.rs` are generated from generated by the FlatBuffers compiler.
+// `object_detector_metadata_schema_generated.rs`, `schema_generated.rs` are generated from generated by the FlatBuffers compiler.
 // The schema files are licensed under Apache 2.0, and originally developed by Google for Mediapipe and TensorFlow.
 
 #![allow(non_snake_case)]
@@ -9,7 +9,7 @@ mod metadata_schema_generated;
 mod schema_generated;
 
 pub(super) use metadata_schema_generated::tflite as tflite_metadata;
-pub(super) use schema_generated::tflite;
+pub(crate) use schema_generated::tflite;
 
 #[cfg(feature = "vision")]
 mod image_segmenter_metadata_schema_generated;
@@ -17,3 +17,10 @@ mod image_segmenter_metadata_schema_generated;
 pub(super) use image_segmenter_metadata_schema_generated::mediapipe::tasks as custom_img_segmentation;
 #[cfg(feature = "vision")]
 pub(super) const CUSTOM_SEGMENTATION_METADATA_NAME: &'static str = "SEGMENTER_METADATA";
+
+#[cfg(feature = "vision")]
+mod object_detector_metadata_schema_generated;
+#[cfg(feature = "vision")]
+pub(super) use object_detector_metadata_schema_generated::mediapipe::tasks as custom_object_detection;
+#[cfg(feature = "vision")]
+pub(super) const CUSTOM_OBJECT_DETECTION_METADATA_NAME: &str = "DETECTOR_METADATA";
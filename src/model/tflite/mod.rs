@@ -1,24 +1,41 @@
 mod generated;
+#[cfg(test)]
+pub(crate) mod test_model;
 
 use super::*;
 use generated::{tflite as tflite_model, tflite_metadata};
 
+#[cfg(feature = "native-tflite")]
+pub(crate) use generated::tflite as tflite_schema;
+
 pub(crate) struct TfLiteModelResource {
+    input_names: Vec<String>,
+    output_names: Vec<String>,
     input_shape: Vec<Vec<usize>>,
     output_shape: Vec<Vec<usize>>,
     input_types: Vec<TensorType>,
     output_types: Vec<TensorType>,
+    input_quantization_parameters: Vec<Option<QuantizationParameters>>,
     output_quantization_parameters: Vec<Option<QuantizationParameters>>,
     to_tensor_info: Vec<ToTensorInfo>,
     // Option<(label_filename, HashMap<label_locale, label_local_filename>)>
     output_label_files: Vec<Option<(String, HashMap<String, String>)>>,
     output_name_map: HashMap<String, usize>,
     associated_files: HashMap<String, Vec<u8>>,
+    vocab_files: Vec<String>,
+    // metadata process unit names, including the subgraph process units
+    input_process_units: Vec<Vec<&'static str>>,
+    output_process_units: Vec<Vec<&'static str>>,
     // now it only used for image segmentation
     output_activation: Activation,
 
     #[cfg(feature = "vision")]
     output_bound_box_indices: Vec<Option<[usize; 4]>>,
+    #[cfg(feature = "vision")]
+    detector_metadata: Option<DetectorMetadata>,
+    // string input tensors are passed to backends as serialized bytes
+    #[cfg(feature = "text")]
+    input_is_string: Vec<bool>,
 }
 
 impl TfLiteModelResource {
@@ -38,24 +55,36 @@ impl TfLiteModelResource {
 
         let model = tflite_model::root_as_model(buf)?;
         let mut _self = Self {
+            input_names: Vec::new(),
+            output_names: Vec::new(),
             input_shape: Vec::new(),
             output_shape: Vec::new(),
             input_types: Vec::new(),
             output_types: Vec::new(),
+            input_quantization_parameters: Vec::new(),
             output_quantization_parameters: Vec::new(),
             to_tensor_info: Vec::new(),
             output_label_files: Vec::new(),
             output_name_map: Default::default(),
             associated_files,
+            vocab_files: Vec::new(),
+            input_process_units: Vec::new(),
+            output_process_units: Vec::new(),
             output_activation: Default::default(),
             #[cfg(feature = "vision")]
             output_bound_box_indices: Vec::new(),
+            #[cfg(feature = "vision")]
+            detector_metadata: None,
+            #[cfg(feature = "text")]
+            input_is_string: Vec::new(),
         };
         _self.parse_subgraph(&model)?;
         let metadata = Self::parse_model_metadata(&model)?;
         if let Some(metadata) = metadata {
             _self.parse_model_metadata_content(&metadata)?;
         }
+        #[cfg(feature = "text")]
+        _self.parse_string_model_input_info();
         Ok(_self)
     }
 
@@ -88,7 +117,16 @@ impl TfLiteModelResource {
                     )));
                 }
                 let t = tensors.get(index);
-                self.input_types.push(Self::tflite_type_parse(t.type_())?);
+                self.input_names
+                    .push(t.name().unwrap_or_default().to_string());
+                if t.type_() == tflite_model::TensorType::STRING {
+                    self.input_types.push(TensorType::U8);
+                } else {
+                    self.input_types.push(Self::tflite_type_parse(t.type_())?);
+                }
+                #[cfg(feature = "text")]
+                self.input_is_string
+                    .push(t.type_() == tflite_model::TensorType::STRING);
                 if let Some(s) = t.shape() {
                     let len = s.len();
                     let mut shape = Vec::with_capacity(len);
@@ -109,6 +147,13 @@ impl TfLiteModelResource {
                         i
                     )));
                 }
+
+                if let Some(q) = Self::parse_quantization_parameters(&t) {
+                    while self.input_quantization_parameters.len() < i {
+                        self.input_quantization_parameters.push(None);
+                    }
+                    self.input_quantization_parameters.push(Some(q));
+                }
             }
 
             self.output_shape.reserve(outputs.len());
@@ -123,6 +168,8 @@ impl TfLiteModelResource {
                     )));
                 }
                 let t = tensors.get(index);
+                self.output_names
+                    .push(t.name().unwrap_or_default().to_string());
                 let tensor_type = Self::tflite_type_parse(t.type_())?;
                 self.output_types.push(tensor_type);
 
@@ -147,20 +194,11 @@ impl TfLiteModelResource {
                     )));
                 }
 
-                if let Some(q) = t.quantization() {
-                    if let (Some(z), Some(s)) = (q.zero_point(), q.scale()) {
-                        if z.len() > 0 && s.len() > 0 {
-                            while self.output_quantization_parameters.len() < i {
-                                self.output_quantization_parameters.push(None);
-                            }
-                            self.output_quantization_parameters.push(Some(
-                                QuantizationParameters {
-                                    scale: s.get(0),
-                                    zero_point: z.get(0) as i32,
-                                },
-                            ));
-                        }
+                if let Some(q) = Self::parse_quantization_parameters(&t) {
+                    while self.output_quantization_parameters.len() < i {
+                        self.output_quantization_parameters.push(None);
                     }
+                    self.output_quantization_parameters.push(Some(q));
                 }
             }
         } else {
@@ -171,6 +209,21 @@ impl TfLiteModelResource {
         Ok(())
     }
 
+    #[inline(always)]
+    fn parse_quantization_parameters(t: &tflite_model::Tensor) -> Option<QuantizationParameters> {
+        if let Some(q) = t.quantization() {
+            if let (Some(z), Some(s)) = (q.zero_point(), q.scale()) {
+                if z.len() > 0 && s.len() > 0 {
+                    return Some(QuantizationParameters {
+                        scale: s.get(0),
+                        zero_point: z.get(0) as i32,
+                    });
+                }
+            }
+        }
+        None
+    }
+
     #[inline]
     fn parse_model_metadata<'buf>(
         model: &tflite_model::Model<'buf>,
@@ -219,10 +272,24 @@ impl TfLiteModelResource {
                 return Ok(());
             }
         };
+        self.input_process_units = Self::parse_process_units_names(
+            subgraph.input_tensor_metadata(),
+            subgraph.input_process_units(),
+            self.input_shape.len(),
+        );
+        self.output_process_units = Self::parse_process_units_names(
+            subgraph.output_tensor_metadata(),
+            subgraph.output_process_units(),
+            self.output_shape.len(),
+        );
+
         if let Some(input_tensors) = subgraph.input_tensor_metadata() {
             let len = input_tensors.len();
             for i in 0..len {
                 let input = input_tensors.get(i);
+                if let (Some(name), Some(n)) = (input.name(), self.input_names.get_mut(i)) {
+                    *n = name.to_string();
+                }
                 if input.content().is_none() {
                     continue;
                 }
@@ -251,6 +318,9 @@ impl TfLiteModelResource {
                 // parse output name
                 if let Some(name) = output.name() {
                     self.output_name_map.insert(name.to_string(), i);
+                    if let Some(n) = self.output_names.get_mut(i) {
+                        *n = name.to_string();
+                    }
                 }
                 // parse output associated files
                 let mut label = None;
@@ -338,6 +408,11 @@ impl TfLiteModelResource {
                                 ));
                             };
                         }
+                    } else if name == generated::CUSTOM_OBJECT_DETECTION_METADATA_NAME {
+                        if let Some(data) = m.data() {
+                            let meta = generated::custom_object_detection::root_as_object_detector_options(data.bytes())?;
+                            self.detector_metadata = Some(Self::parse_detector_metadata(&meta)?);
+                        }
                     }
                 }
             }
@@ -345,6 +420,100 @@ impl TfLiteModelResource {
         Ok(())
     }
 
+    #[cfg(feature = "vision")]
+    fn parse_detector_metadata(
+        meta: &generated::custom_object_detection::ObjectDetectorOptions,
+    ) -> Result<DetectorMetadata, Error> {
+        let mut res = DetectorMetadata::default();
+        if let Some(anchors) = meta
+            .ssd_anchors_options()
+            .and_then(|o| o.fixed_anchors_schema())
+            .and_then(|s| s.anchors())
+        {
+            res.anchors = anchors
+                .iter()
+                .map(|a| Anchor {
+                    x_center: a.x_center(),
+                    y_center: a.y_center(),
+                    w: a.width(),
+                    h: a.height(),
+                })
+                .collect();
+            if res.anchors.is_empty() {
+                return Err(Error::ModelInconsistentError(
+                    "The fixed anchors schema in metadata has no anchors".into(),
+                ));
+            }
+        }
+
+        if let Some(o) = meta.tensors_decoding_options() {
+            let to_usize = |v: i32, name: &str| {
+                usize::try_from(v).map_err(|_| {
+                    Error::ModelParseError(format!(
+                        "Invalid `{}` value `{}` in TensorsDecodingOptions",
+                        name, v
+                    ))
+                })
+            };
+            let decoding_options = TensorsDecodingOptions {
+                num_classes: to_usize(o.num_classes(), "num_classes")?,
+                num_boxes: to_usize(o.num_boxes(), "num_boxes")?,
+                num_coords: to_usize(o.num_coords(), "num_coords")?,
+                keypoint_coord_offset: to_usize(
+                    o.keypoint_coord_offset(),
+                    "keypoint_coord_offset",
+                )?,
+                num_key_points: to_usize(o.num_keypoints(), "num_keypoints")?,
+                num_values_per_key_point: to_usize(
+                    o.num_values_per_keypoint(),
+                    "num_values_per_keypoint",
+                )?,
+                x_scale: o.x_scale(),
+                y_scale: o.y_scale(),
+                w_scale: o.w_scale(),
+                h_scale: o.h_scale(),
+                apply_exponential_on_box_size: o.apply_exponential_on_box_size(),
+                sigmoid_score: o.sigmoid_score(),
+            };
+            decoding_options.check()?;
+            if !res.anchors.is_empty() && res.anchors.len() != decoding_options.num_boxes {
+                return Err(Error::ModelInconsistentError(format!(
+                    "The number of anchors `{}` in metadata does not match the number of boxes `{}`",
+                    res.anchors.len(),
+                    decoding_options.num_boxes
+                )));
+            }
+            res.tensors_decoding_options = Some(decoding_options);
+        }
+        Ok(res)
+    }
+
+    #[inline]
+    fn parse_process_units_names(
+        tensors: Option<
+            flatbuffers::Vector<flatbuffers::ForwardsUOffset<tflite_metadata::TensorMetadata>>,
+        >,
+        subgraph_process_units: Option<
+            flatbuffers::Vector<flatbuffers::ForwardsUOffset<tflite_metadata::ProcessUnit>>,
+        >,
+        tensor_count: usize,
+    ) -> Vec<Vec<&'static str>> {
+        let name =
+            |p: tflite_metadata::ProcessUnit| p.options_type().variant_name().unwrap_or("UNKNOWN");
+        let mut res = vec![Vec::new(); tensor_count];
+        for (i, names) in res.iter_mut().enumerate() {
+            if let Some(t) = tensors.as_ref().filter(|t| i < t.len()) {
+                if let Some(p) = t.get(i).process_units() {
+                    names.extend(p.iter().map(name));
+                }
+            }
+            if let Some(p) = subgraph_process_units.as_ref() {
+                names.extend(p.iter().map(name));
+            }
+        }
+        res
+    }
+
     #[cfg(feature = "vision")]
     #[inline]
     fn parse_vision_model_input_info(
@@ -496,6 +665,25 @@ impl TfLiteModelResource {
                         .push(ToTensorInfo::new_text(text_model_input));
                     break;
                 }
+                if let Some(sp) = process_units
+                    .get(i)
+                    .options_as_sentence_piece_tokenizer_options()
+                {
+                    if self.input_shape.len() != 3 {
+                        return Err(Error::ModelParseError(format!(
+                            "Model input tensors must be `3` in bert model, but got `{}`",
+                            self.input_shape.len()
+                        )));
+                    }
+                    let max_seq_len = Self::get_max_seq_len(&self.input_shape)?;
+                    let tokenizer = self.process_sentence_piece_model(sp)?;
+                    let text_model_input =
+                        TextToTensorInfo::new_sentence_piece_model(max_seq_len, tokenizer, true)?;
+                    self.to_tensor_info.clear();
+                    self.to_tensor_info
+                        .push(ToTensorInfo::new_text(text_model_input));
+                    break;
+                }
             }
         }
         // regex model
@@ -549,12 +737,63 @@ impl TfLiteModelResource {
                             .push(ToTensorInfo::new_text(text_model_input));
                         break;
                     }
+                    if let Some(sp) = process_units
+                        .get(i)
+                        .options_as_sentence_piece_tokenizer_options()
+                    {
+                        let max_seq_len = Self::get_max_seq_len(&self.input_shape)?;
+                        let tokenizer = self.process_sentence_piece_model(sp)?;
+                        let text_model_input = TextToTensorInfo::new_sentence_piece_model(
+                            max_seq_len,
+                            tokenizer,
+                            false,
+                        )?;
+                        self.to_tensor_info
+                            .push(ToTensorInfo::new_text(text_model_input));
+                        break;
+                    }
                 }
             }
         }
         Ok(())
     }
 
+    /// The model takes string tensors as input, such as the language detector and the
+    /// universal sentence encoder. It does not need metadata to tokenize the text.
+    #[cfg(feature = "text")]
+    #[inline]
+    fn parse_string_model_input_info(&mut self) {
+        if !self.to_tensor_info.is_empty()
+            || self.input_is_string.is_empty()
+            || self.input_is_string.iter().any(|s| !*s)
+        {
+            return;
+        }
+        let find = |name: &str| self.input_names.iter().position(|n| n == name);
+        let query_text_index = find(TextToTensorInfo::USE_QUERY_TEXT_NAME);
+        let response_context_index = find(TextToTensorInfo::USE_RESPONSE_CONTEXT_NAME);
+        let response_text_index = find(TextToTensorInfo::USE_RESPONSE_TEXT_NAME);
+        let found = [
+            query_text_index,
+            response_context_index,
+            response_text_index,
+        ]
+        .iter()
+        .filter(|i| i.is_some())
+        .count();
+        if found == self.input_names.len() {
+            self.to_tensor_info
+                .push(ToTensorInfo::new_text(TextToTensorInfo::UseEncoderModel {
+                    query_text_index,
+                    response_context_index,
+                    response_text_index,
+                }));
+        } else if self.input_names.len() == 1 {
+            self.to_tensor_info
+                .push(ToTensorInfo::new_text(TextToTensorInfo::StringModel));
+        }
+    }
+
     /// Return the filename and MemoryTextFile.
     #[cfg(feature = "text")]
     #[inline]
@@ -567,6 +806,38 @@ impl TfLiteModelResource {
             >,
         >,
     ) -> Result<(&'buf str, MemoryTextFile), Error> {
+        let filename = Self::get_vocab_filename(files)?;
+        self.vocab_files.push(filename.to_string());
+        Ok((
+            filename,
+            MemoryTextFile::new(self.get_file_content(filename)?),
+        ))
+    }
+
+    /// Parse the SentencePiece model file, and delete the file content.
+    #[cfg(feature = "text")]
+    #[inline]
+    fn process_sentence_piece_model(
+        &mut self,
+        options: tflite_metadata::SentencePieceTokenizerOptions,
+    ) -> Result<SentencePieceTokenizer, Error> {
+        let filename = Self::get_vocab_filename(options.sentencePiece_model())?;
+        self.vocab_files.push(filename.to_string());
+        let tokenizer = SentencePieceTokenizer::new(self.get_file_content(filename)?)?;
+        self.associated_files.remove(filename);
+        Ok(tokenizer)
+    }
+
+    #[cfg(feature = "text")]
+    #[inline]
+    fn get_vocab_filename<'buf>(
+        files: Option<
+            flatbuffers::Vector<
+                'buf,
+                flatbuffers::ForwardsUOffset<tflite_metadata::AssociatedFile<'buf>>,
+            >,
+        >,
+    ) -> Result<&'buf str, Error> {
         if files.is_none() || files.unwrap().len() == 0 {
             return Err(Error::ModelParseError(
                 "No vocab files have been found".into(),
@@ -580,20 +851,16 @@ impl TfLiteModelResource {
                 files.len()
             )));
         }
-        let filename = if let Some(n) = files.get(0).name() {
-            n
+        if let Some(n) = files.get(0).name() {
+            Ok(n)
         } else {
-            return Err(Error::ModelParseError(
+            Err(Error::ModelParseError(
                 "Cannot get associated filename.".into(),
-            ));
-        };
-        Ok((
-            filename,
-            MemoryTextFile::new(self.get_file_content(filename)?),
-        ))
+            ))
+        }
     }
 
-    // for bert and regex model.
+    // for bert, regex and sentence piece model.
     #[cfg(feature = "text")]
     #[inline(always)]
     fn get_max_seq_len(input_shape: &Vec<Vec<usize>>) -> Result<u32, Error> {
@@ -660,6 +927,10 @@ impl ModelResourceTrait for TfLiteModelResource {
         return GraphEncoding::TensorflowLite;
     }
 
+    fn model_graph_buffer<'a>(&'a self, model_buffer: &'a [u8]) -> &'a [u8] {
+        model_buffer
+    }
+
     fn input_tensor_count(&self) -> usize {
         self.input_shape.len()
     }
@@ -684,10 +955,26 @@ impl ModelResourceTrait for TfLiteModelResource {
         self.output_shape.get(index).map(|v| v.as_slice())
     }
 
+    fn input_tensor_name(&self, index: usize) -> Option<&str> {
+        self.input_names.get(index).map(|n| n.as_str())
+    }
+
+    fn output_tensor_name(&self, index: usize) -> Option<&str> {
+        self.output_names.get(index).map(|n| n.as_str())
+    }
+
     fn output_tensor_name_to_index(&self, name: &str) -> Option<usize> {
         self.output_name_map.get(name).cloned()
     }
 
+    fn input_tensor_quantization_parameters(&self, index: usize) -> Option<QuantizationParameters> {
+        if let Some(i) = self.input_quantization_parameters.get(index) {
+            i.clone()
+        } else {
+            None
+        }
+    }
+
     fn output_tensor_quantization_parameters(
         &self,
         index: usize,
@@ -720,6 +1007,41 @@ impl ModelResourceTrait for TfLiteModelResource {
         ));
     }
 
+    fn output_tensor_label_files(&self, index: usize) -> Option<(&str, Vec<(&str, &str)>)> {
+        match self.output_label_files.get(index) {
+            Some(Some((label_file, locales))) => Some((
+                label_file.as_str(),
+                locales
+                    .iter()
+                    .map(|(l, f)| (l.as_str(), f.as_str()))
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn associated_file_names(&self) -> Vec<&str> {
+        self.associated_files.keys().map(|f| f.as_str()).collect()
+    }
+
+    fn vocab_file_names(&self) -> Vec<&str> {
+        self.vocab_files.iter().map(|f| f.as_str()).collect()
+    }
+
+    fn input_process_units(&self, index: usize) -> &[&'static str] {
+        self.input_process_units
+            .get(index)
+            .map(|p| p.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn output_process_units(&self, index: usize) -> &[&'static str] {
+        self.output_process_units
+            .get(index)
+            .map(|p| p.as_slice())
+            .unwrap_or_default()
+    }
+
     #[cfg(feature = "vision")]
     fn output_bounding_box_properties(&self, index: usize, slice: &mut [usize]) -> bool {
         if let Some(o) = self.output_bound_box_indices.get(index) {
@@ -731,6 +1053,11 @@ impl ModelResourceTrait for TfLiteModelResource {
         false
     }
 
+    #[cfg(feature = "vision")]
+    fn detector_metadata(&self) -> Option<&DetectorMetadata> {
+        self.detector_metadata.as_ref()
+    }
+
     fn to_tensor_info(&self, input_index: usize) -> Option<&ToTensorInfo> {
         self.to_tensor_info.get(input_index)
     }
@@ -741,3 +1068,120 @@ impl ModelResourceTrait for TfLiteModelResource {
 }
 
 // todo: The GPU backend isn't able to process int data. If the input tensor is quantized, forces the image preprocessing graph to use CPU backend.
+
+#[cfg(test)]
+#[cfg(feature = "vision")]
+mod test {
+    use super::*;
+    use generated::custom_object_detection as meta;
+
+    const ANCHORS: [(f32, f32); 2] = [(0.25, 0.5), (0.75, 0.5)];
+
+    fn detector_metadata_buffer(
+        anchors: &[(f32, f32)],
+        num_boxes: i32,
+        num_coords: i32,
+    ) -> Vec<u8> {
+        let mut fbb = flatbuffers::FlatBufferBuilder::new();
+        let anchors = anchors
+            .iter()
+            .map(|(x, y)| {
+                meta::FixedAnchor::create(
+                    &mut fbb,
+                    &meta::FixedAnchorArgs {
+                        x_center: *x,
+                        y_center: *y,
+                        width: 1.,
+                        height: 1.,
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let anchors = fbb.create_vector(&anchors);
+        let fixed_anchors_schema = meta::FixedAnchorsSchema::create(
+            &mut fbb,
+            &meta::FixedAnchorsSchemaArgs {
+                anchors: Some(anchors),
+            },
+        );
+        let ssd_anchors_options = meta::SsdAnchorsOptions::create(
+            &mut fbb,
+            &meta::SsdAnchorsOptionsArgs {
+                fixed_anchors_schema: Some(fixed_anchors_schema),
+            },
+        );
+        let tensors_decoding_options = meta::TensorsDecodingOptions::create(
+            &mut fbb,
+            &meta::TensorsDecodingOptionsArgs {
+                num_classes: 1,
+                num_boxes,
+                num_coords,
+                keypoint_coord_offset: 4,
+                num_keypoints: 6,
+                num_values_per_keypoint: 2,
+                x_scale: 128.,
+                y_scale: 128.,
+                w_scale: 128.,
+                h_scale: 128.,
+                apply_exponential_on_box_size: false,
+                sigmoid_score: true,
+            },
+        );
+        let root = meta::ObjectDetectorOptions::create(
+            &mut fbb,
+            &meta::ObjectDetectorOptionsArgs {
+                min_parser_version: None,
+                ssd_anchors_options: Some(ssd_anchors_options),
+                tensors_decoding_options: Some(tensors_decoding_options),
+            },
+        );
+        meta::finish_object_detector_options_buffer(&mut fbb, root);
+        fbb.finished_data().to_vec()
+    }
+
+    #[test]
+    fn test_parse_detector_metadata() {
+        let buf = detector_metadata_buffer(&ANCHORS, 2, 16);
+        let options = meta::root_as_object_detector_options(&buf).unwrap();
+        let res = TfLiteModelResource::parse_detector_metadata(&options).unwrap();
+        assert_eq!(res.anchors.len(), 2);
+        assert_eq!(res.anchors[1].x_center, 0.75);
+        assert_eq!(res.anchors[1].y_center, 0.5);
+        let decoding_options = res.tensors_decoding_options.unwrap();
+        assert_eq!(decoding_options.num_boxes, 2);
+        assert_eq!(decoding_options.num_coords, 16);
+        assert_eq!(decoding_options.num_key_points, 6);
+        assert_eq!(decoding_options.x_scale, 128.);
+        assert!(decoding_options.sigmoid_score);
+
+        let buf = detector_metadata_buffer(&ANCHORS, -1, 16);
+        let options = meta::root_as_object_detector_options(&buf).unwrap();
+        assert!(TfLiteModelResource::parse_detector_metadata(&options).is_err());
+    }
+
+    #[test]
+    fn test_parse_inconsistent_detector_metadata() {
+        for (anchors, num_boxes, num_coords) in [
+            // 6 key points at offset 4 need 16 coords
+            (&ANCHORS[..], 2, 15),
+            // no box coords
+            (&ANCHORS[..], 2, 0),
+            // no boxes
+            (&[][..], 0, 16),
+            // no anchors in the fixed anchors schema
+            (&[][..], 2, 16),
+            // the number of anchors does not match the number of boxes
+            (&ANCHORS[..], 3, 16),
+        ] {
+            let buf = detector_metadata_buffer(anchors, num_boxes, num_coords);
+            let options = meta::root_as_object_detector_options(&buf).unwrap();
+            let res = TfLiteModelResource::parse_detector_metadata(&options);
+            assert!(
+                matches!(res, Err(Error::ModelInconsistentError(_))),
+                "{} boxes and {} coords",
+                num_boxes,
+                num_coords
+            );
+        }
+    }
+}
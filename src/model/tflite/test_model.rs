@@ -0,0 +1,190 @@
+//! Build tiny TfLite models for unit tests, such as the inference backends and task sessions tests.
+
+use super::generated::tflite as schema;
+use flatbuffers::{FlatBufferBuilder, UnionWIPOffset, WIPOffset};
+
+pub(crate) use schema::{BuiltinOperator, BuiltinOptions, TensorType};
+
+/// Quantization of a test tensor: ```(scale, zero_point, quantized_dimension)```.
+pub(crate) type TestQuantization<'q> = (&'q [f32], &'q [i64], i32);
+
+/// Builtin options of a test operator.
+pub(crate) type TestOptions = Option<(BuiltinOptions, WIPOffset<UnionWIPOffset>)>;
+
+/// A TfLite model builder with one subgraph.
+pub(crate) struct TestModelBuilder {
+    fbb: FlatBufferBuilder<'static>,
+    buffers: Vec<WIPOffset<schema::Buffer<'static>>>,
+    tensors: Vec<WIPOffset<schema::Tensor<'static>>>,
+    op_codes: Vec<WIPOffset<schema::OperatorCode<'static>>>,
+    operators: Vec<WIPOffset<schema::Operator<'static>>>,
+    metadata: Option<Vec<u8>>,
+}
+
+impl TestModelBuilder {
+    pub(crate) fn new() -> Self {
+        let mut fbb = FlatBufferBuilder::new();
+        // the buffer 0 is always empty
+        let empty = schema::Buffer::create(&mut fbb, &schema::BufferArgs { data: None });
+        Self {
+            fbb,
+            buffers: vec![empty],
+            tensors: Vec::new(),
+            op_codes: Vec::new(),
+            operators: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    /// Add a tensor, ```data``` is the little endian bytes of a constant tensor.
+    /// Return the tensor index.
+    pub(crate) fn tensor(
+        &mut self,
+        name: &str,
+        shape: &[i32],
+        type_: TensorType,
+        data: Option<&[u8]>,
+        quantization: Option<TestQuantization>,
+    ) -> i32 {
+        let buffer = match data {
+            Some(d) => {
+                let data = self.fbb.create_vector(d);
+                self.buffers.push(schema::Buffer::create(
+                    &mut self.fbb,
+                    &schema::BufferArgs { data: Some(data) },
+                ));
+                self.buffers.len() as u32 - 1
+            }
+            None => 0,
+        };
+        let quantization = quantization.map(|(scale, zero_point, axis)| {
+            let scale = self.fbb.create_vector(scale);
+            let zero_point = self.fbb.create_vector(zero_point);
+            schema::QuantizationParameters::create(
+                &mut self.fbb,
+                &schema::QuantizationParametersArgs {
+                    scale: Some(scale),
+                    zero_point: Some(zero_point),
+                    quantized_dimension: axis,
+                    ..Default::default()
+                },
+            )
+        });
+        let name = self.fbb.create_string(name);
+        let shape = self.fbb.create_vector(shape);
+        self.tensors.push(schema::Tensor::create(
+            &mut self.fbb,
+            &schema::TensorArgs {
+                shape: Some(shape),
+                type_,
+                buffer,
+                name: Some(name),
+                quantization,
+                ..Default::default()
+            },
+        ));
+        self.tensors.len() as i32 - 1
+    }
+
+    /// Add a ```FLOAT32``` constant tensor.
+    #[inline(always)]
+    pub(crate) fn f32_tensor(&mut self, name: &str, shape: &[i32], data: &[f32]) -> i32 {
+        let bytes: Vec<u8> = data.iter().flat_map(|f| f.to_le_bytes()).collect();
+        self.tensor(name, shape, TensorType::FLOAT32, Some(&bytes), None)
+    }
+
+    /// Add an operator, ```options``` creates the builtin options.
+    pub(crate) fn operator(
+        &mut self,
+        code: BuiltinOperator,
+        inputs: &[i32],
+        outputs: &[i32],
+        options: impl FnOnce(&mut FlatBufferBuilder<'static>) -> TestOptions,
+    ) {
+        self.op_codes.push(schema::OperatorCode::create(
+            &mut self.fbb,
+            &schema::OperatorCodeArgs {
+                deprecated_builtin_code: code.0.min(127) as i8,
+                builtin_code: code,
+                version: 1,
+                ..Default::default()
+            },
+        ));
+        let (builtin_options_type, builtin_options) = match options(&mut self.fbb) {
+            Some((t, o)) => (t, Some(o)),
+            None => (BuiltinOptions::NONE, None),
+        };
+        let inputs = self.fbb.create_vector(inputs);
+        let outputs = self.fbb.create_vector(outputs);
+        self.operators.push(schema::Operator::create(
+            &mut self.fbb,
+            &schema::OperatorArgs {
+                opcode_index: self.op_codes.len() as u32 - 1,
+                inputs: Some(inputs),
+                outputs: Some(outputs),
+                builtin_options_type,
+                builtin_options,
+                ..Default::default()
+            },
+        ));
+    }
+
+    /// Set the ```TFLITE_METADATA``` buffer.
+    #[inline(always)]
+    pub(crate) fn metadata(&mut self, buf: Vec<u8>) {
+        self.metadata = Some(buf);
+    }
+
+    pub(crate) fn finish(mut self, inputs: &[i32], outputs: &[i32]) -> Vec<u8> {
+        let metadata = self.metadata.take().map(|m| {
+            let data = self.fbb.create_vector(&m);
+            self.buffers.push(schema::Buffer::create(
+                &mut self.fbb,
+                &schema::BufferArgs { data: Some(data) },
+            ));
+            let name = self
+                .fbb
+                .create_string(super::TfLiteModelResource::METADATA_NAME);
+            let m = schema::Metadata::create(
+                &mut self.fbb,
+                &schema::MetadataArgs {
+                    name: Some(name),
+                    buffer: self.buffers.len() as u32 - 1,
+                },
+            );
+            self.fbb.create_vector(&[m])
+        });
+
+        let fbb = &mut self.fbb;
+        let tensors = fbb.create_vector(&self.tensors);
+        let operators = fbb.create_vector(&self.operators);
+        let inputs = fbb.create_vector(inputs);
+        let outputs = fbb.create_vector(outputs);
+        let subgraph = schema::SubGraph::create(
+            fbb,
+            &schema::SubGraphArgs {
+                tensors: Some(tensors),
+                inputs: Some(inputs),
+                outputs: Some(outputs),
+                operators: Some(operators),
+                ..Default::default()
+            },
+        );
+        let subgraphs = fbb.create_vector(&[subgraph]);
+        let op_codes = fbb.create_vector(&self.op_codes);
+        let buffers = fbb.create_vector(&self.buffers);
+        let model = schema::Model::create(
+            fbb,
+            &schema::ModelArgs {
+                version: 3,
+                operator_codes: Some(op_codes),
+                subgraphs: Some(subgraphs),
+                buffers: Some(buffers),
+                metadata,
+                ..Default::default()
+            },
+        );
+        schema::finish_model_buffer(fbb, model);
+        fbb.finished_data().to_vec()
+    }
+}
@@ -0,0 +1,366 @@
+// DEFLATE format spec: https://www.rfc-editor.org/rfc/rfc1951
+// The decoder is a straightforward implementation of the spec, like zlib's `puff`.
+
+use crate::Error;
+
+const MAX_BITS: usize = 15;
+const MAX_LIT_LEN_CODES: usize = 288;
+const MAX_DIST_CODES: usize = 30;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+// order of code length code lengths
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+macro_rules! inflate_error {
+    ( $($arg:tt)* ) => {
+        Error::ZipFileParseError(format!("Invalid deflate data: {}", format!($($arg)*)))
+    };
+}
+
+struct BitReader<'buf> {
+    buf: &'buf [u8],
+    pos: usize,
+    bit_buf: u32,
+    bit_count: u32,
+}
+
+impl<'buf> BitReader<'buf> {
+    #[inline(always)]
+    fn new(buf: &'buf [u8]) -> Self {
+        Self {
+            buf,
+            pos: 0,
+            bit_buf: 0,
+            bit_count: 0,
+        }
+    }
+
+    /// Read ```n``` (<= 16) bits, least significant bit first.
+    #[inline(always)]
+    fn bits(&mut self, n: u32) -> Result<u32, Error> {
+        while self.bit_count < n {
+            if self.pos >= self.buf.len() {
+                return Err(inflate_error!("unexpected end of data"));
+            }
+            self.bit_buf |= (self.buf[self.pos] as u32) << self.bit_count;
+            self.pos += 1;
+            self.bit_count += 8;
+        }
+        let res = self.bit_buf & ((1 << n) - 1);
+        self.bit_buf >>= n;
+        self.bit_count -= n;
+        Ok(res)
+    }
+
+    /// Discard the remaining bits in current byte.
+    #[inline(always)]
+    fn align_to_byte(&mut self) {
+        self.bit_buf = 0;
+        self.bit_count = 0;
+    }
+}
+
+/// Canonical huffman code, ```counts[len]``` is the number of codes of length ```len```,
+/// ```symbols``` are ordered by code.
+struct Huffman {
+    counts: [u16; MAX_BITS + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, Error> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for l in lengths {
+            counts[*l as usize] += 1;
+        }
+        // check over-subscribed, incomplete codes are allowed
+        let mut left = 1i32;
+        for c in counts.iter().skip(1) {
+            left <<= 1;
+            left -= *c as i32;
+            if left < 0 {
+                return Err(inflate_error!("over-subscribed huffman code"));
+            }
+        }
+
+        let mut offsets = [0u16; MAX_BITS + 1];
+        for len in 1..MAX_BITS {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, l) in lengths.iter().enumerate() {
+            if *l != 0 {
+                symbols[offsets[*l as usize] as usize] = symbol as u16;
+                offsets[*l as usize] += 1;
+            }
+        }
+        Ok(Self { counts, symbols })
+    }
+
+    #[inline]
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, Error> {
+        // code, first code and first index of current length
+        let mut code = 0i32;
+        let mut first = 0i32;
+        let mut index = 0i32;
+        for len in 1..=MAX_BITS {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        Err(inflate_error!("invalid huffman code"))
+    }
+}
+
+// The max compression ratio of deflate is about 1032:1, used to bound the pre-allocated capacity.
+const MAX_COMPRESSION_RATIO: usize = 1032;
+
+/// Decompress raw deflate data. ```max_size``` is the declared uncompressed size, it is an error if the
+/// output exceeds it.
+pub(super) fn inflate(data: &[u8], max_size: usize) -> Result<Vec<u8>, Error> {
+    // the declared size cannot be trusted, the buffer grows on demand beyond the bounded capacity
+    let mut out =
+        Vec::with_capacity(max_size.min(data.len().saturating_mul(MAX_COMPRESSION_RATIO)));
+    let mut reader = BitReader::new(data);
+    loop {
+        let last = reader.bits(1)?;
+        match reader.bits(2)? {
+            0 => stored_block(&mut reader, &mut out, max_size)?,
+            1 => {
+                let (lit_len, dist) = fixed_huffman()?;
+                huffman_block(&mut reader, &mut out, max_size, &lit_len, &dist)?;
+            }
+            2 => {
+                let (lit_len, dist) = dynamic_huffman(&mut reader)?;
+                huffman_block(&mut reader, &mut out, max_size, &lit_len, &dist)?;
+            }
+            t => return Err(inflate_error!("invalid block type `{}`", t)),
+        }
+        if last == 1 {
+            return Ok(out);
+        }
+    }
+}
+
+#[inline(always)]
+fn check_output_size(out: &[u8], len: usize, max_size: usize) -> Result<(), Error> {
+    if out.len() + len > max_size {
+        return Err(inflate_error!(
+            "output exceeds the declared size `{}`",
+            max_size
+        ));
+    }
+    Ok(())
+}
+
+#[inline]
+fn stored_block(reader: &mut BitReader, out: &mut Vec<u8>, max_size: usize) -> Result<(), Error> {
+    reader.align_to_byte();
+    let pos = reader.pos;
+    if pos + 4 > reader.buf.len() {
+        return Err(inflate_error!("unexpected end of data"));
+    }
+    let len = u16::from_le_bytes([reader.buf[pos], reader.buf[pos + 1]]);
+    let nlen = u16::from_le_bytes([reader.buf[pos + 2], reader.buf[pos + 3]]);
+    if len != !nlen {
+        return Err(inflate_error!("stored block length does not match"));
+    }
+    let start = pos + 4;
+    let end = start + len as usize;
+    if end > reader.buf.len() {
+        return Err(inflate_error!("unexpected end of data"));
+    }
+    check_output_size(out, len as usize, max_size)?;
+    out.extend_from_slice(&reader.buf[start..end]);
+    reader.pos = end;
+    Ok(())
+}
+
+#[inline]
+fn fixed_huffman() -> Result<(Huffman, Huffman), Error> {
+    let mut lengths = [0u8; MAX_LIT_LEN_CODES];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5; MAX_DIST_CODES])?))
+}
+
+#[inline]
+fn dynamic_huffman(reader: &mut BitReader) -> Result<(Huffman, Huffman), Error> {
+    let num_lit_len = reader.bits(5)? as usize + 257;
+    let num_dist = reader.bits(5)? as usize + 1;
+    let num_code_len = reader.bits(4)? as usize + 4;
+    if num_lit_len > MAX_LIT_LEN_CODES || num_dist > MAX_DIST_CODES {
+        return Err(inflate_error!("too many length or distance codes"));
+    }
+
+    let mut code_len_lengths = [0u8; 19];
+    for i in CODE_LENGTH_ORDER.iter().take(num_code_len) {
+        code_len_lengths[*i] = reader.bits(3)? as u8;
+    }
+    let code_len = Huffman::new(&code_len_lengths)?;
+
+    let mut lengths = vec![0u8; num_lit_len + num_dist];
+    let mut i = 0;
+    while i < lengths.len() {
+        let symbol = code_len.decode(reader)?;
+        if symbol < 16 {
+            lengths[i] = symbol as u8;
+            i += 1;
+            continue;
+        }
+        let (value, repeat) = match symbol {
+            16 => {
+                if i == 0 {
+                    return Err(inflate_error!("repeat with no previous length"));
+                }
+                (lengths[i - 1], 3 + reader.bits(2)? as usize)
+            }
+            17 => (0, 3 + reader.bits(3)? as usize),
+            _ => (0, 11 + reader.bits(7)? as usize),
+        };
+        if i + repeat > lengths.len() {
+            return Err(inflate_error!("too many code lengths"));
+        }
+        lengths[i..i + repeat].fill(value);
+        i += repeat;
+    }
+    if lengths[256] == 0 {
+        return Err(inflate_error!("missing end-of-block code"));
+    }
+
+    Ok((
+        Huffman::new(&lengths[..num_lit_len])?,
+        Huffman::new(&lengths[num_lit_len..])?,
+    ))
+}
+
+#[inline]
+fn huffman_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    max_size: usize,
+    lit_len: &Huffman,
+    dist: &Huffman,
+) -> Result<(), Error> {
+    loop {
+        let symbol = lit_len.decode(reader)? as usize;
+        if symbol < 256 {
+            check_output_size(out, 1, max_size)?;
+            out.push(symbol as u8);
+            continue;
+        }
+        if symbol == 256 {
+            return Ok(());
+        }
+
+        let symbol = symbol - 257;
+        if symbol >= LENGTH_BASE.len() {
+            return Err(inflate_error!("invalid length symbol"));
+        }
+        let len = LENGTH_BASE[symbol] as usize + reader.bits(LENGTH_EXTRA[symbol] as u32)? as usize;
+        let symbol = dist.decode(reader)? as usize;
+        if symbol >= DIST_BASE.len() {
+            return Err(inflate_error!("invalid distance symbol"));
+        }
+        let distance =
+            DIST_BASE[symbol] as usize + reader.bits(DIST_EXTRA[symbol] as u32)? as usize;
+        if distance > out.len() {
+            return Err(inflate_error!("distance `{}` is too far back", distance));
+        }
+        check_output_size(out, len, max_size)?;
+        // the copy may overlap with the output
+        let start = out.len() - distance;
+        for j in 0..len {
+            let b = out[start + j];
+            out.push(b);
+        }
+    }
+}
+
+/// CRC-32 used by zip files (polynomial 0xEDB88320).
+pub(super) fn crc32(data: &[u8]) -> u32 {
+    const fn make_table() -> [u32; 256] {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut c = i as u32;
+            let mut k = 0;
+            while k < 8 {
+                c = if c & 1 != 0 {
+                    0xEDB88320 ^ (c >> 1)
+                } else {
+                    c >> 1
+                };
+                k += 1;
+            }
+            table[i] = c;
+            i += 1;
+        }
+        table
+    }
+    const TABLE: [u32; 256] = make_table();
+
+    let mut crc = !0u32;
+    for b in data {
+        crc = TABLE[((crc ^ *b as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+
+    #[test]
+    fn test_inflate() {
+        // stored block
+        assert_eq!(
+            inflate(&[0x01, 0x02, 0x00, 0xfd, 0xff, 0x61, 0x62], 2).unwrap(),
+            b"ab"
+        );
+        // fixed huffman block with back reference: "abcabcabcabc"
+        assert_eq!(
+            inflate(&[0x4b, 0x4c, 0x4a, 0x4e, 0x84, 0x21, 0x00], 12).unwrap(),
+            b"abcabcabcabc"
+        );
+        // invalid block type
+        assert!(inflate(&[0x07], 0).is_err());
+        // truncated
+        assert!(inflate(&[0x4b, 0x4c], 12).is_err());
+        // the output exceeds the declared size
+        assert!(inflate(&[0x01, 0x02, 0x00, 0xfd, 0xff, 0x61, 0x62], 1).is_err());
+        assert!(inflate(&[0x4b, 0x4c, 0x4a, 0x4e, 0x84, 0x21, 0x00], 11).is_err());
+        // the declared size is not used to pre-allocate the buffer
+        assert!(inflate(&[0x4b, 0x4c, 0x4a, 0x4e, 0x84, 0x21, 0x00], usize::MAX).is_ok());
+    }
+}
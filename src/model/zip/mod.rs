@@ -0,0 +1,1140 @@
+// ZIP format spec: https://pkware.cachefly.net/webdocs/casestudies/APPNOTE.TXT
+
+mod inflate;
+
+use crate::Error;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+
+macro_rules! read_le_u16 {
+    ( $buf:expr, $offset:expr ) => {{
+        let start = $offset;
+        let low = $buf[start] as u16;
+        let high = $buf[start + 1] as u16;
+        (high << 8) | low
+    }};
+}
+
+macro_rules! read_le_u32 {
+    ( $buf:expr, $offset:expr ) => {{
+        let start = $offset;
+        let arr = [
+            $buf[start],
+            $buf[start + 1],
+            $buf[start + 2],
+            $buf[start + 3],
+        ];
+        u32::from_le_bytes(arr)
+    }};
+}
+
+macro_rules! read_le_u64 {
+    ( $buf:expr, $offset:expr ) => {{
+        let start = $offset;
+        let mut arr = [0u8; 8];
+        arr.copy_from_slice(&$buf[start..start + 8]);
+        u64::from_le_bytes(arr)
+    }};
+}
+
+/*
+4.3.16  End of central directory record:
+
+      end of central dir signature    4 bytes  (0x06054b50)
+      number of this disk             2 bytes
+      number of the disk with the
+      start of the central directory  2 bytes
+      total number of entries in the
+      central directory on this disk  2 bytes
+      total number of entries in
+      the central directory           2 bytes
+      size of the central directory   4 bytes
+      offset of start of central
+      directory with respect to
+      the starting disk number        4 bytes
+      .ZIP file comment length        2 bytes
+      .ZIP file comment       (variable size)
+ */
+struct EndOfCentralDirectoryRecord<'buf> {
+    buf: &'buf [u8],
+}
+
+impl<'buf> EndOfCentralDirectoryRecord<'buf> {
+    const HEAD_SIGNATURE: &'static [u8] = &[0x50, 0x4b, 0x05, 0x06];
+    const MIN_SIZE: usize = 20; // at least 20 bytes
+
+    const NUMBER_OF_THIS_DISK_POS: usize = 4;
+    const DISK_WHERE_CENTRAL_DIRECTORY_STARTS_POS: usize = 6;
+    const NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_ON_THIS_DISK_POS: usize = 8;
+    const TOTAL_NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_POS: usize = 10;
+    const SIZE_OF_CENTRAL_DIRECTORY_POS: usize = 12;
+    const OFFSET_OF_START_OF_CENTRAL_DIRECTORY_POS: usize = 16;
+    const COMMENT_LENGTH_POS: usize = 20;
+    const COMMENT_POS: usize = 22;
+
+    #[inline(always)]
+    fn number_of_this_disk(&self) -> u16 {
+        read_le_u16!(self.buf, Self::NUMBER_OF_THIS_DISK_POS)
+    }
+
+    #[inline(always)]
+    fn disk_where_central_directory_starts(&self) -> u16 {
+        read_le_u16!(self.buf, Self::DISK_WHERE_CENTRAL_DIRECTORY_STARTS_POS)
+    }
+
+    #[inline(always)]
+    fn number_of_central_directory_records_on_this_disk(&self) -> u16 {
+        read_le_u16!(
+            self.buf,
+            Self::NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_ON_THIS_DISK_POS
+        )
+    }
+
+    #[inline(always)]
+    fn total_number_of_central_directory_records(&self) -> u16 {
+        read_le_u16!(
+            self.buf,
+            Self::TOTAL_NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_POS
+        )
+    }
+
+    #[inline(always)]
+    fn size_of_central_directory(&self) -> u32 {
+        read_le_u32!(self.buf, Self::SIZE_OF_CENTRAL_DIRECTORY_POS)
+    }
+
+    #[inline(always)]
+    fn offset_of_start_of_central_directory(&self) -> u32 {
+        read_le_u32!(self.buf, Self::OFFSET_OF_START_OF_CENTRAL_DIRECTORY_POS)
+    }
+
+    #[inline(always)]
+    fn comment_length(&self) -> u16 {
+        read_le_u16!(self.buf, Self::COMMENT_LENGTH_POS)
+    }
+
+    #[inline(always)]
+    fn comment(&self) -> &'buf [u8] {
+        &self.buf[Self::COMMENT_POS..Self::COMMENT_POS + self.comment_length() as usize]
+    }
+
+    /// If any of these fields is saturated, the real value is in the ZIP64 end of central directory record.
+    #[inline(always)]
+    fn is_zip64(&self) -> bool {
+        self.total_number_of_central_directory_records() == u16::MAX
+            || self.size_of_central_directory() == u32::MAX
+            || self.offset_of_start_of_central_directory() == u32::MAX
+    }
+
+    #[inline(always)]
+    fn try_find_start_pos(buf: &'buf [u8]) -> usize {
+        let mut start_pos = match buf.len().checked_sub(Self::MIN_SIZE) {
+            None => {
+                return buf.len();
+            }
+            Some(p) => p,
+        };
+        loop {
+            if &buf[start_pos..start_pos + 4] == Self::HEAD_SIGNATURE {
+                return start_pos;
+            }
+            start_pos = match start_pos.checked_sub(1) {
+                None => {
+                    return buf.len();
+                }
+                Some(p) => p,
+            };
+        }
+    }
+
+    #[inline(always)]
+    fn new_with_start_pos(buf: &'buf [u8], start_pos: usize) -> Result<Self, Error> {
+        if start_pos >= buf.len() {
+            return Err(Error::ZipFileParseError(format!(
+                "End of central directory start pos error `{}` >= `{}`",
+                start_pos,
+                buf.len()
+            )));
+        }
+        let res = Self {
+            buf: &buf[start_pos..],
+        };
+        if res.number_of_this_disk() != 0
+            || res.disk_where_central_directory_starts() != 0
+            || res.number_of_central_directory_records_on_this_disk()
+                != res.total_number_of_central_directory_records()
+        {
+            return Err(Error::ZipFileParseError(
+                "Unsupported multi-disk zip file.".into(),
+            ));
+        }
+        if !res.is_zip64() {
+            let central_directory = res.offset_of_start_of_central_directory() as usize;
+            if central_directory >= start_pos
+                || central_directory + res.size_of_central_directory() as usize > buf.len()
+            {
+                return Err(Error::ZipFileParseError(
+                    "Central directory information error".into(),
+                ));
+            }
+        }
+        if start_pos + Self::COMMENT_POS + res.comment_length() as usize > buf.len() {
+            return Err(Error::ZipFileParseError(
+                "Comment length is too long".into(),
+            ));
+        }
+        return Ok(res);
+    }
+
+    #[inline]
+    fn new(buf: &'buf [u8]) -> Result<Self, Error> {
+        let start_pos = Self::try_find_start_pos(buf);
+        if start_pos == 0 {
+            return Err(Error::ZipFileParseError(
+                "Cannot find end of central directory.".into(),
+            ));
+        }
+        Self::new_with_start_pos(buf, start_pos)
+    }
+}
+
+/*
+4.3.15 Zip64 end of central directory locator
+
+      zip64 end of central dir locator
+      signature                       4 bytes  (0x07064b50)
+      number of the disk with the
+      start of the zip64 end of
+      central directory               4 bytes
+      relative offset of the zip64
+      end of central directory record 8 bytes
+      total number of disks           4 bytes
+
+4.3.14  Zip64 end of central directory record
+
+      zip64 end of central dir
+      signature                       4 bytes  (0x06064b50)
+      size of zip64 end of central
+      directory record                8 bytes
+      version made by                 2 bytes
+      version needed to extract       2 bytes
+      number of this disk             4 bytes
+      number of the disk with the
+      start of the central directory  4 bytes
+      total number of entries in the
+      central directory on this disk  8 bytes
+      total number of entries in the
+      central directory               8 bytes
+      size of the central directory   8 bytes
+      offset of start of central
+      directory with respect to
+      the starting disk number        8 bytes
+      zip64 extensible data sector    (variable size)
+ */
+struct Zip64EndOfCentralDirectoryRecord<'buf> {
+    buf: &'buf [u8],
+}
+
+impl<'buf> Zip64EndOfCentralDirectoryRecord<'buf> {
+    const LOCATOR_HEAD_SIGNATURE: &'static [u8] = &[0x50, 0x4b, 0x06, 0x07];
+    const LOCATOR_SIZE: usize = 20;
+    const LOCATOR_DISK_POS: usize = 4;
+    const LOCATOR_OFFSET_POS: usize = 8;
+    const LOCATOR_TOTAL_NUMBER_OF_DISKS_POS: usize = 16;
+
+    const HEAD_SIGNATURE: &'static [u8] = &[0x50, 0x4b, 0x06, 0x06];
+    const MIN_SIZE: usize = 56;
+    const NUMBER_OF_THIS_DISK_POS: usize = 16;
+    const DISK_WHERE_CENTRAL_DIRECTORY_STARTS_POS: usize = 20;
+    const NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_ON_THIS_DISK_POS: usize = 24;
+    const TOTAL_NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_POS: usize = 32;
+    const SIZE_OF_CENTRAL_DIRECTORY_POS: usize = 40;
+    const OFFSET_OF_START_OF_CENTRAL_DIRECTORY_POS: usize = 48;
+
+    #[inline(always)]
+    fn number_of_this_disk(&self) -> u32 {
+        read_le_u32!(self.buf, Self::NUMBER_OF_THIS_DISK_POS)
+    }
+
+    #[inline(always)]
+    fn disk_where_central_directory_starts(&self) -> u32 {
+        read_le_u32!(self.buf, Self::DISK_WHERE_CENTRAL_DIRECTORY_STARTS_POS)
+    }
+
+    #[inline(always)]
+    fn number_of_central_directory_records_on_this_disk(&self) -> u64 {
+        read_le_u64!(
+            self.buf,
+            Self::NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_ON_THIS_DISK_POS
+        )
+    }
+
+    #[inline(always)]
+    fn total_number_of_central_directory_records(&self) -> u64 {
+        read_le_u64!(
+            self.buf,
+            Self::TOTAL_NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_POS
+        )
+    }
+
+    #[inline(always)]
+    fn size_of_central_directory(&self) -> u64 {
+        read_le_u64!(self.buf, Self::SIZE_OF_CENTRAL_DIRECTORY_POS)
+    }
+
+    #[inline(always)]
+    fn offset_of_start_of_central_directory(&self) -> u64 {
+        read_le_u64!(self.buf, Self::OFFSET_OF_START_OF_CENTRAL_DIRECTORY_POS)
+    }
+
+    /// Find the record by the locator, which is just before the end of central directory record.
+    #[inline]
+    fn new_with_end_of_central_directory_pos(
+        buf: &'buf [u8],
+        end_of_central_directory_pos: usize,
+    ) -> Result<Self, Error> {
+        let locator_pos = match end_of_central_directory_pos.checked_sub(Self::LOCATOR_SIZE) {
+            Some(p) if &buf[p..p + 4] == Self::LOCATOR_HEAD_SIGNATURE => p,
+            _ => {
+                return Err(Error::ZipFileParseError(
+                    "Cannot find zip64 end of central directory locator".into(),
+                ));
+            }
+        };
+        let locator = &buf[locator_pos..end_of_central_directory_pos];
+        if read_le_u32!(locator, Self::LOCATOR_DISK_POS) != 0
+            || read_le_u32!(locator, Self::LOCATOR_TOTAL_NUMBER_OF_DISKS_POS) > 1
+        {
+            return Err(Error::ZipFileParseError(
+                "Unsupported multi-disk zip file.".into(),
+            ));
+        }
+
+        let start_pos = read_le_u64!(locator, Self::LOCATOR_OFFSET_POS) as usize;
+        if start_pos > locator_pos || locator_pos - start_pos < Self::MIN_SIZE {
+            return Err(Error::ZipFileParseError(format!(
+                "Zip64 end of central directory record offset error `{}`",
+                start_pos
+            )));
+        }
+        let res = Self {
+            buf: &buf[start_pos..locator_pos],
+        };
+        if &res.buf[..4] != Self::HEAD_SIGNATURE {
+            return Err(Error::ZipFileParseError(format!(
+                "Invalid zip64 end of central directory head magic `{:?}`",
+                &res.buf[..4]
+            )));
+        }
+        if res.number_of_this_disk() != 0
+            || res.disk_where_central_directory_starts() != 0
+            || res.number_of_central_directory_records_on_this_disk()
+                != res.total_number_of_central_directory_records()
+        {
+            return Err(Error::ZipFileParseError(
+                "Unsupported multi-disk zip file.".into(),
+            ));
+        }
+        let central_directory = res.offset_of_start_of_central_directory();
+        let central_directory_end = central_directory
+            .checked_add(res.size_of_central_directory())
+            .unwrap_or(u64::MAX);
+        if central_directory >= start_pos as u64 || central_directory_end > start_pos as u64 {
+            return Err(Error::ZipFileParseError(
+                "Central directory information error".into(),
+            ));
+        }
+        Ok(res)
+    }
+}
+
+/*
+  4.3.12  Central directory structure:
+     [central directory header 1]
+     .
+     .
+     .
+     [central directory header n]
+     [digital signature]
+
+     File header:
+       central file header signature   4 bytes  (0x02014b50)
+       version made by                 2 bytes
+       version needed to extract       2 bytes
+       general purpose bit flag        2 bytes
+       compression method              2 bytes
+       last mod file time              2 bytes
+       last mod file date              2 bytes
+       crc-32                          4 bytes
+       compressed size                 4 bytes
+       uncompressed size               4 bytes
+       file name length                2 bytes
+       extra field length              2 bytes
+       file comment length             2 bytes
+       disk number start               2 bytes
+       internal file attributes        2 bytes
+       external file attributes        4 bytes
+       relative offset of local header 4 bytes
+
+       file name (variable size)
+       extra field (variable size)
+       file comment (variable size)
+*/
+struct CentralDirectory<'buf> {
+    buf: &'buf [u8],
+}
+
+impl<'buf> CentralDirectory<'buf> {
+    const MIN_SIZE: usize = 46;
+    const HEAD_SIGNATURE: &'static [u8] = &[0x50, 0x4b, 0x01, 0x02];
+    const VERSION_MADE_BY_POS: usize = 4;
+    const VERSION_NEEDED_TO_EXTRACT_POS: usize = 6;
+    const GENERAL_PURPOSE_BIT_FLAG_POS: usize = 8;
+    const COMPRESSION_METHOD_POS: usize = 10;
+    const LAST_MOD_FILE_TIME_POS: usize = 12;
+    const LAST_MOD_FILE_DATE_POS: usize = 14;
+    const CRC_32_POS: usize = 16;
+    const COMPRESSED_SIZE_POS: usize = 20;
+    const UNCOMPRESSED_SIZE_POS: usize = 24;
+    const FILE_NAME_LENGTH_POS: usize = 28;
+    const EXTRA_FIELD_LENGTH_POS: usize = 30;
+    const FILE_COMMENT_LENGTH_POS: usize = 32;
+    const DISK_NUMBER_START_POS: usize = 34;
+    const INTERNAL_FILE_ATTRIBUTES_POS: usize = 36;
+    const EXTERNAL_FILE_ATTRIBUTES_POS: usize = 38;
+    const RELATIVE_OFFSET_OF_LOCAL_HEADER_POS: usize = 42;
+    const FILE_NAME_POS: usize = 46;
+
+    const ENCRYPTED_FLAG: u16 = 0x1;
+    const COMPRESSION_METHOD_STORE: u16 = 0;
+    const COMPRESSION_METHOD_DEFLATE: u16 = 8;
+    const ZIP64_EXTENDED_INFORMATION_HEADER_ID: u16 = 0x0001;
+
+    #[inline]
+    fn new(buf: &'buf [u8]) -> Result<Self, Error> {
+        let len = buf.len();
+        if len < Self::MIN_SIZE {
+            return Err(Error::ZipFileParseError(format!(
+                "Central Directory buffer size `{}` is too short!",
+                len
+            )));
+        }
+
+        if &buf[..4] != Self::HEAD_SIGNATURE {
+            return Err(Error::ZipFileParseError(format!(
+                "Invalid Central Directory head magic `{:?}`",
+                &buf[..4]
+            )));
+        }
+
+        let res = Self { buf };
+        if len < res.size() {
+            return Err(Error::ZipFileParseError(format!(
+                "Buffer length `{}` is too short! file name length is `{}`, extra field length is `{}`, file comment length is `{}`",
+                len, res.file_comment_length(), res.extra_field_length(), res.file_comment_length()
+            )));
+        }
+        if res.general_purpose_bit_flag() & Self::ENCRYPTED_FLAG != 0 {
+            return Err(Error::ZipFileParseError(format!(
+                "Unsupported encrypted file `{}`",
+                String::from_utf8_lossy(res.file_name())
+            )));
+        }
+        match res.compression_method() {
+            Self::COMPRESSION_METHOD_STORE => {
+                if res.compressed_size() != res.uncompressed_size() {
+                    return Err(Error::ZipFileParseError(format!(
+                        "Compressed size `{}` != Uncompressed size `{}`",
+                        res.compressed_size(),
+                        res.uncompressed_size()
+                    )));
+                }
+            }
+            Self::COMPRESSION_METHOD_DEFLATE => {}
+            m => {
+                return Err(Error::ZipFileParseError(format!(
+                    "Unsupported compression method `{}` for file `{}`, only `Store` and `Deflate` are supported",
+                    m,
+                    String::from_utf8_lossy(res.file_name())
+                )));
+            }
+        }
+
+        Ok(res)
+    }
+
+    #[inline(always)]
+    fn version_made_by(&self) -> u16 {
+        read_le_u16!(self.buf, Self::VERSION_MADE_BY_POS)
+    }
+
+    #[inline(always)]
+    fn version_needed_to_extract(&self) -> u16 {
+        read_le_u16!(self.buf, Self::VERSION_NEEDED_TO_EXTRACT_POS)
+    }
+
+    #[inline(always)]
+    fn general_purpose_bit_flag(&self) -> u16 {
+        read_le_u16!(self.buf, Self::GENERAL_PURPOSE_BIT_FLAG_POS)
+    }
+
+    #[inline(always)]
+    fn compression_method(&self) -> u16 {
+        read_le_u16!(self.buf, Self::COMPRESSION_METHOD_POS)
+    }
+
+    #[inline(always)]
+    fn last_mod_file_time(&self) -> u16 {
+        read_le_u16!(self.buf, Self::LAST_MOD_FILE_TIME_POS)
+    }
+
+    #[inline(always)]
+    fn last_mod_file_date(&self) -> u16 {
+        read_le_u16!(self.buf, Self::LAST_MOD_FILE_DATE_POS)
+    }
+
+    #[inline(always)]
+    fn crc_32(&self) -> u32 {
+        read_le_u32!(self.buf, Self::CRC_32_POS)
+    }
+
+    #[inline(always)]
+    fn compressed_size(&self) -> u32 {
+        read_le_u32!(self.buf, Self::COMPRESSED_SIZE_POS)
+    }
+
+    #[inline(always)]
+    fn uncompressed_size(&self) -> u32 {
+        read_le_u32!(self.buf, Self::UNCOMPRESSED_SIZE_POS)
+    }
+
+    #[inline(always)]
+    fn file_name_length(&self) -> u16 {
+        read_le_u16!(self.buf, Self::FILE_NAME_LENGTH_POS)
+    }
+
+    #[inline(always)]
+    fn extra_field_length(&self) -> u16 {
+        read_le_u16!(self.buf, Self::EXTRA_FIELD_LENGTH_POS)
+    }
+
+    #[inline(always)]
+    fn file_comment_length(&self) -> u16 {
+        read_le_u16!(self.buf, Self::FILE_COMMENT_LENGTH_POS)
+    }
+
+    #[inline(always)]
+    fn disk_number_start(&self) -> u16 {
+        read_le_u16!(self.buf, Self::DISK_NUMBER_START_POS)
+    }
+
+    #[inline(always)]
+    fn internal_file_attributes(&self) -> u16 {
+        read_le_u16!(self.buf, Self::INTERNAL_FILE_ATTRIBUTES_POS)
+    }
+
+    #[inline(always)]
+    fn external_file_attributes(&self) -> u32 {
+        read_le_u32!(self.buf, Self::EXTERNAL_FILE_ATTRIBUTES_POS)
+    }
+
+    #[inline(always)]
+    fn relative_offset_of_local_header(&self) -> u32 {
+        read_le_u32!(self.buf, Self::RELATIVE_OFFSET_OF_LOCAL_HEADER_POS)
+    }
+
+    #[inline(always)]
+    fn file_name(&self) -> &'buf [u8] {
+        &self.buf[Self::FILE_NAME_POS..Self::FILE_NAME_POS + self.file_name_length() as usize]
+    }
+
+    #[inline(always)]
+    fn extra_field(&self) -> &'buf [u8] {
+        let start = Self::FILE_NAME_POS + self.file_name_length() as usize;
+        &self.buf[start..start + self.extra_field_length() as usize]
+    }
+
+    #[inline(always)]
+    fn file_comment(&self) -> &'buf [u8] {
+        let start = Self::FILE_NAME_POS
+            + self.file_name_length() as usize
+            + self.extra_field_length() as usize;
+        &self.buf[start..start + self.file_name_length() as usize]
+    }
+
+    fn size(&self) -> usize {
+        Self::FILE_NAME_POS
+            + self.file_name_length() as usize
+            + self.extra_field_length() as usize
+            + self.file_comment_length() as usize
+    }
+
+    /*
+    4.5.3 -Zip64 Extended Information Extra Field (0x0001):
+
+        Value      Size       Description
+        -----      ----       -----------
+        0x0001     2 bytes    Tag for this "extra" block type
+        Size       2 bytes    Size of this "extra" block
+        Original
+        Size       8 bytes    Original uncompressed file size
+        Compressed
+        Size       8 bytes    Size of compressed data
+        Relative Header
+        Offset     8 bytes    Offset of local header record
+        Disk Start
+        Number     4 bytes    Number of the disk on which
+                              this file starts
+
+    The fields only appear if the corresponding fields in the central directory are saturated.
+     */
+    /// Get the file sizes and location, use the ZIP64 extended information if present.
+    fn zip64_extended_information(&self) -> Result<Zip64ExtendedInformation, Error> {
+        let mut res = Zip64ExtendedInformation {
+            uncompressed_size: self.uncompressed_size() as u64,
+            compressed_size: self.compressed_size() as u64,
+            relative_offset_of_local_header: self.relative_offset_of_local_header() as u64,
+            disk_number_start: self.disk_number_start() as u32,
+        };
+        let zip64_fields_num = [
+            res.uncompressed_size == u32::MAX as u64,
+            res.compressed_size == u32::MAX as u64,
+            res.relative_offset_of_local_header == u32::MAX as u64,
+        ]
+        .iter()
+        .filter(|b| **b)
+        .count();
+        let zip64_disk = res.disk_number_start == u16::MAX as u32;
+        if zip64_fields_num == 0 && !zip64_disk {
+            return Ok(res);
+        }
+
+        let extra = self.extra_field();
+        let mut pos = 0;
+        while pos + 4 <= extra.len() {
+            let header_id = read_le_u16!(extra, pos);
+            let data_size = read_le_u16!(extra, pos + 2) as usize;
+            pos += 4;
+            if pos + data_size > extra.len() {
+                break;
+            }
+            if header_id != Self::ZIP64_EXTENDED_INFORMATION_HEADER_ID {
+                pos += data_size;
+                continue;
+            }
+
+            let expect_size = zip64_fields_num * 8 + if zip64_disk { 4 } else { 0 };
+            if data_size < expect_size {
+                return Err(Error::ZipFileParseError(format!(
+                    "Zip64 extended information size `{}` is too short, expect `{}`",
+                    data_size, expect_size
+                )));
+            }
+            let data = &extra[pos..pos + data_size];
+            let mut offset = 0;
+            for field in [
+                &mut res.uncompressed_size,
+                &mut res.compressed_size,
+                &mut res.relative_offset_of_local_header,
+            ] {
+                if *field == u32::MAX as u64 {
+                    *field = read_le_u64!(data, offset);
+                    offset += 8;
+                }
+            }
+            if zip64_disk {
+                res.disk_number_start = read_le_u32!(data, offset);
+            }
+            return Ok(res);
+        }
+        Err(Error::ZipFileParseError(format!(
+            "Cannot find zip64 extended information for file `{}`",
+            String::from_utf8_lossy(self.file_name())
+        )))
+    }
+}
+
+/// File sizes and location in central directory, which may be stored in the ZIP64 extra field.
+struct Zip64ExtendedInformation {
+    uncompressed_size: u64,
+    compressed_size: u64,
+    relative_offset_of_local_header: u64,
+    disk_number_start: u32,
+}
+
+/*
+  local file header signature     4 bytes  (0x04034b50)
+     version needed to extract       2 bytes
+     general purpose bit flag        2 bytes
+     compression method              2 bytes
+     last mod file time              2 bytes
+     last mod file date              2 bytes
+     crc-32                          4 bytes
+     compressed size                 4 bytes
+     uncompressed size               4 bytes
+     file name length                2 bytes
+     extra field length              2 bytes
+
+     file name (variable size)
+     extra field (variable size)
+*/
+struct LocalFileHeader<'buf> {
+    buf: &'buf [u8],
+}
+
+impl<'buf> LocalFileHeader<'buf> {
+    const HEAD_MAGIC: &'static [u8] = &[0x50, 0x4b, 0x03, 0x04];
+    const MIN_SIZE: usize = 28;
+    const VERSION_NEEDED_TO_EXTRACT_POS: usize = 4;
+    const GENERAL_PURPOSE_BIT_FLAG_POS: usize = 6;
+    const COMPRESSION_METHOD_POS: usize = 8;
+    const LAST_MOD_FILE_TIME_POS: usize = 10;
+    const LAST_MOD_FILE_DATA_POS: usize = 12;
+    const CRC_32_POS: usize = 14;
+    const COMPRESSED_SIZE_POS: usize = 18;
+    const UNCOMPRESSED_SIZE_POS: usize = 22;
+    const FILE_NAME_LENGTH_POS: usize = 26;
+    const EXTRA_FIELD_LENGTH_POS: usize = 28;
+    const FILE_NAME_POS: usize = 30;
+
+    #[inline]
+    fn new(buf: &'buf [u8]) -> Result<Self, Error> {
+        let res = Self { buf };
+        let len = buf.len();
+        if len < Self::MIN_SIZE {
+            return Err(Error::ZipFileParseError(format!(
+                "Local file header is too short `{}`",
+                len
+            )));
+        }
+        if &buf[..4] != Self::HEAD_MAGIC {
+            return Err(Error::ZipFileParseError(format!(
+                "Invalid head magic for local head magic: `{:?}`",
+                &buf[..4]
+            )));
+        }
+
+        if len < res.size() {
+            return Err(Error::ZipFileParseError(format!(
+                "Expect local file header size at least `{}`, but got `{}`",
+                res.size(),
+                len
+            )));
+        }
+        Ok(res)
+    }
+
+    #[inline(always)]
+    fn version_needed_to_extract(&self) -> u16 {
+        read_le_u16!(self.buf, Self::VERSION_NEEDED_TO_EXTRACT_POS)
+    }
+
+    #[inline(always)]
+    fn general_purpose_bit_flag(&self) -> u16 {
+        read_le_u16!(self.buf, Self::GENERAL_PURPOSE_BIT_FLAG_POS)
+    }
+
+    #[inline(always)]
+    fn compression_method(&self) -> u16 {
+        read_le_u16!(self.buf, Self::COMPRESSION_METHOD_POS)
+    }
+
+    #[inline(always)]
+    fn last_mod_file_time(&self) -> u16 {
+        read_le_u16!(self.buf, Self::LAST_MOD_FILE_TIME_POS)
+    }
+
+    #[inline(always)]
+    fn last_mod_file_data(&self) -> u16 {
+        read_le_u16!(self.buf, Self::LAST_MOD_FILE_DATA_POS)
+    }
+
+    #[inline(always)]
+    fn crc_32(&self) -> u32 {
+        read_le_u32!(self.buf, Self::CRC_32_POS)
+    }
+
+    #[inline(always)]
+    fn compressed_size(&self) -> u32 {
+        read_le_u32!(self.buf, Self::COMPRESSED_SIZE_POS)
+    }
+
+    #[inline(always)]
+    fn uncompressed_size(&self) -> u32 {
+        read_le_u32!(self.buf, Self::UNCOMPRESSED_SIZE_POS)
+    }
+
+    #[inline(always)]
+    fn file_name_length(&self) -> u16 {
+        read_le_u16!(self.buf, Self::FILE_NAME_LENGTH_POS)
+    }
+
+    #[inline(always)]
+    fn extra_field_length(&self) -> u16 {
+        read_le_u16!(self.buf, Self::EXTRA_FIELD_LENGTH_POS)
+    }
+
+    #[inline(always)]
+    fn file_name(&self) -> &'buf [u8] {
+        let len = self.file_name_length() as usize;
+        &self.buf[Self::FILE_NAME_POS..Self::FILE_NAME_POS + len]
+    }
+
+    #[inline(always)]
+    fn extra_field(&self) -> &'buf [u8] {
+        let start = Self::FILE_NAME_POS + self.file_name_length() as usize;
+        let end = start + self.extra_field_length() as usize;
+        &self.buf[start..end]
+    }
+
+    #[inline(always)]
+    fn size(&self) -> usize {
+        Self::FILE_NAME_POS + self.file_name_length() as usize + self.extra_field_length() as usize
+    }
+}
+
+enum ZipFileContent {
+    // range of the file data in zip buffer
+    Stored(Range<usize>),
+    Inflated(Vec<u8>),
+}
+
+pub(crate) struct ZipFiles<'buf> {
+    buf: &'buf [u8],
+    end_of_central_directory_record: EndOfCentralDirectoryRecord<'buf>,
+    files: HashMap<Cow<'buf, str>, ZipFileContent>,
+}
+
+impl<'buf> ZipFiles<'buf> {
+    #[inline]
+    pub fn try_new(buf: &'buf [u8]) -> Result<Option<Self>, Error> {
+        let start_pos = EndOfCentralDirectoryRecord::try_find_start_pos(buf);
+        if start_pos == buf.len() {
+            return Ok(None);
+        }
+        Self::new_with_start_pos(buf, start_pos).map(|z| Some(z))
+    }
+
+    #[inline]
+    pub fn new(buf: &'buf [u8]) -> Result<Self, Error> {
+        let start_pos = EndOfCentralDirectoryRecord::try_find_start_pos(buf);
+        if start_pos == buf.len() {
+            return Err(Error::ZipFileParseError(
+                "Cannot find end of central directory.".into(),
+            ));
+        }
+        Self::new_with_start_pos(buf, start_pos)
+    }
+
+    #[inline]
+    fn new_with_start_pos(buf: &'buf [u8], start_pos: usize) -> Result<Self, Error> {
+        let r = EndOfCentralDirectoryRecord::new_with_start_pos(buf, start_pos)?;
+        let (num, mut start, size) = if r.is_zip64() {
+            let r64 = Zip64EndOfCentralDirectoryRecord::new_with_end_of_central_directory_pos(
+                buf, start_pos,
+            )?;
+            (
+                r64.total_number_of_central_directory_records(),
+                r64.offset_of_start_of_central_directory() as usize,
+                r64.size_of_central_directory() as usize,
+            )
+        } else {
+            (
+                r.total_number_of_central_directory_records() as u64,
+                r.offset_of_start_of_central_directory() as usize,
+                r.size_of_central_directory() as usize,
+            )
+        };
+        let end = size + start;
+
+        let mut files = HashMap::new();
+        for _ in 0..num {
+            let c = CentralDirectory::new(&buf[start..end])?;
+            let filename = String::from_utf8_lossy(c.file_name());
+            let info = c.zip64_extended_information()?;
+            if info.disk_number_start != 0 {
+                return Err(Error::ZipFileParseError(format!(
+                    "Disk number must be `0`, but get `{}`",
+                    info.disk_number_start
+                )));
+            }
+
+            let local_file_head_offset = info.relative_offset_of_local_header as usize;
+            if local_file_head_offset >= start_pos {
+                return Err(Error::ZipFileParseError(format!(
+                    "Local file header offset `{}` is out of range",
+                    local_file_head_offset
+                )));
+            }
+            let local_file_head = LocalFileHeader::new(&buf[local_file_head_offset..])?;
+            let file_offset = local_file_head_offset + local_file_head.size();
+            let data_end = file_offset
+                .checked_add(info.compressed_size as usize)
+                .unwrap_or(usize::MAX);
+            let data_range = file_offset..data_end;
+            if data_range.end > start_pos {
+                return Err(Error::ZipFileParseError(format!(
+                    "File `{}` data is out of range",
+                    filename
+                )));
+            }
+
+            let content = match c.compression_method() {
+                CentralDirectory::COMPRESSION_METHOD_STORE => {
+                    ZipFileContent::Stored(data_range.clone())
+                }
+                _ => ZipFileContent::Inflated(inflate::inflate(
+                    &buf[data_range.clone()],
+                    info.uncompressed_size as usize,
+                )?),
+            };
+            let data = match &content {
+                ZipFileContent::Stored(r) => &buf[r.clone()],
+                ZipFileContent::Inflated(d) => d.as_slice(),
+            };
+            if data.len() as u64 != info.uncompressed_size {
+                return Err(Error::ZipFileParseError(format!(
+                    "File `{}` size mismatch, expect `{}`, but got `{}`",
+                    filename,
+                    info.uncompressed_size,
+                    data.len()
+                )));
+            }
+            let crc = inflate::crc32(data);
+            if crc != c.crc_32() {
+                return Err(Error::ZipFileParseError(format!(
+                    "File `{}` CRC-32 mismatch, expect `{:#010x}`, but got `{:#010x}`",
+                    filename,
+                    c.crc_32(),
+                    crc
+                )));
+            }
+            files.insert(filename, content);
+
+            start += c.size();
+        }
+        Ok(Self {
+            buf,
+            end_of_central_directory_record: r,
+            files,
+        })
+    }
+
+    /// Get the file contents, compressed files are decompressed when parsing the zip archive.
+    #[inline(always)]
+    pub fn get_file(&self, name: &str) -> Option<&[u8]> {
+        self.files.get(name).map(|c| match c {
+            ZipFileContent::Stored(r) => &self.buf[r.clone()],
+            ZipFileContent::Inflated(d) => d.as_slice(),
+        })
+    }
+
+    /// Get the file range in zip buffer, return ```None``` if the file is not found or is compressed.
+    #[inline(always)]
+    pub fn get_file_offset(&self, name: &str) -> Option<Range<usize>> {
+        match self.files.get(name) {
+            Some(ZipFileContent::Stored(r)) => Some(r.clone()),
+            _ => None,
+        }
+    }
+
+    /// Get all file names in the zip archive.
+    #[inline(always)]
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(|k| k.as_ref())
+    }
+
+    /// Copy all file contents to HashMap<filename, file contents>
+    #[inline]
+    pub fn copy_contents(&self) -> HashMap<String, Vec<u8>> {
+        self.file_names()
+            .map(|name| (name.to_string(), self.get_file(name).unwrap().to_vec()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::zip::{
+        CentralDirectory, EndOfCentralDirectoryRecord, LocalFileHeader, ZipFiles,
+    };
+
+    const ZIP_PATH: &'static str = "assets/testdata/test.zip";
+    const DEFLATE_ZIP_PATH: &str = "assets/testdata/test_deflate.zip";
+    const MODEL_PATH: &'static str =
+        "assets/models/image_classification/efficientnet_lite0_fp32.tflite";
+
+    #[test]
+    fn test_end_of_central_directory_record() {
+        assert!(EndOfCentralDirectoryRecord::new(&[]).is_err());
+
+        let buf = std::fs::read(ZIP_PATH).unwrap();
+        let r = EndOfCentralDirectoryRecord::new(buf.as_slice()).unwrap();
+        assert_eq!(r.number_of_this_disk(), 0);
+        assert_eq!(r.disk_where_central_directory_starts(), 0);
+        assert_eq!(r.number_of_central_directory_records_on_this_disk(), 2);
+        assert_eq!(r.total_number_of_central_directory_records(), 2);
+        assert_eq!(r.size_of_central_directory(), 150);
+        assert_eq!(r.offset_of_start_of_central_directory(), 130);
+        assert_eq!(r.comment_length(), 0);
+        assert_eq!(r.comment(), []);
+    }
+
+    #[test]
+    fn test_central_directory_record() {
+        let buf = std::fs::read(ZIP_PATH).unwrap();
+        let r = EndOfCentralDirectoryRecord::new(buf.as_slice()).unwrap();
+        let num = r.total_number_of_central_directory_records();
+        let mut start = r.offset_of_start_of_central_directory() as usize;
+        let end = r.size_of_central_directory() as usize + start;
+
+        let c = CentralDirectory::new(&buf[start..end]).unwrap();
+        let filename = String::from_utf8_lossy(c.file_name());
+        assert_eq!(filename, "1.txt");
+        assert_eq!(c.uncompressed_size(), 2);
+        let offset = c.relative_offset_of_local_header() as usize;
+        let local_file_header = LocalFileHeader::new(&buf[offset..]).unwrap();
+        let file_offset = offset + local_file_header.size();
+        let file_content = &buf[file_offset..file_offset + c.uncompressed_size() as usize];
+        assert_eq!(String::from_utf8_lossy(file_content), "1\n");
+        start += c.size();
+
+        let c = CentralDirectory::new(&buf[start..end]).unwrap();
+        let filename = String::from_utf8_lossy(c.file_name());
+        assert_eq!(filename, "2.txt");
+        assert_eq!(c.uncompressed_size(), 2);
+        let offset = c.relative_offset_of_local_header() as usize;
+        let local_file_header = LocalFileHeader::new(&buf[offset..]).unwrap();
+        let file_offset = offset + local_file_header.size();
+        let file_content = &buf[file_offset..file_offset + c.uncompressed_size() as usize];
+        assert_eq!(String::from_utf8_lossy(file_content), "2\n");
+    }
+
+    #[test]
+    fn test_zip_file() {
+        let buf = std::fs::read(ZIP_PATH).unwrap();
+        let zip_file = ZipFiles::new(buf.as_slice()).unwrap();
+        assert_eq!(zip_file.files.len(), 2);
+        assert_eq!(zip_file.get_file("1.txt").unwrap(), &[49, 10]);
+        assert_eq!(zip_file.get_file("2.txt").unwrap(), &[50, 10]);
+    }
+
+    #[test]
+    fn test_deflate_zip_file() {
+        let buf = std::fs::read(DEFLATE_ZIP_PATH).unwrap();
+        let zip_file = ZipFiles::new(buf.as_slice()).unwrap();
+        assert_eq!(zip_file.files.len(), 4);
+        assert_eq!(zip_file.get_file("stored.txt").unwrap(), b"stored\n");
+        assert!(zip_file.get_file_offset("stored.txt").is_some());
+        // fixed huffman codes
+        assert_eq!(zip_file.get_file("small.txt").unwrap(), b"hello, deflate\n");
+        assert!(zip_file.get_file_offset("small.txt").is_none());
+        // dynamic huffman codes
+        let large: Vec<u8> = (0..2000)
+            .flat_map(|i| {
+                format!("{}: the quick brown fox jumps over the lazy dog\n", i).into_bytes()
+            })
+            .collect();
+        assert_eq!(zip_file.get_file("large.txt").unwrap(), large.as_slice());
+        // stored blocks
+        let level0: Vec<u8> = (0..300).flat_map(|_| 0..=255u8).collect();
+        assert_eq!(zip_file.get_file("level0.txt").unwrap(), level0.as_slice());
+    }
+
+    #[test]
+    fn test_zip_file_error() {
+        let buf = std::fs::read(ZIP_PATH).unwrap();
+        let r = EndOfCentralDirectoryRecord::new(buf.as_slice()).unwrap();
+        let central_directory = r.offset_of_start_of_central_directory() as usize;
+
+        // crc mismatch
+        let mut corrupted = buf.clone();
+        let c = CentralDirectory::new(&buf[central_directory..]).unwrap();
+        let offset = c.relative_offset_of_local_header() as usize;
+        let file_offset = offset + LocalFileHeader::new(&buf[offset..]).unwrap().size();
+        corrupted[file_offset] = b'3';
+        let err = ZipFiles::new(corrupted.as_slice()).err().unwrap();
+        assert!(err.to_string().contains("CRC-32 mismatch"));
+
+        // bzip2
+        let mut unsupported = buf.clone();
+        unsupported[central_directory + CentralDirectory::COMPRESSION_METHOD_POS] = 12;
+        let err = ZipFiles::new(unsupported.as_slice()).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("Unsupported compression method `12`"));
+    }
+
+    // a zip archive with one stored file, all sizes and offsets are in zip64 records.
+    fn zip64_archive(name: &str, content: &[u8]) -> Vec<u8> {
+        let crc = super::inflate::crc32(content);
+        let size = content.len() as u64;
+        let mut extra = vec![0x01, 0x00, 16, 0x00];
+        extra.extend_from_slice(&size.to_le_bytes());
+        extra.extend_from_slice(&size.to_le_bytes());
+
+        let mut buf = vec![0x50, 0x4b, 0x03, 0x04, 45, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        buf.extend_from_slice(&crc.to_le_bytes());
+        buf.extend_from_slice(&[0xff; 8]);
+        buf.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        buf.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+        buf.extend_from_slice(name.as_bytes());
+        buf.extend_from_slice(&extra);
+        buf.extend_from_slice(content);
+
+        let central_directory = buf.len() as u64;
+        extra[2] = 24;
+        extra.extend_from_slice(&0u64.to_le_bytes());
+        buf.extend_from_slice(&[0x50, 0x4b, 0x01, 0x02, 45, 0, 45, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        buf.extend_from_slice(&crc.to_le_bytes());
+        buf.extend_from_slice(&[0xff; 8]);
+        buf.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        buf.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+        buf.extend_from_slice(&[0; 10]);
+        buf.extend_from_slice(&[0xff; 4]);
+        buf.extend_from_slice(name.as_bytes());
+        buf.extend_from_slice(&extra);
+        let central_directory_size = buf.len() as u64 - central_directory;
+
+        let zip64_end_of_central_directory = buf.len() as u64;
+        buf.extend_from_slice(&[0x50, 0x4b, 0x06, 0x06]);
+        buf.extend_from_slice(&44u64.to_le_bytes());
+        buf.extend_from_slice(&[45, 0, 45, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        buf.extend_from_slice(&1u64.to_le_bytes());
+        buf.extend_from_slice(&1u64.to_le_bytes());
+        buf.extend_from_slice(&central_directory_size.to_le_bytes());
+        buf.extend_from_slice(&central_directory.to_le_bytes());
+
+        buf.extend_from_slice(&[0x50, 0x4b, 0x06, 0x07, 0, 0, 0, 0]);
+        buf.extend_from_slice(&zip64_end_of_central_directory.to_le_bytes());
+        buf.extend_from_slice(&[1, 0, 0, 0]);
+
+        buf.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0]);
+        buf.extend_from_slice(&[0xff; 12]);
+        buf.extend_from_slice(&[0, 0]);
+        buf
+    }
+
+    #[test]
+    fn test_zip64_file() {
+        let buf = zip64_archive("model.tflite", b"zip64");
+        let r = EndOfCentralDirectoryRecord::new(buf.as_slice()).unwrap();
+        assert!(r.is_zip64());
+        let zip_file = ZipFiles::new(buf.as_slice()).unwrap();
+        assert_eq!(zip_file.files.len(), 1);
+        assert_eq!(zip_file.get_file("model.tflite").unwrap(), b"zip64");
+
+        // broken locator
+        let mut broken = buf.clone();
+        let locator = buf.len() - 22 - 20;
+        broken[locator] = 0;
+        assert!(ZipFiles::new(broken.as_slice()).is_err());
+
+        // the central directory offset + size overflows
+        let mut overflow = buf.clone();
+        let size_pos = locator - 56 + 40;
+        overflow[size_pos..size_pos + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(ZipFiles::new(overflow.as_slice()).is_err());
+    }
+}
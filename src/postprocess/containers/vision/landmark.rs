@@ -79,7 +79,14 @@ impl Display for Landmark {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "      x:       {}", self.x)?;
         writeln!(f, "      y:       {}", self.y)?;
-        writeln!(f, "      z:       {}", self.z)
+        writeln!(f, "      z:       {}", self.z)?;
+        if let Some(visibility) = self.visibility {
+            writeln!(f, "      visibility: {}", visibility)?;
+        }
+        if let Some(presence) = self.presence {
+            writeln!(f, "      presence:   {}", presence)?;
+        }
+        Ok(())
     }
 }
 
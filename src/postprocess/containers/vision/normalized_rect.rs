@@ -1,4 +1,4 @@
-use super::{Detection, Rect};
+use super::{Detection, NormalizedLandmark, Rect};
 
 /// A rectangle with rotation in normalized coordinates. The values of box center
 /// location and size are within [0, 1].
@@ -78,6 +78,90 @@ impl NormalizedRect {
         r
     }
 
+    /// create from the alignment points of detection, the rectangle is a square centered at the
+    /// start key point, and its side length is twice the distance between the two key points.
+    /// * alignment_option: (angle in radians, start_key_point_index, end_key_point_index)
+    ///   angle is counter-clockwise
+    pub(crate) fn from_alignment_points(
+        detection: &Detection,
+        alignment_option: (f32, usize, usize),
+        img_w: u32,
+        img_h: u32,
+    ) -> Self {
+        let (angle, s_id, e_id) = alignment_option;
+        let key_points = detection.key_points.as_ref().unwrap();
+        Self::from_alignment_vector(
+            angle,
+            (key_points[s_id].x, key_points[s_id].y),
+            (key_points[e_id].x, key_points[e_id].y),
+            img_w,
+            img_h,
+        )
+    }
+
+    /// create from an alignment vector in normalized coordinates, the rectangle is a square
+    /// centered at the start point, and its side length is twice the length of the vector.
+    /// * angle: the target angle of the vector in radians, counter-clockwise
+    pub(crate) fn from_alignment_vector(
+        angle: f32,
+        start: (f32, f32),
+        end: (f32, f32),
+        img_w: u32,
+        img_h: u32,
+    ) -> Self {
+        let img_w = img_w as f32;
+        let img_h = img_h as f32;
+        let x0 = start.0 * img_w;
+        let y0 = start.1 * img_h;
+        let x1 = end.0 * img_w;
+        let y1 = end.1 * img_h;
+
+        let box_size = ((x1 - x0) * (x1 - x0) + (y1 - y0) * (y1 - y0)).sqrt() * 2.;
+        let rotation = angle - (-(y1 - y0)).atan2(x1 - x0);
+        Self {
+            x_center: x0 / img_w,
+            y_center: y0 / img_h,
+            width: box_size / img_w,
+            height: box_size / img_h,
+            rotation: Some(Self::normalize_radians(rotation)),
+            rect_id: None,
+        }
+    }
+
+    /// create from the bounding box of landmarks
+    /// * rotation_option: (angle in radians, start_landmark_index, end_landmark_index)
+    ///   angle is counter-clockwise
+    pub(crate) fn from_landmarks(
+        landmarks: &[NormalizedLandmark],
+        rotation_option: Option<(f32, usize, usize)>,
+        img_w: u32,
+        img_h: u32,
+    ) -> Self {
+        assert!(landmarks.len() > 1);
+        let mut rect = Rect {
+            left: f32::MAX,
+            top: f32::MAX,
+            right: f32::MIN,
+            bottom: f32::MIN,
+        };
+        for l in landmarks {
+            rect.left = min_f32!(l.x, rect.left);
+            rect.top = min_f32!(l.y, rect.top);
+            rect.right = max_f32!(l.x, rect.right);
+            rect.bottom = max_f32!(l.y, rect.bottom);
+        }
+        let mut r = Self::init_from_rect(&rect);
+        if let Some((angle, s_id, e_id)) = rotation_option {
+            let x0 = landmarks[s_id].x * img_w as f32;
+            let y0 = landmarks[s_id].y * img_h as f32;
+            let x1 = landmarks[e_id].x * img_w as f32;
+            let y1 = landmarks[e_id].y * img_h as f32;
+            let rotation = angle - (-(y1 - y0)).atan2(x1 - x0);
+            r.rotation = Some(Self::normalize_radians(rotation))
+        }
+        r
+    }
+
     /// geometric transformation
     ///
     /// rotation is counter-clockwise in radians
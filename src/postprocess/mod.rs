@@ -4,10 +4,12 @@ pub use containers::*;
 
 /// stateless operators for tensor
 mod ops;
-pub(crate) use ops::{Activation, QuantizationParameters};
+pub use ops::{Activation, QuantizationParameters};
 
 /// stateful objects, convert tensor to results
 mod processing;
+#[cfg(feature = "vision")]
+pub use processing::SsdAnchorsBuilder;
 pub(crate) use processing::*;
 
 /// Utils to make use of task results, such as drawing utils.
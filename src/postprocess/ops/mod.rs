@@ -10,6 +10,7 @@ pub(super) use softmax::Softmax;
 
 pub use dequantize::QuantizationParameters;
 
+/// Activation function applied to the model outputs.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum Activation {
     None,
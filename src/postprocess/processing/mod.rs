@@ -84,4 +84,6 @@ pub(crate) use common::*;
 #[cfg(feature = "vision")]
 mod vision;
 #[cfg(feature = "vision")]
+pub use vision::SsdAnchorsBuilder;
+#[cfg(feature = "vision")]
 pub(crate) use vision::*;
@@ -3,11 +3,14 @@ use super::*;
 mod non_max_suppression;
 mod ssd_anchors_generator;
 mod tensors_to_detection;
+mod tensors_to_image;
 mod tensors_to_landmarks;
 mod tensors_to_segmentation;
 
 pub(crate) use non_max_suppression::*;
+pub use ssd_anchors_generator::SsdAnchorsBuilder;
 pub(crate) use ssd_anchors_generator::*;
 pub(crate) use tensors_to_detection::*;
+pub(crate) use tensors_to_image::*;
 pub(crate) use tensors_to_landmarks::*;
 pub(crate) use tensors_to_segmentation::*;
@@ -10,7 +10,20 @@ pub(crate) struct Anchor {
 }
 
 /// Options to generate anchors for SSD object detection models.
-pub(crate) struct SsdAnchorsBuilder {
+///
+/// Detectors such as [`FaceDetectorBuilder`](crate::tasks::vision::FaceDetectorBuilder) read anchors from model metadata.
+/// Use it to generate anchors for models without anchors in metadata, such as the full-range face detection model:
+/// ```
+/// use mediapipe_rs::postprocess::SsdAnchorsBuilder;
+///
+/// let anchors = SsdAnchorsBuilder::new(192, 192, 0.1484375, 0.75, 1)
+///     .strides(vec![4])
+///     .aspect_ratios(vec![1.0])
+///     .interpolated_scale_aspect_ratio(0.0)
+///     .fixed_anchor_size(true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SsdAnchorsBuilder {
     /// Size of input images.
     input_size_width: u32,
     input_size_height: u32,
@@ -77,6 +90,7 @@ pub(crate) struct SsdAnchorsBuilder {
 
 #[allow(unused)]
 impl SsdAnchorsBuilder {
+    /// Create a new builder with the input image size, min and max scales, and the number of output feature maps.
     #[inline(always)]
     pub fn new(
         input_size_width: u32,
@@ -124,7 +138,7 @@ impl SsdAnchorsBuilder {
     }
 
     /// reference: https://github.com/google/mediapipe/blob/master/mediapipe/calculators/tflite/ssd_anchors_calculator.cc
-    pub fn generate(self) -> Vec<Anchor> {
+    pub(crate) fn generate(self) -> Vec<Anchor> {
         let mut ans = Vec::new();
 
         let mut layer_id = 0;
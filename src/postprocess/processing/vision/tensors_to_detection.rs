@@ -5,6 +5,7 @@ use super::*;
 use crate::postprocess::{
     CategoriesFilter, Category, Detection, DetectionResult, NormalizedKeypoint, Rect,
 };
+use crate::preprocess::vision::ImageTransform;
 
 /// Tells the calculator how to convert the detector output to bounding boxes.
 #[derive(Debug, Clone, Copy)]
@@ -24,6 +25,60 @@ impl Default for DetectionBoxFormat {
     }
 }
 
+/// Options to decode the raw output tensors of SSD detection models, which can be read from model metadata.
+#[derive(Debug, Clone)]
+pub(crate) struct TensorsDecodingOptions {
+    /// The number of output classes predicted by the detection model.
+    pub num_classes: usize,
+    /// The number of output boxes predicted by the detection model.
+    pub num_boxes: usize,
+    /// The number of output values per boxes predicted by the detection model.
+    pub num_coords: usize,
+    /// The offset of keypoint coordinates in the location tensor.
+    pub keypoint_coord_offset: usize,
+    /// The number of predicted key points.
+    pub num_key_points: usize,
+    /// The dimension of each keypoint, e.g. number of values predicted for each keypoint.
+    pub num_values_per_key_point: usize,
+    /// Parameters for decoding SSD detection model.
+    pub x_scale: f32,
+    pub y_scale: f32,
+    pub w_scale: f32,
+    pub h_scale: f32,
+    pub apply_exponential_on_box_size: bool,
+    pub sigmoid_score: bool,
+}
+
+impl TensorsDecodingOptions {
+    /// Check the options read from model metadata or derived from model shapes.
+    /// The box and all key points must be in the `num_coords` values of each box.
+    pub(crate) fn check(&self) -> Result<(), crate::Error> {
+        if self.num_boxes == 0 || self.num_classes == 0 {
+            return Err(crate::Error::ModelInconsistentError(format!(
+                "Invalid detector decoding options: `{}` boxes and `{}` classes",
+                self.num_boxes, self.num_classes
+            )));
+        }
+        if self.num_key_points > 0 && self.num_values_per_key_point < 2 {
+            return Err(crate::Error::ModelInconsistentError(format!(
+                "Invalid detector decoding options: key point has `{}` values, at least 2",
+                self.num_values_per_key_point
+            )));
+        }
+        let key_points_end = self
+            .num_key_points
+            .checked_mul(self.num_values_per_key_point)
+            .and_then(|n| n.checked_add(self.keypoint_coord_offset));
+        if self.num_coords < 4 || key_points_end.map_or(true, |end| end > self.num_coords) {
+            return Err(crate::Error::ModelInconsistentError(format!(
+                "Invalid detector decoding options: `{}` coords cannot contain a box and `{}` key points at offset `{}`",
+                self.num_coords, self.num_key_points, self.keypoint_coord_offset
+            )));
+        }
+        Ok(())
+    }
+}
+
 struct ToDetectionOptions {
     /// The number of output classes predicted by the detection model.
     /// if categories buffer is not None, num_classes must be 1
@@ -54,6 +109,8 @@ struct ToDetectionOptions {
     sigmoid_score: bool,                 // [default = false];
     /// Whether the detection coordinates from the input tensors should be flipped vertically (along the y-direction).
     flip_vertically: bool, //[default = false];
+    /// Project the detection coordinates from the input tensor back to the image.
+    image_transform: Option<ImageTransform>,
 }
 
 macro_rules! box_y_min {
@@ -135,6 +192,7 @@ impl Default for ToDetectionOptions {
             apply_exponential_on_box_size: false,
             sigmoid_score: false,
             flip_vertically: false,
+            image_transform: None,
         }
     }
 }
@@ -237,6 +295,23 @@ impl<'a> TensorsToDetection<'a> {
         self.options.h_scale = h_scale;
     }
 
+    #[inline]
+    pub(crate) fn set_decoding_options(&mut self, decoding_options: &TensorsDecodingOptions) {
+        self.options.num_classes = decoding_options.num_classes;
+        self.options.num_coords = decoding_options.num_coords;
+        self.options.num_key_points = decoding_options.num_key_points;
+        self.options.num_values_per_key_point = decoding_options.num_values_per_key_point;
+        self.options.keypoint_coord_offset = decoding_options.keypoint_coord_offset;
+        // the options from model are checked by `TensorsDecodingOptions::check` when the task is built
+        debug_assert!(decoding_options.check().is_ok());
+        self.options.x_scale = decoding_options.x_scale;
+        self.options.y_scale = decoding_options.y_scale;
+        self.options.w_scale = decoding_options.w_scale;
+        self.options.h_scale = decoding_options.h_scale;
+        self.options.apply_exponential_on_box_size = decoding_options.apply_exponential_on_box_size;
+        self.options.sigmoid_score = decoding_options.sigmoid_score;
+    }
+
     #[inline(always)]
     pub(crate) fn set_sigmoid_score(&mut self, sigmoid_score: bool) {
         self.options.sigmoid_score = sigmoid_score;
@@ -252,6 +327,15 @@ impl<'a> TensorsToDetection<'a> {
         self.options.score_clipping_thresh = Some(score_clipping_thresh);
     }
 
+    #[inline(always)]
+    pub(crate) fn set_image_transform(&mut self, image_transform: ImageTransform) {
+        self.options.image_transform = if image_transform.is_identity() {
+            None
+        } else {
+            Some(image_transform)
+        };
+    }
+
     #[inline(always)]
     pub(crate) fn set_nms_overlap_type(&mut self, overlap_type: NonMaxSuppressionOverlapType) {
         self.nms.set_overlap_type(overlap_type);
@@ -292,7 +376,7 @@ impl<'a> TensorsToDetection<'a> {
         if let Some(ref mut c) = self.categories_buf {
             realloc_output_buffer!(c, num_boxes);
         }
-        realloc_output_buffer!(self.location_buf, num_boxes * num_boxes);
+        realloc_output_buffer!(self.location_buf, num_boxes * self.options.num_coords);
     }
 
     pub(crate) fn result(&mut self, num_boxes: usize) -> DetectionResult {
@@ -391,6 +475,21 @@ impl<'a> TensorsToDetection<'a> {
             rect.bottom = bottom;
             rect.top = top;
         }
+        if let Some(ref transform) = options.image_transform {
+            // the bounding box of the projected corners
+            let corners = [
+                transform.to_image(rect.left, rect.top),
+                transform.to_image(rect.right, rect.top),
+                transform.to_image(rect.left, rect.bottom),
+                transform.to_image(rect.right, rect.bottom),
+            ];
+            rect = Rect {
+                left: corners.iter().map(|c| c.0).fold(f32::MAX, f32::min),
+                top: corners.iter().map(|c| c.1).fold(f32::MAX, f32::min),
+                right: corners.iter().map(|c| c.0).fold(f32::MIN, f32::max),
+                bottom: corners.iter().map(|c| c.1).fold(f32::MIN, f32::max),
+            };
+        }
         if rect.left.is_nan()
             || rect.right.is_nan()
             || rect.top.is_nan()
@@ -410,8 +509,12 @@ impl<'a> TensorsToDetection<'a> {
                 } else {
                     location[index + 1]
                 };
+                let (x, y) = match options.image_transform {
+                    Some(ref transform) => transform.to_image(location[index], y),
+                    None => (location[index], y),
+                };
                 key_points.push(NormalizedKeypoint {
-                    x: location[index],
+                    x,
                     y,
                     label: None,
                     score: None,
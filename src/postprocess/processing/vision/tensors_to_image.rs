@@ -0,0 +1,169 @@
+// These reference files are licensed under Apache 2.0, and originally developed by Google for Mediapipe:
+// https://github.com/google/mediapipe/raw/master/mediapipe/tasks/cc/vision/face_stylizer/calculators/tensors_to_image_calculator.cc
+
+use super::*;
+use crate::preprocess::vision::{ImageDataLayout, ImageLikeTensorShape};
+use image::RgbImage;
+
+pub(crate) struct TensorsToImage {
+    tensor_buffer: OutputBuffer,
+    image_data_layout: ImageDataLayout,
+    tensor_shape: ImageLikeTensorShape,
+    /// The (min, max) range of float tensor values, which is mapped to [0, 255].
+    float_range: (f32, f32),
+}
+
+impl TensorsToImage {
+    /// Check the output tensor can be converted to image, it must be float32,
+    /// or uint8 with quantization parameters.
+    #[inline]
+    pub(crate) fn check_tensor_type(
+        tensor_buf_info: (TensorType, Option<QuantizationParameters>),
+    ) -> Result<(), crate::Error> {
+        match tensor_buf_info {
+            (TensorType::F32, _) | (TensorType::U8, Some(_)) => Ok(()),
+            (TensorType::U8, None) => Err(crate::Error::ModelInconsistentError(
+                "Missing quantization parameters for the uint8 output image tensor".into(),
+            )),
+            (t, _) => Err(crate::Error::ModelInconsistentError(format!(
+                "Expect the output image tensor type is `F32` or `U8`, but got `{:?}`",
+                t
+            ))),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn new(
+        tensor_buf_info: (TensorType, Option<QuantizationParameters>),
+        image_data_layout: ImageDataLayout,
+        tensor_shape: &[usize],
+        float_range: (f32, f32),
+    ) -> Result<Self, crate::Error> {
+        Self::check_tensor_type(tensor_buf_info)?;
+        let tensor_shape = ImageLikeTensorShape::parse(image_data_layout, tensor_shape)?;
+        if tensor_shape.batch != 1 {
+            return Err(crate::Error::ModelInconsistentError(format!(
+                "Unsupported batch size `{}`, now only support batch size = 1",
+                tensor_shape.batch
+            )));
+        }
+        if tensor_shape.channels != 1 && tensor_shape.channels != 3 && tensor_shape.channels != 4 {
+            return Err(crate::Error::ModelInconsistentError(format!(
+                "Expect the output image has `1`, `3` or `4` channels, but got `{}`",
+                tensor_shape.channels
+            )));
+        }
+        if float_range.0 >= float_range.1 {
+            return Err(crate::Error::ArgumentError(format!(
+                "Invalid output float range `[{}, {}]`",
+                float_range.0, float_range.1
+            )));
+        }
+
+        let elem_size = tensor_shape.elem_size();
+        Ok(Self {
+            tensor_buffer: empty_output_buffer!(tensor_buf_info, elem_size),
+            image_data_layout,
+            tensor_shape,
+            float_range,
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn tensor_buffer(&mut self) -> &mut [u8] {
+        self.tensor_buffer.data_buffer.as_mut_slice()
+    }
+
+    /// Convert the output tensor to rgb image, the gray image is expanded to 3 channels
+    /// and the alpha channel is dropped.
+    pub(crate) fn image(&mut self) -> RgbImage {
+        let tensor = output_buffer_mut_slice!(self.tensor_buffer);
+        let (min, max) = self.float_range;
+        let scale = 255. / (max - min);
+        let to_u8 = |v: f32| ((v - min) * scale).round().clamp(0., 255.) as u8;
+
+        let width = self.tensor_shape.width;
+        let height = self.tensor_shape.height;
+        let channels = self.tensor_shape.channels;
+        let mut res = RgbImage::new(width as u32, height as u32);
+        for (i, p) in res.pixels_mut().enumerate() {
+            for c in 0..3 {
+                // gray image uses the only channel
+                let src_c = if channels == 1 { 0 } else { c };
+                let index = match self.image_data_layout {
+                    ImageDataLayout::NHWC => i * channels + src_c,
+                    // batch is always 1 now
+                    ImageDataLayout::NCHW | ImageDataLayout::CHWN => src_c * width * height + i,
+                };
+                p.0[c] = to_u8(tensor[index]);
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tensors_to_image() {
+        let tensor: [f32; 12] = [-1., 0., 1., 1., -1., 0., 0., 0., 0., 2., -2., 1.];
+        let mut tensors_to_image = TensorsToImage::new(
+            (TensorType::F32, None),
+            ImageDataLayout::NHWC,
+            &[1, 2, 2, 3],
+            (-1., 1.),
+        )
+        .unwrap();
+        for (dst, v) in tensors_to_image
+            .tensor_buffer()
+            .chunks_exact_mut(4)
+            .zip(tensor.iter())
+        {
+            dst.copy_from_slice(&v.to_ne_bytes());
+        }
+        let img = tensors_to_image.image();
+        assert_eq!(img.dimensions(), (2, 2));
+        assert_eq!(img.get_pixel(0, 0).0, [0, 128, 255]);
+        assert_eq!(img.get_pixel(1, 0).0, [255, 0, 128]);
+        assert_eq!(img.get_pixel(0, 1).0, [128, 128, 128]);
+        // out of range values are clamped
+        assert_eq!(img.get_pixel(1, 1).0, [255, 0, 255]);
+
+        let mut tensors_to_image = TensorsToImage::new(
+            (TensorType::F32, None),
+            ImageDataLayout::NCHW,
+            &[1, 3, 2, 2],
+            (-1., 1.),
+        )
+        .unwrap();
+        for (dst, v) in tensors_to_image
+            .tensor_buffer()
+            .chunks_exact_mut(4)
+            .zip(tensor.iter())
+        {
+            dst.copy_from_slice(&v.to_ne_bytes());
+        }
+        let img = tensors_to_image.image();
+        assert_eq!(img.get_pixel(0, 0).0, [0, 0, 128]);
+        assert_eq!(img.get_pixel(1, 1).0, [255, 128, 255]);
+
+        assert!(TensorsToImage::new(
+            (TensorType::F32, None),
+            ImageDataLayout::NHWC,
+            &[1, 2, 2, 2],
+            (-1., 1.),
+        )
+        .is_err());
+        for tensor_type in [TensorType::F16, TensorType::I32, TensorType::U8] {
+            assert!(TensorsToImage::new(
+                (tensor_type, None),
+                ImageDataLayout::NHWC,
+                &[1, 2, 2, 3],
+                (-1., 1.),
+            )
+            .is_err());
+        }
+    }
+}
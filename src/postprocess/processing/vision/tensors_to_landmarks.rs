@@ -83,6 +83,16 @@ impl TensorsToLandmarks {
         assert!(self.options.img_size.is_some());
     }
 
+    #[inline(always)]
+    pub(crate) fn set_visibility_score_sigmoid(&mut self, visibility_score_sigmoid: bool) {
+        self.options.visibility_score_sigmoid = visibility_score_sigmoid;
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_presence_score_sigmoid(&mut self, presence_score_sigmoid: bool) {
+        self.options.presence_score_sigmoid = presence_score_sigmoid;
+    }
+
     #[inline(always)]
     pub(crate) fn landmark_buffer(&mut self) -> &mut [u8] {
         self.landmark_buffer.data_buffer.as_mut_slice()
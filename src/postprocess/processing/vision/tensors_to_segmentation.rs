@@ -3,13 +3,14 @@
 
 use super::*;
 use crate::postprocess::{Activation, ImageCategoryMask, ImageConfidenceMask};
-use crate::preprocess::vision::{ImageDataLayout, ImageLikeTensorShape};
+use crate::preprocess::vision::{ImageDataLayout, ImageLikeTensorShape, ImageTransform};
 
 pub(crate) struct TensorsToSegmentation {
     activation: Activation,
     tensor_buffer: OutputBuffer,
     image_data_layout: ImageDataLayout,
     tensor_shape: ImageLikeTensorShape,
+    image_transform: Option<ImageTransform>,
 }
 
 impl TensorsToSegmentation {
@@ -34,9 +35,25 @@ impl TensorsToSegmentation {
             tensor_buffer: empty_output_buffer!(tensor_buf_info, elem_size),
             image_data_layout,
             tensor_shape,
+            image_transform: None,
         })
     }
 
+    /// Set the transform to project the masks from the input tensor back to the image.
+    #[inline(always)]
+    pub(crate) fn set_image_transform(&mut self, image_transform: ImageTransform) {
+        self.image_transform = if image_transform.is_identity() {
+            None
+        } else {
+            Some(image_transform)
+        };
+    }
+
+    #[inline(always)]
+    pub(crate) fn image_transform(&self) -> Option<&ImageTransform> {
+        self.image_transform.as_ref()
+    }
+
     #[inline(always)]
     pub(crate) fn tenor_buffer(&mut self) -> &mut [u8] {
         self.tensor_buffer.data_buffer.as_mut_slice()
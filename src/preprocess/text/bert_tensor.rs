@@ -17,6 +17,53 @@ pub(super) fn to_bert_tensors<T: AsMut<[E]>, E: AsMut<[u8]>>(
     max_seq_len: u32,
     classifier_token_id: i32,
     separator_token_id: i32,
+) -> Result<(), Error> {
+    fill_bert_tensors(
+        s,
+        token_index_map,
+        output_buffers,
+        max_seq_len,
+        classifier_token_id,
+        separator_token_id,
+        |_| {},
+    )
+}
+
+/// Same as `to_bert_tensors`, and record the tokens written to the tensors.
+/// The token at index `i` is at position `i + 1` of the tensors, after the `[CLS]` token.
+pub(crate) fn to_bert_tensors_with_tokens<T: AsMut<[E]>, E: AsMut<[u8]>>(
+    s: &str,
+    token_index_map: &HashMap<String, i32>,
+    output_buffers: &mut T,
+    max_seq_len: u32,
+    classifier_token_id: i32,
+    separator_token_id: i32,
+    tokens: &mut Vec<BertToken>,
+) -> Result<(), Error> {
+    tokens.clear();
+    fill_bert_tensors(
+        s,
+        token_index_map,
+        output_buffers,
+        max_seq_len,
+        classifier_token_id,
+        separator_token_id,
+        |t| tokens.push(t),
+    )?;
+    // the last token may be replaced by [SEP]
+    tokens.truncate(max_seq_len as usize - 2);
+    Ok(())
+}
+
+#[inline(always)]
+fn fill_bert_tensors<T: AsMut<[E]>, E: AsMut<[u8]>>(
+    s: &str,
+    token_index_map: &HashMap<String, i32>,
+    output_buffers: &mut T,
+    max_seq_len: u32,
+    classifier_token_id: i32,
+    separator_token_id: i32,
+    mut on_token: impl FnMut(BertToken),
 ) -> Result<(), Error> {
     // check outputs
     if output_buffers.as_mut().len() != 3 {
@@ -58,35 +105,20 @@ pub(super) fn to_bert_tensors<T: AsMut<[E]>, E: AsMut<[u8]>>(
     index += 1;
 
     // split string
-    let mut string = s.to_ascii_lowercase();
-
-    let mut now = string.as_mut_str();
-    while let Some(m) = DELIM_REGEX.find(now) {
-        let start = m.start();
-        let end = m.end();
-        if start != 0 {
-            let token = &now[..start];
-            do_word_piece_tokenize(input_ids, &mut index, token, token_index_map);
-            if index >= indices_size {
-                break;
-            }
-        }
-
-        let delim_token = &now[start..end];
-        // include delim token
-        if INCLUDE_DELIM_REGEX.is_match(delim_token) {
-            do_word_piece_tokenize(input_ids, &mut index, delim_token, token_index_map);
-            if index >= indices_size {
-                break;
-            }
-        }
-
-        (_, now) = now.split_at_mut(end);
-    }
-
-    if !now.is_empty() {
-        do_word_piece_tokenize(input_ids, &mut index, now, token_index_map);
-    }
+    let mut string_buffer = String::new();
+    basic_tokenize(s, |word, offset| {
+        word_piece_tokenize(
+            word,
+            token_index_map,
+            &mut string_buffer,
+            |id, start, end| {
+                input_ids[index] = id;
+                index += 1;
+                on_token(BertToken::new(id, offset, start, end));
+                index < indices_size
+            },
+        )
+    });
 
     // the last [SEP]
     if index < indices_size {
@@ -113,49 +145,115 @@ const DEFAULT_SUFFIX_INDICATOR: &'static str = "##";
 const DEFAULT_UNKNOWN_TOKEN: &'static str = "[UNK]";
 // const DEFAULT_SPLIT_UNKNOWN_CHARS: bool = false;
 
-#[inline(always)]
-fn do_word_piece_tokenize(
-    input_ids: &mut [i32],
-    index: &mut usize,
-    token: &str,
+/// A word piece token and its byte range in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BertToken {
+    pub(crate) id: i32,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    /// The token is not the first piece of a word, such as `##ing`.
+    pub(crate) is_sub_word: bool,
+}
+
+impl BertToken {
+    /// `start` and `end` are the byte range in the word, and `word_offset` is the word byte offset.
+    #[inline(always)]
+    fn new(id: i32, word_offset: usize, start: usize, end: usize) -> Self {
+        Self {
+            id,
+            start: word_offset + start,
+            end: word_offset + end,
+            is_sub_word: start != 0,
+        }
+    }
+}
+
+/// Tokenize the string to word piece tokens, and keep the byte range of every token,
+/// which can be used to map the model outputs back to the source text.
+pub(crate) fn bert_tokenize(
+    s: &str,
     token_index_map: &HashMap<String, i32>,
+    tokens: &mut Vec<BertToken>,
 ) {
-    if *index >= input_ids.len() {
-        return;
+    tokens.clear();
+    let mut string_buffer = String::new();
+    basic_tokenize(s, |word, offset| {
+        word_piece_tokenize(
+            word,
+            token_index_map,
+            &mut string_buffer,
+            |id, start, end| {
+                tokens.push(BertToken::new(id, offset, start, end));
+                true
+            },
+        )
+    });
+}
+
+/// Split the string by whitespace and punctuation, the punctuation is kept as a word.
+/// Call `f` with every word and its byte offset, and stop if `f` returns `false`.
+fn basic_tokenize(s: &str, mut f: impl FnMut(&str, usize) -> bool) {
+    // the ascii lowercase string has the same byte offsets as the source string
+    let string = s.to_ascii_lowercase();
+
+    let mut offset = 0;
+    while let Some(m) = DELIM_REGEX.find(&string[offset..]) {
+        let start = offset + m.start();
+        let end = offset + m.end();
+        if start != offset && !f(&string[offset..start], offset) {
+            return;
+        }
+
+        let delim_token = &string[start..end];
+        // include delim token
+        if INCLUDE_DELIM_REGEX.is_match(delim_token) && !f(delim_token, start) {
+            return;
+        }
+
+        offset = end;
     }
 
-    if token.as_bytes().len() > DEFAULT_MAX_BYTES_PER_TOKEN {
+    if offset < string.len() {
+        f(&string[offset..], offset);
+    }
+}
+
+/// Call `f` with every token id and its byte range in the word, and stop if `f` returns `false`.
+/// Return `false` if it is stopped.
+#[inline(always)]
+fn word_piece_tokenize(
+    word: &str,
+    token_index_map: &HashMap<String, i32>,
+    string_buffer: &mut String,
+    mut f: impl FnMut(i32, usize, usize) -> bool,
+) -> bool {
+    let unknown_id = *token_index_map.get(DEFAULT_UNKNOWN_TOKEN).unwrap_or(&0);
+    let word_len = word.len();
+
+    if word_len > DEFAULT_MAX_BYTES_PER_TOKEN {
         // use unknown token
-        input_ids[*index] = *token_index_map.get(DEFAULT_UNKNOWN_TOKEN).unwrap_or(&0);
-        *index += 1;
-        if *index >= input_ids.len() {
-            return;
+        if !f(unknown_id, 0, word_len) {
+            return false;
         }
     }
 
-    let token_len = token.len();
-    // use string buffer to save temp string concat result
-    let mut string_buffer = String::with_capacity(token_len + DEFAULT_SUFFIX_INDICATOR.len());
     let mut token_start = 0;
-    while token_start < token_len {
+    while token_start < word_len {
         if let Some((token_end, token_index)) =
-            longest_match_starting_at(token, token_start, token_index_map, &mut string_buffer)
+            longest_match_starting_at(word, token_start, token_index_map, string_buffer)
         {
             // add sub word, and the token_index is the corresponding index
-            input_ids[*index] = token_index;
-            *index += 1;
-            if *index >= input_ids.len() {
-                return;
+            if !f(token_index, token_start, token_end) {
+                return false;
             }
             token_start = token_end
         } else {
             // no token found
             // default is using unknown token
-            input_ids[*index] = *token_index_map.get(DEFAULT_UNKNOWN_TOKEN).unwrap_or(&0);
-            *index += 1;
-            return;
+            return f(unknown_id, token_start, word_len);
         }
     }
+    true
 }
 
 // return Option<(token_end, token_index)>
@@ -168,6 +266,11 @@ fn longest_match_starting_at(
     // token: &str is a valid utf-8 string and indexed by utf-8 chars
     let mut token_end = token.len();
     while token_end > token_start {
+        // skip the byte offsets in the middle of a multi-byte char
+        if !token.is_char_boundary(token_end) {
+            token_end -= 1;
+            continue;
+        }
         let str_to_lookup = if token_start > 0 {
             string_buffer.clear();
             string_buffer.extend(DEFAULT_SUFFIX_INDICATOR.chars());
@@ -185,3 +288,68 @@ fn longest_match_starting_at(
     // no token found
     None
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bert_tokenize() {
+        let token_index_map: HashMap<String, i32> = [
+            ("[UNK]", 0),
+            ("hello", 1),
+            ("play", 2),
+            ("##ing", 3),
+            (",", 4),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+
+        let s = "Hello,  Playing xyz";
+        let mut tokens = Vec::new();
+        bert_tokenize(s, &token_index_map, &mut tokens);
+        let ids: Vec<i32> = tokens.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 4, 2, 3, 0]);
+        let spans: Vec<&str> = tokens.iter().map(|t| &s[t.start..t.end]).collect();
+        assert_eq!(spans, vec!["Hello", ",", "Play", "ing", "xyz"]);
+
+        // the unknown word has multi-byte chars
+        let s = "Zürich playing";
+        bert_tokenize(s, &token_index_map, &mut tokens);
+        let spans: Vec<&str> = tokens.iter().map(|t| &s[t.start..t.end]).collect();
+        assert_eq!(spans, vec!["Zürich", "play", "ing"]);
+        assert_eq!(tokens[0].id, 0);
+    }
+
+    #[test]
+    fn test_to_bert_tensors_with_tokens() {
+        let token_index_map: HashMap<String, i32> = [
+            ("[UNK]", 0),
+            ("hello", 1),
+            ("play", 2),
+            ("##ing", 3),
+            (",", 4),
+            ("[CLS]", 5),
+            ("[SEP]", 6),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+
+        let s = "Hello, playing";
+        let mut buffers = vec![vec![0u8; 5 * 4]; 3];
+        let mut tokens = Vec::new();
+        to_bert_tensors_with_tokens(s, &token_index_map, &mut buffers, 5, 5, 6, &mut tokens)
+            .unwrap();
+        let input_ids: Vec<i32> = buffers[0]
+            .chunks_exact(4)
+            .map(|b| i32::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(input_ids, vec![5, 1, 4, 2, 6]);
+        // `##ing` is replaced by [SEP]
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(&s[tokens[2].start..tokens[2].end], "play");
+        assert!(!tokens[2].is_sub_word);
+    }
+}
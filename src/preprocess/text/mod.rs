@@ -1,5 +1,10 @@
 mod bert_tensor;
 mod regex_to_tensor;
+mod sentence_piece;
+mod string_tensor;
+
+pub(crate) use bert_tensor::{bert_tokenize, to_bert_tensors_with_tokens, BertToken};
+pub use sentence_piece::{SentencePieceModelType, SentencePieceTokenizer};
 
 use super::*;
 use regex::Regex;
@@ -14,6 +19,18 @@ pub trait TextToTensors {
         to_tensor_info: &TextToTensorInfo,
         output_buffers: &mut T,
     ) -> Result<(), Error>;
+
+    /// Serialize the text to the string tensors, used if [`TextToTensorInfo::is_string_model`] is true.
+    /// The output buffers are resized, because the string tensors have variable length.
+    fn to_string_tensors(
+        &self,
+        _to_tensor_info: &TextToTensorInfo,
+        _output_buffers: &mut [Vec<u8>],
+    ) -> Result<(), Error> {
+        Err(Error::ArgumentError(
+            "The input cannot be converted to string tensors".into(),
+        ))
+    }
 }
 
 /// Necessary information for the text to tensors.
@@ -42,10 +59,33 @@ pub enum TextToTensorInfo {
         unknown_id: i32,
         pad_id: i32,
     },
+    /// A model expecting input passed through a SentencePiece tokenizer.
+    /// If the model takes 3 input tensors, the inputs are the same as the bert model.
+    SentencePieceModel {
+        tokenizer: SentencePieceTokenizer,
+
+        /// maximum input sequence length.
+        max_seq_len: u32,
+
+        /// The `[CLS]` and `[SEP]` token ids, only for the bert inputs.
+        bert_token_ids: Option<(i32, i32)>,
+    },
     /// A model taking a string tensor input.
     StringModel,
-    /// A UniversalSentenceEncoder-based model.
+    /// A UniversalSentenceEncoder-based model, whose input tensors are
+    /// the query text, the response context and the response text, in order.
     UseModel,
+    /// A UniversalSentenceEncoder-based model, whose input tensors are found by their names.
+    /// The query encoder only has the query text input,
+    /// and the response encoder only has the response context and response text inputs.
+    UseEncoderModel {
+        /// Input tensor index of the query text.
+        query_text_index: Option<usize>,
+        /// Input tensor index of the response context, which is always empty.
+        response_context_index: Option<usize>,
+        /// Input tensor index of the response text.
+        response_text_index: Option<usize>,
+    },
 }
 
 macro_rules! check_map {
@@ -68,6 +108,40 @@ impl TextToTensorInfo {
     pub const REGEX_UNKNOWN_TOKEN: &'static str = "<UNKNOWN>";
     pub const BERT_CLASSIFIER_TOKEN: &'static str = "[CLS]";
     pub const BERT_SEPARATOR_TOKEN: &'static str = "[SEP]";
+    pub const USE_QUERY_TEXT_NAME: &'static str = "inp_text";
+    pub const USE_RESPONSE_CONTEXT_NAME: &'static str = "res_context";
+    pub const USE_RESPONSE_TEXT_NAME: &'static str = "res_text";
+
+    /// Return true if the model takes string tensors as input,
+    /// which must be written by [`TextToTensors::to_string_tensors`].
+    #[inline(always)]
+    pub fn is_string_model(&self) -> bool {
+        matches!(
+            self,
+            Self::StringModel | Self::UseModel | Self::UseEncoderModel { .. }
+        )
+    }
+
+    /// Return the input tensor indices of the universal sentence encoder model:
+    /// ```(query_text_index, response_context_index, response_text_index)```.
+    #[inline]
+    pub(crate) fn use_model_input_indices(
+        &self,
+    ) -> Option<(Option<usize>, Option<usize>, Option<usize>)> {
+        match self {
+            Self::UseModel => Some((Some(0), Some(1), Some(2))),
+            Self::UseEncoderModel {
+                query_text_index,
+                response_context_index,
+                response_text_index,
+            } => Some((
+                *query_text_index,
+                *response_context_index,
+                *response_text_index,
+            )),
+            _ => None,
+        }
+    }
 
     pub fn new_regex_model(
         max_seq_len: u32,
@@ -112,6 +186,39 @@ impl TextToTensorInfo {
             separator_token_id,
         })
     }
+
+    pub fn new_sentence_piece_model(
+        max_seq_len: u32,
+        tokenizer: SentencePieceTokenizer,
+        is_bert_input: bool,
+    ) -> Result<Self, Error> {
+        let bert_token_ids = if is_bert_input {
+            if max_seq_len < 2 {
+                return Err(Error::ModelInconsistentError(
+                    "Bert model max seq length must be at least `2`".into(),
+                ));
+            }
+            let get = |token| {
+                tokenizer.piece_to_id(token).ok_or_else(|| {
+                    Error::ModelInconsistentError(format!(
+                        "SentencePiece model doesn't have `{}` token.",
+                        token
+                    ))
+                })
+            };
+            Some((
+                get(Self::BERT_CLASSIFIER_TOKEN)?,
+                get(Self::BERT_SEPARATOR_TOKEN)?,
+            ))
+        } else {
+            None
+        };
+        Ok(Self::SentencePieceModel {
+            tokenizer,
+            max_seq_len,
+            bert_token_ids,
+        })
+    }
 }
 
 impl TextToTensors for &str {
@@ -157,10 +264,58 @@ impl TextToTensors for &str {
                     *pad_id,
                 );
             }
-            TextToTensorInfo::StringModel | TextToTensorInfo::UseModel => {
-                todo!("Text String model")
+            TextToTensorInfo::SentencePieceModel {
+                tokenizer,
+                max_seq_len,
+                bert_token_ids,
+            } => sentence_piece::sentence_piece_to_tensors(
+                self,
+                tokenizer,
+                output_buffers,
+                *max_seq_len,
+                *bert_token_ids,
+            ),
+            TextToTensorInfo::StringModel
+            | TextToTensorInfo::UseModel
+            | TextToTensorInfo::UseEncoderModel { .. } => Err(Error::ArgumentError(
+                "The model takes string tensors as input, use `to_string_tensors` instead".into(),
+            )),
+        }
+    }
+
+    fn to_string_tensors(
+        &self,
+        to_tensor_info: &TextToTensorInfo,
+        output_buffers: &mut [Vec<u8>],
+    ) -> Result<(), Error> {
+        if let TextToTensorInfo::StringModel = to_tensor_info {
+            debug_assert_eq!(output_buffers.len(), 1);
+            return string_tensor::to_string_tensor(self, &mut output_buffers[0]);
+        }
+        let (query_text_index, response_context_index, response_text_index) =
+            match to_tensor_info.use_model_input_indices() {
+                Some(indices) => indices,
+                None => {
+                    return Err(Error::ArgumentError(
+                        "The model doesn't take string tensors as input, use `to_tensors` instead"
+                            .into(),
+                    ))
+                }
+            };
+        for (i, buf) in output_buffers.iter_mut().enumerate() {
+            let index = Some(i);
+            if index == query_text_index || index == response_text_index {
+                string_tensor::to_string_tensor(self, buf)?;
+            } else if index == response_context_index {
+                string_tensor::to_string_tensor("", buf)?;
+            } else {
+                return Err(Error::ModelInconsistentError(format!(
+                    "Unknown universal sentence encoder model input `{}`",
+                    i
+                )));
             }
         }
+        Ok(())
     }
 }
 
@@ -173,6 +328,16 @@ impl TextToTensors for String {
     ) -> Result<(), Error> {
         self.as_str().to_tensors(to_tensor_info, output_buffers)
     }
+
+    #[inline(always)]
+    fn to_string_tensors(
+        &self,
+        to_tensor_info: &TextToTensorInfo,
+        output_buffers: &mut [Vec<u8>],
+    ) -> Result<(), Error> {
+        self.as_str()
+            .to_string_tensors(to_tensor_info, output_buffers)
+    }
 }
 
 impl<'a> TextToTensors for Cow<'a, str> {
@@ -187,4 +352,49 @@ impl<'a> TextToTensors for Cow<'a, str> {
             Cow::Owned(s) => s.to_tensors(to_tensor_info, output_buffers),
         }
     }
+
+    #[inline(always)]
+    fn to_string_tensors(
+        &self,
+        to_tensor_info: &TextToTensorInfo,
+        output_buffers: &mut [Vec<u8>],
+    ) -> Result<(), Error> {
+        match self {
+            Cow::Borrowed(s) => (*s).to_string_tensors(to_tensor_info, output_buffers),
+            Cow::Owned(s) => s.to_string_tensors(to_tensor_info, output_buffers),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_use_model_to_tensors() {
+        let info = TextToTensorInfo::UseEncoderModel {
+            query_text_index: Some(2),
+            response_context_index: Some(0),
+            response_text_index: Some(1),
+        };
+        let mut buffers = vec![Vec::new(); 3];
+        "hi".to_string_tensors(&info, &mut buffers).unwrap();
+        // count, offsets and string bytes
+        assert_eq!(buffers[0].len(), 12);
+        assert_eq!(&buffers[1][12..], b"hi");
+        assert_eq!(&buffers[2][12..], b"hi");
+
+        let mut buffers = vec![Vec::new(); 4];
+        assert!("hi".to_string_tensors(&info, &mut buffers).is_err());
+        // the string tensors cannot be written by `to_tensors`
+        let mut buffers = vec![Vec::new(); 3];
+        assert!("hi".to_tensors(&info, &mut buffers).is_err());
+
+        // the input tensors are in order
+        "hi".to_string_tensors(&TextToTensorInfo::UseModel, &mut buffers)
+            .unwrap();
+        assert_eq!(&buffers[0][12..], b"hi");
+        assert_eq!(buffers[1].len(), 12);
+        assert_eq!(&buffers[2][12..], b"hi");
+    }
 }
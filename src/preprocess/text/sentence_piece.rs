@@ -0,0 +1,687 @@
+// These reference files are licensed under Apache 2.0, and originally developed by Google:
+// * https://github.com/google/sentencepiece/blob/master/src/sentencepiece_model.proto
+// * https://github.com/google/sentencepiece/blob/master/src/normalizer.cc
+// * https://github.com/google/sentencepiece/blob/master/src/unigram_model.cc
+// * https://github.com/google/sentencepiece/blob/master/src/bpe_model.cc
+
+use super::*;
+use crate::model::protobuf::ProtobufReader;
+
+/// The algorithm of a SentencePiece model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SentencePieceModelType {
+    Unigram,
+    Bpe,
+    Word,
+    Char,
+}
+
+/// A pure rust SentencePiece tokenizer, which loads the serialized SentencePiece model proto.
+#[derive(Debug)]
+pub struct SentencePieceTokenizer {
+    model_type: SentencePieceModelType,
+    // piece -> (id, score), only the normal and user defined pieces are used to encode.
+    pieces: HashMap<String, (i32, f32)>,
+    // control pieces such as `<s>` and `[CLS]`, which are never produced by the encoder.
+    control_pieces: HashMap<String, i32>,
+    vocab_size: usize,
+    max_piece_chars: usize,
+    min_score: f32,
+    // ids of pieces `<0x00>` to `<0xFF>`, used if byte fallback is enabled.
+    byte_piece_ids: Option<Vec<i32>>,
+
+    unk_id: i32,
+    bos_id: i32,
+    eos_id: i32,
+    pad_id: i32,
+
+    normalizer: Normalizer,
+}
+
+impl SentencePieceTokenizer {
+    /// The meta symbol to replace the whitespace: `▁` (U+2581).
+    pub const SPACE_SYMBOL: char = '\u{2581}';
+
+    // the penalty of unknown characters in unigram model
+    const UNKNOWN_PENALTY: f32 = 10.;
+
+    // SentencePiece.Type
+    const PIECE_TYPE_NORMAL: u64 = 1;
+    const PIECE_TYPE_UNKNOWN: u64 = 2;
+    const PIECE_TYPE_CONTROL: u64 = 3;
+    const PIECE_TYPE_USER_DEFINED: u64 = 4;
+    const PIECE_TYPE_BYTE: u64 = 6;
+
+    /// Parse the serialized SentencePiece `ModelProto`.
+    pub fn new(model_proto: &[u8]) -> Result<Self, Error> {
+        let mut res = Self {
+            model_type: SentencePieceModelType::Unigram,
+            pieces: HashMap::new(),
+            control_pieces: HashMap::new(),
+            vocab_size: 0,
+            max_piece_chars: 0,
+            min_score: f32::MAX,
+            byte_piece_ids: None,
+            unk_id: 0,
+            bos_id: 1,
+            eos_id: 2,
+            pad_id: -1,
+            normalizer: Normalizer::default(),
+        };
+        let mut byte_fallback = false;
+        let mut byte_piece_ids = vec![-1; 256];
+
+        let mut reader = ProtobufReader::new(model_proto);
+        while let Some((field_number, value)) = reader.next_field()? {
+            let bytes = match value.as_bytes() {
+                Some(b) => b,
+                None => continue,
+            };
+            match field_number {
+                // repeated SentencePiece pieces
+                1 => {
+                    let id = res.vocab_size as i32;
+                    res.vocab_size += 1;
+                    let (piece, score, piece_type) = Self::parse_piece(bytes)?;
+                    match piece_type {
+                        Self::PIECE_TYPE_NORMAL | Self::PIECE_TYPE_USER_DEFINED => {
+                            res.max_piece_chars = res.max_piece_chars.max(piece.chars().count());
+                            res.min_score = res.min_score.min(score);
+                            res.pieces.insert(piece.to_string(), (id, score));
+                        }
+                        Self::PIECE_TYPE_UNKNOWN => res.unk_id = id,
+                        Self::PIECE_TYPE_CONTROL => {
+                            res.control_pieces.insert(piece.to_string(), id);
+                        }
+                        Self::PIECE_TYPE_BYTE => {
+                            if let Some(b) = piece
+                                .strip_prefix("<0x")
+                                .and_then(|p| p.strip_suffix('>'))
+                                .and_then(|p| u8::from_str_radix(p, 16).ok())
+                            {
+                                byte_piece_ids[b as usize] = id;
+                            }
+                        }
+                        // unused pieces are never produced by the encoder
+                        _ => {}
+                    }
+                }
+                // TrainerSpec
+                2 => {
+                    let mut r = ProtobufReader::new(bytes);
+                    while let Some((n, v)) = r.next_field()? {
+                        let v = match v.as_varint() {
+                            Some(v) => v,
+                            None => continue,
+                        };
+                        match n {
+                            3 => {
+                                res.model_type = match v {
+                                    1 => SentencePieceModelType::Unigram,
+                                    2 => SentencePieceModelType::Bpe,
+                                    3 => SentencePieceModelType::Word,
+                                    4 => SentencePieceModelType::Char,
+                                    _ => {
+                                        return Err(Error::ModelParseError(format!(
+                                            "Unknown SentencePiece model type `{}`",
+                                            v
+                                        )));
+                                    }
+                                }
+                            }
+                            35 => byte_fallback = v != 0,
+                            // the int32 negative values are encoded as 64 bits
+                            41 => res.bos_id = v as i32,
+                            42 => res.eos_id = v as i32,
+                            43 => res.pad_id = v as i32,
+                            _ => {}
+                        }
+                    }
+                }
+                // NormalizerSpec
+                3 => res.normalizer = Normalizer::parse(bytes)?,
+                _ => {}
+            }
+        }
+
+        if res.pieces.is_empty() {
+            return Err(Error::ModelParseError(
+                "SentencePiece model has no pieces".into(),
+            ));
+        }
+        if byte_fallback {
+            if byte_piece_ids.iter().any(|i| *i < 0) {
+                return Err(Error::ModelParseError(
+                    "SentencePiece model enables byte fallback but misses byte pieces".into(),
+                ));
+            }
+            res.byte_piece_ids = Some(byte_piece_ids);
+        }
+        Ok(res)
+    }
+
+    // return (piece, score, type)
+    fn parse_piece(buf: &[u8]) -> Result<(&str, f32, u64), Error> {
+        let mut piece = "";
+        let mut score = 0.;
+        let mut piece_type = Self::PIECE_TYPE_NORMAL;
+        let mut reader = ProtobufReader::new(buf);
+        while let Some((n, v)) = reader.next_field()? {
+            match n {
+                1 => piece = v.as_str()?,
+                2 => score = v.as_f32().unwrap_or_default(),
+                3 => piece_type = v.as_varint().unwrap_or(Self::PIECE_TYPE_NORMAL),
+                _ => {}
+            }
+        }
+        Ok((piece, score, piece_type))
+    }
+
+    /// Get the model algorithm.
+    #[inline(always)]
+    pub fn model_type(&self) -> SentencePieceModelType {
+        self.model_type
+    }
+
+    /// Get the number of pieces, including the control and unknown pieces.
+    #[inline(always)]
+    pub fn vocab_size(&self) -> usize {
+        self.vocab_size
+    }
+
+    /// Get the id of the unknown piece.
+    #[inline(always)]
+    pub fn unk_id(&self) -> i32 {
+        self.unk_id
+    }
+
+    /// Get the id of the begin of sentence piece, `-1` if it is disabled.
+    #[inline(always)]
+    pub fn bos_id(&self) -> i32 {
+        self.bos_id
+    }
+
+    /// Get the id of the end of sentence piece, `-1` if it is disabled.
+    #[inline(always)]
+    pub fn eos_id(&self) -> i32 {
+        self.eos_id
+    }
+
+    /// Get the id of the padding piece, `-1` if it is disabled.
+    #[inline(always)]
+    pub fn pad_id(&self) -> i32 {
+        self.pad_id
+    }
+
+    /// Get the id of a normal, user defined or control piece.
+    #[inline(always)]
+    pub fn piece_to_id(&self, piece: &str) -> Option<i32> {
+        self.pieces
+            .get(piece)
+            .map(|p| p.0)
+            .or_else(|| self.control_pieces.get(piece).cloned())
+    }
+
+    /// Encode the text to piece ids.
+    pub fn encode(&self, text: &str, ids: &mut Vec<i32>) {
+        ids.clear();
+        let normalized = self.normalizer.normalize(text);
+        if normalized.is_empty() {
+            return;
+        }
+        let pieces = match self.model_type {
+            SentencePieceModelType::Unigram => self.encode_unigram(&normalized),
+            SentencePieceModelType::Bpe => self.encode_bpe(&normalized),
+            SentencePieceModelType::Word => {
+                // the space symbol is the prefix of a word
+                let mut words = Vec::new();
+                let mut start = 0;
+                for (i, c) in normalized.char_indices() {
+                    if c == Self::SPACE_SYMBOL && i > start {
+                        words.push(&normalized[start..i]);
+                        start = i;
+                    }
+                }
+                words.push(&normalized[start..]);
+                words
+            }
+            SentencePieceModelType::Char => normalized
+                .char_indices()
+                .map(|(i, c)| &normalized[i..i + c.len_utf8()])
+                .collect(),
+        };
+
+        for piece in pieces {
+            match self.pieces.get(piece) {
+                Some((id, _)) => ids.push(*id),
+                None => match self.byte_piece_ids {
+                    Some(ref byte_piece_ids) => {
+                        ids.extend(piece.bytes().map(|b| byte_piece_ids[b as usize]))
+                    }
+                    // merge the continuous unknown pieces
+                    None if ids.last() == Some(&self.unk_id) => {}
+                    None => ids.push(self.unk_id),
+                },
+            }
+        }
+    }
+
+    /// Find the best segmentation with the max sum of piece scores.
+    fn encode_unigram<'s>(&self, s: &'s str) -> Vec<&'s str> {
+        let mut boundaries: Vec<usize> = s.char_indices().map(|(i, _)| i).collect();
+        boundaries.push(s.len());
+        let chars = boundaries.len() - 1;
+
+        // (best score, start char index of the last piece)
+        let mut best = vec![(f32::MIN, 0); chars + 1];
+        best[0].0 = 0.;
+        let unknown_score = self.min_score - Self::UNKNOWN_PENALTY;
+        for end in 1..=chars {
+            for start in end.saturating_sub(self.max_piece_chars)..end {
+                if best[start].0 == f32::MIN {
+                    continue;
+                }
+                let piece = &s[boundaries[start]..boundaries[end]];
+                let score = match self.pieces.get(piece) {
+                    Some((_, score)) => *score,
+                    // the single character can always be an unknown piece
+                    None if start + 1 == end => unknown_score,
+                    None => continue,
+                };
+                if best[start].0 + score > best[end].0 {
+                    best[end] = (best[start].0 + score, start);
+                }
+            }
+        }
+
+        let mut res = Vec::new();
+        let mut end = chars;
+        while end > 0 {
+            let start = best[end].1;
+            res.push(&s[boundaries[start]..boundaries[end]]);
+            end = start;
+        }
+        res.reverse();
+        res
+    }
+
+    /// Merge the adjacent symbols with the highest score, until no symbols can be merged.
+    fn encode_bpe<'s>(&self, s: &'s str) -> Vec<&'s str> {
+        // symbols are byte ranges of the string
+        let mut symbols: Vec<(usize, usize)> = s
+            .char_indices()
+            .map(|(i, c)| (i, i + c.len_utf8()))
+            .collect();
+        loop {
+            let mut best: Option<(usize, f32)> = None;
+            for i in 0..symbols.len().saturating_sub(1) {
+                let merged = &s[symbols[i].0..symbols[i + 1].1];
+                match (self.pieces.get(merged), best) {
+                    (Some((_, score)), Some((_, b))) if *score <= b => {}
+                    (Some((_, score)), _) => best = Some((i, *score)),
+                    _ => {}
+                }
+            }
+            match best {
+                Some((i, _)) => {
+                    symbols[i].1 = symbols[i + 1].1;
+                    symbols.remove(i + 1);
+                }
+                None => break,
+            }
+        }
+        symbols
+            .into_iter()
+            .map(|(start, end)| &s[start..end])
+            .collect()
+    }
+}
+
+pub(super) fn sentence_piece_to_tensors<T: AsMut<[E]>, E: AsMut<[u8]>>(
+    s: &str,
+    tokenizer: &SentencePieceTokenizer,
+    output_buffers: &mut T,
+    max_seq_len: u32,
+    bert_token_ids: Option<(i32, i32)>,
+) -> Result<(), Error> {
+    let indices_size = max_seq_len as usize;
+    let min_bytes = indices_size * std::mem::size_of::<i32>();
+    let expect_inputs = if bert_token_ids.is_some() { 3 } else { 1 };
+    if output_buffers.as_mut().len() != expect_inputs {
+        return Err(Error::ModelInconsistentError(format!(
+            "SentencePiece model input must be `{}` tensors, but got `{}`",
+            expect_inputs,
+            output_buffers.as_mut().len()
+        )));
+    }
+    for (i, buf) in output_buffers.as_mut().iter_mut().enumerate() {
+        if buf.as_mut().len() < min_bytes {
+            return Err(Error::ModelInconsistentError(format!(
+                "Expect input buffer `{}` at least `{}` bytes, but got `{}`",
+                i,
+                min_bytes,
+                buf.as_mut().len()
+            )));
+        }
+    }
+
+    let mut ids = Vec::with_capacity(indices_size);
+    tokenizer.encode(s, &mut ids);
+    let input_ids = unsafe {
+        core::slice::from_raw_parts_mut(
+            output_buffers.as_mut()[0].as_mut().as_mut_ptr() as *mut i32,
+            indices_size,
+        )
+    };
+
+    match bert_token_ids {
+        // [CLS] ids [SEP], the same layout as the bert model
+        Some((classifier_token_id, separator_token_id)) => {
+            ids.truncate(indices_size - 2);
+            input_ids[0] = classifier_token_id;
+            input_ids[1..ids.len() + 1].copy_from_slice(&ids);
+            input_ids[ids.len() + 1] = separator_token_id;
+            let len = ids.len() + 2;
+            input_ids[len..].fill(0);
+
+            output_buffers.as_mut()[1].as_mut()[..min_bytes].fill(0);
+            let input_masks = unsafe {
+                core::slice::from_raw_parts_mut(
+                    output_buffers.as_mut()[2].as_mut().as_mut_ptr() as *mut i32,
+                    indices_size,
+                )
+            };
+            input_masks[..len].fill(1);
+            input_masks[len..].fill(0);
+        }
+        None => {
+            ids.truncate(indices_size);
+            input_ids[..ids.len()].copy_from_slice(&ids);
+            input_ids[ids.len()..].fill(tokenizer.pad_id().max(0));
+        }
+    }
+    Ok(())
+}
+
+/// The SentencePiece text normalizer.
+#[derive(Debug)]
+struct Normalizer {
+    chars_map: Option<CharsMap>,
+    add_dummy_prefix: bool,
+    remove_extra_whitespaces: bool,
+    escape_whitespaces: bool,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self {
+            chars_map: None,
+            add_dummy_prefix: true,
+            remove_extra_whitespaces: true,
+            escape_whitespaces: true,
+        }
+    }
+}
+
+impl Normalizer {
+    fn parse(buf: &[u8]) -> Result<Self, Error> {
+        let mut res = Self::default();
+        let mut reader = ProtobufReader::new(buf);
+        while let Some((n, v)) = reader.next_field()? {
+            match n {
+                2 => {
+                    if let Some(b) = v.as_bytes().filter(|b| !b.is_empty()) {
+                        res.chars_map = Some(CharsMap::parse(b)?);
+                    }
+                }
+                3 => res.add_dummy_prefix = v.as_varint() != Some(0),
+                4 => res.remove_extra_whitespaces = v.as_varint() != Some(0),
+                5 => res.escape_whitespaces = v.as_varint() != Some(0),
+                _ => {}
+            }
+        }
+        Ok(res)
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        // map the characters, such as NFKC normalization
+        let mapped = match self.chars_map {
+            Some(ref m) => {
+                let mut mapped = String::with_capacity(text.len());
+                let mut i = 0;
+                while i < text.len() {
+                    match m.longest_match(&text.as_bytes()[i..]) {
+                        Some((len, replacement)) if text.is_char_boundary(i + len) => {
+                            mapped.push_str(replacement);
+                            i += len;
+                        }
+                        _ => {
+                            let c = text[i..].chars().next().unwrap();
+                            mapped.push(c);
+                            i += c.len_utf8();
+                        }
+                    }
+                }
+                Cow::Owned(mapped)
+            }
+            None => Cow::Borrowed(text),
+        };
+
+        let mut res = String::with_capacity(mapped.len() + 3);
+        let space = if self.escape_whitespaces {
+            SentencePieceTokenizer::SPACE_SYMBOL
+        } else {
+            ' '
+        };
+        // the leading whitespaces are removed
+        let mut prev_is_space = self.remove_extra_whitespaces;
+        for c in mapped.chars() {
+            if c == ' ' {
+                if !(self.remove_extra_whitespaces && prev_is_space) {
+                    res.push(space);
+                }
+                prev_is_space = true;
+            } else {
+                res.push(c);
+                prev_is_space = false;
+            }
+        }
+        if self.remove_extra_whitespaces && prev_is_space && res.ends_with(space) {
+            res.pop();
+        }
+        if self.add_dummy_prefix && !res.is_empty() {
+            res.insert(0, space);
+        }
+        res
+    }
+}
+
+/// The precompiled characters map, which is a double array trie built by darts-clone.
+/// The layout is: `[trie size: u32][trie units: u32 * n][null-terminated replacement strings]`
+#[derive(Debug)]
+struct CharsMap {
+    units: Vec<u32>,
+    replacements: Vec<u8>,
+}
+
+impl CharsMap {
+    fn parse(buf: &[u8]) -> Result<Self, Error> {
+        if buf.len() < 4 {
+            return Err(Error::ModelParseError(
+                "Invalid SentencePiece precompiled chars map".into(),
+            ));
+        }
+        let trie_size = u32::from_le_bytes(buf[..4].try_into().unwrap()) as usize;
+        if trie_size & 3 != 0 || trie_size > buf.len() - 4 {
+            return Err(Error::ModelParseError(format!(
+                "Invalid SentencePiece chars map trie size `{}`",
+                trie_size
+            )));
+        }
+        let units = buf[4..4 + trie_size]
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        Ok(Self {
+            units,
+            replacements: buf[4 + trie_size..].to_vec(),
+        })
+    }
+
+    /// Return the longest matched prefix length and its replacement.
+    fn longest_match(&self, key: &[u8]) -> Option<(usize, &str)> {
+        #[inline(always)]
+        fn offset(unit: u32) -> usize {
+            ((unit >> 10) << ((unit & (1 << 9)) >> 6)) as usize
+        }
+        #[inline(always)]
+        fn label(unit: u32) -> u32 {
+            unit & ((1 << 31) | 0xFF)
+        }
+        #[inline(always)]
+        fn has_leaf(unit: u32) -> bool {
+            (unit >> 8) & 1 == 1
+        }
+
+        let mut res = None;
+        let mut node_pos = offset(*self.units.first()?);
+        for (i, b) in key.iter().enumerate() {
+            node_pos ^= *b as usize;
+            let unit = match self.units.get(node_pos) {
+                Some(u) if label(*u) == *b as u32 => *u,
+                _ => break,
+            };
+            node_pos ^= offset(unit);
+            if has_leaf(unit) {
+                if let Some(v) = self.units.get(node_pos) {
+                    res = Some((i + 1, (v & ((1 << 31) - 1)) as usize));
+                }
+            }
+        }
+
+        let (len, value) = res?;
+        let replacement = self.replacements.get(value..)?;
+        let end = replacement.iter().position(|b| *b == 0)?;
+        std::str::from_utf8(&replacement[..end])
+            .ok()
+            .map(|s| (len, s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // encode a minimal SentencePiece model proto
+    fn model_proto(pieces: &[(&str, f32, u8)], model_type: u8) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for (piece, score, piece_type) in pieces {
+            let mut p = vec![0x0a, piece.len() as u8];
+            p.extend_from_slice(piece.as_bytes());
+            p.push(0x15);
+            p.extend_from_slice(&score.to_le_bytes());
+            p.extend_from_slice(&[0x18, *piece_type]);
+            buf.extend_from_slice(&[0x0a, p.len() as u8]);
+            buf.extend_from_slice(&p);
+        }
+        // trainer spec: model_type
+        buf.extend_from_slice(&[0x12, 0x02, 0x18, model_type]);
+        buf
+    }
+
+    const PIECES: [(&str, f32, u8); 8] = [
+        ("<unk>", 0., 2),
+        ("<s>", 0., 3),
+        ("</s>", 0., 3),
+        ("\u{2581}", -2., 1),
+        ("\u{2581}hello", -1., 1),
+        ("wor", -3., 1),
+        ("ld", -3., 1),
+        ("world", -4., 1),
+    ];
+
+    #[test]
+    fn test_unigram() {
+        let tokenizer = SentencePieceTokenizer::new(&model_proto(&PIECES, 1)).unwrap();
+        assert_eq!(tokenizer.model_type(), SentencePieceModelType::Unigram);
+        assert_eq!(tokenizer.vocab_size(), 8);
+        assert_eq!(tokenizer.unk_id(), 0);
+
+        let mut ids = Vec::new();
+        tokenizer.encode("  Hello world  ", &mut ids);
+        // `Hello` is unknown because the characters are not normalized
+        assert_eq!(ids, vec![3, 0, 3, 7]);
+        tokenizer.encode("hello   world", &mut ids);
+        assert_eq!(ids, vec![4, 3, 7]);
+        tokenizer.encode("   ", &mut ids);
+        assert!(ids.is_empty());
+
+        // [CLS] and [SEP] are missing, use `<s>` and `</s>` as bert tokens.
+        let mut buffers = vec![vec![0u8; 4 * 5]; 3];
+        sentence_piece_to_tensors("hello world", &tokenizer, &mut buffers, 5, Some((1, 2)))
+            .unwrap();
+        let to_i32 = |b: &[u8]| -> Vec<i32> {
+            b.chunks_exact(4)
+                .map(|c| i32::from_le_bytes(c.try_into().unwrap()))
+                .collect()
+        };
+        assert_eq!(to_i32(&buffers[0]), vec![1, 4, 3, 7, 2]);
+        assert_eq!(to_i32(&buffers[1]), vec![0; 5]);
+        assert_eq!(to_i32(&buffers[2]), vec![1; 5]);
+
+        let mut buffers = vec![vec![0u8; 4 * 5]; 1];
+        sentence_piece_to_tensors("hello", &tokenizer, &mut buffers, 5, None).unwrap();
+        assert_eq!(to_i32(&buffers[0]), vec![4, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_bpe() {
+        let pieces = [
+            ("<unk>", 0., 2),
+            ("\u{2581}", -5., 1),
+            ("w", -5., 1),
+            ("o", -5., 1),
+            ("r", -5., 1),
+            ("l", -5., 1),
+            ("d", -5., 1),
+            ("wo", -1., 1),
+            ("wor", -2., 1),
+            ("ld", -1.5, 1),
+            ("\u{2581}wor", -3., 1),
+        ];
+        let tokenizer = SentencePieceTokenizer::new(&model_proto(&pieces, 2)).unwrap();
+        assert_eq!(tokenizer.model_type(), SentencePieceModelType::Bpe);
+        let mut ids = Vec::new();
+        // merge order: `wo`, `ld`, `wor`, `▁wor`
+        tokenizer.encode("world low", &mut ids);
+        assert_eq!(ids, vec![10, 9, 1, 5, 3, 2]);
+        tokenizer.encode("xy", &mut ids);
+        assert_eq!(ids, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_chars_map() {
+        // map `A` to `a`: root -> `A`(0x41) -> leaf with value 0
+        let mut units = vec![0u32; 0x43];
+        // root unit, offset 1
+        units[0] = 1 << 10;
+        // node at 1 ^ 0x41 = 0x40, label `A`, has leaf, offset 1
+        units[0x40] = 0x41 | (1 << 8) | (1 << 10);
+        // leaf at 0x40 ^ 1 = 0x41, value 0
+        units[0x41] = 1 << 31;
+        let mut buf = ((units.len() * 4) as u32).to_le_bytes().to_vec();
+        for u in units {
+            buf.extend_from_slice(&u.to_le_bytes());
+        }
+        buf.extend_from_slice(b"a\0");
+        let chars_map = CharsMap::parse(&buf).unwrap();
+        assert_eq!(chars_map.longest_match(b"AB"), Some((1, "a")));
+        assert_eq!(chars_map.longest_match(b"B"), None);
+
+        let normalizer = Normalizer {
+            chars_map: Some(chars_map),
+            ..Default::default()
+        };
+        assert_eq!(normalizer.normalize(" BAA  A "), "\u{2581}Baa\u{2581}a");
+    }
+}
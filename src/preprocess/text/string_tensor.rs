@@ -0,0 +1,47 @@
+// The string tensor layout reference file is licensed under Apache 2.0, and originally developed by Google:
+// * https://github.com/tensorflow/tensorflow/blob/master/tensorflow/lite/string_util.h
+
+use super::*;
+
+/// Serialize the string to a TfLite string tensor with one element.
+/// The layout is: `[count: i32][offsets: i32 * (count + 1)][string bytes]`, all integers are little endian.
+pub(super) fn to_string_tensor(s: &str, output_buffer: &mut Vec<u8>) -> Result<(), Error> {
+    const COUNT: usize = 1;
+    let header_bytes = std::mem::size_of::<i32>() * (COUNT + 2);
+    let total_bytes = header_bytes + s.len();
+    if total_bytes > i32::MAX as usize {
+        return Err(Error::ArgumentError(format!(
+            "Input text is too long, got `{}` bytes",
+            s.len()
+        )));
+    }
+
+    output_buffer.clear();
+    output_buffer.reserve(total_bytes);
+    output_buffer.extend_from_slice(&(COUNT as i32).to_le_bytes());
+    output_buffer.extend_from_slice(&(header_bytes as i32).to_le_bytes());
+    output_buffer.extend_from_slice(&(total_bytes as i32).to_le_bytes());
+    output_buffer.extend_from_slice(s.as_bytes());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_string_tensor() {
+        let mut buf = vec![0; 4];
+        to_string_tensor("hello", &mut buf).unwrap();
+        assert_eq!(buf.len(), 17);
+        assert_eq!(&buf[0..4], &1i32.to_le_bytes());
+        assert_eq!(&buf[4..8], &12i32.to_le_bytes());
+        assert_eq!(&buf[8..12], &17i32.to_le_bytes());
+        assert_eq!(&buf[12..], b"hello");
+
+        // the buffer is reused
+        to_string_tensor("", &mut buf).unwrap();
+        assert_eq!(buf.len(), 12);
+        assert_eq!(&buf[8..12], &12i32.to_le_bytes());
+    }
+}
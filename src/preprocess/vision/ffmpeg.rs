@@ -78,8 +78,11 @@ impl<'a> ImageToTensor for FFMpegFrame<'a> {
         let src_height = self.0.source.frame.height();
         let mut scale_frame_buffer = self.0.scale_frame_buffer.borrow_mut();
 
-        // crop and rotate, then scale using filter
-        let data = if process_options.rotation != 0. || process_options.region_of_interest.is_some()
+        // crop, rotate and flip, then scale using filter
+        let (data, stride) = if process_options.rotation != 0.
+            || process_options.region_of_interest.is_some()
+            || process_options.flip_horizontally
+            || process_options.resize_mode != ResizeMode::Stretch
         {
             const IN_NODE: &'static str = "Parsed_buffer_0";
             const OUT_NODE_PREFIX: &'static str = "Parsed_buffersink_";
@@ -100,8 +103,19 @@ impl<'a> ImageToTensor for FFMpegFrame<'a> {
                 String::new()
             };
             if process_options.rotation != 0. {
+                // the output contains the whole rotated image
                 num_node += 1;
-                desc.extend(format!("[r_in];[r_in]rotate={}", process_options.rotation).chars());
+                desc.extend(
+                    format!(
+                        "[r_in];[r_in]rotate={0}:ow=rotw({0}):oh=roth({0})",
+                        process_options.rotation
+                    )
+                    .chars(),
+                );
+            }
+            if process_options.flip_horizontally {
+                num_node += 1;
+                desc.extend("[h_in];[h_in]hflip".chars());
             }
             let out_format = match to_tensor_info.color_space {
                 ImageColorSpaceType::GRAYSCALE => {
@@ -109,12 +123,28 @@ impl<'a> ImageToTensor for FFMpegFrame<'a> {
                 }
                 _ => ffmpeg_next::ffi::AVPixelFormat::AV_PIX_FMT_RGB24 as u32,
             };
+            let (width, height) = (to_tensor_info.width(), to_tensor_info.height());
+            let scale = match process_options.resize_mode {
+                ResizeMode::Stretch => format!("scale={}:{}", width, height),
+                ResizeMode::Letterbox => {
+                    num_node += 1;
+                    format!(
+                        "scale={0}:{1}:force_original_aspect_ratio=decrease[p_in];[p_in]pad={0}:{1}:(ow-iw)/2:(oh-ih)/2",
+                        width, height
+                    )
+                }
+                ResizeMode::CenterCrop => {
+                    num_node += 1;
+                    format!(
+                        "scale={0}:{1}:force_original_aspect_ratio=increase[cc_in];[cc_in]crop={0}:{1}",
+                        width, height
+                    )
+                }
+            };
             desc.extend(
                 format!(
-                    "[s_in];[s_in]scale={}:{}[f];[f]format=pix_fmts={}[out];[out]buffersink",
-                    to_tensor_info.width(),
-                    to_tensor_info.height(),
-                    out_format
+                    "[s_in];[s_in]{}[f];[f]format=pix_fmts={}[out];[out]buffersink",
+                    scale, out_format
                 )
                 .chars(),
             );
@@ -132,7 +162,7 @@ impl<'a> ImageToTensor for FFMpegFrame<'a> {
                 .unwrap()
                 .sink()
                 .frame(&mut scale_frame_buffer)?;
-            scale_frame_buffer.data(0)
+            (scale_frame_buffer.data(0), scale_frame_buffer.stride(0))
         } else {
             // scale and convert format
             let scale_key = ScaleKey {
@@ -147,9 +177,9 @@ impl<'a> ImageToTensor for FFMpegFrame<'a> {
             if let Some(scale_ctx) = cached_scale_ctx(&mut scales_cache, src_format, scale_key) {
                 // scale frame
                 scale_ctx.run(&self.0.source.frame, &mut scale_frame_buffer)?;
-                scale_frame_buffer.data(0)
+                (scale_frame_buffer.data(0), scale_frame_buffer.stride(0))
             } else {
-                self.0.source.frame.data(0)
+                (self.0.source.frame.data(0), self.0.source.frame.stride(0))
             }
         };
 
@@ -164,7 +194,28 @@ impl<'a> ImageToTensor for FFMpegFrame<'a> {
                 image::rgb8_image_buffer_to_tensor(&img, to_tensor_info, output_buffer)?;
             }
             ImageColorSpaceType::GRAYSCALE => {
-                todo!("gray image")
+                let width = to_tensor_info.width() as usize;
+                let height = to_tensor_info.height() as usize;
+                // the frame lines may be padded for alignment
+                let packed: Vec<u8>;
+                let data = if stride == width {
+                    data
+                } else {
+                    packed = data
+                        .chunks(stride)
+                        .take(height)
+                        .flat_map(|line| &line[..width])
+                        .copied()
+                        .collect();
+                    packed.as_slice()
+                };
+                let img = image::ImageBuffer::<image::Luma<u8>, &[u8]>::from_raw(
+                    to_tensor_info.width(),
+                    to_tensor_info.height(),
+                    data,
+                )
+                .unwrap();
+                image::gray8_image_buffer_to_tensor(&img, to_tensor_info, output_buffer)?;
             }
         }
 
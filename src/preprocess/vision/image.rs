@@ -1,20 +1,19 @@
 extern crate image as image_crate;
 
+use super::warp::{self, WarpSource};
 use super::*;
 pub(super) use image_crate::{
-    imageops, DynamicImage, EncodableLayout, GenericImageView, ImageBuffer, Pixel, Rgb, RgbImage,
+    DynamicImage, EncodableLayout, GenericImageView, GrayImage, ImageBuffer, Luma, Rgb, RgbImage,
 };
 
-const IMAGE_RESIZE_FILTER: imageops::FilterType = imageops::FilterType::Gaussian;
-
 macro_rules! get_rgb_mean_std_from_info {
     ( $info:ident ) => {{
         let r_mean = $info.normalization_options.0.get(0).unwrap();
         let r_std = $info.normalization_options.1.get(0).unwrap();
         let g_mean = $info.normalization_options.0.get(1).unwrap_or(r_mean);
         let g_std = $info.normalization_options.1.get(1).unwrap_or(r_std);
-        let b_mean = $info.normalization_options.0.get(1).unwrap_or(r_mean);
-        let b_std = $info.normalization_options.1.get(1).unwrap_or(r_std);
+        let b_mean = $info.normalization_options.0.get(2).unwrap_or(r_mean);
+        let b_std = $info.normalization_options.1.get(2).unwrap_or(r_std);
         (r_mean, r_std, g_mean, g_std, b_mean, b_std)
     }};
 }
@@ -29,7 +28,12 @@ impl ImageToTensor for DynamicImage {
     ) -> Result<(), Error> {
         match info.color_space {
             ImageColorSpaceType::GRAYSCALE => {
-                unimplemented!()
+                if let Some(luma) = self.as_luma8() {
+                    luma.to_tensor(info, process_options, output_buffer)
+                } else {
+                    self.to_luma8()
+                        .to_tensor(info, process_options, output_buffer)
+                }
             }
             // we treat unknown as rgb8
             ImageColorSpaceType::RGB | ImageColorSpaceType::UNKNOWN => {
@@ -58,53 +62,35 @@ impl ImageToTensor for RgbImage {
         process_options: &ImageProcessingOptions,
         output_buffer: &mut T,
     ) -> Result<(), Error> {
-        let mut tmp_rgb_img;
-
-        let mut rgb_img = if let Some(ref roi) = process_options.region_of_interest {
-            // check roi
-            let weight = self.width() as f32;
-            let height = self.height() as f32;
-            let x = (roi.x_min * weight) as u32;
-            let y = (roi.y_min * height) as u32;
-            let w = (roi.width * weight) as u32;
-            let h = (roi.height * height) as u32;
-            tmp_rgb_img = imageops::crop_imm(self, x, y, w, h).to_image();
-            let abs = process_options.rotation.abs();
-            if abs > 0.01 {
-                if (abs - std::f32::consts::PI).abs() < 0.01 {
-                    imageops::rotate180_in_place(&mut tmp_rgb_img);
-                } else {
-                    tmp_rgb_img = ops_inner::rotate_any(&tmp_rgb_img, process_options.rotation);
-                }
-            }
-            &tmp_rgb_img
-        } else {
-            let abs = process_options.rotation.abs();
-            if abs > 0.01 {
-                if (abs - std::f32::consts::PI).abs() < 0.01 {
-                    tmp_rgb_img = imageops::rotate180(self);
-                } else {
-                    tmp_rgb_img = ops_inner::rotate_any(self, process_options.rotation);
-                }
-                &tmp_rgb_img
-            } else {
-                self
-            }
-        };
-
-        let width = info.width();
-        let height = info.height();
-        if width != rgb_img.width() || height != rgb_img.height() {
-            tmp_rgb_img = imageops::resize(rgb_img, width, height, IMAGE_RESIZE_FILTER);
-            rgb_img = &tmp_rgb_img;
+        if info.color_space != ImageColorSpaceType::GRAYSCALE
+            && is_plain_copy(self.dimensions(), info, process_options)
+        {
+            return rgb8_image_buffer_to_tensor(self, info, output_buffer);
         }
+        warp::warp_to_tensor(self, info, process_options, output_buffer)
+    }
 
-        if info.color_space == ImageColorSpaceType::GRAYSCALE {
-            // todo: gray image
-            unimplemented!()
-        }
+    /// return image size: (weight, height)
+    #[inline(always)]
+    fn image_size(&self) -> (u32, u32) {
+        self.dimensions()
+    }
+}
 
-        rgb8_image_buffer_to_tensor(rgb_img, info, output_buffer)
+impl ImageToTensor for GrayImage {
+    #[inline]
+    fn to_tensor<T: AsMut<[u8]>>(
+        &self,
+        info: &ImageToTensorInfo,
+        process_options: &ImageProcessingOptions,
+        output_buffer: &mut T,
+    ) -> Result<(), Error> {
+        if info.color_space == ImageColorSpaceType::GRAYSCALE
+            && is_plain_copy(self.dimensions(), info, process_options)
+        {
+            return gray8_image_buffer_to_tensor(self, info, output_buffer);
+        }
+        warp::warp_to_tensor(self, info, process_options, output_buffer)
     }
 
     /// return image size: (weight, height)
@@ -114,6 +100,57 @@ impl ImageToTensor for RgbImage {
     }
 }
 
+/// Whether the image can be copied to the tensor without crop, rotation, flip and resize.
+#[inline(always)]
+fn is_plain_copy(
+    (width, height): (u32, u32),
+    info: &ImageToTensorInfo,
+    process_options: &ImageProcessingOptions,
+) -> bool {
+    process_options.region_of_interest.is_none()
+        && process_options.rotation == 0.
+        && !process_options.flip_horizontally
+        && width == info.width()
+        && height == info.height()
+}
+
+impl<Container> WarpSource for ImageBuffer<Rgb<u8>, Container>
+where
+    Container: std::ops::Deref<Target = [u8]>,
+{
+    #[inline(always)]
+    fn dimensions(&self) -> (u32, u32) {
+        ImageBuffer::dimensions(self)
+    }
+
+    #[inline(always)]
+    fn rgb_at(&self, x: usize, y: usize) -> [f32; 3] {
+        let i = (y * self.width() as usize + x) * 3;
+        let d = self.as_raw();
+        [d[i] as f32, d[i + 1] as f32, d[i + 2] as f32]
+    }
+}
+
+impl<Container> WarpSource for ImageBuffer<Luma<u8>, Container>
+where
+    Container: std::ops::Deref<Target = [u8]>,
+{
+    #[inline(always)]
+    fn dimensions(&self) -> (u32, u32) {
+        ImageBuffer::dimensions(self)
+    }
+
+    #[inline(always)]
+    fn rgb_at(&self, x: usize, y: usize) -> [f32; 3] {
+        [self.gray_at(x, y); 3]
+    }
+
+    #[inline(always)]
+    fn gray_at(&self, x: usize, y: usize) -> f32 {
+        self.as_raw()[y * self.width() as usize + x] as f32
+    }
+}
+
 #[inline(always)]
 pub(super) fn rgb8_image_buffer_to_tensor<'t, Container>(
     img: &'t ImageBuffer<Rgb<u8>, Container>,
@@ -159,10 +196,11 @@ where
                     Ok(())
                 }
                 ImageDataLayout::NCHW | ImageDataLayout::CHWN => {
-                    for start in 0..3 {
-                        let mut i = start as usize;
-                        while i < hw {
-                            let f = ((bytes[i] as f32) - r_mean) / r_std;
+                    let mean_std = [(r_mean, r_std), (g_mean, g_std), (b_mean, b_std)];
+                    for (start, (mean, std)) in mean_std.iter().enumerate() {
+                        let mut i = start;
+                        while i < hw * 3 {
+                            let f = ((bytes[i] as f32) - *mean) / *std;
                             res[res_index..res_index + 4].copy_from_slice(&f.to_ne_bytes());
                             res_index += 4;
                             i += 3;
@@ -186,10 +224,10 @@ where
                     let hw = (img.width() * img.height()) as usize;
                     for c in 0..3 {
                         let mut i = c as usize;
-                        while i < hw {
+                        while i < hw * 3 {
                             res[res_index] = bytes[i];
                             res_index += 1;
-                            i += c;
+                            i += 3;
                         }
                     }
                     Ok(())
@@ -200,78 +238,208 @@ where
     }
 }
 
-mod ops_inner {
+/// Convert the single channel image to tensor.
+/// For one channel, the ```NHWC``` and ```NCHW``` layouts have the same memory order.
+#[inline(always)]
+pub(super) fn gray8_image_buffer_to_tensor<Container>(
+    img: &ImageBuffer<Luma<u8>, Container>,
+    info: &ImageToTensorInfo,
+    output_buffer: &mut impl AsMut<[u8]>,
+) -> Result<(), Error>
+where
+    Container: std::ops::Deref<Target = [u8]>,
+{
+    debug_assert!(
+        img.width() == info.width()
+            && img.height() == info.height()
+            && info.color_space == ImageColorSpaceType::GRAYSCALE
+    );
+
+    let res = output_buffer.as_mut();
+    let bytes = img.as_bytes();
+    match info.tensor_type {
+        TensorType::F32 => {
+            // the identity normalization if the model has no normalization options
+            let mean = info.normalization_options.0.first().copied().unwrap_or(0.);
+            let std = info.normalization_options.1.first().copied().unwrap_or(1.);
+            debug_assert_eq!(res.len(), bytes.len() * std::mem::size_of::<f32>());
+            for (b, r) in bytes.iter().zip(res.chunks_exact_mut(4)) {
+                let f = ((*b as f32) - mean) / std;
+                r.copy_from_slice(&f.to_ne_bytes());
+            }
+            Ok(())
+        }
+        TensorType::U8 => {
+            debug_assert_eq!(res.len(), bytes.len());
+            res.copy_from_slice(bytes);
+            Ok(())
+        }
+        t => Err(Error::ModelInconsistentError(format!(
+            "Unsupported image tensor type `{:?}`",
+            t
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
     use super::*;
 
-    /// Rotate an image any radians clockwise.
-    /// angle is in radians
-    #[inline]
-    pub fn rotate_any<I: GenericImageView>(
-        image: &I,
-        angle: f32,
-    ) -> ImageBuffer<I::Pixel, Vec<<I::Pixel as Pixel>::Subpixel>>
-    where
-        I::Pixel: 'static,
-    {
-        let (width, height) = image.dimensions();
-
-        let cos = angle.cos();
-        let sin = angle.sin();
-        let new_width = ((cos * width as f32).abs() + (sin * height as f32).abs()) as u32;
-        let new_height = ((sin * width as f32).abs() + (cos * height as f32).abs()) as u32;
-
-        let mut out = ImageBuffer::new(new_width, new_height);
-        rotate_any_in(image, &mut out, angle);
-        out
+    fn gray_info(tensor_type: TensorType, layout: ImageDataLayout) -> ImageToTensorInfo {
+        ImageToTensorInfo {
+            image_data_layout: layout,
+            color_space: ImageColorSpaceType::GRAYSCALE,
+            tensor_type,
+            tensor_shape: ImageLikeTensorShape {
+                batch: 1,
+                width: 2,
+                height: 2,
+                channels: 1,
+            },
+            stats_min: vec![],
+            stats_max: vec![],
+            normalization_options: (vec![127.5], vec![127.5]),
+        }
     }
 
-    #[inline]
-    fn rotate_any_in<I, Container>(
-        image: &I,
-        destination: &mut ImageBuffer<I::Pixel, Container>,
-        angle: f32,
-    ) where
-        I: GenericImageView,
-        I::Pixel: 'static,
-        Container: std::ops::DerefMut<Target = [<I::Pixel as Pixel>::Subpixel]>,
-    {
-        let (dst_w, dst_h) = destination.dimensions();
-        let (src_w, src_h) = image.dimensions();
-
-        //  x_old = cos(angle) * (x - dst_w / 2) + sin(angle) * (y - dst_h / 2) + src_w / 2
-        //  y_old = - sin(angle) * (x - dst_w / 2) + cos(angle) * (y - dst_h / 2) + src_h / 2
-
-        let cos = angle.cos();
-        let sin = angle.sin();
-        let src_w_div2 = src_w as f32 / 2.;
-        let src_h_div2 = src_h as f32 / 2.;
-        let dst_w_div2 = dst_w as f32 / 2.;
-        let dst_h_div2 = dst_h as f32 / 2.;
-        for x in 0..dst_w {
-            let add_x_old = cos * (x as f32 - dst_w_div2) + src_w_div2 + sin * (-dst_h_div2);
-            let add_y_old = -sin * (x as f32 - dst_w_div2) + src_h_div2 + cos * (-dst_h_div2);
-            for y in 0..dst_h {
-                let x_old = add_x_old + sin * (y as f32);
-                if x_old < 0. {
-                    continue;
-                }
-                let x_old = x_old as u32;
-                if x_old >= src_w {
-                    continue;
-                }
+    #[test]
+    fn test_gray_image_to_tensor() {
+        let options = ImageProcessingOptions::default();
+        let img = GrayImage::from_raw(2, 2, vec![0, 255, 255, 0]).unwrap();
 
-                let y_old = add_y_old + cos * (y as f32);
-                if y_old < 0. {
-                    continue;
-                }
-                let y_old = y_old as u32;
-                if y_old >= src_h {
-                    continue;
-                }
+        let info = gray_info(TensorType::U8, ImageDataLayout::NHWC);
+        let mut buf = vec![0u8; 4];
+        img.to_tensor(&info, &options, &mut buf).unwrap();
+        assert_eq!(buf, vec![0, 255, 255, 0]);
 
-                let pixel = image.get_pixel(x_old, y_old);
-                destination.put_pixel(x, y, pixel);
-            }
+        let info = gray_info(TensorType::F32, ImageDataLayout::NCHW);
+        let mut buf = vec![0u8; 16];
+        DynamicImage::ImageLuma8(img)
+            .to_tensor(&info, &options, &mut buf)
+            .unwrap();
+        let res: Vec<f32> = buf
+            .chunks_exact(4)
+            .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+            .collect();
+        assert_eq!(res, vec![-1., 1., 1., -1.]);
+
+        // no normalization options
+        let mut info = gray_info(TensorType::F32, ImageDataLayout::NHWC);
+        info.normalization_options = (vec![], vec![]);
+        let img = GrayImage::from_raw(2, 2, vec![0, 255, 255, 0]).unwrap();
+        img.to_tensor(&info, &options, &mut buf).unwrap();
+        let res: Vec<f32> = buf
+            .chunks_exact(4)
+            .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+            .collect();
+        assert_eq!(res, vec![0., 255., 255., 0.]);
+
+        // unsupported tensor type
+        let info = gray_info(TensorType::I32, ImageDataLayout::NHWC);
+        assert!(img.to_tensor(&info, &options, &mut buf).is_err());
+
+        // rgb image is converted to luma
+        let rgb = RgbImage::from_raw(2, 2, vec![255; 12]).unwrap();
+        let info = gray_info(TensorType::U8, ImageDataLayout::NHWC);
+        let mut buf = vec![0u8; 4];
+        rgb.to_tensor(&info, &options, &mut buf).unwrap();
+        assert_eq!(buf, vec![255; 4]);
+    }
+
+    fn rgb_info(tensor_type: TensorType, layout: ImageDataLayout) -> ImageToTensorInfo {
+        ImageToTensorInfo {
+            image_data_layout: layout,
+            color_space: ImageColorSpaceType::RGB,
+            tensor_type,
+            tensor_shape: ImageLikeTensorShape {
+                batch: 1,
+                width: 2,
+                height: 1,
+                channels: 3,
+            },
+            stats_min: vec![],
+            stats_max: vec![],
+            normalization_options: (vec![10., 20., 30.], vec![1., 2., 5.]),
         }
     }
+
+    fn f32_values(buf: &[u8]) -> Vec<f32> {
+        buf.chunks_exact(4)
+            .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_rgb_image_to_tensor() {
+        let options = ImageProcessingOptions::default();
+        let img = RgbImage::from_raw(2, 1, vec![11, 22, 35, 12, 24, 40]).unwrap();
+
+        // every channel uses its own mean and std
+        let info = rgb_info(TensorType::F32, ImageDataLayout::NHWC);
+        let mut buf = vec![0u8; 24];
+        img.to_tensor(&info, &options, &mut buf).unwrap();
+        assert_eq!(f32_values(&buf), vec![1., 1., 1., 2., 2., 2.]);
+
+        let info = rgb_info(TensorType::F32, ImageDataLayout::NCHW);
+        img.to_tensor(&info, &options, &mut buf).unwrap();
+        assert_eq!(f32_values(&buf), vec![1., 2., 1., 2., 1., 2.]);
+
+        // one mean and std for all channels
+        let mut info = rgb_info(TensorType::F32, ImageDataLayout::NCHW);
+        info.normalization_options = (vec![10.], vec![1.]);
+        img.to_tensor(&info, &options, &mut buf).unwrap();
+        assert_eq!(f32_values(&buf), vec![1., 2., 12., 14., 25., 30.]);
+
+        let info = rgb_info(TensorType::U8, ImageDataLayout::NCHW);
+        let mut buf = vec![0u8; 6];
+        img.to_tensor(&info, &options, &mut buf).unwrap();
+        assert_eq!(buf, vec![11, 12, 22, 24, 35, 40]);
+
+        let info = rgb_info(TensorType::U8, ImageDataLayout::NHWC);
+        img.to_tensor(&info, &options, &mut buf).unwrap();
+        assert_eq!(buf, vec![11, 22, 35, 12, 24, 40]);
+    }
+
+    #[test]
+    fn test_warp_image_to_tensor() {
+        #[rustfmt::skip]
+        let img = GrayImage::from_raw(4, 4, vec![
+            0, 0, 100, 100,
+            0, 0, 100, 100,
+            40, 40, 200, 200,
+            40, 40, 200, 200,
+        ])
+        .unwrap();
+        let info = gray_info(TensorType::U8, ImageDataLayout::NHWC);
+        let mut buf = vec![0u8; 4];
+
+        // downscale averages the covered area
+        img.to_tensor(&info, &Default::default(), &mut buf).unwrap();
+        assert_eq!(buf, vec![0, 100, 40, 200]);
+
+        // crop the right half and rotate 90 degrees clockwise
+        let options = ImageProcessingOptions::new()
+            .region_of_interest(0.5, 0., 1., 1.)
+            .unwrap()
+            .rotation_degrees(90)
+            .unwrap();
+        img.to_tensor(&info, &options, &mut buf).unwrap();
+        assert_eq!(buf, vec![200, 100, 200, 100]);
+
+        // flip horizontally, and rotate 45 degrees whose corners are padded with zeros
+        let options = ImageProcessingOptions::new().flip_horizontally(true);
+        img.to_tensor(&info, &options, &mut buf).unwrap();
+        assert_eq!(buf, vec![100, 0, 200, 40]);
+        let options = ImageProcessingOptions::new().rotation_degrees(45).unwrap();
+        let mut info = gray_info(TensorType::U8, ImageDataLayout::NHWC);
+        info.tensor_shape.width = 3;
+        info.tensor_shape.height = 3;
+        let mut buf = vec![0u8; 9];
+        GrayImage::from_raw(4, 4, vec![100; 16])
+            .unwrap()
+            .to_tensor(&info, &options, &mut buf)
+            .unwrap();
+        assert_eq!(buf[4], 100);
+        assert!([buf[0], buf[2], buf[6], buf[8]].iter().all(|c| *c < 100));
+    }
 }
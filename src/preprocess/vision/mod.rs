@@ -1,4 +1,7 @@
 mod image;
+mod raw_image;
+pub use raw_image::{RawImage, RawPixelFormat};
+mod warp;
 
 #[cfg(feature = "ffmpeg")]
 mod ffmpeg;
@@ -6,11 +9,11 @@ mod ffmpeg;
 pub use ffmpeg::FFMpegVideoData;
 
 use super::*;
-use crate::tasks::vision::ImageProcessingOptions;
+use crate::tasks::vision::{ImageProcessingOptions, ResizeMode};
 use crate::TensorType;
 
 /// Every type implement the [`ImageToTensor`] trait can be used as vision tasks input.
-/// Now the builtin impl: image crate images.
+/// Now the builtin impl: image crate images, [`RawImage`].
 pub trait ImageToTensor {
     /// convert image to tensors, save to output_buffers
     fn to_tensor<T: AsMut<[u8]>>(
@@ -71,6 +74,190 @@ pub struct ImageToTensorInfo {
     pub normalization_options: (Vec<f32>, Vec<f32>),
 }
 
+/// The transform from the processed image (after crop and rotation) to the model input tensor
+/// in normalized coordinates: ```tensor = image * scale + offset```.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct ResizeTransform {
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+}
+
+impl Default for ResizeTransform {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            scale_x: 1.,
+            scale_y: 1.,
+            offset_x: 0.,
+            offset_y: 0.,
+        }
+    }
+}
+
+impl ResizeTransform {
+    /// Create the transform to resize the image (`src_w` x `src_h`) to the tensor (`dst_w` x `dst_h`).
+    pub fn new(mode: ResizeMode, src_w: f32, src_h: f32, dst_w: f32, dst_h: f32) -> Self {
+        let scale = match mode {
+            ResizeMode::Stretch => return Self::default(),
+            ResizeMode::Letterbox => (dst_w / src_w).min(dst_h / src_h),
+            ResizeMode::CenterCrop => (dst_w / src_w).max(dst_h / src_h),
+        };
+        let scale_x = src_w * scale / dst_w;
+        let scale_y = src_h * scale / dst_h;
+        Self {
+            scale_x,
+            scale_y,
+            offset_x: (1. - scale_x) / 2.,
+            offset_y: (1. - scale_y) / 2.,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Project the normalized tensor coordinate back to the normalized image coordinate.
+    #[inline(always)]
+    pub fn to_image(self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x - self.offset_x) / self.scale_x,
+            (y - self.offset_y) / self.scale_y,
+        )
+    }
+}
+
+/// The transform from the model input tensor back to the original image.
+///
+/// The image is cropped by the region of interest, rotated, flipped horizontally, and then resized
+/// to the tensor by the resize mode.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct ImageTransform {
+    resize_transform: ResizeTransform,
+    flip_horizontally: bool,
+    img_w: f32,
+    img_h: f32,
+    /// the size of the rotated region, in pixels
+    rotated_w: f32,
+    rotated_h: f32,
+    /// the center of the region of interest, in pixels
+    center_x: f32,
+    center_y: f32,
+    cos: f32,
+    sin: f32,
+}
+
+impl ImageTransform {
+    pub fn new(
+        img_size: (u32, u32),
+        tensor_size: (u32, u32),
+        process_options: &ImageProcessingOptions,
+    ) -> Self {
+        let (img_w, img_h) = (img_size.0 as f32, img_size.1 as f32);
+        let (crop_x, crop_y, crop_w, crop_h) = match process_options.region_of_interest {
+            Some(ref roi) => (
+                roi.x_min * img_w,
+                roi.y_min * img_h,
+                roi.width * img_w,
+                roi.height * img_h,
+            ),
+            None => (0., 0., img_w, img_h),
+        };
+        // the rotated region contains the whole crop
+        let (sin, cos) = process_options.rotation.sin_cos();
+        let rotated_w = (cos * crop_w).abs() + (sin * crop_h).abs();
+        let rotated_h = (sin * crop_w).abs() + (cos * crop_h).abs();
+        Self {
+            resize_transform: ResizeTransform::new(
+                process_options.resize_mode,
+                rotated_w,
+                rotated_h,
+                tensor_size.0 as f32,
+                tensor_size.1 as f32,
+            ),
+            flip_horizontally: process_options.flip_horizontally,
+            img_w,
+            img_h,
+            rotated_w,
+            rotated_h,
+            center_x: crop_x + crop_w / 2.,
+            center_y: crop_y + crop_h / 2.,
+            cos,
+            sin,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_identity(&self) -> bool {
+        self.resize_transform.is_identity()
+            && !self.flip_horizontally
+            && self.sin == 0.
+            && self.cos == 1.
+            && self.rotated_w == self.img_w
+            && self.rotated_h == self.img_h
+    }
+
+    #[inline(always)]
+    pub fn resize_transform(&self) -> &ResizeTransform {
+        &self.resize_transform
+    }
+
+    /// The size of the rotated region, in pixels.
+    #[inline(always)]
+    pub fn rotated_size(&self) -> (f32, f32) {
+        (self.rotated_w, self.rotated_h)
+    }
+
+    /// Project the normalized tensor coordinate to the normalized coordinate of the rotated
+    /// and flipped region.
+    #[inline(always)]
+    pub fn tensor_to_region(&self, x: f32, y: f32) -> (f32, f32) {
+        let (x, y) = self.resize_transform.to_image(x, y);
+        if self.flip_horizontally {
+            (1. - x, y)
+        } else {
+            (x, y)
+        }
+    }
+
+    /// Project the normalized coordinate of the rotated and flipped region to the image pixel
+    /// coordinate.
+    #[inline(always)]
+    pub fn region_to_pixel(&self, x: f32, y: f32) -> (f32, f32) {
+        let dx = (x - 0.5) * self.rotated_w;
+        let dy = (y - 0.5) * self.rotated_h;
+        // inverse rotation to the crop coordinate
+        (
+            self.center_x + self.cos * dx + self.sin * dy,
+            self.center_y - self.sin * dx + self.cos * dy,
+        )
+    }
+
+    /// Project the normalized tensor coordinate back to the normalized image coordinate.
+    #[inline(always)]
+    pub fn to_image(self, x: f32, y: f32) -> (f32, f32) {
+        let (x, y) = self.tensor_to_region(x, y);
+        let (x, y) = self.region_to_pixel(x, y);
+        (x / self.img_w, y / self.img_h)
+    }
+
+    /// Project the normalized image coordinate to the normalized tensor coordinate.
+    #[inline(always)]
+    pub fn to_tensor(self, x: f32, y: f32) -> (f32, f32) {
+        let px = x * self.img_w - self.center_x;
+        let py = y * self.img_h - self.center_y;
+        let mut x = (self.cos * px - self.sin * py) / self.rotated_w + 0.5;
+        let y = (self.sin * px + self.cos * py) / self.rotated_h + 0.5;
+        if self.flip_horizontally {
+            x = 1. - x;
+        }
+        let t = &self.resize_transform;
+        (x * t.scale_x + t.offset_x, y * t.scale_y + t.offset_y)
+    }
+}
+
 /// The tensor shape which contains image information.
 #[derive(Debug, Copy, Clone)]
 pub struct ImageLikeTensorShape {
@@ -0,0 +1,309 @@
+use super::warp::{self, WarpSource};
+use super::*;
+
+/// The pixel format of a [`RawImage`] buffer.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum RawPixelFormat {
+    /// Packed 8-bit ```R, G, B```.
+    RGB,
+    /// Packed 8-bit ```R, G, B, A```, the alpha channel is ignored.
+    RGBA,
+    /// Packed 8-bit ```B, G, R```.
+    BGR,
+    /// Packed 8-bit ```B, G, R, A```, the alpha channel is ignored.
+    BGRA,
+    /// 8-bit gray.
+    GRAY8,
+    /// Y plane followed by an interleaved ```U, V``` plane with 2x2 subsampling.
+    NV12,
+    /// Y plane followed by an interleaved ```V, U``` plane with 2x2 subsampling.
+    NV21,
+    /// Y plane followed by a U plane and a V plane with 2x2 subsampling.
+    I420,
+}
+
+impl RawPixelFormat {
+    /// Return the bytes of one pixel in the packed formats, or the bytes of one luma sample in
+    /// the YUV formats.
+    #[inline(always)]
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            RawPixelFormat::RGB | RawPixelFormat::BGR => 3,
+            RawPixelFormat::RGBA | RawPixelFormat::BGRA => 4,
+            RawPixelFormat::GRAY8
+            | RawPixelFormat::NV12
+            | RawPixelFormat::NV21
+            | RawPixelFormat::I420 => 1,
+        }
+    }
+}
+
+/// A borrowed raw pixel buffer, such as a camera frame, which can be used as vision tasks input
+/// without copying to an image.
+///
+/// The color conversion, crop, rotation and resize are done in one pass when writing the tensor.
+/// The YUV formats use the BT.601 limited range.
+#[derive(Debug, Clone)]
+pub struct RawImage<'a> {
+    data: &'a [u8],
+    format: RawPixelFormat,
+    width: u32,
+    height: u32,
+    /// bytes of a row, for the YUV formats, it is the stride of the Y plane.
+    stride: usize,
+    timestamp_ms: Option<u64>,
+}
+
+impl<'a> RawImage<'a> {
+    /// Create a new raw image whose rows have no padding.
+    #[inline(always)]
+    pub fn new(
+        data: &'a [u8],
+        format: RawPixelFormat,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Error> {
+        Self::new_with_stride(data, format, width, height, Self::min_stride(format, width))
+    }
+
+    /// The bytes of a row without padding. The NV12 and NV21 UV plane rows have
+    /// ```width.div_ceil(2)``` interleaved pairs, which may be longer than the Y plane rows.
+    #[inline(always)]
+    fn min_stride(format: RawPixelFormat, width: u32) -> usize {
+        match format {
+            RawPixelFormat::NV12 | RawPixelFormat::NV21 => (width as usize).div_ceil(2) * 2,
+            _ => width as usize * format.bytes_per_pixel(),
+        }
+    }
+
+    /// Create a new raw image with the row stride in bytes.
+    /// For the YUV formats, the stride is the Y plane stride. The NV12 and NV21 UV plane has the
+    /// same stride, so the stride must hold the UV pairs of a row, and the I420 U and V planes
+    /// have the half stride.
+    pub fn new_with_stride(
+        data: &'a [u8],
+        format: RawPixelFormat,
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Result<Self, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::ArgumentError(format!(
+                "Raw image size must be greater than `0`, but got `{}x{}`",
+                width, height
+            )));
+        }
+        let row_bytes = Self::min_stride(format, width);
+        if stride < row_bytes {
+            return Err(Error::ArgumentError(format!(
+                "Raw image stride must be at least `{}`, but got `{}`",
+                row_bytes, stride
+            )));
+        }
+        let h = height as usize;
+        let chroma_h = h.div_ceil(2);
+        let expect_len = match format {
+            RawPixelFormat::NV12 | RawPixelFormat::NV21 => stride * (h + chroma_h),
+            RawPixelFormat::I420 => stride * h + stride.div_ceil(2) * chroma_h * 2,
+            _ => stride * h,
+        };
+        if data.len() < expect_len {
+            return Err(Error::ArgumentError(format!(
+                "Raw image buffer must be at least `{}` bytes, but got `{}`",
+                expect_len,
+                data.len()
+            )));
+        }
+        Ok(Self {
+            data,
+            format,
+            width,
+            height,
+            stride,
+            timestamp_ms: None,
+        })
+    }
+
+    /// Set the timestamp (ms) of the frame, which is necessary for video and live stream mode.
+    #[inline(always)]
+    pub fn with_timestamp_ms(mut self, timestamp_ms: u64) -> Self {
+        self.timestamp_ms = Some(timestamp_ms);
+        self
+    }
+
+    /// Get the pixel format.
+    #[inline(always)]
+    pub fn format(&self) -> RawPixelFormat {
+        self.format
+    }
+
+    /// Get the row stride in bytes.
+    #[inline(always)]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+}
+
+impl<'a> WarpSource for RawImage<'a> {
+    #[inline(always)]
+    fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    #[inline(always)]
+    fn rgb_at(&self, x: usize, y: usize) -> [f32; 3] {
+        let bpp = self.format.bytes_per_pixel();
+        let i = y * self.stride + x * bpp;
+        let d = self.data;
+        match self.format {
+            RawPixelFormat::RGB | RawPixelFormat::RGBA => {
+                [d[i] as f32, d[i + 1] as f32, d[i + 2] as f32]
+            }
+            RawPixelFormat::BGR | RawPixelFormat::BGRA => {
+                [d[i + 2] as f32, d[i + 1] as f32, d[i] as f32]
+            }
+            RawPixelFormat::GRAY8 => [d[i] as f32; 3],
+            RawPixelFormat::NV12 | RawPixelFormat::NV21 => {
+                let uv = self.stride * self.height as usize + (y / 2) * self.stride + (x / 2) * 2;
+                let (u, v) = if self.format == RawPixelFormat::NV12 {
+                    (d[uv], d[uv + 1])
+                } else {
+                    (d[uv + 1], d[uv])
+                };
+                yuv_to_rgb(d[i], u, v)
+            }
+            RawPixelFormat::I420 => {
+                let chroma_stride = self.stride.div_ceil(2);
+                let chroma_size = chroma_stride * (self.height as usize).div_ceil(2);
+                let u = self.stride * self.height as usize + (y / 2) * chroma_stride + x / 2;
+                yuv_to_rgb(d[i], d[u], d[u + chroma_size])
+            }
+        }
+    }
+}
+
+impl<'a> ImageToTensor for RawImage<'a> {
+    fn to_tensor<T: AsMut<[u8]>>(
+        &self,
+        info: &ImageToTensorInfo,
+        process_options: &ImageProcessingOptions,
+        output_buffer: &mut T,
+    ) -> Result<(), Error> {
+        warp::warp_to_tensor(self, info, process_options, output_buffer)
+    }
+
+    /// return image size: (weight, height)
+    #[inline(always)]
+    fn image_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// return the current timestamp (ms)
+    #[inline(always)]
+    fn timestamp_ms(&self) -> Option<u64> {
+        self.timestamp_ms
+    }
+}
+
+/// BT.601 limited range YUV to RGB.
+#[inline(always)]
+fn yuv_to_rgb(y: u8, u: u8, v: u8) -> [f32; 3] {
+    let y = 1.164 * (y as f32 - 16.);
+    let u = u as f32 - 128.;
+    let v = v as f32 - 128.;
+    [
+        (y + 1.596 * v).clamp(0., 255.),
+        (y - 0.813 * v - 0.391 * u).clamp(0., 255.),
+        (y + 2.018 * u).clamp(0., 255.),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn info(color_space: ImageColorSpaceType, layout: ImageDataLayout) -> ImageToTensorInfo {
+        let channels = if color_space == ImageColorSpaceType::GRAYSCALE {
+            1
+        } else {
+            3
+        };
+        ImageToTensorInfo {
+            image_data_layout: layout,
+            color_space,
+            tensor_type: TensorType::U8,
+            tensor_shape: ImageLikeTensorShape {
+                batch: 1,
+                width: 2,
+                height: 2,
+                channels,
+            },
+            stats_min: vec![],
+            stats_max: vec![],
+            normalization_options: (vec![0.], vec![1.]),
+        }
+    }
+
+    #[test]
+    fn test_raw_image_formats() {
+        let options = ImageProcessingOptions::default();
+        let rgb_info = info(ImageColorSpaceType::RGB, ImageDataLayout::NHWC);
+        let mut buf = vec![0; 12];
+
+        // 2x2 BGRA with row padding
+        #[rustfmt::skip]
+        let bgra = [
+            3, 2, 1, 255, 6, 5, 4, 255, 0, 0,
+            9, 8, 7, 255, 12, 11, 10, 255, 0, 0,
+        ];
+        let img = RawImage::new_with_stride(&bgra, RawPixelFormat::BGRA, 2, 2, 10).unwrap();
+        img.to_tensor(&rgb_info, &options, &mut buf).unwrap();
+        assert_eq!(buf, (1..=12).collect::<Vec<u8>>());
+
+        // rotate 180 and NCHW
+        let nchw_info = info(ImageColorSpaceType::RGB, ImageDataLayout::NCHW);
+        let options_180 = ImageProcessingOptions::new().rotation_degrees(180).unwrap();
+        img.to_tensor(&nchw_info, &options_180, &mut buf).unwrap();
+        assert_eq!(buf, vec![10, 7, 4, 1, 11, 8, 5, 2, 12, 9, 6, 3]);
+
+        // gray NV12 and I420: Y = 16 + 219 / 255 * 128 ~ 126, UV = 128
+        let gray_info = info(ImageColorSpaceType::GRAYSCALE, ImageDataLayout::NHWC);
+        let mut buf = vec![0; 4];
+        let nv12 = [126, 126, 126, 126, 128, 128];
+        let img = RawImage::new(&nv12, RawPixelFormat::NV12, 2, 2).unwrap();
+        img.to_tensor(&gray_info, &options, &mut buf).unwrap();
+        assert_eq!(buf, vec![128; 4]);
+        let img = RawImage::new(&nv12, RawPixelFormat::I420, 2, 2).unwrap();
+        img.to_tensor(&gray_info, &options, &mut buf).unwrap();
+        assert_eq!(buf, vec![128; 4]);
+
+        // a 3x1 image is letterboxed to 3x3, the top and bottom are padded
+        let mut gray_info = info(ImageColorSpaceType::GRAYSCALE, ImageDataLayout::NHWC);
+        gray_info.tensor_shape.width = 3;
+        gray_info.tensor_shape.height = 3;
+        let mut buf = vec![0; 9];
+        let img = RawImage::new(&[200; 3], RawPixelFormat::GRAY8, 3, 1).unwrap();
+        let letterbox = ImageProcessingOptions::new().resize_mode(ResizeMode::Letterbox);
+        img.to_tensor(&gray_info, &letterbox, &mut buf).unwrap();
+        assert_eq!(buf, vec![0, 0, 0, 200, 200, 200, 0, 0, 0]);
+        let center_crop = ImageProcessingOptions::new().resize_mode(ResizeMode::CenterCrop);
+        img.to_tensor(&gray_info, &center_crop, &mut buf).unwrap();
+        assert_eq!(buf, vec![200; 9]);
+
+        // 3x2 NV12, the rows are padded to hold the 2 UV pairs, and the last column uses the
+        // second pair: R = 1.164 * (126 - 16) + 1.596 * (178 - 128)
+        let mut rgb_info = info(ImageColorSpaceType::RGB, ImageDataLayout::NHWC);
+        rgb_info.tensor_shape.width = 3;
+        let mut buf = vec![0; 18];
+        let nv12 = [126, 126, 126, 0, 126, 126, 126, 0, 128, 128, 128, 178];
+        assert!(RawImage::new_with_stride(&nv12, RawPixelFormat::NV12, 3, 2, 3).is_err());
+        let img = RawImage::new(&nv12, RawPixelFormat::NV12, 3, 2).unwrap();
+        assert_eq!(img.stride(), 4);
+        img.to_tensor(&rgb_info, &options, &mut buf).unwrap();
+        let row = [128, 128, 128, 128, 128, 128, 208, 87, 128];
+        assert_eq!(buf, [row, row].concat());
+
+        assert!(RawImage::new(&nv12[..5], RawPixelFormat::NV21, 2, 2).is_err());
+        assert!(RawImage::new_with_stride(&bgra, RawPixelFormat::RGBA, 2, 2, 6).is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use super::*;
+
+const FOOTPRINT_TOLERANCE: f32 = 1e-3;
+
+/// The source image of the affine warp, which can fetch pixels by the integer coordinate.
+pub(super) trait WarpSource {
+    /// return image size: (weight, height)
+    fn dimensions(&self) -> (u32, u32);
+
+    /// Get the RGB value of a pixel.
+    fn rgb_at(&self, x: usize, y: usize) -> [f32; 3];
+
+    /// Get the gray value of a pixel, which uses the same luma coefficients as the ```image``` crate.
+    #[inline(always)]
+    fn gray_at(&self, x: usize, y: usize) -> f32 {
+        let [r, g, b] = self.rgb_at(x, y);
+        r * 0.2126 + g * 0.7152 + b * 0.0722
+    }
+}
+
+/// Crop the region of interest, rotate, flip, resize and normalize the source image to the tensor
+/// in one pass, without intermediate images.
+///
+/// Each output element is mapped back to the source image by the affine transform.
+/// The pixels are sampled by bilinear interpolation, and averaged over the covered area when
+/// downscaling. The region out of the source image is filled with zero.
+pub(super) fn warp_to_tensor<S: WarpSource>(
+    src: &S,
+    info: &ImageToTensorInfo,
+    process_options: &ImageProcessingOptions,
+    output_buffer: &mut impl AsMut<[u8]>,
+) -> Result<(), Error> {
+    let res = output_buffer.as_mut();
+    match info.color_space {
+        ImageColorSpaceType::GRAYSCALE => warp_channels(
+            src.dimensions(),
+            |x, y| [src.gray_at(x, y)],
+            info,
+            process_options,
+            res,
+        ),
+        // we treat unknown as rgb8
+        ImageColorSpaceType::RGB | ImageColorSpaceType::UNKNOWN => warp_channels(
+            src.dimensions(),
+            |x, y| src.rgb_at(x, y),
+            info,
+            process_options,
+            res,
+        ),
+    }
+}
+
+/// The affine transform from the tensor pixel coordinate to the source image pixel coordinate.
+struct Affine {
+    out_w: f32,
+    out_h: f32,
+    transform: ImageTransform,
+}
+
+impl Affine {
+    /// The number of source pixels covered by one tensor pixel in each axis.
+    #[inline(always)]
+    fn footprint(&self) -> (f32, f32) {
+        let (rotated_w, rotated_h) = self.transform.rotated_size();
+        let resize_transform = self.transform.resize_transform();
+        (
+            rotated_w / (resize_transform.scale_x * self.out_w),
+            rotated_h / (resize_transform.scale_y * self.out_h),
+        )
+    }
+
+    /// Map the tensor coordinate to the source image coordinate.
+    /// Return ```None``` if it is in the letterbox padding.
+    #[inline(always)]
+    fn to_source(&self, u: f32, v: f32) -> Option<(f32, f32)> {
+        let (nx, ny) = self
+            .transform
+            .tensor_to_region(u / self.out_w, v / self.out_h);
+        if !(0. ..1.).contains(&nx) || !(0. ..1.).contains(&ny) {
+            return None;
+        }
+        Some(self.transform.region_to_pixel(nx, ny))
+    }
+}
+
+fn warp_channels<const C: usize>(
+    src_size: (u32, u32),
+    fetch: impl Fn(usize, usize) -> [f32; C],
+    info: &ImageToTensorInfo,
+    process_options: &ImageProcessingOptions,
+    res: &mut [u8],
+) -> Result<(), Error> {
+    let out_w = info.width() as usize;
+    let out_h = info.height() as usize;
+    let elem_bytes = match info.tensor_type {
+        TensorType::F32 => std::mem::size_of::<f32>(),
+        TensorType::U8 => 1,
+        t => {
+            return Err(Error::ModelInconsistentError(format!(
+                "Unsupported image tensor type `{:?}`",
+                t
+            )));
+        }
+    };
+    let expect_bytes = out_w * out_h * C * elem_bytes;
+    if res.len() < expect_bytes {
+        return Err(Error::ModelInconsistentError(format!(
+            "Expect input buffer at least `{}` bytes, but got `{}`",
+            expect_bytes,
+            res.len()
+        )));
+    }
+
+    let affine = Affine {
+        out_w: out_w as f32,
+        out_h: out_h as f32,
+        transform: ImageTransform::new(src_size, (out_w as u32, out_h as u32), process_options),
+    };
+    // sample a grid in each tensor pixel to average the covered area when downscaling,
+    // the tolerance avoids the rounding error of the rotation, e.g. ```sin(PI)```
+    let (footprint_x, footprint_y) = affine.footprint();
+    let taps_x = ((footprint_x - FOOTPRINT_TOLERANCE).ceil() as usize).max(1);
+    let taps_y = ((footprint_y - FOOTPRINT_TOLERANCE).ceil() as usize).max(1);
+    let taps_scale = 1. / (taps_x * taps_y) as f32;
+
+    let (mean, std) = &info.normalization_options;
+    let mean_std: [(f32, f32); C] = std::array::from_fn(|c| {
+        (
+            *mean.get(c).or(mean.first()).unwrap_or(&0.),
+            *std.get(c).or(std.first()).unwrap_or(&1.),
+        )
+    });
+    let is_nhwc = info.image_data_layout == ImageDataLayout::NHWC;
+    let plane = out_w * out_h;
+
+    for v in 0..out_h {
+        for u in 0..out_w {
+            let mut pixel = [0f32; C];
+            for ty in 0..taps_y {
+                let y = v as f32 + (ty as f32 + 0.5) / taps_y as f32;
+                for tx in 0..taps_x {
+                    let x = u as f32 + (tx as f32 + 0.5) / taps_x as f32;
+                    if let Some((sx, sy)) = affine.to_source(x, y) {
+                        let p = bilinear(&fetch, src_size, sx, sy);
+                        for (sum, value) in pixel.iter_mut().zip(p) {
+                            *sum += value;
+                        }
+                    }
+                }
+            }
+
+            let pixel_index = v * out_w + u;
+            for (c, (value, (m, s))) in pixel.iter().zip(mean_std.iter()).enumerate() {
+                let value = value * taps_scale;
+                let index = if is_nhwc {
+                    pixel_index * C + c
+                } else {
+                    c * plane + pixel_index
+                };
+                match info.tensor_type {
+                    TensorType::F32 => {
+                        let f = (value - m) / s;
+                        res[index * 4..index * 4 + 4].copy_from_slice(&f.to_ne_bytes());
+                    }
+                    _ => res[index] = value.round().clamp(0., 255.) as u8,
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Bilinear sample at the continuous coordinate, whose pixel centers are at ```.5```.
+/// Return zero if out of the image.
+#[inline(always)]
+fn bilinear<const C: usize>(
+    fetch: &impl Fn(usize, usize) -> [f32; C],
+    (width, height): (u32, u32),
+    x: f32,
+    y: f32,
+) -> [f32; C] {
+    if x < 0. || y < 0. || x >= width as f32 || y >= height as f32 {
+        return [0.; C];
+    }
+    let x = (x - 0.5).max(0.);
+    let y = (y - 0.5).max(0.);
+    let x0 = x as usize;
+    let y0 = y as usize;
+    let x1 = (x0 + 1).min(width as usize - 1);
+    let y1 = (y0 + 1).min(height as usize - 1);
+    let fx = x - x0 as f32;
+    let fy = y - y0 as f32;
+
+    let p00 = fetch(x0, y0);
+    let p01 = fetch(x1, y0);
+    let p10 = fetch(x0, y1);
+    let p11 = fetch(x1, y1);
+    let mut res = [0.; C];
+    for c in 0..C {
+        let top = p00[c] + (p01[c] - p00[c]) * fx;
+        let bottom = p10[c] + (p11[c] - p10[c]) * fx;
+        res[c] = top + (bottom - top) * fy;
+    }
+    res
+}
@@ -0,0 +1,58 @@
+use super::AudioEmbedder;
+use crate::tasks::common::{BaseTaskOptions, EmbeddingOptions};
+use crate::Error;
+
+/// Configure the build options of a new **Audio Embedding** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+pub struct AudioEmbedderBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+    pub(super) embedding_options: EmbeddingOptions,
+}
+
+impl Default for AudioEmbedderBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            embedding_options: Default::default(),
+        }
+    }
+}
+
+impl AudioEmbedderBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    base_task_options_impl!(AudioEmbedder);
+
+    embedding_options_impl!();
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    #[inline]
+    pub fn build_from_buffer(self, buffer: impl AsRef<[u8]>) -> Result<AudioEmbedder, Error> {
+        let buf = buffer.as_ref();
+        // parse model and get model resources.
+        let model_resource = crate::model::parse_model(buf)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 1, 1);
+        model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_audio()?;
+        let input_tensor_type =
+            model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
+
+        Ok(AudioEmbedder {
+            build_options: self,
+            model_resource,
+            graph,
+            input_tensor_type,
+        })
+    }
+}
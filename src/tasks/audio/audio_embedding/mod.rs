@@ -0,0 +1,119 @@
+mod builder;
+pub use builder::AudioEmbedderBuilder;
+
+use crate::model::ModelResourceTrait;
+use crate::postprocess::{AudioResultsIter, EmbeddingResult, TensorsToEmbedding};
+use crate::preprocess::audio::{AudioData, AudioDataToTensorIter, AudioToTensorInfo};
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+
+/// Performs embedding on audio.
+pub struct AudioEmbedder {
+    build_options: AudioEmbedderBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+    input_tensor_type: TensorType,
+}
+
+impl AudioEmbedder {
+    base_task_options_get_impl!();
+
+    embedding_options_get_impl!();
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<AudioEmbedderSession<'_>, Error> {
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model_resource, to_tensor_info, 0)
+                .try_to_audio()?;
+        let input_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, 0);
+        let output_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, output_tensor_shape, 0);
+
+        let mut tensors_to_embedding = TensorsToEmbedding::new(
+            self.build_options.embedding_options.quantize,
+            self.build_options.embedding_options.l2_normalize,
+        );
+        tensors_to_embedding.add_output_cfg(
+            get_type_and_quantization!(self.model_resource, 0),
+            output_tensor_shape,
+            None,
+        );
+
+        let execution_ctx = self.graph.init_execution_context()?;
+        Ok(AudioEmbedderSession {
+            embedder: self,
+            execution_ctx,
+            tensors_to_embedding,
+            input_to_tensor_info,
+            input_tensor_shape,
+            input_buffer: vec![0; tensor_bytes!(self.input_tensor_type, input_tensor_shape)],
+        })
+    }
+
+    /// Embed audio stream using a new session, and collect all results to [`Vec`]
+    #[inline(always)]
+    pub fn embed(&self, input_stream: impl AudioData) -> Result<Vec<EmbeddingResult>, Error> {
+        self.new_session()?.embed(input_stream)?.to_vec()
+    }
+}
+
+/// Session to run inference.
+/// If process multiple audio input, reuse it can get better performance.
+pub struct AudioEmbedderSession<'model> {
+    embedder: &'model AudioEmbedder,
+    execution_ctx: GraphExecutionContext<'model>,
+    tensors_to_embedding: TensorsToEmbedding,
+
+    // only one input and one output
+    input_to_tensor_info: &'model AudioToTensorInfo,
+    input_tensor_shape: &'model [usize],
+    input_buffer: Vec<u8>,
+}
+
+impl<'model> AudioEmbedderSession<'model> {
+    /// Embed audio stream use this session.
+    /// Return a iterator for results, process input stream when poll next result.
+    /// Every result has the timestamp of the audio window.
+    #[inline(always)]
+    pub fn embed<T>(&mut self, input_stream: T) -> Result<AudioResultsIter<'_, '_, Self, T>, Error>
+    where
+        T: AudioData,
+    {
+        let audio_data = AudioDataToTensorIter::new(self.input_to_tensor_info, input_stream)?;
+        Ok(AudioResultsIter::new(self, audio_data))
+    }
+}
+
+impl<'model> super::TaskSession for AudioEmbedderSession<'model> {
+    type Result = EmbeddingResult;
+
+    #[inline]
+    fn process_next<Source: AudioData>(
+        &mut self,
+        input_stream: &mut AudioDataToTensorIter<Source>,
+    ) -> Result<Option<Self::Result>, Error> {
+        if let Some(timestamp_ms) = input_stream.poll_next_tensors(&mut [&mut self.input_buffer])? {
+            self.execution_ctx.set_input(
+                0,
+                self.embedder.input_tensor_type,
+                self.input_tensor_shape,
+                self.input_buffer.as_slice(),
+            )?;
+            self.execution_ctx.compute()?;
+
+            let output_buffer = self.tensors_to_embedding.output_buffer(0);
+            let output_size = self.execution_ctx.get_output(0, output_buffer)?;
+            if output_size != output_buffer.len() {
+                return Err(Error::ModelInconsistentError(format!(
+                    "Model output bytes size is `{}`, but got `{}`",
+                    output_buffer.len(),
+                    output_size
+                )));
+            }
+
+            return Ok(Some(self.tensors_to_embedding.result(Some(timestamp_ms))));
+        }
+        Ok(None)
+    }
+}
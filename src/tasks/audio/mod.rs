@@ -1,6 +1,8 @@
 mod audio_classification;
+mod audio_embedding;
 
 pub use audio_classification::{AudioClassifier, AudioClassifierBuilder, AudioClassifierSession};
+pub use audio_embedding::{AudioEmbedder, AudioEmbedderBuilder, AudioEmbedderSession};
 
 /// Task session trait to process the audio stream data
 pub trait TaskSession {
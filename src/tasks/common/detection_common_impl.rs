@@ -11,6 +11,17 @@ macro_rules! detector_impl {
             self.new_session()?.detect(input)
         }
 
+        /// Detect one image using a new session with options to specify the rotation, flip and resize mode.
+        #[inline(always)]
+        pub fn detect_with_options(
+            &self,
+            input: &impl crate::preprocess::vision::ImageToTensor,
+            process_options: &crate::tasks::vision::ImageProcessingOptions,
+        ) -> Result<$Result, crate::Error> {
+            self.new_session()?
+                .detect_with_options(input, process_options)
+        }
+
         /// Detect input video stream in a new session, and collect all results to [`Vec`].
         #[inline(always)]
         pub fn detect_for_video(
@@ -23,18 +34,39 @@ macro_rules! detector_impl {
 }
 
 macro_rules! detector_session_impl {
-    ( $Result:ident ) => {
+    ( $SessionName:ident, $Result:ident ) => {
         /// Detect one image using this session.
         #[inline(always)]
         pub fn detect(
             &mut self,
             input: &impl crate::preprocess::vision::ImageToTensor,
         ) -> Result<$Result, crate::Error> {
+            self.detect_with_options(input, &Default::default())
+        }
+
+        /// Detect one image with the rotation, flip and resize mode options using this session.
+        /// The results are projected back to the original image.
+        /// The region of interest is not supported now.
+        pub fn detect_with_options(
+            &mut self,
+            input: &impl crate::preprocess::vision::ImageToTensor,
+            process_options: &crate::tasks::vision::ImageProcessingOptions,
+        ) -> Result<$Result, crate::Error> {
+            if process_options.region_of_interest.is_some() {
+                return Err(crate::Error::ArgumentError(format!(
+                    "{} does not support region of interest.",
+                    stringify!($SessionName)
+                )));
+            }
+
             input.to_tensor(
                 self.image_to_tensor_info,
-                &Default::default(),
+                process_options,
                 &mut self.input_buffer,
             )?;
+            self.tensors_to_detection.set_image_transform(
+                process_options.image_transform(input.image_size(), self.image_to_tensor_info),
+            );
             self.compute(input.timestamp_ms())
         }
 
@@ -52,8 +84,6 @@ macro_rules! detector_session_impl {
 
 macro_rules! detection_task_session_impl {
     ( $SessionName:ident, $Result:ident ) => {
-        use crate::preprocess::vision::ImageToTensor;
-
         impl<'model> super::TaskSession for $SessionName<'model> {
             type Result = $Result;
 
@@ -63,26 +93,102 @@ macro_rules! detection_task_session_impl {
                 process_options: &super::ImageProcessingOptions,
                 video_data: &mut impl crate::preprocess::vision::VideoData,
             ) -> Result<Option<Self::Result>, crate::Error> {
-                if process_options.region_of_interest.is_some() {
-                    return Err(crate::Error::ArgumentError(format!(
-                        "{} does not support region of interest.",
-                        stringify!($SessionName)
-                    )));
-                }
-
-                // todo: support rotation
-                assert_eq!(process_options.rotation, 0.);
-
                 if let Some(frame) = video_data.next_frame()? {
-                    frame.to_tensor(
-                        self.image_to_tensor_info,
-                        process_options,
-                        &mut self.input_buffer,
-                    )?;
-                    return Ok(Some(self.compute(frame.timestamp_ms())?));
+                    return Ok(Some(self.detect_with_options(&frame, process_options)?));
                 }
                 Ok(None)
             }
         }
     };
 }
+
+/// Get the anchors and the tensors decoding options for SSD detectors, such as face and hand detectors.
+///
+/// The anchors are generated by the user supplied anchors builder, or read from model metadata,
+/// or generated by the default anchors builder of the task, in order of priority.
+/// The input size of anchors builders is the model input size.
+/// If model metadata has no decoding options, they are derived from the model input and output shapes.
+pub(crate) fn ssd_detection_options(
+    model_resource: &dyn crate::model::ModelResourceTrait,
+    location_buf_index: usize,
+    score_buf_index: usize,
+    anchors_builder: Option<&crate::postprocess::SsdAnchorsBuilder>,
+    default_anchors_builder: impl FnOnce(u32, u32) -> crate::postprocess::SsdAnchorsBuilder,
+) -> Result<
+    (
+        Vec<crate::postprocess::Anchor>,
+        crate::postprocess::TensorsDecodingOptions,
+    ),
+    crate::Error,
+> {
+    let img_info =
+        model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
+    let width = img_info.width();
+    let height = img_info.height();
+    let location_shape =
+        model_resource_check_and_get_impl!(model_resource, output_tensor_shape, location_buf_index);
+    let score_shape =
+        model_resource_check_and_get_impl!(model_resource, output_tensor_shape, score_buf_index);
+    let metadata = model_resource.detector_metadata();
+
+    let decoding_options = match metadata.and_then(|m| m.tensors_decoding_options.as_ref()) {
+        Some(o) => o.clone(),
+        None => {
+            // location: [1, num_boxes, num_coords], score: [1, num_boxes, num_classes]
+            if location_shape.len() != 3 || score_shape.len() != 3 {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "Expect SSD output tensors with 3 dims, but got `{:?}` and `{:?}`",
+                    location_shape, score_shape
+                )));
+            }
+            let num_coords = location_shape[2];
+            crate::postprocess::TensorsDecodingOptions {
+                num_classes: score_shape[2],
+                num_boxes: location_shape[1],
+                num_coords,
+                keypoint_coord_offset: 4,
+                num_key_points: num_coords.saturating_sub(4) / 2,
+                num_values_per_key_point: 2,
+                x_scale: width as f32,
+                y_scale: height as f32,
+                w_scale: width as f32,
+                h_scale: height as f32,
+                apply_exponential_on_box_size: false,
+                sigmoid_score: true,
+            }
+        }
+    };
+    decoding_options.check()?;
+    let location_elements: usize = location_shape.iter().product();
+    let score_elements: usize = score_shape.iter().product();
+    if location_elements != decoding_options.num_boxes * decoding_options.num_coords
+        || score_elements != decoding_options.num_boxes * decoding_options.num_classes
+    {
+        return Err(crate::Error::ModelInconsistentError(format!(
+            "Output tensor shapes `{:?}` and `{:?}` do not match `{}` boxes with `{}` coords and `{}` classes",
+            location_shape,
+            score_shape,
+            decoding_options.num_boxes,
+            decoding_options.num_coords,
+            decoding_options.num_classes
+        )));
+    }
+
+    let anchors = match (anchors_builder, metadata) {
+        (Some(b), _) => b
+            .clone()
+            .input_size_width(width)
+            .input_size_height(height)
+            .generate(),
+        (None, Some(m)) if !m.anchors.is_empty() => m.anchors.clone(),
+        _ => default_anchors_builder(width, height).generate(),
+    };
+    if anchors.len() != decoding_options.num_boxes {
+        return Err(crate::Error::ModelInconsistentError(format!(
+            "The number of anchors `{}` does not match the number of boxes `{}`, please set the anchors options for this model",
+            anchors.len(),
+            decoding_options.num_boxes
+        )));
+    }
+    Ok((anchors, decoding_options))
+}
@@ -5,4 +5,6 @@ mod options;
 #[cfg(feature = "vision")]
 mod detection_common_impl;
 
+#[cfg(feature = "vision")]
+pub(crate) use detection_common_impl::ssd_detection_options;
 pub(crate) use options::*;
@@ -1,17 +1,40 @@
+#[derive(Clone)]
 pub(crate) struct BaseTaskOptions {
     /// The device to run the models.
     pub device: crate::Device,
+    /// The inference backend to load and run the models.
+    pub backend: std::sync::Arc<dyn crate::inference::InferenceBackend>,
 }
 
 impl Default for BaseTaskOptions {
-    /// Default target is CPU
+    /// Default target is CPU, default backend is WASI-NN (or native TfLite backend for non-wasm targets)
     fn default() -> Self {
         Self {
             device: crate::Device::CPU,
+            #[cfg(not(all(feature = "native-tflite", not(target_arch = "wasm32"))))]
+            backend: std::sync::Arc::new(crate::inference::WasiNNBackend),
+            #[cfg(all(feature = "native-tflite", not(target_arch = "wasm32")))]
+            backend: std::sync::Arc::new(crate::inference::TfLiteNativeBackend),
         }
     }
 }
 
+impl BaseTaskOptions {
+    /// Use the backend and device to load the model graph in the model buffer.
+    #[inline]
+    pub(crate) fn build_graph(
+        &self,
+        model_resource: &dyn crate::model::ModelResourceTrait,
+        model_buffer: &[u8],
+    ) -> Result<crate::Graph, crate::Error> {
+        Ok(crate::Graph::new(self.backend.build_graph(
+            model_resource.model_backend(),
+            self.device,
+            &[model_resource.model_graph_buffer(model_buffer)],
+        )?))
+    }
+}
+
 macro_rules! base_task_options_impl {
     ( $TypeName:ident ) => {
         /// Set execution device.
@@ -42,6 +65,27 @@ macro_rules! base_task_options_impl {
             self
         }
 
+        /// Set the inference backend to run the models. (Default is [`crate::inference::WasiNNBackend`])
+        #[inline(always)]
+        pub fn backend(
+            mut self,
+            backend: impl crate::inference::InferenceBackend + 'static,
+        ) -> Self {
+            self.base_task_options.backend = std::sync::Arc::new(backend);
+            self
+        }
+
+        /// Set the base options, used by the subtasks.
+        #[allow(unused)]
+        #[inline(always)]
+        pub(crate) fn base_task_options(
+            mut self,
+            base_task_options: crate::tasks::common::BaseTaskOptions,
+        ) -> Self {
+            self.base_task_options = base_task_options;
+            self
+        }
+
         /// Use the current build options, read model from file to create a new task instance.
         #[inline(always)]
         pub fn build_from_file(
@@ -78,8 +78,8 @@ macro_rules! face_landmark_options_impl {
             mut self,
             output_facial_transformation_matrixes: bool,
         ) -> Self {
-            self.face_landmark_options.output_facial_transformation_matrixes =
-                output_facial_transformation_matrixes;
+            self.face_landmark_options
+                .output_facial_transformation_matrixes = output_facial_transformation_matrixes;
             self
         }
     };
@@ -0,0 +1,63 @@
+use super::LanguageDetector;
+use crate::tasks::common::{BaseTaskOptions, ClassificationOptions};
+use crate::Error;
+
+/// Configure the build options of a new **Language Detection** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+pub struct LanguageDetectorBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+    pub(super) classification_options: ClassificationOptions,
+}
+
+impl Default for LanguageDetectorBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            classification_options: Default::default(),
+        }
+    }
+}
+
+impl LanguageDetectorBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            classification_options: Default::default(),
+        }
+    }
+
+    base_task_options_impl!(LanguageDetector);
+
+    classification_options_impl!();
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    #[inline]
+    pub fn build_from_buffer(self, buffer: impl AsRef<[u8]>) -> Result<LanguageDetector, Error> {
+        classification_options_check!(self, classification_options);
+
+        let buf = buffer.as_ref();
+        // parse model and get model resources.
+        let model_resource = crate::model::parse_model(buf)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 1, 1);
+        model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_text()?;
+        let input_tensor_type =
+            model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
+
+        Ok(LanguageDetector {
+            build_options: self,
+            model_resource,
+            graph,
+            input_tensor_type,
+        })
+    }
+}
@@ -0,0 +1,132 @@
+mod builder;
+mod result;
+
+pub use builder::LanguageDetectorBuilder;
+pub use result::{LanguageDetectorResult, LanguagePrediction};
+
+use crate::model::ModelResourceTrait;
+use crate::postprocess::{CategoriesFilter, TensorsToClassification};
+use crate::preprocess::text::{TextToTensorInfo, TextToTensors};
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+
+/// Predicts the language of an input text.
+pub struct LanguageDetector {
+    build_options: LanguageDetectorBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+
+    input_tensor_type: TensorType,
+}
+
+impl LanguageDetector {
+    base_task_options_get_impl!();
+
+    classification_options_get_impl!();
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<LanguageDetectorSession<'_>, Error> {
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model_resource, to_tensor_info, 0)
+                .try_to_text()?;
+        let input_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, 0);
+        // the string tensor buffer is resized when the input is written.
+        let input_tensor_buf = if input_to_tensor_info.is_string_model() {
+            Vec::new()
+        } else {
+            vec![0; tensor_bytes!(self.input_tensor_type, input_tensor_shape)]
+        };
+
+        let output_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, output_tensor_shape, 0);
+
+        let execution_ctx = self.graph.init_execution_context()?;
+        let labels = self.model_resource.output_tensor_labels_locale(
+            0,
+            self.build_options
+                .classification_options
+                .display_names_locale
+                .as_ref(),
+        )?;
+
+        let categories_filter = CategoriesFilter::new(
+            &self.build_options.classification_options,
+            labels.0,
+            labels.1,
+        );
+        let mut tensors_to_classification = TensorsToClassification::new();
+        tensors_to_classification.add_classification_options(
+            categories_filter,
+            self.build_options.classification_options.max_results,
+            get_type_and_quantization!(self.model_resource, 0),
+            output_tensor_shape,
+        );
+
+        Ok(LanguageDetectorSession {
+            execution_ctx,
+            tensors_to_classification,
+            input_to_tensor_info,
+            input_tensor_shape,
+            input_tensor_bufs: [input_tensor_buf],
+            input_tensor_type: self.input_tensor_type,
+        })
+    }
+
+    /// Detect the language of the input using a new session.
+    #[inline(always)]
+    pub fn detect(&self, input: &impl TextToTensors) -> Result<LanguageDetectorResult, Error> {
+        self.new_session()?.detect(input)
+    }
+}
+
+/// Session to run inference.
+/// If process multiple text, reuse it can get better performance.
+pub struct LanguageDetectorSession<'a> {
+    execution_ctx: GraphExecutionContext<'a>,
+    tensors_to_classification: TensorsToClassification<'a>,
+
+    // only one input and one output
+    input_to_tensor_info: &'a TextToTensorInfo,
+    input_tensor_shape: &'a [usize],
+    input_tensor_bufs: [Vec<u8>; 1],
+    input_tensor_type: TensorType,
+}
+
+impl<'a> LanguageDetectorSession<'a> {
+    /// Detect the language of the input using this session.
+    pub fn detect(&mut self, input: &impl TextToTensors) -> Result<LanguageDetectorResult, Error> {
+        // the string tensor is a serialized `u8` buffer, and its shape is the buffer length
+        let string_tensor_shape;
+        let input_tensor_shape = if self.input_to_tensor_info.is_string_model() {
+            input.to_string_tensors(self.input_to_tensor_info, &mut self.input_tensor_bufs)?;
+            string_tensor_shape = [self.input_tensor_bufs[0].len()];
+            &string_tensor_shape
+        } else {
+            input.to_tensors(self.input_to_tensor_info, &mut self.input_tensor_bufs)?;
+            self.input_tensor_shape
+        };
+
+        self.execution_ctx.set_input(
+            0,
+            self.input_tensor_type,
+            input_tensor_shape,
+            self.input_tensor_bufs[0].as_slice(),
+        )?;
+        self.execution_ctx.compute()?;
+
+        let output_buffer = self.tensors_to_classification.output_buffer(0);
+        let output_size = self.execution_ctx.get_output(0, output_buffer)?;
+        if output_size != output_buffer.len() {
+            return Err(Error::ModelInconsistentError(format!(
+                "Model output bytes size is `{}`, but got `{}`",
+                output_buffer.len(),
+                output_size
+            )));
+        }
+
+        Ok(LanguageDetectorResult::from_classification_result(
+            self.tensors_to_classification.result(None),
+        ))
+    }
+}
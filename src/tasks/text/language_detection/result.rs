@@ -0,0 +1,60 @@
+use crate::postprocess::ClassificationResult;
+use std::fmt::{Display, Formatter};
+
+/// A language code and its probability.
+#[derive(Debug, Clone)]
+pub struct LanguagePrediction {
+    /// An i18n language / locale code, e.g. "en" for English, "uz" for Uzbek,
+    /// "ja-Latn" for Japanese (romaji).
+    pub language_code: String,
+    /// The probability of the language.
+    pub probability: f32,
+}
+
+/// The output result of LanguageDetector.
+#[derive(Debug, Clone)]
+pub struct LanguageDetectorResult {
+    /// The predicted languages, sorted by descending probability.
+    pub languages: Vec<LanguagePrediction>,
+}
+
+impl LanguageDetectorResult {
+    /// Convert the classification result to language predictions.
+    /// The category name in the model label file is the language code.
+    pub(super) fn from_classification_result(result: ClassificationResult) -> Self {
+        let languages = result
+            .classifications
+            .into_iter()
+            .next()
+            .map(|c| {
+                c.categories
+                    .into_iter()
+                    .filter_map(|category| {
+                        category
+                            .category_name
+                            .map(|language_code| LanguagePrediction {
+                                language_code,
+                                probability: category.score,
+                            })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { languages }
+    }
+}
+
+impl Display for LanguageDetectorResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "LanguageDetectorResult:")?;
+        if self.languages.is_empty() {
+            return writeln!(f, "  No Language");
+        }
+        for (i, l) in self.languages.iter().enumerate() {
+            writeln!(f, "  Language #{}:", i)?;
+            writeln!(f, "    Language code: \"{}\"", l.language_code)?;
+            writeln!(f, "    Probability:   {}", l.probability)?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,20 @@
+mod language_detection;
+mod question_answering;
 mod text_classification;
 mod text_embedding;
+mod token_classification;
 
+pub use language_detection::{LanguageDetector, LanguageDetectorBuilder, LanguageDetectorSession};
+pub use question_answering::{
+    TextQuestionAnswerer, TextQuestionAnswererBuilder, TextQuestionAnswererSession,
+};
 pub use text_classification::{TextClassifier, TextClassifierBuilder, TextClassifierSession};
 pub use text_embedding::{TextEmbedder, TextEmbedderBuilder, TextEmbedderSession};
+pub use token_classification::{TokenClassifier, TokenClassifierBuilder, TokenClassifierSession};
+
+/// Re-export some task results types
+pub mod results {
+    pub use super::language_detection::{LanguageDetectorResult, LanguagePrediction};
+    pub use super::question_answering::{QaAnswer, TextQuestionAnswererResult};
+    pub use super::token_classification::{Entity, TokenClassifierResult};
+}
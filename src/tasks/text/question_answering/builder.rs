@@ -0,0 +1,188 @@
+use super::TextQuestionAnswerer;
+use crate::preprocess::text::TextToTensorInfo;
+use crate::tasks::common::BaseTaskOptions;
+use crate::{Error, TensorType};
+
+/// Configure the build options of a new **Text Question Answering** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+///
+/// default options:
+/// * max_results: 5
+/// * max_answer_len: 32
+/// * doc_stride: 128
+pub struct TextQuestionAnswererBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+
+    /// The maximum number of answers to return.
+    /// Default is 5
+    pub(super) max_results: usize,
+    /// The maximum number of tokens in an answer.
+    /// Default is 32
+    pub(super) max_answer_len: usize,
+    /// The distance in tokens between the starts of two context windows,
+    /// used when the context is longer than the model input.
+    /// Default is 128
+    pub(super) doc_stride: usize,
+}
+
+impl Default for TextQuestionAnswererBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            max_results: 5,
+            max_answer_len: 32,
+            doc_stride: 128,
+        }
+    }
+}
+
+impl TextQuestionAnswererBuilder {
+    /// The input tensor names in model metadata: ids, segment ids and input mask.
+    const INPUT_TENSOR_NAMES: [&'static str; 3] = ["ids", "segment_ids", "mask"];
+    const START_LOGITS_TENSOR_NAME: &'static str = "start_logits";
+    const END_LOGITS_TENSOR_NAME: &'static str = "end_logits";
+
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    base_task_options_impl!(TextQuestionAnswerer);
+
+    /// Set the maximum number of answers to return.
+    /// Default is 5
+    #[inline(always)]
+    pub fn max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Set the maximum number of tokens in an answer.
+    /// Default is 32
+    #[inline(always)]
+    pub fn max_answer_len(mut self, max_answer_len: usize) -> Self {
+        self.max_answer_len = max_answer_len;
+        self
+    }
+
+    /// Set the distance in tokens between the starts of two context windows.
+    /// The context longer than the model input is split to overlapping windows.
+    /// Default is 128
+    #[inline(always)]
+    pub fn doc_stride(mut self, doc_stride: usize) -> Self {
+        self.doc_stride = doc_stride;
+        self
+    }
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    #[inline]
+    pub fn build_from_buffer(
+        self,
+        buffer: impl AsRef<[u8]>,
+    ) -> Result<TextQuestionAnswerer, Error> {
+        if self.max_results == 0 {
+            return Err(Error::ArgumentError(
+                "The number of max results must be greater than `0`".into(),
+            ));
+        }
+        if self.max_answer_len == 0 {
+            return Err(Error::ArgumentError(
+                "The max answer length must be greater than `0`".into(),
+            ));
+        }
+        if self.doc_stride == 0 {
+            return Err(Error::ArgumentError(
+                "The doc stride must be greater than `0`".into(),
+            ));
+        }
+
+        let buf = buffer.as_ref();
+        // parse model and get model resources.
+        let model_resource = crate::model::parse_model(buf)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 3, 2);
+        let to_tensor_info =
+            model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_text()?;
+        let max_seq_len = match to_tensor_info {
+            TextToTensorInfo::BertModel { max_seq_len, .. } => *max_seq_len as usize,
+            _ => {
+                return Err(Error::ModelInconsistentError(
+                    "Question answering model must be a bert model".into(),
+                ));
+            }
+        };
+        // [CLS] query [SEP] context [SEP]
+        if max_seq_len < 4 {
+            return Err(Error::ModelInconsistentError(format!(
+                "Model max seq length must be at least `4`, but got `{}`",
+                max_seq_len
+            )));
+        }
+        for i in 0..3 {
+            let t = model_resource_check_and_get_impl!(model_resource, input_tensor_type, i);
+            if t != TensorType::I32 {
+                return Err(Error::ModelInconsistentError(
+                    "All input tensors should be int32 type".into(),
+                ));
+            }
+        }
+
+        // find the input tensors by names, or use the default bert input order.
+        let mut input_indices = [0, 1, 2];
+        for (index, name) in input_indices
+            .iter_mut()
+            .zip(Self::INPUT_TENSOR_NAMES.iter())
+        {
+            if let Some(i) = (0..3).find(|i| model_resource.input_tensor_name(*i) == Some(*name)) {
+                *index = i;
+            }
+        }
+        if input_indices[0] == input_indices[1]
+            || input_indices[0] == input_indices[2]
+            || input_indices[1] == input_indices[2]
+        {
+            return Err(Error::ModelInconsistentError(
+                "Cannot find the input tensors for ids, segment ids and mask".into(),
+            ));
+        }
+
+        let mut output_indices = [0; 2];
+        for (index, name) in output_indices
+            .iter_mut()
+            .zip([Self::START_LOGITS_TENSOR_NAME, Self::END_LOGITS_TENSOR_NAME].iter())
+        {
+            *index = model_resource
+                .output_tensor_name_to_index(name)
+                .ok_or_else(|| {
+                    Error::ModelInconsistentError(format!(
+                        "Cannot find the output tensor `{}`",
+                        name
+                    ))
+                })?;
+            let t = model_resource_check_and_get_impl!(model_resource, output_tensor_type, *index);
+            if t != TensorType::F32 {
+                return Err(Error::ModelInconsistentError(format!(
+                    "Output tensor `{}` should be float32 type",
+                    name
+                )));
+            }
+        }
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
+
+        Ok(TextQuestionAnswerer {
+            build_options: self,
+            model_resource,
+            graph,
+            max_seq_len,
+            input_indices,
+            output_indices,
+        })
+    }
+}
@@ -0,0 +1,387 @@
+// These reference files are licensed under Apache 2.0, and originally developed by Google:
+// * https://github.com/google/mediapipe/blob/master/mediapipe/tasks/cc/text/text_question_answerer
+// * https://github.com/tensorflow/tflite-support/blob/master/tensorflow_lite_support/cc/task/text/bert_question_answerer.cc
+
+mod builder;
+mod result;
+
+pub use builder::TextQuestionAnswererBuilder;
+pub use result::{QaAnswer, TextQuestionAnswererResult};
+
+use crate::model::ModelResourceTrait;
+use crate::preprocess::text::{bert_tokenize, BertToken, TextToTensorInfo};
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+use std::collections::HashMap;
+
+/// Answers questions based on the given context text, using a BERT model.
+pub struct TextQuestionAnswerer {
+    build_options: TextQuestionAnswererBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+
+    max_seq_len: usize,
+    // model input indices of ids, segment ids and mask
+    input_indices: [usize; 3],
+    // model output indices of start logits and end logits
+    output_indices: [usize; 2],
+}
+
+impl TextQuestionAnswerer {
+    base_task_options_get_impl!();
+
+    /// Get the maximum number of answers to return.
+    #[inline(always)]
+    pub fn max_results(&self) -> usize {
+        self.build_options.max_results
+    }
+
+    /// Get the maximum number of tokens in an answer.
+    #[inline(always)]
+    pub fn max_answer_len(&self) -> usize {
+        self.build_options.max_answer_len
+    }
+
+    /// Get the distance in tokens between the starts of two context windows.
+    #[inline(always)]
+    pub fn doc_stride(&self) -> usize {
+        self.build_options.doc_stride
+    }
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<TextQuestionAnswererSession<'_>, Error> {
+        let (token_index_map, classifier_token_id, separator_token_id) =
+            match model_resource_check_and_get_impl!(self.model_resource, to_tensor_info, 0)
+                .try_to_text()?
+            {
+                TextToTensorInfo::BertModel {
+                    token_index_map,
+                    classifier_token_id,
+                    separator_token_id,
+                    ..
+                } => (token_index_map, *classifier_token_id, *separator_token_id),
+                _ => unreachable!("The model has been checked in builder"),
+            };
+        let mut input_tensor_shapes = Vec::with_capacity(3);
+        for i in self.input_indices {
+            input_tensor_shapes.push(model_resource_check_and_get_impl!(
+                self.model_resource,
+                input_tensor_shape,
+                i
+            ));
+        }
+
+        let execution_ctx = self.graph.init_execution_context()?;
+        Ok(TextQuestionAnswererSession {
+            question_answerer: self,
+            execution_ctx,
+            token_index_map,
+            classifier_token_id,
+            separator_token_id,
+            input_tensor_shapes,
+            input_ids: vec![0; self.max_seq_len],
+            segment_ids: vec![0; self.max_seq_len],
+            input_mask: vec![0; self.max_seq_len],
+            start_logits: vec![0.; self.max_seq_len],
+            end_logits: vec![0.; self.max_seq_len],
+            query_tokens: Vec::new(),
+            context_tokens: Vec::new(),
+        })
+    }
+
+    /// Answer the question based on the context using a new session.
+    #[inline(always)]
+    pub fn answer(
+        &self,
+        context: &str,
+        question: &str,
+    ) -> Result<TextQuestionAnswererResult, Error> {
+        self.new_session()?.answer(context, question)
+    }
+}
+
+/// Session to run inference.
+/// If process multiple questions, reuse it can get better performance.
+pub struct TextQuestionAnswererSession<'a> {
+    question_answerer: &'a TextQuestionAnswerer,
+    execution_ctx: GraphExecutionContext<'a>,
+
+    token_index_map: &'a HashMap<String, i32>,
+    classifier_token_id: i32,
+    separator_token_id: i32,
+
+    // ids, segment ids and mask
+    input_tensor_shapes: Vec<&'a [usize]>,
+    input_ids: Vec<i32>,
+    segment_ids: Vec<i32>,
+    input_mask: Vec<i32>,
+    start_logits: Vec<f32>,
+    end_logits: Vec<f32>,
+
+    query_tokens: Vec<BertToken>,
+    context_tokens: Vec<BertToken>,
+}
+
+impl<'a> TextQuestionAnswererSession<'a> {
+    /// The max number of question tokens, the rest tokens are truncated.
+    const MAX_QUERY_LEN: usize = 64;
+    /// The number of best start and end logits used to find answers in every context window.
+    const N_BEST_LOGITS: usize = 20;
+
+    /// Answer the question based on the context using this session.
+    ///
+    /// The context longer than the model input is split to overlapping windows with `doc_stride`,
+    /// and answers in all windows are ranked together.
+    pub fn answer(
+        &mut self,
+        context: &str,
+        question: &str,
+    ) -> Result<TextQuestionAnswererResult, Error> {
+        let max_seq_len = self.question_answerer.max_seq_len;
+        bert_tokenize(question, self.token_index_map, &mut self.query_tokens);
+        // keep at least one context token
+        self.query_tokens
+            .truncate(Self::MAX_QUERY_LEN.min(max_seq_len - 4));
+        bert_tokenize(context, self.token_index_map, &mut self.context_tokens);
+
+        // [CLS] query [SEP] context [SEP]
+        let context_offset = self.query_tokens.len() + 2;
+        let max_context_len = max_seq_len - context_offset - 1;
+        let stride = self
+            .question_answerer
+            .build_options
+            .doc_stride
+            .min(max_context_len);
+
+        let mut candidates = Vec::new();
+        let mut window_start = 0;
+        loop {
+            let window_end = (window_start + max_context_len).min(self.context_tokens.len());
+            self.fill_inputs(window_start, window_end);
+            self.compute()?;
+            collect_candidates(
+                &self.start_logits,
+                &self.end_logits,
+                context_offset,
+                &self.context_tokens[window_start..window_end],
+                self.question_answerer.build_options.max_answer_len,
+                Self::N_BEST_LOGITS,
+                &mut candidates,
+            );
+            if window_end >= self.context_tokens.len() {
+                break;
+            }
+            window_start += stride;
+        }
+
+        Ok(TextQuestionAnswererResult {
+            answers: rank_answers(
+                context,
+                candidates,
+                self.question_answerer.build_options.max_results,
+            ),
+        })
+    }
+
+    #[inline]
+    fn fill_inputs(&mut self, window_start: usize, window_end: usize) {
+        self.input_ids.fill(0);
+        self.segment_ids.fill(0);
+        self.input_mask.fill(0);
+
+        let mut index = 0;
+        self.input_ids[index] = self.classifier_token_id;
+        index += 1;
+        for t in self.query_tokens.iter() {
+            self.input_ids[index] = t.id;
+            index += 1;
+        }
+        self.input_ids[index] = self.separator_token_id;
+        index += 1;
+
+        let context_start = index;
+        for t in self.context_tokens[window_start..window_end].iter() {
+            self.input_ids[index] = t.id;
+            index += 1;
+        }
+        self.input_ids[index] = self.separator_token_id;
+        index += 1;
+
+        self.segment_ids[context_start..index].fill(1);
+        self.input_mask[..index].fill(1);
+    }
+
+    #[inline]
+    fn compute(&mut self) -> Result<(), Error> {
+        let [ids_index, segment_ids_index, mask_index] = self.question_answerer.input_indices;
+        self.execution_ctx.set_input(
+            ids_index,
+            TensorType::I32,
+            self.input_tensor_shapes[0],
+            &self.input_ids,
+        )?;
+        self.execution_ctx.set_input(
+            segment_ids_index,
+            TensorType::I32,
+            self.input_tensor_shapes[1],
+            &self.segment_ids,
+        )?;
+        self.execution_ctx.set_input(
+            mask_index,
+            TensorType::I32,
+            self.input_tensor_shapes[2],
+            &self.input_mask,
+        )?;
+        self.execution_ctx.compute()?;
+
+        let [start_logits_index, end_logits_index] = self.question_answerer.output_indices;
+        for (index, buffer) in [
+            (start_logits_index, &mut self.start_logits),
+            (end_logits_index, &mut self.end_logits),
+        ] {
+            let output_size = self
+                .execution_ctx
+                .get_output(index, buffer.as_mut_slice())?;
+            let expect_size = std::mem::size_of_val(buffer.as_slice());
+            if output_size != expect_size {
+                return Err(Error::ModelInconsistentError(format!(
+                    "Model output bytes size is `{}`, but got `{}`",
+                    expect_size, output_size
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collect the answer candidates ```(start_byte, end_byte, score)``` in one context window.
+/// `context_offset` is the position of the first context token in the model input.
+fn collect_candidates(
+    start_logits: &[f32],
+    end_logits: &[f32],
+    context_offset: usize,
+    window_tokens: &[BertToken],
+    max_answer_len: usize,
+    n_best: usize,
+    candidates: &mut Vec<(usize, usize, f32)>,
+) {
+    let context_range = context_offset..context_offset + window_tokens.len();
+    let top_indices = |logits: &[f32]| {
+        let mut indices: Vec<usize> = context_range.clone().collect();
+        indices.sort_unstable_by(|a, b| logits[*b].total_cmp(&logits[*a]));
+        indices.truncate(n_best);
+        indices
+    };
+    let start_indices = top_indices(start_logits);
+    let end_indices = top_indices(end_logits);
+
+    for &start in start_indices.iter() {
+        for &end in end_indices.iter() {
+            if end < start || end - start + 1 > max_answer_len {
+                continue;
+            }
+            candidates.push((
+                window_tokens[start - context_offset].start,
+                window_tokens[end - context_offset].end,
+                start_logits[start] + end_logits[end],
+            ));
+        }
+    }
+}
+
+/// Sort the candidates by scores, and remove the same spans from overlapping windows.
+fn rank_answers(
+    context: &str,
+    mut candidates: Vec<(usize, usize, f32)>,
+    max_results: usize,
+) -> Vec<QaAnswer> {
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+    let mut answers: Vec<QaAnswer> = Vec::with_capacity(max_results);
+    for (start, end, score) in candidates {
+        if answers.len() >= max_results {
+            break;
+        }
+        if answers.iter().any(|a| a.start == start && a.end == end) {
+            continue;
+        }
+        answers.push(QaAnswer {
+            text: context[start..end].to_string(),
+            start,
+            end,
+            score,
+        });
+    }
+    answers
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_answers() {
+        let context = "Rust is fast. Rust is safe.";
+        // tokens: rust is fast . rust is safe .
+        let spans = [
+            (0, 4),
+            (5, 7),
+            (8, 12),
+            (12, 13),
+            (14, 18),
+            (19, 21),
+            (22, 26),
+            (26, 27),
+        ];
+        let tokens: Vec<BertToken> = spans
+            .iter()
+            .map(|(start, end)| BertToken {
+                id: 0,
+                start: *start,
+                end: *end,
+                is_sub_word: false,
+            })
+            .collect();
+
+        // two overlapping windows, the model input is: [CLS] q [SEP] context [SEP]
+        let context_offset = 3;
+        let mut candidates = Vec::new();
+        let mut start_logits = vec![0.; 10];
+        let mut end_logits = vec![0.; 10];
+        // "fast" in the first window
+        start_logits[context_offset + 2] = 5.;
+        end_logits[context_offset + 2] = 5.;
+        collect_candidates(
+            &start_logits,
+            &end_logits,
+            context_offset,
+            &tokens[0..4],
+            2,
+            2,
+            &mut candidates,
+        );
+        // "fast" again and "safe" in the second window
+        let mut start_logits = vec![0.; 10];
+        let mut end_logits = vec![0.; 10];
+        start_logits[context_offset] = 4.;
+        end_logits[context_offset] = 4.;
+        start_logits[context_offset + 4] = 6.;
+        end_logits[context_offset + 4] = 6.;
+        collect_candidates(
+            &start_logits,
+            &end_logits,
+            context_offset,
+            &tokens[2..8],
+            2,
+            2,
+            &mut candidates,
+        );
+
+        let answers = rank_answers(context, candidates, 2);
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].text, "safe");
+        assert_eq!((answers[0].start, answers[0].end), (22, 26));
+        assert_eq!(answers[0].score, 12.);
+        assert_eq!(answers[1].text, "fast");
+        assert_eq!(answers[1].score, 10.);
+    }
+}
@@ -0,0 +1,37 @@
+use std::fmt::{Display, Formatter};
+
+/// An answer span in the context.
+#[derive(Debug, Clone)]
+pub struct QaAnswer {
+    /// The answer text, which is ```&context[start..end]```.
+    pub text: String,
+    /// The start byte offset of the answer in the context, always at a character boundary.
+    pub start: usize,
+    /// The end byte offset (exclusive) of the answer in the context, always at a character boundary.
+    pub end: usize,
+    /// The score of the answer, which is the sum of the start and end logits.
+    pub score: f32,
+}
+
+/// The output result of TextQuestionAnswerer.
+#[derive(Debug, Clone)]
+pub struct TextQuestionAnswererResult {
+    /// The answers, sorted by descending scores.
+    pub answers: Vec<QaAnswer>,
+}
+
+impl Display for TextQuestionAnswererResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "TextQuestionAnswererResult:")?;
+        if self.answers.is_empty() {
+            return writeln!(f, "  No Answer");
+        }
+        for (i, a) in self.answers.iter().enumerate() {
+            writeln!(f, "  Answer #{}:", i)?;
+            writeln!(f, "    Text:  \"{}\"", a.text)?;
+            writeln!(f, "    Span:  [{}, {})", a.start, a.end)?;
+            writeln!(f, "    Score: {}", a.score)?;
+        }
+        Ok(())
+    }
+}
@@ -64,11 +64,9 @@ impl TextClassifierBuilder {
             }
         }
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.device,
-        )
-        .build_from_bytes([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
 
         return Ok(TextClassifier {
             build_options: self,
@@ -1,5 +1,7 @@
 use super::TextEmbedder;
+use crate::preprocess::text::TextToTensorInfo;
 use crate::tasks::common::{BaseTaskOptions, EmbeddingOptions};
+use crate::TensorType;
 
 /// Configure the build options of a new **Text Embedding** task instance.
 ///
@@ -20,6 +22,11 @@ impl Default for TextEmbedderBuilder {
 }
 
 impl TextEmbedderBuilder {
+    const USE_QUERY_ENCODING_NAME: &'static str = "query_encoding";
+    const USE_RESPONSE_ENCODING_NAME: &'static str = "response_encoding";
+    /// The universal sentence encoder outputs the response encoding and the query encoding.
+    const USE_QUERY_ENCODING_INDEX: usize = 1;
+
     /// Create a new builder with default options.
     #[inline(always)]
     pub fn new() -> Self {
@@ -38,36 +45,70 @@ impl TextEmbedderBuilder {
         let model_resource = crate::model::parse_model(buf)?;
 
         // check model
-        model_base_check_impl!(model_resource, 1);
-        model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_text()?;
+        let to_tensor_info =
+            model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_text()?;
         let input_count = model_resource.input_tensor_count();
-        if input_count != 1 && input_count != 3 {
-            return Err(crate::Error::ModelInconsistentError(format!(
-                "Expect model input tensor count `1` or `3`, but got `{}`",
-                input_count
-            )));
-        }
+        let (input_tensor_type, output_index) = match to_tensor_info.use_model_input_indices() {
+            Some((query_text_index, ..)) => {
+                // use the query encoding if the model has both query and response encoders.
+                let (name, default_index) = if query_text_index.is_some() {
+                    (
+                        Self::USE_QUERY_ENCODING_NAME,
+                        Self::USE_QUERY_ENCODING_INDEX,
+                    )
+                } else {
+                    (Self::USE_RESPONSE_ENCODING_NAME, 0)
+                };
+                let output_count = model_resource.output_tensor_count();
+                let output_index = match model_resource.output_tensor_name_to_index(name) {
+                    Some(i) => i,
+                    None if output_count == 1 => 0,
+                    None if output_count == 2 => default_index,
+                    None => {
+                        return Err(crate::Error::ModelInconsistentError(format!(
+                            "Expect model output tensor count `1` or `2`, but got `{}`",
+                            output_count
+                        )));
+                    }
+                };
+                (TensorType::U8, output_index)
+            }
+            None if matches!(to_tensor_info, TextToTensorInfo::StringModel) => {
+                model_base_check_impl!(model_resource, 1, 1);
+                (TensorType::U8, 0)
+            }
+            None => {
+                model_base_check_impl!(model_resource, 1);
+                if input_count != 1 && input_count != 3 {
+                    return Err(crate::Error::ModelInconsistentError(format!(
+                        "Expect model input tensor count `1` or `3`, but got `{}`",
+                        input_count
+                    )));
+                }
+                (TensorType::I32, 0)
+            }
+        };
         for i in 0..input_count {
             let t = model_resource_check_and_get_impl!(model_resource, input_tensor_type, i);
-            if t != crate::TensorType::I32 {
-                // todo: string type support
-                return Err(crate::Error::ModelInconsistentError(
-                    "All input tensors should be int32 type".into(),
-                ));
+            if t != input_tensor_type {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "All input tensors should be `{:?}` type",
+                    input_tensor_type
+                )));
             }
         }
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.device,
-        )
-        .build_from_bytes([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
 
         return Ok(TextEmbedder {
             build_options: self,
             model_resource,
             graph,
             input_count,
+            input_tensor_type,
+            output_index,
         });
     }
 }
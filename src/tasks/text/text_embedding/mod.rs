@@ -12,6 +12,8 @@ pub struct TextEmbedder {
     model_resource: Box<dyn ModelResourceTrait>,
     graph: Graph,
     input_count: usize,
+    input_tensor_type: TensorType,
+    output_index: usize,
 }
 
 impl TextEmbedder {
@@ -30,18 +32,26 @@ impl TextEmbedder {
         for i in 0..self.input_count {
             let input_tensor_shape =
                 model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, i);
-            let bytes = input_tensor_shape.iter().fold(4, |sum, b| sum * *b);
+            // the string tensor buffers are resized when the input is written.
+            let bytes = if input_to_tensor_info.is_string_model() {
+                0
+            } else {
+                input_tensor_shape.iter().fold(4, |sum, b| sum * *b)
+            };
             input_tensor_shapes.push(input_tensor_shape);
             input_tensor_bufs.push(vec![0; bytes]);
         }
-        let output_tensor_shape =
-            model_resource_check_and_get_impl!(self.model_resource, output_tensor_shape, 0);
+        let output_tensor_shape = model_resource_check_and_get_impl!(
+            self.model_resource,
+            output_tensor_shape,
+            self.output_index
+        );
         let mut tensor_to_embedding = TensorsToEmbedding::new(
             self.build_options.embedding_options.quantize,
             self.build_options.embedding_options.l2_normalize,
         );
         tensor_to_embedding.add_output_cfg(
-            get_type_and_quantization!(self.model_resource, 0),
+            get_type_and_quantization!(self.model_resource, self.output_index),
             output_tensor_shape,
             None,
         );
@@ -49,6 +59,8 @@ impl TextEmbedder {
         let execution_ctx = self.graph.init_execution_context()?;
         Ok(TextEmbedderSession {
             execution_ctx,
+            input_tensor_type: self.input_tensor_type,
+            output_index: self.output_index,
             tensor_to_embedding,
             input_to_tensor_info,
             input_tensor_shapes,
@@ -72,27 +84,40 @@ pub struct TextEmbedderSession<'a> {
     input_to_tensor_info: &'a TextToTensorInfo,
     input_tensor_shapes: Vec<&'a [usize]>,
     input_tensor_bufs: Vec<Vec<u8>>,
+    input_tensor_type: TensorType,
+    output_index: usize,
 }
 
 impl<'a> TextEmbedderSession<'a> {
     /// Embed one text use this session.
     #[inline(always)]
     pub fn embed(&mut self, input: &impl TextToTensors) -> Result<EmbeddingResult, Error> {
-        input.to_tensors(self.input_to_tensor_info, &mut self.input_tensor_bufs)?;
+        let is_string_model = self.input_to_tensor_info.is_string_model();
+        if is_string_model {
+            input.to_string_tensors(self.input_to_tensor_info, &mut self.input_tensor_bufs)?;
+        } else {
+            input.to_tensors(self.input_to_tensor_info, &mut self.input_tensor_bufs)?;
+        }
 
-        let tensor_type = TensorType::I32;
         for index in 0..self.input_tensor_bufs.len() {
+            // the string tensor is a serialized `u8` buffer, and its shape is the buffer length
+            let string_tensor_shape = [self.input_tensor_bufs[index].len()];
             self.execution_ctx.set_input(
                 index,
-                tensor_type,
-                self.input_tensor_shapes[index],
+                self.input_tensor_type,
+                if is_string_model {
+                    &string_tensor_shape
+                } else {
+                    self.input_tensor_shapes[index]
+                },
                 self.input_tensor_bufs[index].as_slice(),
             )?;
         }
         self.execution_ctx.compute()?;
 
         let output_buffer = self.tensor_to_embedding.output_buffer(0);
-        self.execution_ctx.get_output(0, output_buffer)?;
+        self.execution_ctx
+            .get_output(self.output_index, output_buffer)?;
 
         Ok(self.tensor_to_embedding.result(None))
     }
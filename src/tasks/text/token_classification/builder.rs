@@ -0,0 +1,104 @@
+use super::TokenClassifier;
+use crate::model::MemoryTextFile;
+use crate::preprocess::text::TextToTensorInfo;
+use crate::tasks::common::BaseTaskOptions;
+use crate::{Error, TensorType};
+
+/// Configure the build options of a new **Token Classification** task instance,
+/// such as named entity recognition (NER).
+///
+/// Methods can be chained on it in order to configure it.
+pub struct TokenClassifierBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+}
+
+impl Default for TokenClassifierBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            base_task_options: Default::default(),
+        }
+    }
+}
+
+impl TokenClassifierBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    base_task_options_impl!(TokenClassifier);
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    #[inline]
+    pub fn build_from_buffer(self, buffer: impl AsRef<[u8]>) -> Result<TokenClassifier, Error> {
+        let buf = buffer.as_ref();
+        // parse model and get model resources.
+        let model_resource = crate::model::parse_model(buf)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 3, 1);
+        let max_seq_len =
+            match model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0)
+                .try_to_text()?
+            {
+                TextToTensorInfo::BertModel { max_seq_len, .. } => *max_seq_len as usize,
+                _ => {
+                    return Err(Error::ModelInconsistentError(
+                        "Token classification model must be a bert model".into(),
+                    ));
+                }
+            };
+        for i in 0..3 {
+            let t = model_resource_check_and_get_impl!(model_resource, input_tensor_type, i);
+            if t != TensorType::I32 {
+                return Err(Error::ModelInconsistentError(
+                    "All input tensors should be int32 type".into(),
+                ));
+            }
+        }
+        if model_resource_check_and_get_impl!(model_resource, output_tensor_type, 0)
+            != TensorType::F32
+        {
+            return Err(Error::ModelInconsistentError(
+                "The output tensor should be float32 type".into(),
+            ));
+        }
+        // [batch, max_seq_len, num_labels]
+        let output_shape =
+            model_resource_check_and_get_impl!(model_resource, output_tensor_shape, 0);
+        if output_shape.len() != 3 || output_shape[0] != 1 || output_shape[1] != max_seq_len {
+            return Err(Error::ModelInconsistentError(format!(
+                "Expect output tensor shape `[1, {}, num_labels]`, but got `{:?}`",
+                max_seq_len, output_shape
+            )));
+        }
+        let num_labels = output_shape[2];
+        let mut label_file =
+            MemoryTextFile::new(model_resource.output_tensor_labels_locale(0, "")?.0);
+        let mut labels = Vec::with_capacity(num_labels);
+        while let Some(l) = label_file.next_line() {
+            labels.push(l.trim_end().to_string());
+        }
+        if labels.len() != num_labels {
+            return Err(Error::ModelInconsistentError(format!(
+                "Expect `{}` labels in label file, but got `{}`",
+                num_labels,
+                labels.len()
+            )));
+        }
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
+
+        Ok(TokenClassifier {
+            build_options: self,
+            model_resource,
+            graph,
+            max_seq_len,
+            labels,
+        })
+    }
+}
@@ -0,0 +1,182 @@
+use super::Entity;
+use crate::Error;
+
+/// A word and the prediction of its first word piece token.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct WordPrediction {
+    /// The byte range of the word in the text.
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) label_index: usize,
+    pub(super) score: f32,
+}
+
+/// The tag of a label in the BIO or BIOES scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tag<'a> {
+    Outside,
+    Begin(&'a str),
+    Inside(&'a str),
+    End(&'a str),
+    Single(&'a str),
+}
+
+impl<'a> Tag<'a> {
+    /// Parse labels such as `O`, `B-PER`, `I-PER`, `E-PER` and `S-PER`.
+    /// `L-` and `U-` of the BILOU scheme are the same as `E-` and `S-`.
+    /// A label without prefix is treated as inside an entity.
+    fn parse(label: &'a str) -> Self {
+        if label == "O" {
+            return Tag::Outside;
+        }
+        let bytes = label.as_bytes();
+        if bytes.len() > 2 && (bytes[1] == b'-' || bytes[1] == b'_') {
+            let entity_type = &label[2..];
+            match bytes[0] {
+                b'B' => return Tag::Begin(entity_type),
+                b'I' => return Tag::Inside(entity_type),
+                b'E' | b'L' => return Tag::End(entity_type),
+                b'S' | b'U' => return Tag::Single(entity_type),
+                _ => {}
+            }
+        }
+        Tag::Inside(label)
+    }
+}
+
+/// The entity under decoding: (entity type, start, end, sum of word scores, word count).
+type PendingEntity<'a> = (&'a str, usize, usize, f32, usize);
+
+/// Merge the word predictions to entity spans using BIO or BIOES tags.
+/// The invalid tag sequences are decoded leniently, e.g. an `I-` tag without `B-` starts a new entity.
+/// Return an error if the byte range of an entity is not at the char boundaries of the text.
+pub(super) fn decode_entities(
+    text: &str,
+    words: &[WordPrediction],
+    labels: &[impl AsRef<str>],
+) -> Result<Vec<Entity>, Error> {
+    let mut entities = Vec::new();
+    let mut pending: Option<PendingEntity> = None;
+
+    let finish =
+        |pending: &mut Option<PendingEntity>, entities: &mut Vec<Entity>| -> Result<(), Error> {
+            if let Some((label, start, end, score_sum, count)) = pending.take() {
+                let entity_text = text.get(start..end).ok_or_else(|| {
+                    Error::ArgumentError(format!(
+                        "Entity byte range `{}..{}` is not at the char boundaries of the text",
+                        start, end
+                    ))
+                })?;
+                entities.push(Entity {
+                    label: label.to_string(),
+                    score: score_sum / count as f32,
+                    text: entity_text.to_string(),
+                    start,
+                    end,
+                });
+            }
+            Ok(())
+        };
+
+    for w in words {
+        let tag = labels
+            .get(w.label_index)
+            .map(|l| Tag::parse(l.as_ref()))
+            .unwrap_or(Tag::Outside);
+        match tag {
+            Tag::Outside => finish(&mut pending, &mut entities)?,
+            Tag::Begin(t) => {
+                finish(&mut pending, &mut entities)?;
+                pending = Some((t, w.start, w.end, w.score, 1));
+            }
+            Tag::Inside(t) | Tag::End(t) => {
+                match pending.as_mut() {
+                    Some(p) if p.0 == t => {
+                        p.2 = w.end;
+                        p.3 += w.score;
+                        p.4 += 1;
+                    }
+                    _ => {
+                        finish(&mut pending, &mut entities)?;
+                        pending = Some((t, w.start, w.end, w.score, 1));
+                    }
+                }
+                if matches!(tag, Tag::End(_)) {
+                    finish(&mut pending, &mut entities)?;
+                }
+            }
+            Tag::Single(t) => {
+                finish(&mut pending, &mut entities)?;
+                pending = Some((t, w.start, w.end, w.score, 1));
+                finish(&mut pending, &mut entities)?;
+            }
+        }
+    }
+    finish(&mut pending, &mut entities)?;
+    Ok(entities)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(text: &str, tags: &[usize]) -> Vec<WordPrediction> {
+        let mut res = Vec::new();
+        let mut offset = 0;
+        for (w, label_index) in text.split(' ').zip(tags.iter()) {
+            res.push(WordPrediction {
+                start: offset,
+                end: offset + w.len(),
+                label_index: *label_index,
+                score: 0.5 + 0.1 * res.len() as f32,
+            });
+            offset += w.len() + 1;
+        }
+        res
+    }
+
+    #[test]
+    fn test_decode_bio() {
+        let labels = ["O", "B-PER", "I-PER", "B-LOC", "I-LOC"];
+        let text = "John Smith lives in New York City";
+        let entities =
+            decode_entities(text, &words(text, &[1, 2, 0, 0, 3, 4, 4]), &labels).unwrap();
+        assert_eq!(entities.len(), 2);
+        assert_eq!(entities[0].label, "PER");
+        assert_eq!(entities[0].text, "John Smith");
+        assert_eq!((entities[0].start, entities[0].end), (0, 10));
+        assert!((entities[0].score - 0.55).abs() < 1e-6);
+        assert_eq!(entities[1].label, "LOC");
+        assert_eq!(entities[1].text, "New York City");
+
+        // I- without B- starts a new entity, and a different type splits the entity
+        let entities =
+            decode_entities(text, &words(text, &[2, 4, 0, 0, 4, 4, 0]), &labels).unwrap();
+        let texts: Vec<&str> = entities.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["John", "Smith", "New York"]);
+    }
+
+    #[test]
+    fn test_decode_bioes() {
+        let labels = ["O", "B-PER", "I-PER", "E-PER", "S-LOC"];
+        let text = "Ada King Lovelace visited Paris London";
+        let entities = decode_entities(text, &words(text, &[1, 2, 3, 0, 4, 4]), &labels).unwrap();
+        let texts: Vec<&str> = entities.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["Ada King Lovelace", "Paris", "London"]);
+        assert_eq!(entities[1].label, "LOC");
+    }
+
+    #[test]
+    fn test_decode_non_char_boundary() {
+        let labels = ["O", "S-LOC"];
+        let text = "in Zürich";
+        let entities = decode_entities(text, &words(text, &[0, 1]), &labels).unwrap();
+        assert_eq!(entities[0].text, "Zürich");
+        assert_eq!((entities[0].start, entities[0].end), (3, 10));
+
+        // the byte range ends in the middle of `ü`
+        let mut words = words(text, &[0, 1]);
+        words[1].end = 5;
+        assert!(decode_entities(text, &words, &labels).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+mod builder;
+mod entity_decoder;
+mod result;
+
+pub use builder::TokenClassifierBuilder;
+pub use result::{Entity, TokenClassifierResult};
+
+use crate::model::ModelResourceTrait;
+use crate::preprocess::text::{to_bert_tensors_with_tokens, BertToken, TextToTensorInfo};
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+use entity_decoder::{decode_entities, WordPrediction};
+
+/// Performs token classification on text, such as named entity recognition (NER),
+/// using a BERT model.
+pub struct TokenClassifier {
+    build_options: TokenClassifierBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+
+    max_seq_len: usize,
+    labels: Vec<String>,
+}
+
+impl TokenClassifier {
+    base_task_options_get_impl!();
+
+    /// Get the labels of the model output, such as `O`, `B-PER` and `I-PER`.
+    #[inline(always)]
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<TokenClassifierSession<'_>, Error> {
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model_resource, to_tensor_info, 0)
+                .try_to_text()?;
+        let mut input_tensor_shapes = Vec::with_capacity(3);
+        let mut input_tensor_bufs = Vec::with_capacity(3);
+        for i in 0..3 {
+            let input_tensor_shape =
+                model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, i);
+            input_tensor_shapes.push(input_tensor_shape);
+            input_tensor_bufs.push(vec![0; tensor_bytes!(TensorType::I32, input_tensor_shape)]);
+        }
+
+        let execution_ctx = self.graph.init_execution_context()?;
+        Ok(TokenClassifierSession {
+            token_classifier: self,
+            execution_ctx,
+            input_to_tensor_info,
+            input_tensor_shapes,
+            input_tensor_bufs,
+            output_buffer: vec![0.; self.max_seq_len * self.labels.len()],
+            tokens: Vec::new(),
+            words: Vec::new(),
+        })
+    }
+
+    /// Classify the tokens of the text and return the entities using a new session.
+    #[inline(always)]
+    pub fn classify(&self, text: &str) -> Result<TokenClassifierResult, Error> {
+        self.new_session()?.classify(text)
+    }
+}
+
+/// Session to run inference.
+/// If process multiple text, reuse it can get better performance.
+pub struct TokenClassifierSession<'a> {
+    token_classifier: &'a TokenClassifier,
+    execution_ctx: GraphExecutionContext<'a>,
+
+    input_to_tensor_info: &'a TextToTensorInfo,
+    input_tensor_shapes: Vec<&'a [usize]>,
+    input_tensor_bufs: Vec<Vec<u8>>,
+    output_buffer: Vec<f32>,
+
+    tokens: Vec<BertToken>,
+    words: Vec<WordPrediction>,
+}
+
+impl<'a> TokenClassifierSession<'a> {
+    /// Classify the tokens of the text and return the entities using this session.
+    ///
+    /// Every word is labeled by its first word piece token, and the words are merged to entities
+    /// by the BIO or BIOES tags. The text longer than the model input is truncated.
+    pub fn classify(&mut self, text: &str) -> Result<TokenClassifierResult, Error> {
+        match self.input_to_tensor_info {
+            TextToTensorInfo::BertModel {
+                token_index_map,
+                max_seq_len,
+                classifier_token_id,
+                separator_token_id,
+            } => to_bert_tensors_with_tokens(
+                text,
+                token_index_map,
+                &mut self.input_tensor_bufs,
+                *max_seq_len,
+                *classifier_token_id,
+                *separator_token_id,
+                &mut self.tokens,
+            )?,
+            _ => unreachable!("The model has been checked in builder"),
+        }
+
+        for index in 0..self.input_tensor_bufs.len() {
+            self.execution_ctx.set_input(
+                index,
+                TensorType::I32,
+                self.input_tensor_shapes[index],
+                self.input_tensor_bufs[index].as_slice(),
+            )?;
+        }
+        self.execution_ctx.compute()?;
+
+        let output_size = self
+            .execution_ctx
+            .get_output(0, self.output_buffer.as_mut_slice())?;
+        let expect_size = std::mem::size_of_val(self.output_buffer.as_slice());
+        if output_size != expect_size {
+            return Err(Error::ModelInconsistentError(format!(
+                "Model output bytes size is `{}`, but got `{}`",
+                expect_size, output_size
+            )));
+        }
+
+        // the first token is [CLS]
+        let num_labels = self.token_classifier.labels.len();
+        self.words.clear();
+        for (token, logits) in self
+            .tokens
+            .iter()
+            .zip(self.output_buffer.chunks_exact(num_labels).skip(1))
+        {
+            if token.is_sub_word {
+                if let Some(w) = self.words.last_mut() {
+                    w.end = token.end;
+                    continue;
+                }
+            }
+            let (label_index, score) = softmax_max(logits);
+            self.words.push(WordPrediction {
+                start: token.start,
+                end: token.end,
+                label_index,
+                score,
+            });
+        }
+
+        Ok(TokenClassifierResult {
+            entities: decode_entities(text, &self.words, &self.token_classifier.labels)?,
+        })
+    }
+}
+
+/// Return the index of max logit and its softmax probability.
+#[inline]
+fn softmax_max(logits: &[f32]) -> (usize, f32) {
+    let mut index = 0;
+    for (i, l) in logits.iter().enumerate() {
+        if *l > logits[index] {
+            index = i;
+        }
+    }
+    let max = logits[index];
+    let sum: f32 = logits.iter().map(|l| (l - max).exp()).sum();
+    (index, 1. / sum)
+}
@@ -0,0 +1,40 @@
+use std::fmt::{Display, Formatter};
+
+/// A named entity in the input text.
+#[derive(Debug, Clone)]
+pub struct Entity {
+    /// The entity label without the BIO/BIOES prefix, e.g. "PER" for `B-PER` and `I-PER`.
+    pub label: String,
+    /// The average score of the words in the entity.
+    pub score: f32,
+    /// The entity text, which is ```&text[start..end]```.
+    pub text: String,
+    /// The start byte offset of the entity in the input text.
+    pub start: usize,
+    /// The end byte offset (exclusive) of the entity in the input text.
+    pub end: usize,
+}
+
+/// The output result of TokenClassifier.
+#[derive(Debug, Clone)]
+pub struct TokenClassifierResult {
+    /// The entities, in the order they appear in the input text.
+    pub entities: Vec<Entity>,
+}
+
+impl Display for TokenClassifierResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "TokenClassifierResult:")?;
+        if self.entities.is_empty() {
+            return writeln!(f, "  No Entity");
+        }
+        for (i, e) in self.entities.iter().enumerate() {
+            writeln!(f, "  Entity #{}:", i)?;
+            writeln!(f, "    Label: \"{}\"", e.label)?;
+            writeln!(f, "    Text:  \"{}\"", e.text)?;
+            writeln!(f, "    Span:  [{}, {})", e.start, e.end)?;
+            writeln!(f, "    Score: {}", e.score)?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use super::FaceDetector;
 use crate::postprocess::SsdAnchorsBuilder;
-use crate::tasks::common::BaseTaskOptions;
+use crate::tasks::common::{ssd_detection_options, BaseTaskOptions};
 
 /// Configure the build options of a new **Face Detection** task instance.
 ///
@@ -13,6 +13,8 @@ pub struct FaceDetectorBuilder {
     pub(super) min_detection_confidence: f32,
     /// The minimum non-maximum-suppression threshold for face detection to be considered overlapped.
     pub(super) min_suppression_threshold: f32,
+    /// The options to generate anchors, ```None``` to read anchors from model metadata.
+    pub(super) ssd_anchors: Option<SsdAnchorsBuilder>,
 }
 
 impl Default for FaceDetectorBuilder {
@@ -31,6 +33,7 @@ impl FaceDetectorBuilder {
             num_faces: -1,
             min_detection_confidence: 0.5,
             min_suppression_threshold: 0.3,
+            ssd_anchors: None,
         }
     }
 
@@ -60,6 +63,15 @@ impl FaceDetectorBuilder {
         self
     }
 
+    /// Set the options to generate SSD anchors, the input size is set to the model input size.
+    /// Default is ```None```, the anchors are read from model metadata,
+    /// or generated by the options of the MediaPipe face detection model if the metadata has no anchors.
+    #[inline(always)]
+    pub fn ssd_anchors(mut self, ssd_anchors: SsdAnchorsBuilder) -> Self {
+        self.ssd_anchors = Some(ssd_anchors);
+        self
+    }
+
     /// Use the current build options and use the buffer as model data to create a new task instance.
     #[inline]
     pub fn build_from_buffer(self, buffer: impl AsRef<[u8]>) -> Result<FaceDetector, crate::Error> {
@@ -75,28 +87,27 @@ impl FaceDetectorBuilder {
 
         // check model
         model_base_check_impl!(model_resource, 1, 2);
-        let img_info =
-            model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
-
-        // generate anchors
-        // todo: read info from metadata
-        let num_box = 896;
-        let width = img_info.width();
-        let height = img_info.height();
-        let anchors = SsdAnchorsBuilder::new(width, height, 0.1484375, 0.75, 4)
-            .interpolated_scale_aspect_ratio(1.0)
-            .anchor_offset_x(0.5)
-            .anchor_offset_y(0.5)
-            .strides(vec![8, 16, 16, 16])
-            .aspect_ratios(vec![1.0])
-            .fixed_anchor_size(true)
-            .generate();
+        let location_buf_index = 0;
+        let score_buf_index = 1;
+        let (anchors, decoding_options) = ssd_detection_options(
+            model_resource.as_ref(),
+            location_buf_index,
+            score_buf_index,
+            self.ssd_anchors.as_ref(),
+            |w, h| {
+                SsdAnchorsBuilder::new(w, h, 0.1484375, 0.75, 4)
+                    .interpolated_scale_aspect_ratio(1.0)
+                    .anchor_offset_x(0.5)
+                    .anchor_offset_y(0.5)
+                    .strides(vec![8, 16, 16, 16])
+                    .aspect_ratios(vec![1.0])
+                    .fixed_anchor_size(true)
+            },
+        )?;
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.device,
-        )
-        .build_from_bytes([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
 
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
@@ -106,9 +117,9 @@ impl FaceDetectorBuilder {
             model_resource,
             graph,
             anchors,
-            location_buf_index: 0,
-            score_buf_index: 1,
-            num_box,
+            location_buf_index,
+            score_buf_index,
+            decoding_options,
             input_tensor_type,
         });
     }
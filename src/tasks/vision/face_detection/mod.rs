@@ -4,7 +4,7 @@ pub use builder::FaceDetectorBuilder;
 use crate::model::ModelResourceTrait;
 use crate::postprocess::{
     Anchor, CategoriesFilter, DetectionBoxFormat, DetectionResult, NonMaxSuppressionAlgorithm,
-    NonMaxSuppressionOverlapType, TensorsToDetection,
+    NonMaxSuppressionOverlapType, TensorsDecodingOptions, TensorsToDetection,
 };
 use crate::preprocess::vision::ImageToTensorInfo;
 use crate::{Error, Graph, GraphExecutionContext, TensorType};
@@ -18,7 +18,7 @@ pub struct FaceDetector {
     anchors: Vec<Anchor>,
     location_buf_index: usize,
     score_buf_index: usize,
-    num_box: usize,
+    decoding_options: TensorsDecodingOptions,
 
     // only one input and one output
     input_tensor_type: TensorType,
@@ -70,17 +70,14 @@ impl FaceDetector {
         );
 
         // config options
-        tensors_to_detection.set_anchors_scales(128.0, 128.0, 128.0, 128.0);
-        tensors_to_detection.set_num_coords(16);
-        tensors_to_detection.set_key_points(6, 2, 4);
-        tensors_to_detection.set_sigmoid_score(true);
+        tensors_to_detection.set_decoding_options(&self.decoding_options);
         tensors_to_detection.set_score_clipping_thresh(100.);
         tensors_to_detection.set_box_format(DetectionBoxFormat::XYWH);
         tensors_to_detection.set_nms_min_suppression_threshold(self.min_suppression_threshold());
         tensors_to_detection
             .set_nms_overlap_type(NonMaxSuppressionOverlapType::IntersectionOverUnion);
         tensors_to_detection.set_nms_algorithm(NonMaxSuppressionAlgorithm::WEIGHTED);
-        tensors_to_detection.realloc(self.num_box);
+        tensors_to_detection.realloc(self.decoding_options.num_boxes);
 
         let execution_ctx = self.graph.init_execution_context()?;
         Ok(FaceDetectorSession {
@@ -128,10 +125,12 @@ impl<'model> FaceDetectorSession<'model> {
         )?;
 
         // generate result
-        Ok(self.tensors_to_detection.result(self.detector.num_box))
+        Ok(self
+            .tensors_to_detection
+            .result(self.detector.decoding_options.num_boxes))
     }
 
-    detector_session_impl!(DetectionResult);
+    detector_session_impl!(FaceDetectorSession, DetectionResult);
 }
 
 detection_task_session_impl!(FaceDetectorSession, DetectionResult);
@@ -0,0 +1,57 @@
+// These reference files are licensed under Apache 2.0, and originally developed by Google for Mediapipe:
+// https://github.com/google/mediapipe/raw/master/mediapipe/tasks/cc/vision/face_landmarker/face_blendshapes_graph.cc
+
+use super::FaceLandmarkBlendshapes;
+use crate::postprocess::{Category, Classifications, NormalizedLandmarks};
+
+/// The face landmarks used as the input of face blendshapes model.
+pub(super) const LANDMARKS_SUBSET_INDICES: [usize; 146] = [
+    0, 1, 4, 5, 6, 7, 8, 10, 13, 14, 17, 21, 33, 37, 39, 40, 46, 52, 53, 54, 55, 58, 61, 63, 65,
+    66, 67, 70, 78, 80, 81, 82, 84, 87, 88, 91, 93, 95, 103, 105, 107, 109, 127, 132, 133, 136,
+    144, 145, 146, 148, 149, 150, 152, 153, 154, 155, 157, 158, 159, 160, 161, 162, 163, 168, 172,
+    173, 176, 178, 181, 185, 191, 195, 197, 234, 246, 249, 251, 263, 267, 269, 270, 276, 282, 283,
+    284, 285, 288, 291, 293, 295, 296, 297, 300, 308, 310, 311, 312, 314, 317, 318, 321, 323, 324,
+    332, 334, 336, 338, 356, 361, 362, 365, 373, 374, 375, 377, 378, 379, 380, 381, 382, 384, 385,
+    386, 387, 388, 389, 390, 397, 398, 400, 402, 405, 409, 415, 454, 466, 468, 469, 470, 471, 472,
+    473, 474, 475, 476, 477,
+];
+
+/// The number of landmarks the face landmarks model must output to run face blendshapes model.
+pub(super) const MIN_NUM_LANDMARKS: usize = 478;
+
+/// Convert the landmarks subset to tensor, landmarks are scaled to image size, only x and y are used.
+pub(super) fn landmarks_to_tensor(
+    landmarks: &NormalizedLandmarks,
+    tensor_buffer: &mut impl AsMut<[f32]>,
+    img_size: (u32, u32),
+) {
+    let (w, h) = (img_size.0 as f32, img_size.1 as f32);
+    let buf = tensor_buffer.as_mut();
+    let mut index = 0;
+    for i in LANDMARKS_SUBSET_INDICES {
+        let l = &landmarks[i];
+        buf[index] = l.x * w;
+        buf[index + 1] = l.y * h;
+        index += 2;
+    }
+}
+
+/// Convert the face blendshapes model output to classifications, categories keep the model order.
+pub(super) fn tensor_to_classifications(tensor_buffer: &[f32]) -> Classifications {
+    let categories = tensor_buffer
+        .iter()
+        .zip(FaceLandmarkBlendshapes::NAMES)
+        .enumerate()
+        .map(|(i, (score, name))| Category {
+            index: i as u32,
+            score: *score,
+            category_name: Some(name.to_string()),
+            display_name: None,
+        })
+        .collect();
+    Classifications {
+        head_index: 0,
+        head_name: None,
+        categories,
+    }
+}
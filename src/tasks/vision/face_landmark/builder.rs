@@ -1,4 +1,8 @@
-use super::{FaceDetectorBuilder, FaceLandmarker, TensorType};
+use super::face_geometry::GeometryPipeline;
+use super::{
+    blendshapes_tensor, FaceDetector, FaceDetectorBuilder, FaceLandmarkBlendshapes, FaceLandmarker,
+    TensorType,
+};
 
 use crate::model::ZipFiles;
 use crate::tasks::common::{BaseTaskOptions, FaceLandmarkOptions};
@@ -38,6 +42,10 @@ impl FaceLandmarkerBuilder {
     pub const FACE_DETECTOR_CANDIDATE_NAMES: &'static [&'static str] = &["face_detector.tflite"];
     pub const FACE_LANDMARKS_CANDIDATE_NAMES: &'static [&'static str] =
         &["face_landmarks_detector.tflite"];
+    pub const FACE_BLENDSHAPES_CANDIDATE_NAMES: &'static [&'static str] =
+        &["face_blendshapes.tflite"];
+    pub const GEOMETRY_PIPELINE_METADATA_CANDIDATE_NAMES: &'static [&'static str] =
+        &["geometry_pipeline_metadata_landmarks.binarypb"];
 
     /// Use the current build options and use the buffer as model data to create a new task instance.
     #[inline]
@@ -51,23 +59,37 @@ impl FaceLandmarkerBuilder {
         let zip_file = ZipFiles::new(buf)?;
         let face_detection_file = search_file_in_zip!(
             zip_file,
-            buf,
             Self::FACE_DETECTOR_CANDIDATE_NAMES,
             "FaceDetection"
         );
+
+        let subtask = FaceDetectorBuilder::new()
+            .base_task_options(self.base_task_options.clone())
+            .num_faces(self.face_landmark_options.num_faces)
+            .min_detection_confidence(self.face_landmark_options.min_face_detection_confidence)
+            .build_from_buffer(face_detection_file)?;
+
+        self.build_with_face_detector(&zip_file, Some(subtask))
+    }
+
+    /// Create a new task instance from the face landmarks model and the optional blendshapes and
+    /// geometry files in the task bundle.
+    ///
+    /// The face detector is ```None``` if the face region-of-interest is got from other tasks, such as the
+    /// pose landmarks in holistic landmarker, then the faces are detected by [`FaceLandmarkerSession::detect_in_rect`].
+    ///
+    /// [`FaceLandmarkerSession::detect_in_rect`]: super::FaceLandmarkerSession::detect_in_rect
+    pub(in super::super) fn build_with_face_detector(
+        self,
+        zip_file: &ZipFiles,
+        face_detector: Option<FaceDetector>,
+    ) -> Result<FaceLandmarker, crate::Error> {
         let landmark_file = search_file_in_zip!(
             zip_file,
-            buf,
             Self::FACE_LANDMARKS_CANDIDATE_NAMES,
             "FaceLandmark"
         );
 
-        let subtask_face_detector = FaceDetectorBuilder::new()
-            .device(self.base_task_options.device)
-            .num_faces(self.face_landmark_options.num_faces)
-            .min_detection_confidence(self.face_landmark_options.min_face_detection_confidence)
-            .build_from_buffer(face_detection_file)?;
-
         // parse model and get model resources.
         let model_resource = crate::model::parse_model(landmark_file.as_ref())?;
 
@@ -88,19 +110,125 @@ impl FaceLandmarkerBuilder {
             TensorType::F32
         );
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.device,
-        )
-        .build_from_bytes([landmark_file])?;
+        // 468 is the standard number of facial landmarks used in MediaPipe's Face Mesh model (kMeshLandmarksNum).
+        // For models including the iris, this number increases to 478.
+        // Reference: https://github.com/google/mediapipe/blob/master/mediapipe/tasks/cc/vision/face_landmarker/face_landmarker_result.h
+        let landmarks_shape = model_resource_check_and_get_impl!(
+            model_resource,
+            output_tensor_shape,
+            landmarks_buf_index
+        );
+        let num_landmarks = if landmarks_shape.iter().product::<usize>() >= 478 * 3 {
+            478
+        } else {
+            468
+        };
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), landmark_file)?;
+
+        // face blendshapes model
+        let (blendshapes_model_resource, blendshapes_graph) = if self
+            .face_landmark_options
+            .output_face_blendshapes
+        {
+            if num_landmarks < blendshapes_tensor::MIN_NUM_LANDMARKS {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "Face blendshapes need `{}` face landmarks, but the face landmarks model only outputs `{}`",
+                    blendshapes_tensor::MIN_NUM_LANDMARKS,
+                    num_landmarks
+                )));
+            }
+            let blendshapes_file = search_file_in_zip!(
+                zip_file,
+                Self::FACE_BLENDSHAPES_CANDIDATE_NAMES,
+                "FaceBlendshapes"
+            );
+            let blendshapes_model_resource = crate::model::parse_model(blendshapes_file)?;
+            model_base_check_impl!(blendshapes_model_resource, 1, 1);
+            check_tensor_type!(
+                blendshapes_model_resource,
+                0,
+                input_tensor_type,
+                TensorType::F32
+            );
+            check_tensor_type!(
+                blendshapes_model_resource,
+                0,
+                output_tensor_type,
+                TensorType::F32
+            );
+            let input_size = model_resource_check_and_get_impl!(
+                blendshapes_model_resource,
+                input_tensor_shape,
+                0
+            )
+            .iter()
+            .product::<usize>();
+            if input_size != blendshapes_tensor::LANDMARKS_SUBSET_INDICES.len() * 2 {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "Expect face blendshapes model input tensor elements is `{}`, but got `{}`",
+                    blendshapes_tensor::LANDMARKS_SUBSET_INDICES.len() * 2,
+                    input_size
+                )));
+            }
+            let output_size = model_resource_check_and_get_impl!(
+                blendshapes_model_resource,
+                output_tensor_shape,
+                0
+            )
+            .iter()
+            .product::<usize>();
+            if output_size != FaceLandmarkBlendshapes::NAMES.len() {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "Expect face blendshapes model output tensor elements is `{}`, but got `{}`",
+                    FaceLandmarkBlendshapes::NAMES.len(),
+                    output_size
+                )));
+            }
+            let blendshapes_graph = self
+                .base_task_options
+                .build_graph(blendshapes_model_resource.as_ref(), blendshapes_file)?;
+            (Some(blendshapes_model_resource), Some(blendshapes_graph))
+        } else {
+            (None, None)
+        };
+
+        // face geometry pipeline
+        let geometry_pipeline = if self
+            .face_landmark_options
+            .output_facial_transformation_matrixes
+        {
+            let metadata_file = search_file_in_zip!(
+                zip_file,
+                Self::GEOMETRY_PIPELINE_METADATA_CANDIDATE_NAMES,
+                "FaceGeometry"
+            );
+            let pipeline = GeometryPipeline::new(metadata_file)?;
+            if num_landmarks < pipeline.num_landmarks() {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "Face geometry needs `{}` face landmarks, but the face landmarks model only outputs `{}`",
+                    pipeline.num_landmarks(),
+                    num_landmarks
+                )));
+            }
+            Some(pipeline)
+        } else {
+            None
+        };
 
         Ok(FaceLandmarker {
             build_options: self,
             model_resource,
             graph,
-            face_detector: subtask_face_detector,
+            face_detector,
+            blendshapes_model_resource,
+            blendshapes_graph,
+            geometry_pipeline,
             score_buf_index,
             landmarks_buf_index,
+            num_landmarks,
             input_tensor_type,
         })
     }
@@ -0,0 +1,393 @@
+// These reference files are licensed under Apache 2.0, and originally developed by Google for Mediapipe:
+// https://github.com/google/mediapipe/raw/master/mediapipe/tasks/cc/vision/face_geometry/libs/geometry_pipeline.cc
+// https://github.com/google/mediapipe/raw/master/mediapipe/tasks/cc/vision/face_geometry/proto/geometry_pipeline_metadata.proto
+// https://github.com/google/mediapipe/raw/master/mediapipe/tasks/cc/vision/face_geometry/proto/mesh_3d.proto
+
+use super::procrustes_solver::solve_weighted_orthogonal_problem;
+use crate::model::protobuf::ProtobufReader;
+use crate::postprocess::NormalizedLandmarks;
+use crate::Error;
+
+/// A 3D face mesh in the canonical face space, the unit is centimeter.
+#[derive(Debug, Clone)]
+pub struct FaceMesh {
+    /// Vertex positions ```(x, y, z)```.
+    pub vertices: Vec<[f32; 3]>,
+    /// Vertex texture coordinates ```(u, v)```.
+    pub texture_coords: Vec<[f32; 2]>,
+    /// Triangle list, every three indices form a triangle.
+    pub indices: Vec<u32>,
+}
+
+/// Estimate the face geometry from face landmarks, using the canonical face model from the
+/// geometry pipeline metadata.
+///
+/// The virtual perspective camera is the same as MediaPipe FaceLandmarker: the origin point is at
+/// the top left corner, vertical field of view is 63 degrees and near plane is 1 centimeter.
+pub(super) struct GeometryPipeline {
+    canonical_mesh: FaceMesh,
+    landmark_weights: Vec<f32>,
+}
+
+impl GeometryPipeline {
+    const VERTICAL_FOV_DEGREES: f32 = 63.;
+    const NEAR: f32 = 1.;
+
+    // GeometryPipelineMetadata
+    const METADATA_CANONICAL_MESH_FIELD: u32 = 1;
+    const METADATA_PROCRUSTES_LANDMARK_BASIS_FIELD: u32 = 2;
+    const METADATA_INPUT_SOURCE_FIELD: u32 = 3;
+    const INPUT_SOURCE_FACE_DETECTION_PIPELINE: u64 = 2;
+    // WeightedLandmarkRef
+    const LANDMARK_REF_ID_FIELD: u32 = 1;
+    const LANDMARK_REF_WEIGHT_FIELD: u32 = 2;
+    // Mesh3d
+    const MESH_VERTEX_TYPE_FIELD: u32 = 1;
+    const MESH_PRIMITIVE_TYPE_FIELD: u32 = 2;
+    const MESH_VERTEX_BUFFER_FIELD: u32 = 3;
+    const MESH_INDEX_BUFFER_FIELD: u32 = 4;
+    // VERTEX_PT: position (x, y, z) and texture coordinate (u, v)
+    const MESH_VERTEX_SIZE: usize = 5;
+
+    /// Parse the ```GeometryPipelineMetadata``` protobuf binary.
+    pub(super) fn new(metadata: &[u8]) -> Result<Self, Error> {
+        let mut canonical_mesh = None;
+        let mut landmark_basis = Vec::new();
+        let mut reader = ProtobufReader::new(metadata);
+        while let Some((field, value)) = reader.next_field()? {
+            match field {
+                Self::METADATA_CANONICAL_MESH_FIELD => {
+                    canonical_mesh = Some(Self::parse_mesh(value.as_bytes().unwrap_or_default())?)
+                }
+                Self::METADATA_PROCRUSTES_LANDMARK_BASIS_FIELD => {
+                    let mut landmark_ref =
+                        ProtobufReader::new(value.as_bytes().unwrap_or_default());
+                    let (mut id, mut weight) = (0, 0.);
+                    while let Some((field, value)) = landmark_ref.next_field()? {
+                        match field {
+                            Self::LANDMARK_REF_ID_FIELD => {
+                                id = value.as_varint().unwrap_or_default()
+                            }
+                            Self::LANDMARK_REF_WEIGHT_FIELD => {
+                                weight = value.as_f32().unwrap_or_default()
+                            }
+                            _ => {}
+                        }
+                    }
+                    landmark_basis.push((id as usize, weight));
+                }
+                Self::METADATA_INPUT_SOURCE_FIELD
+                    if value.as_varint() == Some(Self::INPUT_SOURCE_FACE_DETECTION_PIPELINE) =>
+                {
+                    return Err(Error::ModelParseError(
+                        "Face geometry from face detection is not supported".into(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        let canonical_mesh = canonical_mesh.ok_or(Error::ModelParseError(
+            "Geometry pipeline metadata has no canonical mesh".into(),
+        ))?;
+        if landmark_basis.is_empty() {
+            return Err(Error::ModelParseError(
+                "Geometry pipeline metadata has no procrustes landmark basis".into(),
+            ));
+        }
+        let mut landmark_weights = vec![0.; canonical_mesh.vertices.len()];
+        for (id, weight) in landmark_basis {
+            if id >= landmark_weights.len() || weight < 0. {
+                return Err(Error::ModelParseError(format!(
+                    "Invalid procrustes landmark basis: landmark id `{}`, weight `{}`",
+                    id, weight
+                )));
+            }
+            landmark_weights[id] = weight;
+        }
+
+        Ok(Self {
+            canonical_mesh,
+            landmark_weights,
+        })
+    }
+
+    fn parse_mesh(buf: &[u8]) -> Result<FaceMesh, Error> {
+        let mut vertex_buffer = Vec::new();
+        let mut index_buffer = Vec::new();
+        let mut reader = ProtobufReader::new(buf);
+        while let Some((field, value)) = reader.next_field()? {
+            match field {
+                // only VERTEX_PT and TRIANGLE are defined
+                Self::MESH_VERTEX_TYPE_FIELD | Self::MESH_PRIMITIVE_TYPE_FIELD
+                    if value.as_varint() != Some(0) =>
+                {
+                    return Err(Error::ModelParseError(format!(
+                        "Unsupported canonical mesh vertex or primitive type `{:?}`",
+                        value
+                    )));
+                }
+                Self::MESH_VERTEX_BUFFER_FIELD => value.read_repeated_f32(&mut vertex_buffer)?,
+                Self::MESH_INDEX_BUFFER_FIELD => value.read_repeated_varint(&mut index_buffer)?,
+                _ => {}
+            }
+        }
+
+        if vertex_buffer.is_empty()
+            || vertex_buffer.len() % Self::MESH_VERTEX_SIZE != 0
+            || index_buffer.len() % 3 != 0
+        {
+            return Err(Error::ModelParseError(format!(
+                "Invalid canonical mesh: vertex buffer size `{}`, index buffer size `{}`",
+                vertex_buffer.len(),
+                index_buffer.len()
+            )));
+        }
+        let num_vertices = vertex_buffer.len() / Self::MESH_VERTEX_SIZE;
+        let mut indices = Vec::with_capacity(index_buffer.len());
+        for i in index_buffer {
+            if i >= num_vertices as u64 {
+                return Err(Error::ModelParseError(format!(
+                    "Canonical mesh index `{}` is out of range `{}`",
+                    i, num_vertices
+                )));
+            }
+            indices.push(i as u32);
+        }
+
+        Ok(FaceMesh {
+            vertices: vertex_buffer
+                .chunks_exact(Self::MESH_VERTEX_SIZE)
+                .map(|v| [v[0], v[1], v[2]])
+                .collect(),
+            texture_coords: vertex_buffer
+                .chunks_exact(Self::MESH_VERTEX_SIZE)
+                .map(|v| [v[3], v[4]])
+                .collect(),
+            indices,
+        })
+    }
+
+    /// The number of face landmarks used by the pipeline.
+    #[inline(always)]
+    pub(super) fn num_landmarks(&self) -> usize {
+        self.canonical_mesh.vertices.len()
+    }
+
+    /// Estimate the facial transformation matrix (row-major) and the face mesh in canonical face space.
+    /// Return ```None``` if the face geometry cannot be estimated.
+    pub(super) fn estimate(
+        &self,
+        landmarks: &NormalizedLandmarks,
+        img_size: (u32, u32),
+    ) -> Option<([[f32; 4]; 4], FaceMesh)> {
+        // perspective camera frustum
+        let height_at_near =
+            2. * Self::NEAR * (0.5 * Self::VERTICAL_FOV_DEGREES.to_radians()).tan();
+        let width_at_near = img_size.0 as f32 * height_at_near / img_size.1 as f32;
+        let (left, bottom) = (-0.5 * width_at_near, -0.5 * height_at_near);
+
+        // project landmarks to screen space, the origin point is at the top left corner
+        let mut screen_landmarks: Vec<[f32; 3]> = landmarks
+            .iter()
+            .take(self.num_landmarks())
+            .map(|l| {
+                [
+                    l.x * width_at_near + left,
+                    (1. - l.y) * height_at_near + bottom,
+                    l.z * width_at_near,
+                ]
+            })
+            .collect();
+        let depth_offset = screen_landmarks[0][2];
+
+        // 1st iteration: don't unproject XY because it's unsafe to do so due to the relative
+        // nature of the Z coordinate. Instead, run the first estimation on the projected XY and
+        // use that scale to unproject for the 2nd iteration.
+        let mut intermediate_landmarks = screen_landmarks.clone();
+        Self::move_and_rescale_z(depth_offset, 1., &mut intermediate_landmarks);
+        let first_iteration_scale = self.estimate_scale(&intermediate_landmarks)?;
+
+        // 2nd iteration: unproject XY using the scale from the 1st iteration.
+        intermediate_landmarks.copy_from_slice(&screen_landmarks);
+        Self::move_and_rescale_z(
+            depth_offset,
+            first_iteration_scale,
+            &mut intermediate_landmarks,
+        );
+        Self::unproject_xy_and_change_handedness(&mut intermediate_landmarks);
+        let second_iteration_scale = self.estimate_scale(&intermediate_landmarks)?;
+
+        // use the total scale to unproject the screen landmarks to metric landmarks
+        let total_scale = first_iteration_scale * second_iteration_scale;
+        Self::move_and_rescale_z(depth_offset, total_scale, &mut screen_landmarks);
+        Self::unproject_xy_and_change_handedness(&mut screen_landmarks);
+        let mut metric_landmarks = screen_landmarks;
+
+        let pose_transform = solve_weighted_orthogonal_problem(
+            &self.canonical_mesh.vertices,
+            &metric_landmarks,
+            &self.landmark_weights,
+        )?;
+
+        // transform metric landmarks to the canonical face space using the inverse pose:
+        // the pose is a similarity transform [s * R | t], so the inverse of the 3x3 part is its
+        // transposed matrix divided by s^2.
+        let scale_square = (0..3).map(|i| pose_transform[i][0].powi(2)).sum::<f32>();
+        for p in metric_landmarks.iter_mut() {
+            let d = [
+                p[0] - pose_transform[0][3],
+                p[1] - pose_transform[1][3],
+                p[2] - pose_transform[2][3],
+            ];
+            for (k, v) in p.iter_mut().enumerate() {
+                *v = (pose_transform[0][k] * d[0]
+                    + pose_transform[1][k] * d[1]
+                    + pose_transform[2][k] * d[2])
+                    / scale_square;
+            }
+        }
+
+        Some((
+            pose_transform,
+            FaceMesh {
+                vertices: metric_landmarks,
+                texture_coords: self.canonical_mesh.texture_coords.clone(),
+                indices: self.canonical_mesh.indices.clone(),
+            },
+        ))
+    }
+
+    #[inline(always)]
+    fn estimate_scale(&self, landmarks: &[[f32; 3]]) -> Option<f32> {
+        let m = solve_weighted_orthogonal_problem(
+            &self.canonical_mesh.vertices,
+            landmarks,
+            &self.landmark_weights,
+        )?;
+        Some((m[0][0] * m[0][0] + m[1][0] * m[1][0] + m[2][0] * m[2][0]).sqrt())
+    }
+
+    #[inline(always)]
+    fn move_and_rescale_z(depth_offset: f32, scale: f32, landmarks: &mut [[f32; 3]]) {
+        for p in landmarks {
+            p[2] = (p[2] - depth_offset + Self::NEAR) / scale;
+        }
+    }
+
+    #[inline(always)]
+    fn unproject_xy_and_change_handedness(landmarks: &mut [[f32; 3]]) {
+        for p in landmarks {
+            p[0] = p[0] * p[2] / Self::NEAR;
+            p[1] = p[1] * p[2] / Self::NEAR;
+            p[2] = -p[2];
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::postprocess::{Landmark, Landmarks};
+
+    fn varint(mut v: u64, out: &mut Vec<u8>) {
+        while v >= 0x80 {
+            out.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        out.push(v as u8);
+    }
+
+    fn field_varint(field: u32, v: u64, out: &mut Vec<u8>) {
+        varint((field as u64) << 3, out);
+        varint(v, out);
+    }
+
+    fn field_f32(field: u32, v: f32, out: &mut Vec<u8>) {
+        varint(((field as u64) << 3) | 5, out);
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn field_bytes(field: u32, b: &[u8], out: &mut Vec<u8>) {
+        varint(((field as u64) << 3) | 2, out);
+        varint(b.len() as u64, out);
+        out.extend_from_slice(b);
+    }
+
+    const VERTICES: [[f32; 3]; 5] = [
+        [0., 0., 0.],
+        [3., 0., 0.],
+        [0., 4., 0.],
+        [0., 0., 2.],
+        [1., 1., 1.],
+    ];
+
+    fn metadata(index_buffer: &[u64]) -> Vec<u8> {
+        let mut mesh = Vec::new();
+        field_varint(1, 0, &mut mesh);
+        // packed vertex buffer
+        let mut vertex_buffer = Vec::new();
+        for v in VERTICES {
+            for f in [v[0], v[1], v[2], 0.5, 0.5] {
+                vertex_buffer.extend_from_slice(&f.to_le_bytes());
+            }
+        }
+        field_bytes(3, &vertex_buffer, &mut mesh);
+        // unpacked index buffer
+        for i in index_buffer {
+            field_varint(4, *i, &mut mesh);
+        }
+
+        let mut res = Vec::new();
+        field_bytes(1, &mesh, &mut res);
+        for id in 0..VERTICES.len() {
+            let mut landmark_ref = Vec::new();
+            field_varint(1, id as u64, &mut landmark_ref);
+            field_f32(2, 1., &mut landmark_ref);
+            field_bytes(2, &landmark_ref, &mut res);
+        }
+        field_varint(3, 1, &mut res);
+        res
+    }
+
+    #[test]
+    fn test_geometry_pipeline() {
+        assert!(GeometryPipeline::new(&metadata(&[0, 1, 5])).is_err());
+        assert!(GeometryPipeline::new(&metadata(&[0, 1])).is_err());
+        let pipeline = GeometryPipeline::new(&metadata(&[0, 1, 2, 1, 3, 4])).unwrap();
+        assert_eq!(pipeline.num_landmarks(), 5);
+
+        // put the canonical face at 40 centimeters in front of the camera, and project it.
+        let img_size = (640, 480);
+        let depth = 40.;
+        let height_at_near = 2. * (0.5 * GeometryPipeline::VERTICAL_FOV_DEGREES.to_radians()).tan();
+        let width_at_near = height_at_near * img_size.0 as f32 / img_size.1 as f32;
+        let landmarks = Landmarks(
+            VERTICES
+                .iter()
+                .map(|v| {
+                    let d = depth - v[2];
+                    Landmark {
+                        x: v[0] / d / width_at_near + 0.5,
+                        y: 0.5 - v[1] / d / height_at_near,
+                        z: (d - depth) / depth / width_at_near,
+                        visibility: None,
+                        presence: None,
+                        name: None,
+                    }
+                })
+                .collect(),
+        );
+
+        let (pose, mesh) = pipeline.estimate(&landmarks, img_size).unwrap();
+        assert_eq!(pose[3], [0., 0., 0., 1.]);
+        assert!((pose[2][3] + depth).abs() < 1., "{:?}", pose);
+        assert_eq!(mesh.indices, [0, 1, 2, 1, 3, 4]);
+        assert_eq!(mesh.texture_coords, [[0.5; 2]; 5]);
+        for (v, c) in mesh.vertices.iter().zip(VERTICES) {
+            for k in 0..3 {
+                assert!((v[k] - c[k]).abs() < 0.1, "{:?}", mesh.vertices);
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
+mod blendshapes_tensor;
 mod builder;
+mod face_geometry;
 mod face_landmark_blendshapes;
 mod face_landmark_connections;
+mod procrustes_solver;
 mod result;
 
-use super::{
-    FaceDetector, FaceDetectorBuilder, FaceDetectorSession
-};
+use super::{FaceDetector, FaceDetectorBuilder, FaceDetectorSession};
 pub use builder::FaceLandmarkerBuilder;
+pub use face_geometry::FaceMesh;
 pub use face_landmark_blendshapes::FaceLandmarkBlendshapes;
 pub use face_landmark_connections::FaceLandmarkConnections;
 pub use result::{FaceLandmarkResult, FaceLandmarkResults};
@@ -22,10 +24,16 @@ pub struct FaceLandmarker {
     model_resource: Box<dyn ModelResourceTrait>,
     graph: Graph,
 
-    face_detector: FaceDetector,
+    face_detector: Option<FaceDetector>,
+
+    blendshapes_model_resource: Option<Box<dyn ModelResourceTrait>>,
+    blendshapes_graph: Option<Graph>,
+
+    geometry_pipeline: Option<face_geometry::GeometryPipeline>,
 
     score_buf_index: usize,
     landmarks_buf_index: usize,
+    num_landmarks: usize,
 
     // only one input and one output
     input_tensor_type: TensorType,
@@ -41,7 +49,8 @@ impl FaceLandmarker {
     /// Get the subtask: face detector.
     #[inline(always)]
     pub fn subtask_face_detector(&self) -> &FaceDetector {
-        &self.face_detector
+        // the face landmarker built by `FaceLandmarkerBuilder` always has a face detector
+        self.face_detector.as_ref().unwrap()
     }
 
     /// Create a new task session that contains processing buffers and can do inference.
@@ -61,22 +70,40 @@ impl FaceLandmarker {
             self.landmarks_buf_index
         );
 
-        // 468 is the standard number of facial landmarks used in MediaPipe's Face Mesh model (kMeshLandmarksNum).
-        // For models including the iris, this number increases to 478.
-        // Reference: https://github.com/google/mediapipe/blob/master/mediapipe/tasks/cc/vision/face_landmarker/face_landmarker_result.h
         let mut tensors_to_landmarks =
-            TensorsToLandmarks::new(468, landmarks_out, landmarks_shape)?;
+            TensorsToLandmarks::new(self.num_landmarks, landmarks_out, landmarks_shape)?;
         tensors_to_landmarks
             .set_image_size(image_to_tensor_info.width(), image_to_tensor_info.height());
         tensors_to_landmarks.set_normalize_z(Self::LANDMARKS_NORMALIZE_Z);
 
-        let face_detector_session = self.face_detector.new_session()?;
+        let blendshapes_input_shape = if let Some(ref r) = self.blendshapes_model_resource {
+            Some(model_resource_check_and_get_impl!(r, input_tensor_shape, 0))
+        } else {
+            None
+        };
+        let blendshapes_execution_ctx = if let Some(ref g) = self.blendshapes_graph {
+            Some(g.init_execution_context()?)
+        } else {
+            None
+        };
+
+        let face_detector_session = match self.face_detector {
+            Some(ref d) => Some(d.new_session()?),
+            None => None,
+        };
         let execution_ctx = self.graph.init_execution_context()?;
 
         Ok(FaceLandmarkerSession {
             face_landmarker: self,
             execution_ctx,
             face_detector_session,
+            blendshapes_execution_ctx,
+            blendshapes_input_shape,
+            blendshapes_input_buffer: vec![
+                0.;
+                blendshapes_tensor::LANDMARKS_SUBSET_INDICES.len() * 2
+            ],
+            blendshapes_output_buffer: vec![0.; FaceLandmarkBlendshapes::NAMES.len()],
             image_to_tensor_info,
             input_tensor_shape,
             input_buffer: vec![0; tensor_bytes!(self.input_tensor_type, input_tensor_shape)],
@@ -92,7 +119,12 @@ pub struct FaceLandmarkerSession<'model> {
     face_landmarker: &'model FaceLandmarker,
     execution_ctx: GraphExecutionContext<'model>,
 
-    face_detector_session: FaceDetectorSession<'model>,
+    face_detector_session: Option<FaceDetectorSession<'model>>,
+
+    blendshapes_execution_ctx: Option<GraphExecutionContext<'model>>,
+    blendshapes_input_shape: Option<&'model [usize]>,
+    blendshapes_input_buffer: Vec<f32>,
+    blendshapes_output_buffer: Vec<f32>,
 
     image_to_tensor_info: &'model ImageToTensorInfo,
     input_tensor_shape: &'model [usize],
@@ -107,8 +139,25 @@ impl<'model> FaceLandmarkerSession<'model> {
     /// Detect one image using this task session.
     #[inline(always)]
     pub fn detect(&mut self, input: &impl ImageToTensor) -> Result<FaceLandmarkResults, Error> {
+        self.detect_with_options(input, &Default::default())
+    }
+
+    /// Detect one image with options using this task session.
+    /// The options are applied to the detection stage, e.g. the resize mode.
+    pub fn detect_with_options(
+        &mut self,
+        input: &impl ImageToTensor,
+        process_options: &super::ImageProcessingOptions,
+    ) -> Result<FaceLandmarkResults, Error> {
         let (img_w, img_h) = input.image_size();
-        let face_detection_result = self.face_detector_session.detect(input)?;
+        let face_detection_result = match self.face_detector_session {
+            Some(ref mut s) => s.detect_with_options(input, process_options)?,
+            None => {
+                return Err(Error::ArgumentError(
+                    "The face landmarker has no face detector, use `detect_in_rect` instead".into(),
+                ))
+            }
+        };
         let mut face_landmark_results = Vec::with_capacity(face_detection_result.detections.len());
 
         for d in face_detection_result.detections.iter() {
@@ -122,52 +171,96 @@ impl<'model> FaceLandmarkerSession<'model> {
             )
             .transform(img_w, img_h, 1.5, 1.5, 0.0, 0.0, None, false);
 
-            // image to tensor
-            input.to_tensor(
-                self.image_to_tensor_info,
-                &super::ImageProcessingOptions::from_normalized_rect(&face_rect),
-                &mut self.input_buffer,
-            )?;
+            if let Some(r) = self.detect_in_rect(input, &face_rect)? {
+                face_landmark_results.push(r);
+            }
+        }
 
-            // set input and compute
-            self.execution_ctx.set_input(
-                0,
-                self.face_landmarker.input_tensor_type,
-                self.input_tensor_shape,
-                self.input_buffer.as_slice(),
-            )?;
-            self.execution_ctx.compute()?;
+        Ok(FaceLandmarkResults(face_landmark_results))
+    }
 
-            // check face presence score
-            self.execution_ctx.get_output(
-                self.face_landmarker.score_buf_index,
-                &mut self.score_of_face_presence,
-            )?;
-            if self.score_of_face_presence[0] < self.face_landmarker.min_face_presence_confidence()
-            {
-                continue;
-            }
+    /// Detect the face landmarks in the region-of-interest of the image.
+    /// Return ```None``` if the face presence score is less than the threshold.
+    pub(crate) fn detect_in_rect(
+        &mut self,
+        input: &impl ImageToTensor,
+        face_rect: &NormalizedRect,
+    ) -> Result<Option<FaceLandmarkResult>, Error> {
+        let (img_w, img_h) = input.image_size();
 
-            // get landmarks
-            self.execution_ctx.get_output(
-                self.face_landmarker.landmarks_buf_index,
-                self.tensors_to_landmarks.landmark_buffer(),
-            )?;
-            let mut face_landmarks = self.tensors_to_landmarks.result(true);
+        // image to tensor
+        input.to_tensor(
+            self.image_to_tensor_info,
+            &super::ImageProcessingOptions::from_normalized_rect(face_rect),
+            &mut self.input_buffer,
+        )?;
 
-            // do projection
-            crate::postprocess::projection_normalized_landmarks(
-                &mut face_landmarks,
-                &face_rect,
-                false,
-            );
+        // set input and compute
+        self.execution_ctx.set_input(
+            0,
+            self.face_landmarker.input_tensor_type,
+            self.input_tensor_shape,
+            self.input_buffer.as_slice(),
+        )?;
+        self.execution_ctx.compute()?;
 
-            face_landmark_results.push(FaceLandmarkResult {
-                face_landmarks,
-            });
+        // check face presence score
+        self.execution_ctx.get_output(
+            self.face_landmarker.score_buf_index,
+            &mut self.score_of_face_presence,
+        )?;
+        if self.score_of_face_presence[0] < self.face_landmarker.min_face_presence_confidence() {
+            return Ok(None);
         }
 
-        Ok(FaceLandmarkResults(face_landmark_results))
+        // get landmarks
+        self.execution_ctx.get_output(
+            self.face_landmarker.landmarks_buf_index,
+            self.tensors_to_landmarks.landmark_buffer(),
+        )?;
+        let mut face_landmarks = self.tensors_to_landmarks.result(true);
+
+        // do projection
+        crate::postprocess::projection_normalized_landmarks(&mut face_landmarks, face_rect, false);
+
+        // face blendshapes
+        let face_blendshapes = if let Some(ref mut ctx) = self.blendshapes_execution_ctx {
+            blendshapes_tensor::landmarks_to_tensor(
+                &face_landmarks,
+                &mut self.blendshapes_input_buffer,
+                (img_w, img_h),
+            );
+            ctx.set_input(
+                0,
+                TensorType::F32,
+                self.blendshapes_input_shape.unwrap(),
+                self.blendshapes_input_buffer.as_slice(),
+            )?;
+            ctx.compute()?;
+            ctx.get_output(0, &mut self.blendshapes_output_buffer)?;
+            Some(blendshapes_tensor::tensor_to_classifications(
+                &self.blendshapes_output_buffer,
+            ))
+        } else {
+            None
+        };
+
+        // face geometry
+        let (facial_transformation_matrix, face_mesh) = match self.face_landmarker.geometry_pipeline
+        {
+            Some(ref p) => match p.estimate(&face_landmarks, (img_w, img_h)) {
+                Some((m, mesh)) => (Some(m), Some(mesh)),
+                None => (None, None),
+            },
+            None => (None, None),
+        };
+
+        Ok(Some(FaceLandmarkResult {
+            face_landmarks,
+            face_blendshapes,
+            facial_transformation_matrix,
+            face_mesh,
+        }))
     }
 
     /// Detect input video stream use this session.
@@ -187,12 +280,14 @@ impl<'model> super::TaskSession for FaceLandmarkerSession<'model> {
     #[inline]
     fn process_next(
         &mut self,
-        _process_options: &super::ImageProcessingOptions,
+        process_options: &super::ImageProcessingOptions,
         video_data: &mut impl VideoData,
     ) -> Result<Option<Self::Result>, Error> {
         // todo: video track optimize
         if let Some(frame) = video_data.next_frame()? {
-            return self.detect(&frame).map(|r| Some(r));
+            return self
+                .detect_with_options(&frame, process_options)
+                .map(|r| Some(r));
         }
         Ok(None)
     }
@@ -0,0 +1,254 @@
+// These reference files are licensed under Apache 2.0, and originally developed by Google for Mediapipe:
+// https://github.com/google/mediapipe/raw/master/mediapipe/tasks/cc/vision/face_geometry/libs/procrustes_solver.cc
+//
+// The optimal rotation is solved by the quaternion method (Horn, 1987) instead of SVD,
+// which always gives a proper rotation (reflection is not allowed).
+
+const ABSOLUTE_ERROR_EPS: f32 = 1e-9;
+
+/// Solve the weighted orthogonal problem: find the similarity transform ```M = [s * R | t]```
+/// which minimizes ```sum(w_i * |s * R * source_i + t - target_i|^2)```.
+///
+/// Return the row-major 4x4 transform matrix, or ```None``` if the problem is degenerate.
+pub(super) fn solve_weighted_orthogonal_problem(
+    source: &[[f32; 3]],
+    target: &[[f32; 3]],
+    weights: &[f32],
+) -> Option<[[f32; 4]; 4]> {
+    debug_assert!(source.len() == target.len() && source.len() == weights.len());
+
+    let total_weight: f32 = weights.iter().sum();
+    if total_weight <= ABSOLUTE_ERROR_EPS {
+        return None;
+    }
+
+    // weighted center of mass
+    let mut source_center = [0f32; 3];
+    let mut target_center = [0f32; 3];
+    for ((s, t), w) in source.iter().zip(target).zip(weights) {
+        for k in 0..3 {
+            source_center[k] += w * s[k];
+            target_center[k] += w * t[k];
+        }
+    }
+    for k in 0..3 {
+        source_center[k] /= total_weight;
+        target_center[k] /= total_weight;
+    }
+
+    // design matrix: sum(w_i * target_i * transposed(source_i - source_center))
+    let mut design = [[0f64; 3]; 3];
+    let mut scale_denominator = 0f32;
+    for ((s, t), w) in source.iter().zip(target).zip(weights) {
+        let c = [
+            s[0] - source_center[0],
+            s[1] - source_center[1],
+            s[2] - source_center[2],
+        ];
+        for i in 0..3 {
+            for j in 0..3 {
+                design[i][j] += (w * t[i] * c[j]) as f64;
+            }
+            scale_denominator += w * c[i] * s[i];
+        }
+    }
+    let design_norm = design.iter().flatten().map(|d| d * d).sum::<f64>().sqrt();
+    if design_norm <= ABSOLUTE_ERROR_EPS as f64 || scale_denominator <= ABSOLUTE_ERROR_EPS {
+        return None;
+    }
+
+    let rotation = optimal_rotation(&design);
+
+    // optimal scale: sum(w_i * target_i . (R * (source_i - source_center))) / sum(w_i * |source_i - source_center|^2)
+    let mut scale_numerator = 0f32;
+    for ((s, t), w) in source.iter().zip(target).zip(weights) {
+        let c = [
+            s[0] - source_center[0],
+            s[1] - source_center[1],
+            s[2] - source_center[2],
+        ];
+        for i in 0..3 {
+            let rc = rotation[i][0] * c[0] + rotation[i][1] * c[1] + rotation[i][2] * c[2];
+            scale_numerator += w * t[i] * rc;
+        }
+    }
+    let scale = scale_numerator / scale_denominator;
+    if scale <= ABSOLUTE_ERROR_EPS {
+        return None;
+    }
+
+    let mut res = [[0f32; 4]; 4];
+    for i in 0..3 {
+        for j in 0..3 {
+            res[i][j] = scale * rotation[i][j];
+        }
+        res[i][3] = target_center[i]
+            - (res[i][0] * source_center[0]
+                + res[i][1] * source_center[1]
+                + res[i][2] * source_center[2]);
+    }
+    res[3][3] = 1.;
+    Some(res)
+}
+
+/// The rotation ```R``` which maximizes ```trace(transposed(R) * design)```.
+fn optimal_rotation(design: &[[f64; 3]; 3]) -> [[f32; 3]; 3] {
+    // Horn uses S = sum(source_i * transposed(target_i)), which is the transposed design matrix.
+    let s = |i: usize, j: usize| design[j][i];
+    let n = [
+        [
+            s(0, 0) + s(1, 1) + s(2, 2),
+            s(1, 2) - s(2, 1),
+            s(2, 0) - s(0, 2),
+            s(0, 1) - s(1, 0),
+        ],
+        [
+            s(1, 2) - s(2, 1),
+            s(0, 0) - s(1, 1) - s(2, 2),
+            s(0, 1) + s(1, 0),
+            s(2, 0) + s(0, 2),
+        ],
+        [
+            s(2, 0) - s(0, 2),
+            s(0, 1) + s(1, 0),
+            -s(0, 0) + s(1, 1) - s(2, 2),
+            s(1, 2) + s(2, 1),
+        ],
+        [
+            s(0, 1) - s(1, 0),
+            s(2, 0) + s(0, 2),
+            s(1, 2) + s(2, 1),
+            -s(0, 0) - s(1, 1) + s(2, 2),
+        ],
+    ];
+
+    // the unit quaternion is the eigenvector of the largest eigenvalue
+    let (values, vectors) = symmetric_eigen(n);
+    let mut max = 0;
+    for i in 1..4 {
+        if values[i] > values[max] {
+            max = i;
+        }
+    }
+    let (w, x, y, z) = (
+        vectors[0][max],
+        vectors[1][max],
+        vectors[2][max],
+        vectors[3][max],
+    );
+    [
+        [
+            (1. - 2. * (y * y + z * z)) as f32,
+            (2. * (x * y - w * z)) as f32,
+            (2. * (x * z + w * y)) as f32,
+        ],
+        [
+            (2. * (x * y + w * z)) as f32,
+            (1. - 2. * (x * x + z * z)) as f32,
+            (2. * (y * z - w * x)) as f32,
+        ],
+        [
+            (2. * (x * z - w * y)) as f32,
+            (2. * (y * z + w * x)) as f32,
+            (1. - 2. * (x * x + y * y)) as f32,
+        ],
+    ]
+}
+
+/// Cyclic Jacobi eigenvalue algorithm for a symmetric 4x4 matrix.
+/// Return the eigenvalues and the eigenvectors (as columns).
+fn symmetric_eigen(mut a: [[f64; 4]; 4]) -> ([f64; 4], [[f64; 4]; 4]) {
+    const MAX_SWEEPS: usize = 64;
+
+    let mut v = [[0f64; 4]; 4];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.;
+    }
+
+    for _ in 0..MAX_SWEEPS {
+        let mut off_diagonal = 0f64;
+        for (i, row) in a.iter().enumerate() {
+            for x in &row[i + 1..] {
+                off_diagonal += x * x;
+            }
+        }
+        if off_diagonal < 1e-30 {
+            break;
+        }
+
+        for p in 0..4 {
+            for q in p + 1..4 {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (mut row_p, mut row_q) = (a[p], a[q]);
+                for (apk, aqk) in row_p.iter_mut().zip(row_q.iter_mut()) {
+                    (*apk, *aqk) = (c * *apk - s * *aqk, s * *apk + c * *aqk);
+                }
+                (a[p], a[q]) = (row_p, row_q);
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    ([a[0][0], a[1][1], a[2][2], a[3][3]], v)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve_weighted_orthogonal_problem() {
+        let source = [
+            [0., 0., 0.],
+            [1., 0., 0.],
+            [0., 2., 0.],
+            [0., 0., 3.],
+            [1., 1., 1.],
+        ];
+        // rotate 90 degrees around z axis, scale 2 and translate (1, 2, 3)
+        let target = source.map(|p| [-2. * p[1] + 1., 2. * p[0] + 2., 2. * p[2] + 3.]);
+        let weights = [1., 1., 0.5, 1., 2.];
+
+        let m = solve_weighted_orthogonal_problem(&source, &target, &weights).unwrap();
+        let expect = [
+            [0., -2., 0., 1.],
+            [2., 0., 0., 2.],
+            [0., 0., 2., 3.],
+            [0., 0., 0., 1.],
+        ];
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((m[i][j] - expect[i][j]).abs() < 1e-4, "{:?}", m);
+            }
+        }
+
+        // mirrored target, the rotation must not be a reflection
+        let mirrored = source.map(|p| [-p[0], p[1], p[2]]);
+        let m = solve_weighted_orthogonal_problem(&source, &mirrored, &weights).unwrap();
+        let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        assert!(det > 0.);
+
+        // all points are the same
+        assert!(
+            solve_weighted_orthogonal_problem(&[[1.; 3]; 3], &[[1.; 3]; 3], &[1.; 3]).is_none()
+        );
+    }
+}
@@ -1,5 +1,6 @@
+use super::FaceMesh;
 use crate::postprocess::utils::{draw_landmarks_with_options, DefaultPixel, DrawLandmarksOptions};
-use crate::postprocess::NormalizedLandmarks;
+use crate::postprocess::{Classifications, NormalizedLandmarks};
 use std::fmt::{Display, Formatter};
 use std::ops::{Deref, DerefMut};
 
@@ -8,6 +9,15 @@ use std::ops::{Deref, DerefMut};
 pub struct FaceLandmarkResult {
     /// Detected face landmarks in normalized image coordinates.
     pub face_landmarks: NormalizedLandmarks,
+    /// Optional face blendshapes, categories are in the order of [`FaceLandmarkBlendshapes`].
+    ///
+    /// [`FaceLandmarkBlendshapes`]: crate::tasks::vision::FaceLandmarkBlendshapes
+    pub face_blendshapes: Option<Classifications>,
+    /// Optional facial transformation matrix (row-major), which transforms the canonical face mesh
+    /// to the detected face in the camera space. ```None``` if the face geometry cannot be estimated.
+    pub facial_transformation_matrix: Option<[[f32; 4]; 4]>,
+    /// Optional face mesh of the detected face in the canonical face space.
+    pub face_mesh: Option<FaceMesh>,
 }
 
 impl FaceLandmarkResult {
@@ -69,13 +79,22 @@ impl Display for FaceLandmarkResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "  Landmarks:")?;
         for (i, l) in self.face_landmarks.iter().enumerate() {
-            writeln!(
-                f,
-                "    Normalized Landmark #{}:",
-                i
-            )?;
+            writeln!(f, "    Normalized Landmark #{}:", i)?;
             write!(f, "{}", l)?;
         }
+        if let Some(ref b) = self.face_blendshapes {
+            writeln!(f, "  Blendshapes:")?;
+            for (i, c) in b.categories.iter().enumerate() {
+                writeln!(f, "    Category #{}:", i)?;
+                write!(f, "{}", c)?;
+            }
+        }
+        if let Some(ref m) = self.facial_transformation_matrix {
+            writeln!(f, "  Facial transformation matrix:")?;
+            for row in m {
+                writeln!(f, "    {:?}", row)?;
+            }
+        }
         Ok(())
     }
 }
@@ -92,4 +111,4 @@ impl Display for FaceLandmarkResults {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,108 @@
+use super::{FaceDetectorBuilder, FaceLandmarkerBuilder, FaceStylizer};
+
+use crate::model::ZipFiles;
+use crate::postprocess::TensorsToImage;
+use crate::tasks::common::BaseTaskOptions;
+
+/// Configure the build options of a new **Face Stylization** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+pub struct FaceStylizerBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+    /// The minimum confidence score for the face detection to be considered successful.
+    pub(super) min_face_detection_confidence: f32,
+}
+
+impl Default for FaceStylizerBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FaceStylizerBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            min_face_detection_confidence: 0.5,
+        }
+    }
+
+    base_task_options_impl!(FaceStylizer);
+
+    /// Set the minimum confidence score for the face detection to be considered successful.
+    /// Default is 0.5
+    #[inline(always)]
+    pub fn min_face_detection_confidence(mut self, min_face_detection_confidence: f32) -> Self {
+        self.min_face_detection_confidence = min_face_detection_confidence;
+        self
+    }
+
+    pub const FACE_LANDMARKER_SUBTASK_CANDIDATE_NAMES: &'static [&'static str] =
+        &["face_landmarker.task"];
+    pub const FACE_STYLIZER_CANDIDATE_NAMES: &'static [&'static str] = &["face_stylizer.tflite"];
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    ///
+    /// The buffer is a face stylizer task bundle, which contains the stylization model and the
+    /// face landmarker task bundle, only the face detection model of the face landmarker is used.
+    #[inline]
+    pub fn build_from_buffer(self, buffer: impl AsRef<[u8]>) -> Result<FaceStylizer, crate::Error> {
+        if !(0. ..=1.).contains(&self.min_face_detection_confidence) {
+            return Err(crate::Error::ArgumentError(format!(
+                "The min_face_detection_confidence must in range [0.0, 1.0], but got `{}`",
+                self.min_face_detection_confidence
+            )));
+        }
+        let buf = buffer.as_ref();
+
+        let zip_file = ZipFiles::new(buf)?;
+        let stylizer_file = search_file_in_zip!(
+            zip_file,
+            Self::FACE_STYLIZER_CANDIDATE_NAMES,
+            "FaceStylizer"
+        );
+        let face_landmarker_file = search_file_in_zip!(
+            zip_file,
+            Self::FACE_LANDMARKER_SUBTASK_CANDIDATE_NAMES,
+            "FaceStylizer"
+        );
+
+        // subtask: face detection
+        let face_landmarker_zip_file = ZipFiles::new(face_landmarker_file)?;
+        let face_detection_file = search_file_in_zip!(
+            face_landmarker_zip_file,
+            FaceLandmarkerBuilder::FACE_DETECTOR_CANDIDATE_NAMES,
+            "FaceStylizer"
+        );
+        let face_detector = FaceDetectorBuilder::new()
+            .base_task_options(self.base_task_options.clone())
+            .num_faces(1)
+            .min_detection_confidence(self.min_face_detection_confidence)
+            .build_from_buffer(face_detection_file)?;
+
+        // parse model and get model resources.
+        let model_resource = crate::model::parse_model(stylizer_file)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 1, 1);
+        model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
+        TensorsToImage::check_tensor_type(get_type_and_quantization!(model_resource, 0))?;
+        let input_tensor_type =
+            model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), stylizer_file)?;
+
+        Ok(FaceStylizer {
+            build_options: self,
+            model_resource,
+            graph,
+            face_detector,
+            input_tensor_type,
+        })
+    }
+}
@@ -0,0 +1,145 @@
+mod builder;
+mod result;
+
+use super::{FaceDetector, FaceDetectorBuilder, FaceDetectorSession, FaceLandmarkerBuilder};
+pub use builder::FaceStylizerBuilder;
+pub use result::FaceStylizerResult;
+
+use crate::model::ModelResourceTrait;
+use crate::postprocess::{NormalizedRect, TensorsToImage};
+use crate::preprocess::vision::{ImageToTensor, ImageToTensorInfo};
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+
+/// Performs face stylization on images.
+pub struct FaceStylizer {
+    build_options: FaceStylizerBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+
+    face_detector: FaceDetector,
+
+    // only one input and one output
+    input_tensor_type: TensorType,
+}
+
+impl FaceStylizer {
+    base_task_options_get_impl!();
+
+    /// The face stylization models output the image in range [-1, 1].
+    const OUTPUT_FLOAT_RANGE: (f32, f32) = (-1., 1.);
+
+    /// Get the minimum confidence score for the face detection to be considered successful.
+    #[inline(always)]
+    pub fn min_face_detection_confidence(&self) -> f32 {
+        self.build_options.min_face_detection_confidence
+    }
+
+    /// Get the subtask: face detector.
+    #[inline(always)]
+    pub fn subtask_face_detector(&self) -> &FaceDetector {
+        &self.face_detector
+    }
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<FaceStylizerSession<'_>, Error> {
+        let image_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model_resource, to_tensor_info, 0)
+                .try_to_image()?;
+        let input_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, 0);
+        let output_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, output_tensor_shape, 0);
+
+        let tensors_to_image = TensorsToImage::new(
+            get_type_and_quantization!(self.model_resource, 0),
+            image_to_tensor_info.image_data_layout,
+            output_tensor_shape,
+            Self::OUTPUT_FLOAT_RANGE,
+        )?;
+        let face_detector_session = self.face_detector.new_session()?;
+        let execution_ctx = self.graph.init_execution_context()?;
+
+        Ok(FaceStylizerSession {
+            face_stylizer: self,
+            execution_ctx,
+            face_detector_session,
+            image_to_tensor_info,
+            input_tensor_shape,
+            input_buffer: vec![0; tensor_bytes!(self.input_tensor_type, input_tensor_shape)],
+            tensors_to_image,
+        })
+    }
+
+    /// Stylize the face in one image using a new session.
+    #[inline(always)]
+    pub fn stylize(&self, input: &impl ImageToTensor) -> Result<Option<FaceStylizerResult>, Error> {
+        self.new_session()?.stylize(input)
+    }
+}
+
+/// Session to run inference.
+/// If process multiple images, reuse it can get better performance.
+pub struct FaceStylizerSession<'model> {
+    face_stylizer: &'model FaceStylizer,
+    execution_ctx: GraphExecutionContext<'model>,
+
+    face_detector_session: FaceDetectorSession<'model>,
+
+    image_to_tensor_info: &'model ImageToTensorInfo,
+    input_tensor_shape: &'model [usize],
+    input_buffer: Vec<u8>,
+    tensors_to_image: TensorsToImage,
+}
+
+impl<'model> FaceStylizerSession<'model> {
+    /// Use the eyes key points of face detection to align the face.
+    const DETECTION_TO_RECT_ROTATION_OPTION: Option<(f32, usize, usize)> = Some((0.0, 0, 1));
+
+    /// Stylize the face in one image, reuse this session data to speedup.
+    /// Return ```None``` if no face is detected.
+    pub fn stylize(
+        &mut self,
+        input: &impl ImageToTensor,
+    ) -> Result<Option<FaceStylizerResult>, Error> {
+        let (img_w, img_h) = input.image_size();
+        let face_detection_result = self.face_detector_session.detect(input)?;
+        let d = match face_detection_result.detections.first() {
+            Some(d) => d,
+            None => return Ok(None),
+        };
+
+        // get roi
+        let face_rect = NormalizedRect::from_detection(
+            d,
+            Self::DETECTION_TO_RECT_ROTATION_OPTION,
+            img_w,
+            img_h,
+            false,
+        )
+        .transform(img_w, img_h, 1.5, 1.5, 0.0, 0.0, None, true);
+
+        // image to tensor
+        input.to_tensor(
+            self.image_to_tensor_info,
+            &super::ImageProcessingOptions::from_normalized_rect(&face_rect),
+            &mut self.input_buffer,
+        )?;
+
+        // set input and compute
+        self.execution_ctx.set_input(
+            0,
+            self.face_stylizer.input_tensor_type,
+            self.input_tensor_shape,
+            self.input_buffer.as_slice(),
+        )?;
+        self.execution_ctx.compute()?;
+
+        self.execution_ctx
+            .get_output(0, self.tensors_to_image.tensor_buffer())?;
+        Ok(Some(FaceStylizerResult {
+            stylized_image: self.tensors_to_image.image(),
+            face_rect,
+        }))
+    }
+}
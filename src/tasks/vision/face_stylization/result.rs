@@ -0,0 +1,141 @@
+use crate::postprocess::NormalizedRect;
+use crate::preprocess::vision::ImageTransform;
+use crate::tasks::vision::ImageProcessingOptions;
+use image::{imageops, RgbImage};
+
+/// The output result of FaceStylizer.
+#[derive(Debug)]
+pub struct FaceStylizerResult {
+    /// The stylized face image, which has the size of the stylization model output.
+    pub stylized_image: RgbImage,
+    /// The region of the stylized face in the source image.
+    pub face_rect: NormalizedRect,
+}
+
+impl FaceStylizerResult {
+    /// Paste the stylized face back into the source image.
+    ///
+    /// The mapping is the inverse of the face alignment, so the face keeps its rotation in the
+    /// source image. The image must be the source image (or has the same size as it).
+    pub fn paste_to(&self, img: &mut RgbImage) {
+        let (img_w, img_h) = img.dimensions();
+        // the same transform as the face image to tensor
+        let transform = ImageTransform::new(
+            (img_w, img_h),
+            self.stylized_image.dimensions(),
+            &ImageProcessingOptions::from_normalized_rect(&self.face_rect),
+        );
+        for (x, y, p) in img.enumerate_pixels_mut() {
+            let (u, v) = transform.to_tensor(
+                (x as f32 + 0.5) / img_w as f32,
+                (y as f32 + 0.5) / img_h as f32,
+            );
+            if let Some(s) = imageops::sample_bilinear(&self.stylized_image, u, v) {
+                *p = s;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn test_paste_to() {
+        let stylized_image = RgbImage::from_pixel(4, 4, Rgb([255, 0, 0]));
+        // the face is the right half of the image
+        let result = FaceStylizerResult {
+            stylized_image,
+            face_rect: NormalizedRect {
+                x_center: 0.75,
+                y_center: 0.5,
+                width: 0.5,
+                height: 1.,
+                rotation: None,
+                rect_id: None,
+            },
+        };
+        let mut img = RgbImage::new(8, 4);
+        result.paste_to(&mut img);
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(img.get_pixel(x, y).0, [0, 0, 0]);
+            }
+            for x in 4..8 {
+                assert_eq!(img.get_pixel(x, y).0, [255, 0, 0]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_paste_rotated_face() {
+        use crate::preprocess::vision::{
+            ImageColorSpaceType, ImageDataLayout, ImageLikeTensorShape, ImageToTensor,
+            ImageToTensorInfo,
+        };
+        use crate::TensorType;
+
+        let src = RgbImage::from_fn(40, 30, |x, y| {
+            Rgb([(x * 5) as u8, (y * 6) as u8, ((x + y) * 3) as u8])
+        });
+        let face_rect = NormalizedRect {
+            x_center: 0.5,
+            y_center: 0.5,
+            width: 0.5,
+            height: 0.6,
+            rotation: Some(std::f32::consts::FRAC_PI_6),
+            rect_id: None,
+        };
+
+        // the face image to tensor, as the stylization model input
+        let (face_w, face_h) = (32, 48);
+        let info = ImageToTensorInfo {
+            image_data_layout: ImageDataLayout::NHWC,
+            color_space: ImageColorSpaceType::RGB,
+            tensor_type: TensorType::U8,
+            tensor_shape: ImageLikeTensorShape {
+                batch: 1,
+                width: face_w,
+                height: face_h,
+                channels: 3,
+            },
+            stats_min: vec![],
+            stats_max: vec![],
+            normalization_options: (vec![0.], vec![1.]),
+        };
+        let mut buf = vec![0; face_w * face_h * 3];
+        src.to_tensor(
+            &info,
+            &ImageProcessingOptions::from_normalized_rect(&face_rect),
+            &mut buf,
+        )
+        .unwrap();
+
+        // the identity stylization must paste the same face back
+        let result = FaceStylizerResult {
+            stylized_image: RgbImage::from_raw(face_w as u32, face_h as u32, buf).unwrap(),
+            face_rect,
+        };
+        let mut img = RgbImage::new(40, 30);
+        result.paste_to(&mut img);
+        assert_eq!(img.get_pixel(0, 0).0, [0, 0, 0]);
+        assert_eq!(img.get_pixel(39, 29).0, [0, 0, 0]);
+        for y in 10..20 {
+            for x in 14..26 {
+                let (a, b) = (img.get_pixel(x, y).0, src.get_pixel(x, y).0);
+                for c in 0..3 {
+                    assert!(
+                        (a[c] as i32 - b[c] as i32).abs() <= 2,
+                        "pixel ({}, {}) is `{:?}`, but the source is `{:?}`",
+                        x,
+                        y,
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+}
@@ -16,11 +16,9 @@ macro_rules! build_graph_and_extra_model_resource {
     ( $file_buf:ident, $self:ident ) => {{
         // parse model and get model resources.
         let model_resource = crate::model::parse_model($file_buf.as_ref())?;
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            $self.base_task_options.device,
-        )
-        .build_from_bytes([$file_buf])?;
+        let graph = $self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), $file_buf)?;
         (model_resource, graph)
     }};
 }
@@ -133,7 +131,6 @@ impl GestureRecognizerBuilder {
         let zip_file = ZipFiles::new(buf)?;
         let hand_gesture_bundle_file = search_file_in_zip!(
             zip_file,
-            buf,
             Self::HAND_GESTURE_CANDIDATE_NAMES,
             Self::TASK_NAME
         );
@@ -141,14 +138,11 @@ impl GestureRecognizerBuilder {
         // subtask: landmark
         let landmark_task_file = search_file_in_zip!(
             zip_file,
-            buf,
             Self::HAND_LANDMARK_SUBTASK_CANDIDATE_NAMES,
             Self::TASK_NAME
         );
         let hand_landmarker = HandLandmarkerBuilder {
-            base_task_options: BaseTaskOptions {
-                device: self.base_task_options.device,
-            },
+            base_task_options: self.base_task_options.clone(),
             hand_landmark_options: self.hand_landmark_options.clone(),
         }
         .build_from_buffer(landmark_task_file)?;
@@ -158,7 +152,6 @@ impl GestureRecognizerBuilder {
 
         let gesture_embed_file = search_file_in_zip!(
             zip_file,
-            hand_gesture_bundle_file,
             Self::GESTURE_EMBEDDER_CANDIDATE_NAMES,
             Self::TASK_NAME
         );
@@ -189,7 +182,6 @@ impl GestureRecognizerBuilder {
 
         let canned_file = search_file_in_zip!(
             zip_file,
-            hand_gesture_bundle_file,
             Self::GESTURE_CANNED_GESTURE_CLASSIFIER_CANDIDATE_NAMES,
             Self::TASK_NAME
         );
@@ -218,13 +210,12 @@ impl GestureRecognizerBuilder {
         let (custom_classify_resources, custom_classify_graph) = {
             let mut search_result = None;
             for name in Self::GESTURE_CUSTOM_GESTURE_CLASSIFIER_CANDIDATE_NAMES {
-                if let Some(r) = zip_file.get_file_offset(*name) {
-                    search_result = Some(r);
+                if let Some(f) = zip_file.get_file(*name) {
+                    search_result = Some(f);
                     break;
                 }
             }
-            if let Some(r) = search_result {
-                let custom_file = &hand_gesture_bundle_file[r];
+            if let Some(custom_file) = search_result {
                 let (r, g) = build_graph_and_extra_model_resource!(custom_file, self);
                 model_base_check_impl!(r, 1, 1);
                 check_tensor_type!(r, 0, input_tensor_type, TensorType::F32);
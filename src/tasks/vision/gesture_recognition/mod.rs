@@ -160,6 +160,18 @@ impl GestureRecognizer {
         self.new_session()?.recognize(input)
     }
 
+    /// Recognize one image using a new task session with options to specify the rotation, flip and
+    /// resize mode.
+    #[inline(always)]
+    pub fn recognize_with_options(
+        &self,
+        input: &impl ImageToTensor,
+        process_options: &super::ImageProcessingOptions,
+    ) -> Result<GestureRecognizerResults, Error> {
+        self.new_session()?
+            .recognize_with_options(input, process_options)
+    }
+
     /// Recognize video stream using a new task session, and collect all results to [`Vec`].
     #[inline(always)]
     pub fn recognize_for_video(
@@ -201,11 +213,23 @@ impl<'model> GestureRecognizerSession<'model> {
     pub fn recognize(
         &mut self,
         input: &impl ImageToTensor,
+    ) -> Result<GestureRecognizerResults, Error> {
+        self.recognize_with_options(input, &Default::default())
+    }
+
+    /// Recognize one image with options using this session.
+    /// The options are applied to the hand detection stage.
+    pub fn recognize_with_options(
+        &mut self,
+        input: &impl ImageToTensor,
+        process_options: &super::ImageProcessingOptions,
     ) -> Result<GestureRecognizerResults, Error> {
         let img_size = input.image_size();
         let timestamp_ms = input.timestamp_ms();
 
-        let hand_landmark_results = self.hand_landmarker_session.detect(input)?;
+        let hand_landmark_results = self
+            .hand_landmarker_session
+            .detect_with_options(input, process_options)?;
         let mut gesture_recognizer_results = Vec::with_capacity(hand_landmark_results.len());
 
         for hand_landmark in hand_landmark_results {
@@ -296,12 +320,14 @@ impl<'model> super::TaskSession for GestureRecognizerSession<'model> {
     #[inline]
     fn process_next(
         &mut self,
-        _process_options: &super::ImageProcessingOptions,
+        process_options: &super::ImageProcessingOptions,
         video_data: &mut impl VideoData,
     ) -> Result<Option<Self::Result>, Error> {
         // todo: video track optimize
         if let Some(frame) = video_data.next_frame()? {
-            return self.recognize(&frame).map(|r| Some(r));
+            return self
+                .recognize_with_options(&frame, process_options)
+                .map(|r| Some(r));
         }
         Ok(None)
     }
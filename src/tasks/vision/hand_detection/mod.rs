@@ -4,7 +4,7 @@ pub use builder::HandDetectorBuilder;
 use crate::model::ModelResourceTrait;
 use crate::postprocess::{
     Anchor, CategoriesFilter, DetectionBoxFormat, DetectionResult, NonMaxSuppressionAlgorithm,
-    NonMaxSuppressionOverlapType, TensorsToDetection,
+    NonMaxSuppressionOverlapType, TensorsDecodingOptions, TensorsToDetection,
 };
 use crate::preprocess::vision::ImageToTensorInfo;
 use crate::{Error, Graph, GraphExecutionContext, TensorType};
@@ -18,7 +18,7 @@ pub struct HandDetector {
     anchors: Vec<Anchor>,
     location_buf_index: usize,
     score_buf_index: usize,
-    num_box: usize,
+    decoding_options: TensorsDecodingOptions,
 
     // only one input and one output
     input_tensor_type: TensorType,
@@ -63,17 +63,14 @@ impl HandDetector {
         );
 
         // config options
-        tensors_to_detection.set_anchors_scales(192.0, 192.0, 192.0, 192.0);
-        tensors_to_detection.set_num_coords(18);
-        tensors_to_detection.set_key_points(7, 2, 4);
-        tensors_to_detection.set_sigmoid_score(true);
+        tensors_to_detection.set_decoding_options(&self.decoding_options);
         tensors_to_detection.set_score_clipping_thresh(100.);
         tensors_to_detection.set_box_format(DetectionBoxFormat::XYWH);
         tensors_to_detection.set_nms_min_suppression_threshold(0.3);
         tensors_to_detection
             .set_nms_overlap_type(NonMaxSuppressionOverlapType::IntersectionOverUnion);
         tensors_to_detection.set_nms_algorithm(NonMaxSuppressionAlgorithm::WEIGHTED);
-        tensors_to_detection.realloc(self.num_box);
+        tensors_to_detection.realloc(self.decoding_options.num_boxes);
 
         let execution_ctx = self.graph.init_execution_context()?;
         Ok(HandDetectorSession {
@@ -122,10 +119,12 @@ impl<'model> HandDetectorSession<'model> {
         )?;
 
         // generate result
-        Ok(self.tensors_to_detection.result(self.detector.num_box))
+        Ok(self
+            .tensors_to_detection
+            .result(self.detector.decoding_options.num_boxes))
     }
 
-    detector_session_impl!(DetectionResult);
+    detector_session_impl!(HandDetectorSession, DetectionResult);
 }
 
 detection_task_session_impl!(HandDetectorSession, DetectionResult);
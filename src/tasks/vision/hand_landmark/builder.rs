@@ -1,4 +1,4 @@
-use super::{HandDetectorBuilder, HandLandmarker, TensorType};
+use super::{HandDetector, HandDetectorBuilder, HandLandmark, HandLandmarker, TensorType};
 
 use crate::model::ZipFiles;
 use crate::tasks::common::{BaseTaskOptions, HandLandmarkOptions};
@@ -51,25 +51,37 @@ impl HandLandmarkerBuilder {
         let zip_file = ZipFiles::new(buf)?;
         let landmark_file = search_file_in_zip!(
             zip_file,
-            buf,
             Self::HAND_LANDMARKS_CANDIDATE_NAMES,
             "HandLandmark"
         );
         let hand_detection_file = search_file_in_zip!(
             zip_file,
-            buf,
             Self::HAND_DETECTOR_CANDIDATE_NAMES,
             "HandDetection"
         );
 
         let subtask = HandDetectorBuilder::new()
-            .device(self.base_task_options.device)
+            .base_task_options(self.base_task_options.clone())
             .num_hands(self.hand_landmark_options.num_hands)
             .min_detection_confidence(self.hand_landmark_options.min_hand_detection_confidence)
             .build_from_buffer(hand_detection_file)?;
 
+        self.build_with_hand_detector(landmark_file, Some(subtask))
+    }
+
+    /// Create a new task instance from the hand landmarks model.
+    ///
+    /// The hand detector is ```None``` if the hand region-of-interest is got from other tasks, such as the
+    /// pose landmarks in holistic landmarker, then the hands are detected by [`HandLandmarkerSession::detect_in_rect`].
+    ///
+    /// [`HandLandmarkerSession::detect_in_rect`]: super::HandLandmarkerSession::detect_in_rect
+    pub(in super::super) fn build_with_hand_detector(
+        self,
+        landmark_file: &[u8],
+        hand_detector: Option<HandDetector>,
+    ) -> Result<HandLandmarker, crate::Error> {
         // parse model and get model resources.
-        let model_resource = crate::model::parse_model(landmark_file.as_ref())?;
+        let model_resource = crate::model::parse_model(landmark_file)?;
 
         // check model
         model_base_check_impl!(model_resource, 1, 4);
@@ -77,11 +89,43 @@ impl HandLandmarkerBuilder {
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
 
-        // todo: get these from metadata
-        let handedness_buf_index = 2;
-        let score_buf_index = 1;
+        // The hand landmarks model has no output tensor names in metadata, so the outputs are in the
+        // layout of MediaPipe hand landmarks models, and their shapes are checked here:
+        // 0: landmarks `[1, 63]`, 21 landmarks with x, y, z
+        // 1: hand presence score `[1, 1]`
+        // 2: handedness score `[1, 1]`
+        // 3: world landmarks `[1, 63]`, 21 landmarks with x, y, z
         let landmarks_buf_index = 0;
+        let score_buf_index = 1;
+        let handedness_buf_index = 2;
         let world_landmarks_buf_index = 3;
+        let elements = |index: usize| -> Result<usize, crate::Error> {
+            Ok(
+                model_resource_check_and_get_impl!(model_resource, output_tensor_shape, index)
+                    .iter()
+                    .product::<usize>(),
+            )
+        };
+        for index in [landmarks_buf_index, world_landmarks_buf_index] {
+            let landmarks_elements = elements(index)?;
+            if landmarks_elements < HandLandmark::NAMES.len() * 3 {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "Expect at least `{}` hand landmarks, but the output tensor `{}` only has `{}` elements",
+                    HandLandmark::NAMES.len(),
+                    index,
+                    landmarks_elements
+                )));
+            }
+        }
+        for index in [score_buf_index, handedness_buf_index] {
+            let score_elements = elements(index)?;
+            if score_elements != 1 {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "Expect the score output tensor `{}` has 1 element, but got `{}`",
+                    index, score_elements
+                )));
+            }
+        }
         // now only fp32 model
         check_tensor_type!(
             model_resource,
@@ -96,17 +140,15 @@ impl HandLandmarkerBuilder {
             TensorType::F32
         );
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.device,
-        )
-        .build_from_bytes([landmark_file])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), landmark_file)?;
 
         Ok(HandLandmarker {
             build_options: self,
             model_resource,
             graph,
-            hand_detector: subtask,
+            hand_detector,
             handedness_buf_index,
             score_buf_index,
             landmarks_buf_index,
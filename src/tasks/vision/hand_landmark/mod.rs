@@ -18,7 +18,8 @@ pub struct HandLandmarker {
     model_resource: Box<dyn ModelResourceTrait>,
     graph: Graph,
 
-    hand_detector: HandDetector,
+    // `None` if the hand region-of-interest is got from other tasks
+    hand_detector: Option<HandDetector>,
 
     handedness_buf_index: usize,
     score_buf_index: usize,
@@ -31,6 +32,7 @@ pub struct HandLandmarker {
 
 impl HandLandmarker {
     const LANDMARKS_NORMALIZE_Z: f32 = 0.4;
+    const HANDEDNESS_LABELS: &'static [u8] = b"Left\nRight";
 
     detector_impl!(HandLandmarkerSession, HandLandmarkResults);
 
@@ -39,7 +41,8 @@ impl HandLandmarker {
     /// Get the subtask: hand detector.
     #[inline(always)]
     pub fn subtask_hand_detector(&self) -> &HandDetector {
-        &self.hand_detector
+        // the hand landmarker built by `HandLandmarkerBuilder` always has a hand detector
+        self.hand_detector.as_ref().unwrap()
     }
 
     /// Create a new task session that contains processing buffers and can do inference.
@@ -52,7 +55,12 @@ impl HandLandmarker {
             model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, 0);
 
         // todo: parse index from metadata
-        let hand_labels = self.model_resource.output_tensor_labels_locale(0, "")?.0;
+        let hand_labels = match self.model_resource.output_tensor_labels_locale(0, "") {
+            Ok((labels, _)) => labels,
+            // the handedness is unused if the hand region-of-interest is got from other tasks
+            Err(_) if self.hand_detector.is_none() => Self::HANDEDNESS_LABELS,
+            Err(e) => return Err(e),
+        };
         let categories_filter =
             CategoriesFilter::new_full(f32::MIN, hand_labels, Some(hand_labels));
 
@@ -82,7 +90,10 @@ impl HandLandmarker {
             world_landmarks_shape,
         )?;
 
-        let hand_detector_session = self.hand_detector.new_session()?;
+        let hand_detector_session = match self.hand_detector {
+            Some(ref d) => Some(d.new_session()?),
+            None => None,
+        };
         let execution_ctx = self.graph.init_execution_context()?;
 
         Ok(HandLandmarkerSession {
@@ -107,7 +118,7 @@ pub struct HandLandmarkerSession<'model> {
     hand_landmarker: &'model HandLandmarker,
     execution_ctx: GraphExecutionContext<'model>,
 
-    hand_detector_session: HandDetectorSession<'model>,
+    hand_detector_session: Option<HandDetectorSession<'model>>,
 
     image_to_tensor_info: &'model ImageToTensorInfo,
     input_tensor_shape: &'model [usize],
@@ -126,8 +137,25 @@ impl<'model> HandLandmarkerSession<'model> {
     /// Detect one image using this task session.
     #[inline(always)]
     pub fn detect(&mut self, input: &impl ImageToTensor) -> Result<HandLandmarkResults, Error> {
+        self.detect_with_options(input, &Default::default())
+    }
+
+    /// Detect one image with options using this task session.
+    /// The options are applied to the detection stage, e.g. the resize mode.
+    pub fn detect_with_options(
+        &mut self,
+        input: &impl ImageToTensor,
+        process_options: &super::ImageProcessingOptions,
+    ) -> Result<HandLandmarkResults, Error> {
         let (img_w, img_h) = input.image_size();
-        let hand_detection_result = self.hand_detector_session.detect(input)?;
+        let hand_detection_result = match self.hand_detector_session {
+            Some(ref mut s) => s.detect_with_options(input, process_options)?,
+            None => {
+                return Err(Error::ArgumentError(
+                    "The hand landmarker has no hand detector, use `detect_in_rect` instead".into(),
+                ))
+            }
+        };
         let mut hand_landmark_results = Vec::with_capacity(hand_detection_result.detections.len());
 
         for d in hand_detection_result.detections.iter() {
@@ -141,77 +169,94 @@ impl<'model> HandLandmarkerSession<'model> {
             )
             .transform(img_w, img_h, 2.6, 2.6, 0.0, -0.5, None, true);
 
-            // image to tensor
-            input.to_tensor(
-                self.image_to_tensor_info,
-                &super::ImageProcessingOptions::from_normalized_rect(&hand_rect),
-                &mut self.input_buffer,
-            )?;
-
-            // set input and compute
-            self.execution_ctx.set_input(
-                0,
-                self.hand_landmarker.input_tensor_type,
-                self.input_tensor_shape,
-                self.input_buffer.as_slice(),
-            )?;
-            self.execution_ctx.compute()?;
-
-            // check hand presence score
-            self.execution_ctx.get_output(
-                self.hand_landmarker.score_buf_index,
-                &mut self.score_of_hand_presence,
-            )?;
-            if self.score_of_hand_presence[0] < self.hand_landmarker.min_hand_presence_confidence()
+            if let Some(r) =
+                self.detect_in_rect(input, &hand_rect, process_options.flip_horizontally)?
             {
-                continue;
+                hand_landmark_results.push(r);
             }
-
-            // get handedness, left or right
-            self.execution_ctx.get_output(
-                self.hand_landmarker.handedness_buf_index,
-                &mut self.score_of_handedness,
-            )?;
-            let category = if self.score_of_handedness[0] > 0.5 {
-                self.categories_filter
-                    .create_category(0, self.score_of_handedness[0])
-                    .unwrap()
-            } else {
-                self.categories_filter
-                    .create_category(1, 1. - self.score_of_handedness[0])
-                    .unwrap()
-            };
-
-            // get landmarks
-            self.execution_ctx.get_output(
-                self.hand_landmarker.landmarks_buf_index,
-                self.tensors_to_landmarks.landmark_buffer(),
-            )?;
-            let mut hand_landmarks = self.tensors_to_landmarks.result(true);
-            self.execution_ctx.get_output(
-                self.hand_landmarker.world_landmarks_buf_index,
-                self.tensors_to_world_landmarks.landmark_buffer(),
-            )?;
-            let mut hand_world_landmarks = self.tensors_to_world_landmarks.result(false);
-
-            // do projection
-            crate::postprocess::projection_normalized_landmarks(
-                &mut hand_landmarks,
-                &hand_rect,
-                false,
-            );
-            crate::postprocess::projection_world_landmark(&mut hand_world_landmarks, &hand_rect);
-
-            hand_landmark_results.push(HandLandmarkResult {
-                handedness: category,
-                hand_landmarks,
-                hand_world_landmarks,
-            });
         }
 
         Ok(HandLandmarkResults(hand_landmark_results))
     }
 
+    /// Detect the hand landmarks in the region-of-interest of the image.
+    /// Return ```None``` if the hand presence score is less than the threshold.
+    ///
+    /// If the image is mirrored, the handedness is the opposite one of the hand in the original image.
+    pub(crate) fn detect_in_rect(
+        &mut self,
+        input: &impl ImageToTensor,
+        hand_rect: &NormalizedRect,
+        flip_horizontally: bool,
+    ) -> Result<Option<HandLandmarkResult>, Error> {
+        // image to tensor
+        input.to_tensor(
+            self.image_to_tensor_info,
+            &super::ImageProcessingOptions::from_normalized_rect(hand_rect),
+            &mut self.input_buffer,
+        )?;
+
+        // set input and compute
+        self.execution_ctx.set_input(
+            0,
+            self.hand_landmarker.input_tensor_type,
+            self.input_tensor_shape,
+            self.input_buffer.as_slice(),
+        )?;
+        self.execution_ctx.compute()?;
+
+        // check hand presence score
+        self.execution_ctx.get_output(
+            self.hand_landmarker.score_buf_index,
+            &mut self.score_of_hand_presence,
+        )?;
+        if self.score_of_hand_presence[0] < self.hand_landmarker.min_hand_presence_confidence() {
+            return Ok(None);
+        }
+
+        // get handedness, left or right
+        self.execution_ctx.get_output(
+            self.hand_landmarker.handedness_buf_index,
+            &mut self.score_of_handedness,
+        )?;
+        let (mut index, score) = if self.score_of_handedness[0] > 0.5 {
+            (0, self.score_of_handedness[0])
+        } else {
+            (1, 1. - self.score_of_handedness[0])
+        };
+        // the landmarks are detected in the original image, so the handedness of the
+        // mirrored image is the opposite one.
+        if flip_horizontally {
+            index = 1 - index;
+        }
+        let category = self
+            .categories_filter
+            .create_category(index, score)
+            .unwrap();
+
+        // get landmarks
+        self.execution_ctx.get_output(
+            self.hand_landmarker.landmarks_buf_index,
+            self.tensors_to_landmarks.landmark_buffer(),
+        )?;
+        let mut hand_landmarks = self.tensors_to_landmarks.result(true);
+        self.execution_ctx.get_output(
+            self.hand_landmarker.world_landmarks_buf_index,
+            self.tensors_to_world_landmarks.landmark_buffer(),
+        )?;
+        let mut hand_world_landmarks = self.tensors_to_world_landmarks.result(false);
+
+        // do projection
+        crate::postprocess::projection_normalized_landmarks(&mut hand_landmarks, hand_rect, false);
+        crate::postprocess::projection_world_landmark(&mut hand_world_landmarks, hand_rect);
+
+        Ok(Some(HandLandmarkResult {
+            handedness: category,
+            hand_landmarks,
+            hand_world_landmarks,
+        }))
+    }
+
     /// Detect input video stream use this session.
     /// Return a iterator for results, process input stream when poll next result.
     #[inline(always)]
@@ -229,12 +274,14 @@ impl<'model> super::TaskSession for HandLandmarkerSession<'model> {
     #[inline]
     fn process_next(
         &mut self,
-        _process_options: &super::ImageProcessingOptions,
+        process_options: &super::ImageProcessingOptions,
         video_data: &mut impl VideoData,
     ) -> Result<Option<Self::Result>, Error> {
         // todo: video track optimize
         if let Some(frame) = video_data.next_frame()? {
-            return self.detect(&frame).map(|r| Some(r));
+            return self
+                .detect_with_options(&frame, process_options)
+                .map(|r| Some(r));
         }
         Ok(None)
     }
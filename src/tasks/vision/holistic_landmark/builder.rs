@@ -0,0 +1,171 @@
+use super::{
+    FaceLandmarkerBuilder, HandLandmarkerBuilder, HolisticLandmarker, PoseLandmarkerBuilder,
+};
+
+use crate::model::ZipFiles;
+use crate::tasks::common::BaseTaskOptions;
+
+/// Configure the build options of a new **Holistic Landmark** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+pub struct HolisticLandmarkerBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+    /// The minimum confidence score for the pose detection to be considered successful.
+    pub(super) min_pose_detection_confidence: f32,
+    /// The minimum confidence score of pose presence score in the pose landmark detection.
+    pub(super) min_pose_landmarks_confidence: f32,
+    /// The minimum confidence score of face presence score in the face landmark detection.
+    pub(super) min_face_landmarks_confidence: f32,
+    /// The minimum confidence score of hand presence score in the hand landmark detection.
+    pub(super) min_hand_landmarks_confidence: f32,
+    /// Whether HolisticLandmarker outputs face blendshapes.
+    pub(super) output_face_blendshapes: bool,
+    /// Whether HolisticLandmarker outputs the segmentation mask for the detected pose.
+    pub(super) output_pose_segmentation_masks: bool,
+}
+
+impl Default for HolisticLandmarkerBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HolisticLandmarkerBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            min_pose_detection_confidence: 0.5,
+            min_pose_landmarks_confidence: 0.5,
+            min_face_landmarks_confidence: 0.5,
+            min_hand_landmarks_confidence: 0.5,
+            output_face_blendshapes: false,
+            output_pose_segmentation_masks: false,
+        }
+    }
+
+    base_task_options_impl!(HolisticLandmarker);
+
+    /// Set the minimum confidence score for the pose detection to be considered successful.
+    /// Default is 0.5
+    #[inline(always)]
+    pub fn min_pose_detection_confidence(mut self, min_pose_detection_confidence: f32) -> Self {
+        self.min_pose_detection_confidence = min_pose_detection_confidence;
+        self
+    }
+
+    /// Set the minimum confidence score of pose presence score in the pose landmark detection.
+    /// Default is 0.5
+    #[inline(always)]
+    pub fn min_pose_landmarks_confidence(mut self, min_pose_landmarks_confidence: f32) -> Self {
+        self.min_pose_landmarks_confidence = min_pose_landmarks_confidence;
+        self
+    }
+
+    /// Set the minimum confidence score of face presence score in the face landmark detection.
+    /// Default is 0.5
+    #[inline(always)]
+    pub fn min_face_landmarks_confidence(mut self, min_face_landmarks_confidence: f32) -> Self {
+        self.min_face_landmarks_confidence = min_face_landmarks_confidence;
+        self
+    }
+
+    /// Set the minimum confidence score of hand presence score in the hand landmark detection.
+    /// Default is 0.5
+    #[inline(always)]
+    pub fn min_hand_landmarks_confidence(mut self, min_hand_landmarks_confidence: f32) -> Self {
+        self.min_hand_landmarks_confidence = min_hand_landmarks_confidence;
+        self
+    }
+
+    /// Set whether HolisticLandmarker outputs face blendshapes.
+    /// Default is false
+    #[inline(always)]
+    pub fn output_face_blendshapes(mut self, output_face_blendshapes: bool) -> Self {
+        self.output_face_blendshapes = output_face_blendshapes;
+        self
+    }
+
+    /// Set whether HolisticLandmarker outputs the segmentation mask for the detected pose.
+    /// Default is false
+    #[inline(always)]
+    pub fn output_pose_segmentation_masks(mut self, output_pose_segmentation_masks: bool) -> Self {
+        self.output_pose_segmentation_masks = output_pose_segmentation_masks;
+        self
+    }
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    ///
+    /// The buffer is a holistic landmarker task bundle, which contains the pose detection, pose
+    /// landmarks, face detection, face landmarks and hand landmarks models.
+    #[inline]
+    pub fn build_from_buffer(
+        self,
+        buffer: impl AsRef<[u8]>,
+    ) -> Result<HolisticLandmarker, crate::Error> {
+        for (name, value) in [
+            (
+                "min_pose_detection_confidence",
+                self.min_pose_detection_confidence,
+            ),
+            (
+                "min_pose_landmarks_confidence",
+                self.min_pose_landmarks_confidence,
+            ),
+            (
+                "min_face_landmarks_confidence",
+                self.min_face_landmarks_confidence,
+            ),
+            (
+                "min_hand_landmarks_confidence",
+                self.min_hand_landmarks_confidence,
+            ),
+        ] {
+            if !(0. ..=1.).contains(&value) {
+                return Err(crate::Error::ArgumentError(format!(
+                    "The {} must in range [0.0, 1.0], but got `{}`",
+                    name, value
+                )));
+            }
+        }
+        let buf = buffer.as_ref();
+
+        // the pose models are in the same bundle, use the subtask to run them.
+        let pose_landmarker = PoseLandmarkerBuilder::new()
+            .base_task_options(self.base_task_options.clone())
+            .num_poses(1)
+            .min_pose_detection_confidence(self.min_pose_detection_confidence)
+            .min_pose_presence_confidence(self.min_pose_landmarks_confidence)
+            .output_segmentation_masks(self.output_pose_segmentation_masks)
+            .build_from_buffer(buf)?;
+
+        // the face and hand landmarks models run in the region-of-interest from pose landmarks,
+        // so the face detection model is unused, and there is no hand detection model in the bundle.
+        let zip_file = ZipFiles::new(buf)?;
+        let face_landmarker = FaceLandmarkerBuilder::new()
+            .base_task_options(self.base_task_options.clone())
+            .num_faces(1)
+            .min_face_presence_confidence(self.min_face_landmarks_confidence)
+            .output_face_blendshapes(self.output_face_blendshapes)
+            .build_with_face_detector(&zip_file, None)?;
+
+        let hand_landmark_file = search_file_in_zip!(
+            zip_file,
+            HandLandmarkerBuilder::HAND_LANDMARKS_CANDIDATE_NAMES,
+            "HandLandmark"
+        );
+        let hand_landmarker = HandLandmarkerBuilder::new()
+            .base_task_options(self.base_task_options.clone())
+            .min_hand_presence_confidence(self.min_hand_landmarks_confidence)
+            .build_with_hand_detector(hand_landmark_file, None)?;
+
+        Ok(HolisticLandmarker {
+            build_options: self,
+            pose_landmarker,
+            face_landmarker,
+            hand_landmarker,
+        })
+    }
+}
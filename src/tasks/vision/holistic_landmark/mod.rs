@@ -0,0 +1,249 @@
+mod builder;
+mod result;
+
+use super::{
+    FaceLandmarker, FaceLandmarkerBuilder, FaceLandmarkerSession, HandLandmark, HandLandmarker,
+    HandLandmarkerBuilder, HandLandmarkerSession, PoseLandmark, PoseLandmarker,
+    PoseLandmarkerBuilder, PoseLandmarkerSession,
+};
+pub use builder::HolisticLandmarkerBuilder;
+pub use result::{HolisticLandmarkResult, HolisticLandmarkResults};
+
+use crate::postprocess::{Landmarks, NormalizedLandmarks, NormalizedRect, VideoResultsIter};
+use crate::preprocess::vision::{ImageToTensor, VideoData};
+use crate::Error;
+
+/// Performs holistic landmark on images and video frames.
+///
+/// The pose landmarks are detected first, then the face and hands region-of-interest are got from
+/// the pose landmarks, and the face and hand landmarks are detected in these regions.
+pub struct HolisticLandmarker {
+    build_options: HolisticLandmarkerBuilder,
+
+    pose_landmarker: PoseLandmarker,
+    face_landmarker: FaceLandmarker,
+    // the hand landmarker without hand detector
+    hand_landmarker: HandLandmarker,
+}
+
+impl HolisticLandmarker {
+    detector_impl!(HolisticLandmarkerSession, HolisticLandmarkResults);
+
+    /// Get the minimum confidence score for the pose detection to be considered successful.
+    #[inline(always)]
+    pub fn min_pose_detection_confidence(&self) -> f32 {
+        self.build_options.min_pose_detection_confidence
+    }
+
+    /// Get the minimum confidence score of pose presence score in the pose landmark detection.
+    #[inline(always)]
+    pub fn min_pose_landmarks_confidence(&self) -> f32 {
+        self.build_options.min_pose_landmarks_confidence
+    }
+
+    /// Get the minimum confidence score of face presence score in the face landmark detection.
+    #[inline(always)]
+    pub fn min_face_landmarks_confidence(&self) -> f32 {
+        self.build_options.min_face_landmarks_confidence
+    }
+
+    /// Get the minimum confidence score of hand presence score in the hand landmark detection.
+    #[inline(always)]
+    pub fn min_hand_landmarks_confidence(&self) -> f32 {
+        self.build_options.min_hand_landmarks_confidence
+    }
+
+    /// Get whether HolisticLandmarker outputs face blendshapes.
+    #[inline(always)]
+    pub fn output_face_blendshapes(&self) -> bool {
+        self.build_options.output_face_blendshapes
+    }
+
+    /// Get whether HolisticLandmarker outputs the segmentation mask for the detected pose.
+    #[inline(always)]
+    pub fn output_pose_segmentation_masks(&self) -> bool {
+        self.build_options.output_pose_segmentation_masks
+    }
+
+    /// Get the subtask: pose landmarker.
+    #[inline(always)]
+    pub fn subtask_pose_landmarker(&self) -> &PoseLandmarker {
+        &self.pose_landmarker
+    }
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<HolisticLandmarkerSession<'_>, Error> {
+        let pose_landmarker_session = self.pose_landmarker.new_session()?;
+        let face_landmarker_session = self.face_landmarker.new_session()?;
+        let hand_landmarker_session = self.hand_landmarker.new_session()?;
+
+        Ok(HolisticLandmarkerSession {
+            pose_landmarker_session,
+            face_landmarker_session,
+            hand_landmarker_session,
+        })
+    }
+}
+
+/// Session to run inference.
+/// If process multiple images or videos, reuse it can get better performance.
+pub struct HolisticLandmarkerSession<'model> {
+    pose_landmarker_session: PoseLandmarkerSession<'model>,
+    face_landmarker_session: FaceLandmarkerSession<'model>,
+    hand_landmarker_session: HandLandmarkerSession<'model>,
+}
+
+impl<'model> HolisticLandmarkerSession<'model> {
+    /// The pose landmarks of face: nose, eyes, ears and mouth.
+    const FACE_POSE_LANDMARKS_NUM: usize = PoseLandmark::MouthRight as usize + 1;
+    /// Rotate the face rectangle to make the line from right eye to left eye horizontal.
+    const FACE_ROTATION_OPTION: Option<(f32, usize, usize)> = Some((
+        0.,
+        PoseLandmark::RightEye as usize,
+        PoseLandmark::LeftEye as usize,
+    ));
+    /// The pose landmarks of hands: (wrist, pinky, index)
+    const LEFT_HAND_POSE_LANDMARKS: (usize, usize, usize) = (
+        PoseLandmark::LeftWrist as usize,
+        PoseLandmark::LeftPinky as usize,
+        PoseLandmark::LeftIndex as usize,
+    );
+    const RIGHT_HAND_POSE_LANDMARKS: (usize, usize, usize) = (
+        PoseLandmark::RightWrist as usize,
+        PoseLandmark::RightPinky as usize,
+        PoseLandmark::RightIndex as usize,
+    );
+    /// The hand is skipped if the visibility of wrist in pose landmarks is less than this value.
+    const HAND_VISIBILITY_THRESHOLD: f32 = 0.1;
+
+    /// Detect one image using this task session.
+    #[inline(always)]
+    pub fn detect(&mut self, input: &impl ImageToTensor) -> Result<HolisticLandmarkResults, Error> {
+        self.detect_with_options(input, &Default::default())
+    }
+
+    /// Detect one image with options using this task session.
+    /// The options are applied to the detection stage, e.g. the resize mode.
+    pub fn detect_with_options(
+        &mut self,
+        input: &impl ImageToTensor,
+        process_options: &super::ImageProcessingOptions,
+    ) -> Result<HolisticLandmarkResults, Error> {
+        let (img_w, img_h) = input.image_size();
+        let pose_landmark_results = self
+            .pose_landmarker_session
+            .detect_with_options(input, process_options)?;
+        let mut holistic_landmark_results = Vec::with_capacity(pose_landmark_results.len());
+
+        for pose in pose_landmark_results {
+            // face
+            let face_rect = NormalizedRect::from_landmarks(
+                &pose.pose_landmarks[..Self::FACE_POSE_LANDMARKS_NUM],
+                Self::FACE_ROTATION_OPTION,
+                img_w,
+                img_h,
+            )
+            .transform(img_w, img_h, 1.5, 1.5, 0.0, 0.0, None, true);
+            let (face_landmarks, face_blendshapes) = match self
+                .face_landmarker_session
+                .detect_in_rect(input, &face_rect)?
+            {
+                Some(face) => (Some(face.face_landmarks), face.face_blendshapes),
+                None => (None, None),
+            };
+
+            // hands
+            let (left_hand_landmarks, left_hand_world_landmarks) = match self.detect_hand(
+                input,
+                &pose.pose_landmarks,
+                Self::LEFT_HAND_POSE_LANDMARKS,
+            )? {
+                Some((l, w)) => (Some(l), Some(w)),
+                None => (None, None),
+            };
+            let (right_hand_landmarks, right_hand_world_landmarks) = match self.detect_hand(
+                input,
+                &pose.pose_landmarks,
+                Self::RIGHT_HAND_POSE_LANDMARKS,
+            )? {
+                Some((l, w)) => (Some(l), Some(w)),
+                None => (None, None),
+            };
+
+            holistic_landmark_results.push(HolisticLandmarkResult {
+                pose_landmarks: pose.pose_landmarks,
+                pose_world_landmarks: pose.pose_world_landmarks,
+                pose_segmentation_mask: pose.segmentation_mask,
+                face_landmarks,
+                face_blendshapes,
+                left_hand_landmarks,
+                left_hand_world_landmarks,
+                right_hand_landmarks,
+                right_hand_world_landmarks,
+            });
+        }
+
+        Ok(HolisticLandmarkResults(holistic_landmark_results))
+    }
+
+    /// Detect the hand landmarks in the hand region-of-interest from pose landmarks.
+    fn detect_hand(
+        &mut self,
+        input: &impl ImageToTensor,
+        pose_landmarks: &NormalizedLandmarks,
+        (wrist, pinky, index): (usize, usize, usize),
+    ) -> Result<Option<(NormalizedLandmarks, Landmarks)>, Error> {
+        if pose_landmarks[wrist].visibility.unwrap_or(1.) < Self::HAND_VISIBILITY_THRESHOLD {
+            return Ok(None);
+        }
+
+        // get roi: the center is the estimated middle finger, and rotate to make the hand upright.
+        let (img_w, img_h) = input.image_size();
+        let wrist = &pose_landmarks[wrist];
+        let pinky = &pose_landmarks[pinky];
+        let index = &pose_landmarks[index];
+        let middle = ((2. * index.x + pinky.x) / 3., (2. * index.y + pinky.y) / 3.);
+        let hand_rect = NormalizedRect::from_alignment_vector(
+            -90. * std::f32::consts::PI / 180.0,
+            middle,
+            (wrist.x, wrist.y),
+            img_w,
+            img_h,
+        )
+        .transform(img_w, img_h, 1.3, 1.3, 0.0, 0.0, None, true);
+
+        // the handedness is unused, the hand is left or right in pose landmarks
+        Ok(self
+            .hand_landmarker_session
+            .detect_in_rect(input, &hand_rect, false)?
+            .map(|r| (r.hand_landmarks, r.hand_world_landmarks)))
+    }
+
+    /// Detect input video stream use this session.
+    /// Return a iterator for results, process input stream when poll next result.
+    #[inline(always)]
+    pub fn detect_for_video<InputVideoData: VideoData>(
+        &mut self,
+        video_data: InputVideoData,
+    ) -> Result<VideoResultsIter<'_, '_, Self, InputVideoData>, Error> {
+        Ok(VideoResultsIter::new(self, video_data))
+    }
+}
+
+impl<'model> super::TaskSession for HolisticLandmarkerSession<'model> {
+    type Result = HolisticLandmarkResults;
+
+    #[inline]
+    fn process_next(
+        &mut self,
+        process_options: &super::ImageProcessingOptions,
+        video_data: &mut impl VideoData,
+    ) -> Result<Option<Self::Result>, Error> {
+        // todo: video track optimize
+        if let Some(frame) = video_data.next_frame()? {
+            return self.detect_with_options(&frame, process_options).map(Some);
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,181 @@
+use super::{HandLandmark, PoseLandmark};
+use crate::postprocess::utils::{draw_landmarks_with_options, DefaultPixel, DrawLandmarksOptions};
+use crate::postprocess::{Classifications, ImageConfidenceMask, Landmarks, NormalizedLandmarks};
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// A single holistic landmark detection result, the face and hands belong to the detected pose.
+#[derive(Debug)]
+pub struct HolisticLandmarkResult {
+    /// Detected pose landmarks in normalized image coordinates, with visibility and presence.
+    pub pose_landmarks: NormalizedLandmarks,
+    /// Detected pose landmarks in world coordinates, with visibility and presence.
+    pub pose_world_landmarks: Landmarks,
+    /// Optional segmentation mask of the detected pose, which has the same size as the input image.
+    pub pose_segmentation_mask: Option<ImageConfidenceMask>,
+    /// Detected face landmarks in normalized image coordinates, ```None``` if no face is found.
+    pub face_landmarks: Option<NormalizedLandmarks>,
+    /// Optional face blendshapes, categories are in the order of [`FaceLandmarkBlendshapes`].
+    ///
+    /// [`FaceLandmarkBlendshapes`]: crate::tasks::vision::FaceLandmarkBlendshapes
+    pub face_blendshapes: Option<Classifications>,
+    /// Detected left hand landmarks in normalized image coordinates, ```None``` if not found.
+    pub left_hand_landmarks: Option<NormalizedLandmarks>,
+    /// Detected left hand landmarks in world coordinates, ```None``` if not found.
+    pub left_hand_world_landmarks: Option<Landmarks>,
+    /// Detected right hand landmarks in normalized image coordinates, ```None``` if not found.
+    pub right_hand_landmarks: Option<NormalizedLandmarks>,
+    /// Detected right hand landmarks in world coordinates, ```None``` if not found.
+    pub right_hand_world_landmarks: Option<Landmarks>,
+}
+
+impl HolisticLandmarkResult {
+    /// Draw this detection result to image with default options
+    #[inline(always)]
+    pub fn draw<I>(&self, img: &mut I)
+    where
+        I: image::GenericImage,
+        I::Pixel: 'static + DefaultPixel,
+        <I::Pixel as image::Pixel>::Subpixel: Into<f32> + imageproc::definitions::Clamp<f32>,
+    {
+        let options = DrawLandmarksOptions::default().connections(PoseLandmark::CONNECTIONS);
+        draw_landmarks_with_options(img, &self.pose_landmarks, &options);
+        if let Some(ref face_landmarks) = self.face_landmarks {
+            draw_landmarks_with_options(img, face_landmarks, &DrawLandmarksOptions::default());
+        }
+        let options = DrawLandmarksOptions::default().connections(HandLandmark::CONNECTIONS);
+        for hand_landmarks in [&self.left_hand_landmarks, &self.right_hand_landmarks]
+            .into_iter()
+            .flatten()
+        {
+            draw_landmarks_with_options(img, hand_landmarks, &options);
+        }
+    }
+}
+
+/// The holistic landmarks detection result from HolisticLandmark
+#[derive(Debug)]
+pub struct HolisticLandmarkResults(pub Vec<HolisticLandmarkResult>);
+
+impl Deref for HolisticLandmarkResults {
+    type Target = Vec<HolisticLandmarkResult>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for HolisticLandmarkResults {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl IntoIterator for HolisticLandmarkResults {
+    type Item = HolisticLandmarkResult;
+    type IntoIter = std::vec::IntoIter<HolisticLandmarkResult>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Display for HolisticLandmarkResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "  PoseLandmarks:")?;
+        for (i, l) in self.pose_landmarks.iter().enumerate() {
+            writeln!(
+                f,
+                "    Normalized Landmark #{} ({}):",
+                i,
+                PoseLandmark::NAMES[i]
+            )?;
+            write!(f, "{}", l)?;
+        }
+        writeln!(f, "  PoseWorldLandmarks:")?;
+        for (i, l) in self.pose_world_landmarks.iter().enumerate() {
+            writeln!(f, "    Landmark #{} ({}):", i, PoseLandmark::NAMES[i])?;
+            write!(f, "{}", l)?;
+        }
+        if let Some(ref mask) = self.pose_segmentation_mask {
+            writeln!(
+                f,
+                "  PoseSegmentationMask: {}x{}",
+                mask.width(),
+                mask.height()
+            )?;
+        }
+
+        match self.face_landmarks {
+            Some(ref face_landmarks) => {
+                writeln!(f, "  FaceLandmarks:")?;
+                for (i, l) in face_landmarks.iter().enumerate() {
+                    writeln!(f, "    Normalized Landmark #{}:", i)?;
+                    write!(f, "{}", l)?;
+                }
+            }
+            None => writeln!(f, "  No FaceLandmarks.")?,
+        }
+        if let Some(ref face_blendshapes) = self.face_blendshapes {
+            writeln!(f, "  FaceBlendshapes:")?;
+            for (i, c) in face_blendshapes.categories.iter().enumerate() {
+                writeln!(f, "    Category #{}:", i)?;
+                write!(f, "{}", c)?;
+            }
+        }
+
+        for (name, landmarks, world_landmarks) in [
+            (
+                "LeftHand",
+                &self.left_hand_landmarks,
+                &self.left_hand_world_landmarks,
+            ),
+            (
+                "RightHand",
+                &self.right_hand_landmarks,
+                &self.right_hand_world_landmarks,
+            ),
+        ] {
+            match landmarks {
+                Some(landmarks) => {
+                    writeln!(f, "  {}Landmarks:", name)?;
+                    for (i, l) in landmarks.iter().enumerate() {
+                        writeln!(
+                            f,
+                            "    Normalized Landmark #{} ({}):",
+                            i,
+                            HandLandmark::NAMES[i]
+                        )?;
+                        write!(f, "{}", l)?;
+                    }
+                }
+                None => writeln!(f, "  No {}Landmarks.", name)?,
+            }
+            if let Some(world_landmarks) = world_landmarks {
+                writeln!(f, "  {}WorldLandmarks:", name)?;
+                for (i, l) in world_landmarks.iter().enumerate() {
+                    writeln!(f, "    Landmark #{} ({}):", i, HandLandmark::NAMES[i])?;
+                    write!(f, "{}", l)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for HolisticLandmarkResults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            writeln!(f, "No HolisticLandmarkResult.")?;
+        } else {
+            for (i, r) in self.iter().enumerate() {
+                writeln!(f, "HolisticLandmarkResult #{}", i)?;
+                write!(f, "{}", r)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -96,11 +96,9 @@ impl ImageSegmenterBuilder {
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.device,
-        )
-        .build_from_bytes([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
 
         let (label, label_locale) =
             model_resource.output_tensor_labels_locale(0, self.display_names_locale.as_str())?;
@@ -90,6 +90,17 @@ impl ImageSegmenter {
         self.new_session()?.segment(input)
     }
 
+    /// Segment one image using a new session with options to specify the rotation, flip and resize mode.
+    #[inline(always)]
+    pub fn segment_with_options(
+        &self,
+        input: &impl ImageToTensor,
+        process_options: &super::ImageProcessingOptions,
+    ) -> Result<ImageSegmentationResult, Error> {
+        self.new_session()?
+            .segment_with_options(input, process_options)
+    }
+
     /// Segment video stream using this session, and collect all results to [`Vec`]
     #[inline(always)]
     pub fn segment_for_video(
@@ -131,47 +142,12 @@ impl<'model> ImageSegmenterSession<'model> {
         let output_buffer = self.tensors_to_segmentation.tenor_buffer();
         self.execution_ctx.get_output(0, output_buffer)?;
 
-        let category_mask = if self.output_category {
-            let mask = self.tensors_to_segmentation.category_mask();
-            if mask.dimensions() == img_size {
-                Some(mask)
-            } else {
-                Some(image::imageops::resize(
-                    &mask,
-                    img_size.0,
-                    img_size.1,
-                    image::imageops::FilterType::Triangle,
-                ))
-            }
-        } else {
-            None
-        };
-        let confidence_masks = if self.output_confidence {
-            let masks = self.tensors_to_segmentation.confidence_masks();
-            if masks[0].dimensions() == img_size {
-                Some(masks)
-            } else {
-                Some(
-                    masks
-                        .iter()
-                        .map(|img| {
-                            image::imageops::resize(
-                                img,
-                                img_size.0,
-                                img_size.1,
-                                image::imageops::FilterType::Triangle,
-                            )
-                        })
-                        .collect(),
-                )
-            }
-        } else {
-            None
-        };
-        Ok(ImageSegmentationResult {
-            confidence_masks,
-            category_mask,
-        })
+        Ok(ImageSegmentationResult::from_tensors(
+            &mut self.tensors_to_segmentation,
+            self.output_category,
+            self.output_confidence,
+            img_size,
+        ))
     }
 
     /// Segment one image, reuse this session data to speedup.
@@ -180,12 +156,32 @@ impl<'model> ImageSegmenterSession<'model> {
         &mut self,
         input: &impl ImageToTensor,
     ) -> Result<ImageSegmentationResult, Error> {
+        self.segment_with_options(input, &Default::default())
+    }
+
+    /// Segment one image with the rotation, flip and resize mode options, reuse this session data to speedup.
+    /// The masks are projected back to the original image.
+    pub fn segment_with_options(
+        &mut self,
+        input: &impl ImageToTensor,
+        process_options: &super::ImageProcessingOptions,
+    ) -> Result<ImageSegmentationResult, Error> {
+        if process_options.region_of_interest.is_some() {
+            return Err(Error::ArgumentError(format!(
+                "{} does not support region of interest.",
+                stringify!(ImageSegmenterSession)
+            )));
+        }
         input.to_tensor(
             self.input_to_tensor_info,
-            &Default::default(),
+            process_options,
             &mut self.input_tensor_buf,
         )?;
-        self.compute(input.image_size())
+        let img_size = input.image_size();
+        self.tensors_to_segmentation.set_image_transform(
+            process_options.image_transform(img_size, self.input_to_tensor_info),
+        );
+        self.compute(img_size)
     }
 
     /// Segment input video stream use this session.
@@ -208,23 +204,8 @@ impl<'model> super::TaskSession for ImageSegmenterSession<'model> {
         process_options: &super::ImageProcessingOptions,
         video_data: &mut impl VideoData,
     ) -> Result<Option<Self::Result>, Error> {
-        if process_options.region_of_interest.is_some() {
-            return Err(Error::ArgumentError(format!(
-                "{} does not support region of interest.",
-                stringify!($SessionName)
-            )));
-        }
-
-        // todo: support rotation
-        assert_eq!(process_options.rotation, 0.);
-
         if let Some(frame) = video_data.next_frame()? {
-            frame.to_tensor(
-                self.input_to_tensor_info,
-                process_options,
-                &mut self.input_tensor_buf,
-            )?;
-            return Ok(Some(self.compute(frame.image_size())?));
+            return Ok(Some(self.segment_with_options(&frame, process_options)?));
         }
         Ok(None)
     }
@@ -1,4 +1,6 @@
-use crate::postprocess::{ImageCategoryMask, ImageConfidenceMask};
+use crate::postprocess::{ImageCategoryMask, ImageConfidenceMask, TensorsToSegmentation};
+use crate::preprocess::vision::ImageTransform;
+use image::{imageops, ImageBuffer, Pixel};
 
 /// The output result of Image Segmentation tasks.
 #[derive(Debug)]
@@ -11,3 +13,84 @@ pub struct ImageSegmentationResult {
     /// the class which the pixel in the original image was predicted to belong to.
     pub category_mask: Option<ImageCategoryMask>,
 }
+
+impl ImageSegmentationResult {
+    /// Get the masks from the output tensor, and resize them to the image size.
+    pub(crate) fn from_tensors(
+        tensors_to_segmentation: &mut TensorsToSegmentation,
+        output_category: bool,
+        output_confidence: bool,
+        img_size: (u32, u32),
+    ) -> Self {
+        let transform = tensors_to_segmentation.image_transform().copied();
+        let category_mask = if output_category {
+            let mask = tensors_to_segmentation.category_mask();
+            // the category mask uses the nearest category
+            Some(resize_mask(
+                mask,
+                img_size,
+                transform.as_ref(),
+                imageops::sample_nearest,
+            ))
+        } else {
+            None
+        };
+        let confidence_masks = if output_confidence {
+            let masks = tensors_to_segmentation.confidence_masks();
+            Some(
+                masks
+                    .into_iter()
+                    .map(|mask| {
+                        resize_mask(
+                            mask,
+                            img_size,
+                            transform.as_ref(),
+                            imageops::sample_bilinear,
+                        )
+                    })
+                    .collect(),
+            )
+        } else {
+            None
+        };
+        Self {
+            confidence_masks,
+            category_mask,
+        }
+    }
+}
+
+/// Resize the mask from the tensor size to the image size.
+/// If the input was transformed, each image pixel is projected to the mask and sampled,
+/// and the image region not covered by the tensor is filled with zero.
+fn resize_mask<P: Pixel + 'static>(
+    mask: ImageBuffer<P, Vec<P::Subpixel>>,
+    img_size: (u32, u32),
+    transform: Option<&ImageTransform>,
+    sample: impl Fn(&ImageBuffer<P, Vec<P::Subpixel>>, f32, f32) -> Option<P>,
+) -> ImageBuffer<P, Vec<P::Subpixel>> {
+    let transform = match transform {
+        Some(t) => t,
+        None => {
+            if mask.dimensions() == img_size {
+                return mask;
+            }
+            return imageops::resize(
+                &mask,
+                img_size.0,
+                img_size.1,
+                imageops::FilterType::Triangle,
+            );
+        }
+    };
+
+    let (img_w, img_h) = (img_size.0 as f32, img_size.1 as f32);
+    let mut res = ImageBuffer::new(img_size.0, img_size.1);
+    for (x, y, p) in res.enumerate_pixels_mut() {
+        let (u, v) = transform.to_tensor((x as f32 + 0.5) / img_w, (y as f32 + 0.5) / img_h);
+        if let Some(s) = sample(&mask, u, v) {
+            *p = s;
+        }
+    }
+    res
+}
@@ -0,0 +1,79 @@
+use super::ImageToImage;
+use crate::postprocess::TensorsToImage;
+use crate::tasks::common::BaseTaskOptions;
+
+/// Configure the build options of a new **Image To Image** task instance,
+/// such as style transfer and super resolution models.
+///
+/// Methods can be chained on it in order to configure it.
+///
+/// default options:
+/// * output_float_range: (0.0, 1.0)
+pub struct ImageToImageBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+
+    /// The value range of the float output tensor, which is mapped to pixel values [0, 255].
+    /// Default is (0.0, 1.0)
+    pub(super) output_float_range: (f32, f32),
+}
+
+impl Default for ImageToImageBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            output_float_range: (0., 1.),
+        }
+    }
+}
+
+impl ImageToImageBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    base_task_options_impl!(ImageToImage);
+
+    /// Set the value range of the float output tensor, which is mapped to pixel values [0, 255].
+    /// Such as ```(-1.0, 1.0)``` for tanh outputs, or ```(0.0, 255.0)``` for unnormalized outputs.
+    #[inline(always)]
+    pub fn output_float_range(mut self, min: f32, max: f32) -> Self {
+        self.output_float_range = (min, max);
+        self
+    }
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    #[inline]
+    pub fn build_from_buffer(self, buffer: impl AsRef<[u8]>) -> Result<ImageToImage, crate::Error> {
+        if self.output_float_range.0 >= self.output_float_range.1 {
+            return Err(crate::Error::ArgumentError(format!(
+                "The output float range min must be less than max, but got `[{}, {}]`",
+                self.output_float_range.0, self.output_float_range.1
+            )));
+        }
+
+        let buf = buffer.as_ref();
+        // parse model and get model resources.
+        let model_resource = crate::model::parse_model(buf)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 1, 1);
+        model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
+        TensorsToImage::check_tensor_type(get_type_and_quantization!(model_resource, 0))?;
+        let input_tensor_type =
+            model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
+
+        Ok(ImageToImage {
+            build_options: self,
+            model_resource,
+            graph,
+            input_tensor_type,
+        })
+    }
+}
@@ -0,0 +1,157 @@
+mod builder;
+pub use builder::ImageToImageBuilder;
+
+use crate::model::ModelResourceTrait;
+use crate::postprocess::{TensorsToImage, VideoResultsIter};
+use crate::preprocess::vision::{ImageToTensor, ImageToTensorInfo, VideoData};
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+use image::RgbImage;
+
+/// Performs image to image transformation on images and video frames,
+/// such as style transfer and super resolution.
+pub struct ImageToImage {
+    build_options: ImageToImageBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+
+    input_tensor_type: TensorType,
+}
+
+impl ImageToImage {
+    base_task_options_get_impl!();
+
+    /// Get the value range of the float output tensor.
+    #[inline(always)]
+    pub fn output_float_range(&self) -> (f32, f32) {
+        self.build_options.output_float_range
+    }
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<ImageToImageSession<'_>, Error> {
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model_resource, to_tensor_info, 0)
+                .try_to_image()?;
+        let input_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, 0);
+        let output_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, output_tensor_shape, 0);
+
+        let tensors_to_image = TensorsToImage::new(
+            get_type_and_quantization!(self.model_resource, 0),
+            input_to_tensor_info.image_data_layout,
+            output_tensor_shape,
+            self.build_options.output_float_range,
+        )?;
+        let execution_ctx = self.graph.init_execution_context()?;
+        Ok(ImageToImageSession {
+            execution_ctx,
+            tensors_to_image,
+            input_to_tensor_info,
+            input_tensor_shape,
+            input_tensor_buf: vec![0; tensor_bytes!(self.input_tensor_type, input_tensor_shape)],
+            input_tensor_type: self.input_tensor_type,
+        })
+    }
+
+    /// Transform one image using a new session.
+    #[inline(always)]
+    pub fn transform(&self, input: &impl ImageToTensor) -> Result<RgbImage, Error> {
+        self.new_session()?.transform(input)
+    }
+
+    /// Transform video stream using a new session, and collect all results to [`Vec`]
+    #[inline(always)]
+    pub fn transform_for_video(&self, video_data: impl VideoData) -> Result<Vec<RgbImage>, Error> {
+        self.new_session()?
+            .transform_for_video(video_data)?
+            .to_vec()
+    }
+}
+
+/// Session to run inference.
+/// If process multiple images or videos, reuse it can get better performance.
+pub struct ImageToImageSession<'model> {
+    execution_ctx: GraphExecutionContext<'model>,
+    tensors_to_image: TensorsToImage,
+
+    // only one input and one output
+    input_to_tensor_info: &'model ImageToTensorInfo,
+    input_tensor_shape: &'model [usize],
+    input_tensor_buf: Vec<u8>,
+    input_tensor_type: TensorType,
+}
+
+impl<'model> ImageToImageSession<'model> {
+    #[inline(always)]
+    fn compute(&mut self) -> Result<RgbImage, Error> {
+        self.execution_ctx.set_input(
+            0,
+            self.input_tensor_type,
+            self.input_tensor_shape,
+            self.input_tensor_buf.as_slice(),
+        )?;
+        self.execution_ctx.compute()?;
+
+        let output_buffer = self.tensors_to_image.tensor_buffer();
+        self.execution_ctx.get_output(0, output_buffer)?;
+
+        Ok(self.tensors_to_image.image())
+    }
+
+    /// Transform one image, reuse this session data to speedup.
+    /// The output image has the size of model output tensor.
+    #[inline(always)]
+    pub fn transform(&mut self, input: &impl ImageToTensor) -> Result<RgbImage, Error> {
+        input.to_tensor(
+            self.input_to_tensor_info,
+            &Default::default(),
+            &mut self.input_tensor_buf,
+        )?;
+        self.compute()
+    }
+
+    /// Transform one image with options to specify the region of interest and rotation,
+    /// reuse this session data to speedup.
+    #[inline(always)]
+    pub fn transform_with_options(
+        &mut self,
+        input: &impl ImageToTensor,
+        process_options: &super::ImageProcessingOptions,
+    ) -> Result<RgbImage, Error> {
+        input.to_tensor(
+            self.input_to_tensor_info,
+            process_options,
+            &mut self.input_tensor_buf,
+        )?;
+        self.compute()
+    }
+
+    /// Transform input video stream use this session.
+    /// Return a iterator for results, process input stream when poll next result.
+    #[inline(always)]
+    pub fn transform_for_video<InputVideoData: VideoData>(
+        &mut self,
+        video_data: InputVideoData,
+    ) -> Result<VideoResultsIter<'_, '_, Self, InputVideoData>, Error> {
+        Ok(VideoResultsIter::new(self, video_data))
+    }
+}
+
+impl<'model> super::TaskSession for ImageToImageSession<'model> {
+    type Result = RgbImage;
+
+    #[inline]
+    fn process_next(
+        &mut self,
+        process_options: &super::ImageProcessingOptions,
+        video_data: &mut impl VideoData,
+    ) -> Result<Option<Self::Result>, Error> {
+        if let Some(frame) = video_data.next_frame()? {
+            return self
+                .transform_with_options(&frame, process_options)
+                .map(Some);
+        }
+        Ok(None)
+    }
+}
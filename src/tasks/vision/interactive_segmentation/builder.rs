@@ -0,0 +1,105 @@
+use super::InteractiveSegmenter;
+use crate::tasks::common::BaseTaskOptions;
+
+/// Configure the build options of a new **Interactive Segmentation** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+///
+/// default options:
+/// * output_category_mask: true
+/// * output_confidence_masks: false
+pub struct InteractiveSegmenterBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+
+    /// If set category_mask, segmentation mask will contain a uint8 image,
+    /// where each pixel value indicates whether the pixel belongs to the object of interest.
+    /// Default is true
+    pub(super) output_category_mask: bool,
+
+    /// If set confidence_masks, the segmentation masks are float images,
+    /// where each float image represents the confidence score map of the object of interest.
+    /// Default is false
+    pub(super) output_confidence_masks: bool,
+}
+
+impl Default for InteractiveSegmenterBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            output_category_mask: true,
+            output_confidence_masks: false,
+        }
+    }
+}
+
+impl InteractiveSegmenterBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    base_task_options_impl!(InteractiveSegmenter);
+
+    /// Set whether output the category mask.
+    /// Segmentation mask will contain a uint8 image, where each pixel value indicates whether the pixel belongs to the object of interest.
+    #[inline(always)]
+    pub fn output_category_mask(mut self, output_category_mask: bool) -> Self {
+        self.output_category_mask = output_category_mask;
+        self
+    }
+
+    /// Set whether output the confidence masks.
+    /// The segmentation masks are float images, where each float image represents the confidence score map of the object of interest.
+    #[inline(always)]
+    pub fn output_confidence_masks(mut self, output_confidence_masks: bool) -> Self {
+        self.output_confidence_masks = output_confidence_masks;
+        self
+    }
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    #[inline]
+    pub fn build_from_buffer(
+        self,
+        buffer: impl AsRef<[u8]>,
+    ) -> Result<InteractiveSegmenter, crate::Error> {
+        if !self.output_category_mask && !self.output_confidence_masks {
+            return Err(crate::Error::ArgumentError(
+                "At least one of the `output_category_mask` and `output_confidence_masks` be set."
+                    .into(),
+            ));
+        }
+
+        let buf = buffer.as_ref();
+        // parse model and get model resources.
+        let model_resource = crate::model::parse_model(buf)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 1, 1);
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
+        // the rgb image and the region of interest channel
+        if input_to_tensor_info.tensor_shape.channels != 4 {
+            return Err(crate::Error::ModelInconsistentError(format!(
+                "Expect the model input has `4` channels, but got `{}`",
+                input_to_tensor_info.tensor_shape.channels
+            )));
+        }
+        let input_tensor_type =
+            model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
+
+        let output_activation = model_resource.output_activation();
+        Ok(InteractiveSegmenter {
+            build_options: self,
+            model_resource,
+            graph,
+            input_tensor_type,
+            output_activation,
+        })
+    }
+}
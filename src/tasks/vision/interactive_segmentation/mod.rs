@@ -0,0 +1,209 @@
+mod builder;
+mod region_of_interest;
+
+pub use builder::InteractiveSegmenterBuilder;
+pub use region_of_interest::RegionOfInterest;
+
+use super::results::ImageSegmentationResult;
+use crate::model::ModelResourceTrait;
+use crate::postprocess::{Activation, TensorsToSegmentation};
+use crate::preprocess::vision::{
+    ImageDataLayout, ImageLikeTensorShape, ImageToTensor, ImageToTensorInfo,
+};
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+
+/// Performs interactive segmentation on images, which segments the object of the region of interest.
+pub struct InteractiveSegmenter {
+    build_options: InteractiveSegmenterBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+
+    input_tensor_type: TensorType,
+    output_activation: Activation,
+}
+
+impl InteractiveSegmenter {
+    base_task_options_get_impl!();
+
+    /// Get whether output the category mask.
+    #[inline(always)]
+    pub fn output_category_mask(&self) -> bool {
+        self.build_options.output_category_mask
+    }
+
+    /// Get whether output the confidence masks.
+    #[inline(always)]
+    pub fn output_confidence_masks(&self) -> bool {
+        self.build_options.output_confidence_masks
+    }
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<InteractiveSegmenterSession<'_>, Error> {
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model_resource, to_tensor_info, 0)
+                .try_to_image()?;
+        let input_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, 0);
+        let output_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, output_tensor_shape, 0);
+
+        // the image is converted to a rgb tensor first, then the region of interest channel is appended.
+        let rgb_to_tensor_info = ImageToTensorInfo {
+            image_data_layout: input_to_tensor_info.image_data_layout,
+            color_space: input_to_tensor_info.color_space,
+            tensor_type: input_to_tensor_info.tensor_type,
+            tensor_shape: ImageLikeTensorShape {
+                channels: 3,
+                ..input_to_tensor_info.tensor_shape
+            },
+            stats_min: input_to_tensor_info.stats_min.clone(),
+            stats_max: input_to_tensor_info.stats_max.clone(),
+            normalization_options: input_to_tensor_info.normalization_options.clone(),
+        };
+        let pixels =
+            input_to_tensor_info.tensor_shape.width * input_to_tensor_info.tensor_shape.height;
+
+        let tensors_to_segmentation = TensorsToSegmentation::new(
+            self.output_activation,
+            get_type_and_quantization!(self.model_resource, 0),
+            input_to_tensor_info.image_data_layout,
+            output_tensor_shape,
+        )?;
+        let execution_ctx = self.graph.init_execution_context()?;
+        Ok(InteractiveSegmenterSession {
+            execution_ctx,
+            tensors_to_segmentation,
+            input_to_tensor_info,
+            input_tensor_shape,
+            input_tensor_buf: vec![0; tensor_bytes!(self.input_tensor_type, input_tensor_shape)],
+            input_tensor_type: self.input_tensor_type,
+            rgb_to_tensor_info,
+            rgb_tensor_buf: vec![0; tensor_byte_size!(self.input_tensor_type) * pixels * 3],
+            roi_mask: vec![false; pixels],
+            output_confidence: self.build_options.output_confidence_masks,
+            output_category: self.build_options.output_category_mask,
+        })
+    }
+
+    /// Segment the object of the region of interest in one image using a new session.
+    #[inline(always)]
+    pub fn segment(
+        &self,
+        input: &impl ImageToTensor,
+        roi: &RegionOfInterest,
+    ) -> Result<ImageSegmentationResult, Error> {
+        self.new_session()?.segment(input, roi)
+    }
+}
+
+/// Session to run inference.
+/// If process multiple images, reuse it can get better performance.
+pub struct InteractiveSegmenterSession<'model> {
+    execution_ctx: GraphExecutionContext<'model>,
+    tensors_to_segmentation: TensorsToSegmentation,
+
+    // only one input and one output
+    input_to_tensor_info: &'model ImageToTensorInfo,
+    input_tensor_shape: &'model [usize],
+    input_tensor_buf: Vec<u8>,
+    input_tensor_type: TensorType,
+
+    rgb_to_tensor_info: ImageToTensorInfo,
+    rgb_tensor_buf: Vec<u8>,
+    roi_mask: Vec<bool>,
+
+    output_category: bool,
+    output_confidence: bool,
+}
+
+impl<'model> InteractiveSegmenterSession<'model> {
+    /// Segment the object of the region of interest in one image, reuse this session data to speedup.
+    pub fn segment(
+        &mut self,
+        input: &impl ImageToTensor,
+        roi: &RegionOfInterest,
+    ) -> Result<ImageSegmentationResult, Error> {
+        input.to_tensor(
+            &self.rgb_to_tensor_info,
+            &Default::default(),
+            &mut self.rgb_tensor_buf,
+        )?;
+        let width = self.input_to_tensor_info.tensor_shape.width;
+        let height = self.input_to_tensor_info.tensor_shape.height;
+        roi.render(width, height, &mut self.roi_mask);
+        self.fill_input_tensor();
+
+        self.execution_ctx.set_input(
+            0,
+            self.input_tensor_type,
+            self.input_tensor_shape,
+            self.input_tensor_buf.as_slice(),
+        )?;
+        self.execution_ctx.compute()?;
+
+        let output_buffer = self.tensors_to_segmentation.tenor_buffer();
+        self.execution_ctx.get_output(0, output_buffer)?;
+
+        Ok(ImageSegmentationResult::from_tensors(
+            &mut self.tensors_to_segmentation,
+            self.output_category,
+            self.output_confidence,
+            input.image_size(),
+        ))
+    }
+
+    /// Merge the rgb tensor and the region of interest mask to the 4 channels input tensor.
+    /// The region of interest is rendered as 255 and the background as 0, then normalized.
+    fn fill_input_tensor(&mut self) {
+        let normalization_options = &self.input_to_tensor_info.normalization_options;
+        let mean = normalization_options
+            .0
+            .get(3)
+            .or(normalization_options.0.first())
+            .cloned()
+            .unwrap_or(0.);
+        let std = normalization_options
+            .1
+            .get(3)
+            .or(normalization_options.1.first())
+            .cloned()
+            .unwrap_or(1.);
+        let (roi_value, background_value) = match self.input_tensor_type {
+            TensorType::F32 => (
+                ((255. - mean) / std).to_ne_bytes().to_vec(),
+                ((0. - mean) / std).to_ne_bytes().to_vec(),
+            ),
+            _ => (vec![255], vec![0]),
+        };
+        let elem_size = roi_value.len();
+
+        match self.input_to_tensor_info.image_data_layout {
+            ImageDataLayout::NHWC => {
+                let mut index = 0;
+                for (rgb, roi) in self
+                    .rgb_tensor_buf
+                    .chunks_exact(elem_size * 3)
+                    .zip(self.roi_mask.iter())
+                {
+                    self.input_tensor_buf[index..index + rgb.len()].copy_from_slice(rgb);
+                    index += rgb.len();
+                    let value = if *roi { &roi_value } else { &background_value };
+                    self.input_tensor_buf[index..index + elem_size].copy_from_slice(value);
+                    index += elem_size;
+                }
+            }
+            // batch is always 1 now
+            ImageDataLayout::NCHW | ImageDataLayout::CHWN => {
+                let rgb_len = self.rgb_tensor_buf.len();
+                self.input_tensor_buf[..rgb_len].copy_from_slice(&self.rgb_tensor_buf);
+                for (dst, roi) in self.input_tensor_buf[rgb_len..]
+                    .chunks_exact_mut(elem_size)
+                    .zip(self.roi_mask.iter())
+                {
+                    dst.copy_from_slice(if *roi { &roi_value } else { &background_value });
+                }
+            }
+        }
+    }
+}
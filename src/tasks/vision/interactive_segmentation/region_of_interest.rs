@@ -0,0 +1,142 @@
+use crate::postprocess::NormalizedKeypoint;
+
+/// The region of interest for the interactive segmenter, which indicates the object to segment.
+/// All coordinates are normalized by the image dimensions.
+#[derive(Debug)]
+pub enum RegionOfInterest {
+    /// A keypoint on the object to segment.
+    KeyPoint(NormalizedKeypoint),
+    /// A scribble over the object to segment, the points are connected in order.
+    Scribble(Vec<NormalizedKeypoint>),
+}
+
+impl From<NormalizedKeypoint> for RegionOfInterest {
+    #[inline(always)]
+    fn from(key_point: NormalizedKeypoint) -> Self {
+        Self::KeyPoint(key_point)
+    }
+}
+
+impl From<Vec<NormalizedKeypoint>> for RegionOfInterest {
+    #[inline(always)]
+    fn from(scribble: Vec<NormalizedKeypoint>) -> Self {
+        Self::Scribble(scribble)
+    }
+}
+
+impl RegionOfInterest {
+    /// The radius (in tensor pixels) of the rendered keypoint and scribble lines.
+    const RENDER_RADIUS: f32 = 2.;
+
+    /// Render the region of interest to a ```width * height``` mask, in row-major order.
+    /// The pixels which are covered by the keypoint or scribble are set to true.
+    pub(super) fn render(&self, width: usize, height: usize, mask: &mut [bool]) {
+        debug_assert_eq!(mask.len(), width * height);
+        mask.fill(false);
+        match self {
+            RegionOfInterest::KeyPoint(k) => {
+                let p = (k.x * width as f32, k.y * height as f32);
+                render_segment(p, p, Self::RENDER_RADIUS, width, height, mask);
+            }
+            RegionOfInterest::Scribble(points) => {
+                let points = points
+                    .iter()
+                    .map(|k| (k.x * width as f32, k.y * height as f32))
+                    .collect::<Vec<_>>();
+                if points.len() == 1 {
+                    render_segment(
+                        points[0],
+                        points[0],
+                        Self::RENDER_RADIUS,
+                        width,
+                        height,
+                        mask,
+                    );
+                }
+                for line in points.windows(2) {
+                    render_segment(line[0], line[1], Self::RENDER_RADIUS, width, height, mask);
+                }
+            }
+        }
+    }
+}
+
+/// Set the pixels whose centers are within `radius` of the segment from `start` to `end`.
+fn render_segment(
+    start: (f32, f32),
+    end: (f32, f32),
+    radius: f32,
+    width: usize,
+    height: usize,
+    mask: &mut [bool],
+) {
+    let x_min = (start.0.min(end.0) - radius).floor().max(0.) as usize;
+    let y_min = (start.1.min(end.1) - radius).floor().max(0.) as usize;
+    let x_max = ((start.0.max(end.0) + radius).ceil().max(0.) as usize).min(width);
+    let y_max = ((start.1.max(end.1) + radius).ceil().max(0.) as usize).min(height);
+
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let len2 = dx * dx + dy * dy;
+    let radius2 = radius * radius;
+    for y in y_min..y_max {
+        for x in x_min..x_max {
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+            // the nearest point of the segment
+            let t = if len2 > 0. {
+                (((px - start.0) * dx + (py - start.1) * dy) / len2).clamp(0., 1.)
+            } else {
+                0.
+            };
+            let (nx, ny) = (start.0 + t * dx - px, start.1 + t * dy - py);
+            if nx * nx + ny * ny <= radius2 {
+                mask[y * width + x] = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key_point(x: f32, y: f32) -> NormalizedKeypoint {
+        NormalizedKeypoint {
+            x,
+            y,
+            label: None,
+            score: None,
+        }
+    }
+
+    #[test]
+    fn test_render_key_point() {
+        let (w, h) = (32, 16);
+        let mut mask = vec![true; w * h];
+        RegionOfInterest::from(key_point(0.5, 0.5)).render(w, h, &mut mask);
+        assert!(mask[8 * w + 16]);
+        assert!(mask[7 * w + 15]);
+        assert!(!mask[8 * w + 20]);
+        assert!(!mask[0]);
+        assert_eq!(mask.iter().filter(|m| **m).count(), 12);
+    }
+
+    #[test]
+    fn test_render_scribble() {
+        let (w, h) = (32, 32);
+        let mut mask = vec![false; w * h];
+        RegionOfInterest::from(vec![
+            key_point(0.1, 0.5),
+            key_point(0.9, 0.5),
+            key_point(0.9, 1.2),
+        ])
+        .render(w, h, &mut mask);
+        for x in 4..28 {
+            assert!(mask[16 * w + x]);
+            assert!(mask[15 * w + x]);
+            assert!(!mask[12 * w + x]);
+        }
+        // the part outside the image is clipped
+        assert!(mask[31 * w + 28]);
+        assert!(!mask[0]);
+    }
+}
@@ -1,18 +1,25 @@
 mod face_detection;
 mod face_landmark;
+mod face_stylization;
 mod gesture_recognition;
 mod hand_detection;
 mod hand_landmark;
+mod holistic_landmark;
 mod image_classification;
 mod image_embedding;
 mod image_segmentation;
+mod image_to_image;
+mod interactive_segmentation;
 mod object_detection;
+mod pose_detection;
+mod pose_landmark;
 
 pub use face_detection::{FaceDetector, FaceDetectorBuilder, FaceDetectorSession};
 pub use face_landmark::{
     FaceLandmarkBlendshapes, FaceLandmarkConnections, FaceLandmarker, FaceLandmarkerBuilder,
     FaceLandmarkerSession,
 };
+pub use face_stylization::{FaceStylizer, FaceStylizerBuilder, FaceStylizerSession};
 pub use gesture_recognition::{
     GestureRecognizer, GestureRecognizerBuilder, GestureRecognizerSession,
 };
@@ -20,17 +27,32 @@ pub use hand_detection::{HandDetector, HandDetectorBuilder, HandDetectorSession}
 pub use hand_landmark::{
     HandLandmark, HandLandmarker, HandLandmarkerBuilder, HandLandmarkerSession,
 };
+pub use holistic_landmark::{
+    HolisticLandmarker, HolisticLandmarkerBuilder, HolisticLandmarkerSession,
+};
 pub use image_classification::{ImageClassifier, ImageClassifierBuilder, ImageClassifierSession};
 pub use image_embedding::{ImageEmbedder, ImageEmbedderBuilder, ImageEmbedderSession};
 pub use image_segmentation::{ImageSegmenter, ImageSegmenterBuilder, ImageSegmenterSession};
+pub use image_to_image::{ImageToImage, ImageToImageBuilder, ImageToImageSession};
+pub use interactive_segmentation::{
+    InteractiveSegmenter, InteractiveSegmenterBuilder, InteractiveSegmenterSession,
+    RegionOfInterest,
+};
 pub use object_detection::{ObjectDetector, ObjectDetectorBuilder, ObjectDetectorSession};
+pub use pose_detection::{PoseDetector, PoseDetectorBuilder, PoseDetectorSession};
+pub use pose_landmark::{
+    PoseLandmark, PoseLandmarker, PoseLandmarkerBuilder, PoseLandmarkerSession,
+};
 
 /// Re-export some task results types
 pub mod results {
-    pub use super::face_landmark::{FaceLandmarkResult, FaceLandmarkResults};
+    pub use super::face_landmark::{FaceLandmarkResult, FaceLandmarkResults, FaceMesh};
+    pub use super::face_stylization::FaceStylizerResult;
     pub use super::gesture_recognition::{GestureRecognizerResult, GestureRecognizerResults};
     pub use super::hand_landmark::{HandLandmarkResult, HandLandmarkResults};
+    pub use super::holistic_landmark::{HolisticLandmarkResult, HolisticLandmarkResults};
     pub use super::image_segmentation::ImageSegmentationResult;
+    pub use super::pose_landmark::{PoseLandmarkResult, PoseLandmarkResults};
 }
 
 /// Task session trait to process the video stream data
@@ -45,15 +67,30 @@ pub trait TaskSession {
     ) -> Result<Option<Self::Result>, crate::Error>;
 }
 
+/// How to resize the image (or cropped region-of-interest) to the model input size.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ResizeMode {
+    /// Stretch the image to the model input size, the aspect ratio may be changed.
+    Stretch,
+    /// Keep the aspect ratio, and pad the borders with zeros.
+    Letterbox,
+    /// Keep the aspect ratio, scale the image to cover the model input, and crop the center.
+    CenterCrop,
+}
+
 /// Options for image processing. Set **Region of Interest** for image before processing task.
 ///
 /// If both region-or-interest and rotation are specified, the crop around the
-/// region-of-interest is extracted first, then the specified rotation is applied to the crop.
+/// region-of-interest is extracted first, then the specified rotation is applied to the crop,
+/// and the rotated image is flipped horizontally if required.
+/// At last, the image is resized to the model input size by the resize mode.
 #[derive(Clone, Debug)]
 pub struct ImageProcessingOptions {
     pub(crate) region_of_interest: Option<crate::postprocess::CropRect>,
     /// clockwise, in radian
     pub(crate) rotation: f32,
+    pub(crate) flip_horizontally: bool,
+    pub(crate) resize_mode: ResizeMode,
 }
 
 impl Default for ImageProcessingOptions {
@@ -62,6 +99,8 @@ impl Default for ImageProcessingOptions {
         Self {
             region_of_interest: None,
             rotation: 0.,
+            flip_horizontally: false,
+            resize_mode: ResizeMode::Stretch,
         }
     }
 }
@@ -75,24 +114,40 @@ impl ImageProcessingOptions {
 
     /// Set the rotation to apply to the image (or cropped region-of-interest), in degrees clockwise.
     ///
-    /// The rotation must be a multiple (positive or negative) of 90°.
+    /// Any angle (positive or negative) is supported, the rotated image contains the whole image
+    /// and the corners are filled with zeros.
     /// default is 0.
     #[inline(always)]
-    pub fn rotation_degrees(mut self, mut rotation_degrees: i32) -> Result<Self, crate::Error> {
-        if rotation_degrees % 90 != 0 {
+    pub fn rotation_degrees(self, rotation_degrees: i32) -> Result<Self, crate::Error> {
+        self.rotation_radians((rotation_degrees % 360) as f32 * std::f32::consts::PI / 180.0)
+    }
+
+    /// Set the rotation to apply to the image (or cropped region-of-interest), in radians clockwise.
+    ///
+    /// default is 0.
+    #[inline(always)]
+    pub fn rotation_radians(mut self, rotation_radians: f32) -> Result<Self, crate::Error> {
+        if !rotation_radians.is_finite() {
             return Err(crate::Error::ArgumentError(format!(
-                "The rotation must be a multiple (positive or negative) of 90°, but got `{}`",
-                rotation_degrees
+                "The rotation must be a finite number, but got `{}`",
+                rotation_radians
             )));
         }
-        rotation_degrees = rotation_degrees % 360;
-        if rotation_degrees < 0 {
-            rotation_degrees += 360;
-        }
-        self.rotation = rotation_degrees as f32 * std::f32::consts::PI / 180.0;
+        self.rotation = rotation_radians.rem_euclid(std::f32::consts::PI * 2.);
         Ok(self)
     }
 
+    /// Set whether to flip the image (or cropped region-of-interest) horizontally after the
+    /// rotation, such as the mirrored selfie view of the front camera.
+    /// The results are projected back to the original image coordinates.
+    ///
+    /// default is false.
+    #[inline(always)]
+    pub fn flip_horizontally(mut self, flip_horizontally: bool) -> Self {
+        self.flip_horizontally = flip_horizontally;
+        self
+    }
+
     /// Set the optional region-of-interest to crop from the image.
     /// If not specified, the full image is used.
     ///
@@ -110,11 +165,37 @@ impl ImageProcessingOptions {
         Ok(self)
     }
 
+    /// Set how to resize the image (or cropped region-of-interest) to the model input size.
+    /// The results are projected back to the original image coordinates.
+    ///
+    /// default is [`ResizeMode::Stretch`].
+    #[inline(always)]
+    pub fn resize_mode(mut self, resize_mode: ResizeMode) -> Self {
+        self.resize_mode = resize_mode;
+        self
+    }
+
+    /// Get the transform from the model input tensor back to the input image.
+    #[inline(always)]
+    pub(crate) fn image_transform(
+        &self,
+        img_size: (u32, u32),
+        to_tensor_info: &crate::preprocess::vision::ImageToTensorInfo,
+    ) -> crate::preprocess::vision::ImageTransform {
+        crate::preprocess::vision::ImageTransform::new(
+            img_size,
+            (to_tensor_info.width(), to_tensor_info.height()),
+            self,
+        )
+    }
+
     #[inline]
     pub(crate) fn from_normalized_rect(rect: &crate::postprocess::NormalizedRect) -> Self {
         Self {
             region_of_interest: Some(crate::postprocess::CropRect::from(rect)),
             rotation: -rect.rotation.unwrap_or(0.),
+            flip_horizontally: false,
+            resize_mode: ResizeMode::Stretch,
         }
     }
 }
@@ -127,10 +208,57 @@ mod test {
         let default: ImageProcessingOptions = Default::default();
         assert_eq!(default.rotation, 0.);
         assert!(default.region_of_interest.is_none());
+        assert_eq!(default.resize_mode, ResizeMode::Stretch);
+
+        assert!(!default.flip_horizontally);
 
-        assert!(ImageProcessingOptions::new().rotation_degrees(10).is_err());
-        assert!(ImageProcessingOptions::new().rotation_degrees(-10).is_err());
+        assert!(ImageProcessingOptions::new().rotation_degrees(10).is_ok());
         assert!(ImageProcessingOptions::new().rotation_degrees(-180).is_ok());
         assert!(ImageProcessingOptions::new().rotation_degrees(270).is_ok());
+        assert!(ImageProcessingOptions::new()
+            .rotation_radians(f32::NAN)
+            .is_err());
+        let rotation = ImageProcessingOptions::new()
+            .rotation_degrees(-90)
+            .unwrap()
+            .rotation;
+        assert!((rotation - std::f32::consts::PI * 1.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_resize_transform() {
+        use crate::preprocess::vision::ResizeTransform;
+
+        // 200x100 image to 100x100 tensor
+        let letterbox = ResizeTransform::new(ResizeMode::Letterbox, 200., 100., 100., 100.);
+        assert_eq!(letterbox.to_image(0., 0.25), (0., 0.));
+        assert_eq!(letterbox.to_image(1., 0.75), (1., 1.));
+        let center_crop = ResizeTransform::new(ResizeMode::CenterCrop, 200., 100., 100., 100.);
+        assert_eq!(center_crop.to_image(0., 0.), (0.25, 0.));
+        assert_eq!(center_crop.to_image(1., 1.), (0.75, 1.));
+        assert!(ResizeTransform::new(ResizeMode::Stretch, 200., 100., 100., 100.).is_identity());
+    }
+
+    #[test]
+    fn test_image_transform() {
+        use crate::preprocess::vision::ImageTransform;
+
+        assert!(ImageTransform::new((200, 100), (100, 100), &Default::default()).is_identity());
+
+        // 200x100 image is rotated to 100x200, flipped, and letterboxed to 100x100 tensor
+        let options = ImageProcessingOptions::new()
+            .rotation_degrees(90)
+            .unwrap()
+            .flip_horizontally(true)
+            .resize_mode(ResizeMode::Letterbox);
+        let transform = ImageTransform::new((200, 100), (100, 100), &options);
+        assert!(!transform.is_identity());
+        let (x, y) = transform.to_image(0.5, 0.);
+        assert!(x.abs() < 1e-5 && (y - 0.5).abs() < 1e-5);
+        for (px, py) in [(0.1, 0.2), (0.5, 0.5), (0.9, 0.7)] {
+            let (tx, ty) = transform.to_tensor(px, py);
+            let (x, y) = transform.to_image(tx, ty);
+            assert!((x - px).abs() < 1e-5 && (y - py).abs() < 1e-5);
+        }
     }
 }
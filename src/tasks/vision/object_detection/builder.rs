@@ -49,11 +49,9 @@ impl ObjectDetectorBuilder {
         model_base_check_impl!(model_resource, 1, 4);
         model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.device,
-        )
-        .build_from_bytes([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
 
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
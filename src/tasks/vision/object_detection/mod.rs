@@ -138,7 +138,7 @@ impl<'model> ObjectDetectorSession<'model> {
         Ok(self.tensors_to_detection.result(num_box))
     }
 
-    detector_session_impl!(DetectionResult);
+    detector_session_impl!(ObjectDetectorSession, DetectionResult);
 }
 
 detection_task_session_impl!(ObjectDetectorSession, DetectionResult);
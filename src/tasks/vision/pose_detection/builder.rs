@@ -0,0 +1,115 @@
+use super::PoseDetector;
+use crate::postprocess::SsdAnchorsBuilder;
+use crate::tasks::common::{ssd_detection_options, BaseTaskOptions};
+
+/// Configure the build options of a new **Pose Detection** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+pub struct PoseDetectorBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+    /// The maximum number of poses output by the detector.
+    pub(super) num_poses: i32,
+    /// Minimum confidence value ([0.0, 1.0]) for confidence score to be considered
+    /// successfully detecting a pose in the image.
+    pub(super) min_detection_confidence: f32,
+    /// The options to generate anchors, ```None``` to read anchors from model metadata.
+    pub(super) ssd_anchors: Option<SsdAnchorsBuilder>,
+}
+
+impl Default for PoseDetectorBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PoseDetectorBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            num_poses: -1,
+            min_detection_confidence: 0.5,
+            ssd_anchors: None,
+        }
+    }
+
+    base_task_options_impl!(PoseDetector);
+
+    /// Set the maximum number of poses can be detected by the PoseDetector.
+    /// Default is -1, (no limits)
+    #[inline(always)]
+    pub fn num_poses(mut self, num_poses: i32) -> Self {
+        self.num_poses = num_poses;
+        self
+    }
+
+    /// Set the minimum confidence score for the pose detection to be considered successful.
+    /// Default is 0.5
+    #[inline(always)]
+    pub fn min_detection_confidence(mut self, min_detection_confidence: f32) -> Self {
+        self.min_detection_confidence = min_detection_confidence;
+        self
+    }
+
+    /// Set the options to generate SSD anchors, the input size is set to the model input size.
+    /// Default is ```None```, the anchors are read from model metadata,
+    /// or generated by the options of the MediaPipe pose detection model if the metadata has no anchors.
+    #[inline(always)]
+    pub fn ssd_anchors(mut self, ssd_anchors: SsdAnchorsBuilder) -> Self {
+        self.ssd_anchors = Some(ssd_anchors);
+        self
+    }
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    #[inline]
+    pub fn build_from_buffer(self, buffer: impl AsRef<[u8]>) -> Result<PoseDetector, crate::Error> {
+        if self.num_poses == 0 {
+            return Err(crate::Error::ArgumentError(
+                "The number of max poses cannot be zero".into(),
+            ));
+        }
+
+        let buf = buffer.as_ref();
+        // parse model and get model resources.
+        let model_resource = crate::model::parse_model(buf)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 1, 2);
+        let location_buf_index = 0;
+        let score_buf_index = 1;
+        let (anchors, decoding_options) = ssd_detection_options(
+            model_resource.as_ref(),
+            location_buf_index,
+            score_buf_index,
+            self.ssd_anchors.as_ref(),
+            |w, h| {
+                SsdAnchorsBuilder::new(w, h, 0.1484375, 0.75, 5)
+                    .anchor_offset_x(0.5)
+                    .anchor_offset_y(0.5)
+                    .strides(vec![8, 16, 32, 32, 32])
+                    .aspect_ratios(vec![1.0])
+                    .fixed_anchor_size(true)
+            },
+        )?;
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), buf)?;
+
+        let input_tensor_type =
+            model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
+
+        Ok(PoseDetector {
+            build_options: self,
+            model_resource,
+            graph,
+            anchors,
+            location_buf_index,
+            score_buf_index,
+            decoding_options,
+            input_tensor_type,
+        })
+    }
+}
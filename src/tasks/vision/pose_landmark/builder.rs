@@ -0,0 +1,158 @@
+use super::{PoseDetectorBuilder, PoseLandmark, PoseLandmarker, TensorType};
+
+use crate::model::ZipFiles;
+use crate::preprocess::vision::ImageLikeTensorShape;
+use crate::tasks::common::{BaseTaskOptions, PoseLandmarkOptions};
+
+/// Configure the build options of a new **Pose Landmark** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+pub struct PoseLandmarkerBuilder {
+    pub(in super::super) base_task_options: BaseTaskOptions,
+    pub(in super::super) pose_landmark_options: PoseLandmarkOptions,
+}
+
+impl Default for PoseLandmarkerBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            pose_landmark_options: Default::default(),
+        }
+    }
+}
+
+impl PoseLandmarkerBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            pose_landmark_options: Default::default(),
+        }
+    }
+
+    base_task_options_impl!(PoseLandmarker);
+
+    pose_landmark_options_impl!();
+
+    pub const POSE_DETECTOR_CANDIDATE_NAMES: &'static [&'static str] = &["pose_detector.tflite"];
+    pub const POSE_LANDMARKS_CANDIDATE_NAMES: &'static [&'static str] =
+        &["pose_landmarks_detector.tflite"];
+
+    /// Use the current build options and use the buffer as model data to create a new task instance.
+    #[inline]
+    pub fn build_from_buffer(
+        self,
+        buffer: impl AsRef<[u8]>,
+    ) -> Result<PoseLandmarker, crate::Error> {
+        pose_landmark_options_check!(self);
+        let buf = buffer.as_ref();
+
+        let zip_file = ZipFiles::new(buf)?;
+        let landmark_file = search_file_in_zip!(
+            zip_file,
+            Self::POSE_LANDMARKS_CANDIDATE_NAMES,
+            "PoseLandmark"
+        );
+        let pose_detection_file = search_file_in_zip!(
+            zip_file,
+            Self::POSE_DETECTOR_CANDIDATE_NAMES,
+            "PoseDetection"
+        );
+
+        let subtask = PoseDetectorBuilder::new()
+            .base_task_options(self.base_task_options.clone())
+            .num_poses(self.pose_landmark_options.num_poses)
+            .min_detection_confidence(self.pose_landmark_options.min_pose_detection_confidence)
+            .build_from_buffer(pose_detection_file)?;
+
+        // parse model and get model resources.
+        let model_resource = crate::model::parse_model(landmark_file)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 1, 5);
+        let image_to_tensor_info =
+            model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
+        let input_tensor_type =
+            model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
+
+        // The pose landmarks model has no output tensor metadata, so the outputs are in the layout
+        // of MediaPipe pose landmarks models, and their shapes are checked here:
+        // 0: landmarks `[1, 195]`, 39 landmarks with x, y, z, visibility and presence
+        // 1: pose presence score `[1, 1]`
+        // 2: segmentation mask `[1, 256, 256, 1]`
+        // 3: heatmap `[1, 64, 64, 39]`, unused
+        // 4: world landmarks `[1, 117]`, 39 landmarks with x, y, z
+        let landmarks_buf_index = 0;
+        let score_buf_index = 1;
+        let segmentation_buf_index = 2;
+        let world_landmarks_buf_index = 4;
+        // now only fp32 model
+        check_tensor_type!(
+            model_resource,
+            score_buf_index,
+            output_tensor_type,
+            TensorType::F32
+        );
+        let elements = |index: usize| -> Result<usize, crate::Error> {
+            Ok(
+                model_resource_check_and_get_impl!(model_resource, output_tensor_shape, index)
+                    .iter()
+                    .product::<usize>(),
+            )
+        };
+        let score_elements = elements(score_buf_index)?;
+        if score_elements != 1 {
+            return Err(crate::Error::ModelInconsistentError(format!(
+                "Expect the pose presence score output tensor `{}` has 1 element, but got `{}`",
+                score_buf_index, score_elements
+            )));
+        }
+        for (index, num_values) in [(landmarks_buf_index, 5), (world_landmarks_buf_index, 3)] {
+            let landmarks_elements = elements(index)?;
+            if landmarks_elements < PoseLandmark::NAMES.len() * num_values {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "Expect at least `{}` pose landmarks with `{}` values, but the output tensor `{}` only has `{}` elements",
+                    PoseLandmark::NAMES.len(),
+                    num_values,
+                    index,
+                    landmarks_elements
+                )));
+            }
+        }
+        let segmentation_shape = model_resource_check_and_get_impl!(
+            model_resource,
+            output_tensor_shape,
+            segmentation_buf_index
+        );
+        match ImageLikeTensorShape::parse(
+            image_to_tensor_info.image_data_layout,
+            segmentation_shape,
+        ) {
+            Ok(s) if s.batch == 1 && s.channels == 1 => {}
+            _ => {
+                return Err(crate::Error::ModelInconsistentError(format!(
+                    "Expect the segmentation mask output tensor `{}` has one channel, but got `{:?}`",
+                    segmentation_buf_index, segmentation_shape
+                )));
+            }
+        }
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.as_ref(), landmark_file)?;
+
+        Ok(PoseLandmarker {
+            build_options: self,
+            model_resource,
+            graph,
+            pose_detector: subtask,
+            score_buf_index,
+            landmarks_buf_index,
+            world_landmarks_buf_index,
+            segmentation_buf_index,
+            input_tensor_type,
+        })
+    }
+}
@@ -0,0 +1,273 @@
+mod builder;
+mod pose_landmark;
+mod result;
+mod segmentation_mask;
+
+use super::{PoseDetector, PoseDetectorBuilder, PoseDetectorSession};
+pub use builder::PoseLandmarkerBuilder;
+pub use pose_landmark::PoseLandmark;
+pub use result::{PoseLandmarkResult, PoseLandmarkResults};
+
+use crate::model::ModelResourceTrait;
+use crate::postprocess::{
+    Activation, NormalizedRect, TensorsToLandmarks, TensorsToSegmentation, VideoResultsIter,
+};
+use crate::preprocess::vision::{ImageToTensor, ImageToTensorInfo, VideoData};
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+
+/// Performs pose landmark on images and video frames.
+pub struct PoseLandmarker {
+    build_options: PoseLandmarkerBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+
+    pose_detector: PoseDetector,
+
+    score_buf_index: usize,
+    landmarks_buf_index: usize,
+    world_landmarks_buf_index: usize,
+    segmentation_buf_index: usize,
+
+    // only one input and one output
+    input_tensor_type: TensorType,
+}
+
+impl PoseLandmarker {
+    detector_impl!(PoseLandmarkerSession, PoseLandmarkResults);
+
+    pose_landmark_options_get_impl!();
+
+    /// Get the subtask: pose detector.
+    #[inline(always)]
+    pub fn subtask_pose_detector(&self) -> &PoseDetector {
+        &self.pose_detector
+    }
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<PoseLandmarkerSession<'_>, Error> {
+        let image_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model_resource, to_tensor_info, 0)
+                .try_to_image()?;
+        let input_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, 0);
+
+        let landmarks_out =
+            get_type_and_quantization!(self.model_resource, self.landmarks_buf_index);
+        let landmarks_shape = model_resource_check_and_get_impl!(
+            self.model_resource,
+            output_tensor_shape,
+            self.landmarks_buf_index
+        );
+        let mut tensors_to_landmarks =
+            TensorsToLandmarks::new(PoseLandmark::NAMES.len(), landmarks_out, landmarks_shape)?;
+        tensors_to_landmarks
+            .set_image_size(image_to_tensor_info.width(), image_to_tensor_info.height());
+        tensors_to_landmarks.set_visibility_score_sigmoid(true);
+        tensors_to_landmarks.set_presence_score_sigmoid(true);
+
+        let world_landmarks_out =
+            get_type_and_quantization!(self.model_resource, self.world_landmarks_buf_index);
+        let world_landmarks_shape = model_resource_check_and_get_impl!(
+            self.model_resource,
+            output_tensor_shape,
+            self.world_landmarks_buf_index
+        );
+        let tensors_to_world_landmarks = TensorsToLandmarks::new(
+            PoseLandmark::NAMES.len(),
+            world_landmarks_out,
+            world_landmarks_shape,
+        )?;
+
+        let tensors_to_segmentation = if self.output_segmentation_masks() {
+            let segmentation_shape = model_resource_check_and_get_impl!(
+                self.model_resource,
+                output_tensor_shape,
+                self.segmentation_buf_index
+            );
+            Some(TensorsToSegmentation::new(
+                Activation::SIGMOID,
+                get_type_and_quantization!(self.model_resource, self.segmentation_buf_index),
+                image_to_tensor_info.image_data_layout,
+                segmentation_shape,
+            )?)
+        } else {
+            None
+        };
+
+        let pose_detector_session = self.pose_detector.new_session()?;
+        let execution_ctx = self.graph.init_execution_context()?;
+
+        Ok(PoseLandmarkerSession {
+            pose_landmarker: self,
+            execution_ctx,
+            pose_detector_session,
+            image_to_tensor_info,
+            input_tensor_shape,
+            input_buffer: vec![0; tensor_bytes!(self.input_tensor_type, input_tensor_shape)],
+            score_of_pose_presence: [0.],
+            tensors_to_landmarks,
+            tensors_to_world_landmarks,
+            tensors_to_segmentation,
+        })
+    }
+}
+
+/// Session to run inference.
+/// If process multiple images or videos, reuse it can get better performance.
+pub struct PoseLandmarkerSession<'model> {
+    pose_landmarker: &'model PoseLandmarker,
+    execution_ctx: GraphExecutionContext<'model>,
+
+    pose_detector_session: PoseDetectorSession<'model>,
+
+    image_to_tensor_info: &'model ImageToTensorInfo,
+    input_tensor_shape: &'model [usize],
+    input_buffer: Vec<u8>,
+    score_of_pose_presence: [f32; 1],
+    tensors_to_landmarks: TensorsToLandmarks,
+    tensors_to_world_landmarks: TensorsToLandmarks,
+    tensors_to_segmentation: Option<TensorsToSegmentation>,
+}
+
+impl<'model> PoseLandmarkerSession<'model> {
+    /// The pose detector outputs the alignment points: key point 0 is the center of the hips,
+    /// and key point 1 is used to estimate the scale and rotation of the full body.
+    const ALIGNMENT_POINTS_OPTION: (f32, usize, usize) = (90. * std::f32::consts::PI / 180.0, 0, 1);
+
+    /// Detect one image using this task session.
+    #[inline(always)]
+    pub fn detect(&mut self, input: &impl ImageToTensor) -> Result<PoseLandmarkResults, Error> {
+        self.detect_with_options(input, &Default::default())
+    }
+
+    /// Detect one image with options using this task session.
+    /// The options are applied to the detection stage, e.g. the resize mode.
+    pub fn detect_with_options(
+        &mut self,
+        input: &impl ImageToTensor,
+        process_options: &super::ImageProcessingOptions,
+    ) -> Result<PoseLandmarkResults, Error> {
+        let (img_w, img_h) = input.image_size();
+        let pose_detection_result = self
+            .pose_detector_session
+            .detect_with_options(input, process_options)?;
+        let mut pose_landmark_results = Vec::with_capacity(pose_detection_result.detections.len());
+
+        for d in pose_detection_result.detections.iter() {
+            // get roi
+            let pose_rect = NormalizedRect::from_alignment_points(
+                d,
+                Self::ALIGNMENT_POINTS_OPTION,
+                img_w,
+                img_h,
+            )
+            .transform(img_w, img_h, 1.25, 1.25, 0.0, 0.0, None, true);
+
+            // image to tensor
+            input.to_tensor(
+                self.image_to_tensor_info,
+                &super::ImageProcessingOptions::from_normalized_rect(&pose_rect),
+                &mut self.input_buffer,
+            )?;
+
+            // set input and compute
+            self.execution_ctx.set_input(
+                0,
+                self.pose_landmarker.input_tensor_type,
+                self.input_tensor_shape,
+                self.input_buffer.as_slice(),
+            )?;
+            self.execution_ctx.compute()?;
+
+            // check pose presence score
+            self.execution_ctx.get_output(
+                self.pose_landmarker.score_buf_index,
+                &mut self.score_of_pose_presence,
+            )?;
+            if self.score_of_pose_presence[0] < self.pose_landmarker.min_pose_presence_confidence()
+            {
+                continue;
+            }
+
+            // get landmarks
+            self.execution_ctx.get_output(
+                self.pose_landmarker.landmarks_buf_index,
+                self.tensors_to_landmarks.landmark_buffer(),
+            )?;
+            let mut pose_landmarks = self.tensors_to_landmarks.result(true);
+            self.execution_ctx.get_output(
+                self.pose_landmarker.world_landmarks_buf_index,
+                self.tensors_to_world_landmarks.landmark_buffer(),
+            )?;
+            let mut pose_world_landmarks = self.tensors_to_world_landmarks.result(false);
+            // world landmarks share the visibility and presence of landmarks
+            for (w, l) in pose_world_landmarks.iter_mut().zip(pose_landmarks.iter()) {
+                w.visibility = l.visibility;
+                w.presence = l.presence;
+            }
+
+            // do projection
+            crate::postprocess::projection_normalized_landmarks(
+                &mut pose_landmarks,
+                &pose_rect,
+                false,
+            );
+            crate::postprocess::projection_world_landmark(&mut pose_world_landmarks, &pose_rect);
+
+            // get segmentation mask
+            let segmentation_mask = match self.tensors_to_segmentation {
+                Some(ref mut tensors_to_segmentation) => {
+                    self.execution_ctx.get_output(
+                        self.pose_landmarker.segmentation_buf_index,
+                        tensors_to_segmentation.tenor_buffer(),
+                    )?;
+                    tensors_to_segmentation
+                        .confidence_masks()
+                        .first()
+                        .map(|roi_mask| {
+                            segmentation_mask::projection_segmentation_mask(
+                                roi_mask, &pose_rect, img_w, img_h,
+                            )
+                        })
+                }
+                None => None,
+            };
+
+            pose_landmark_results.push(PoseLandmarkResult {
+                pose_landmarks,
+                pose_world_landmarks,
+                segmentation_mask,
+            });
+        }
+
+        Ok(PoseLandmarkResults(pose_landmark_results))
+    }
+
+    /// Detect input video stream use this session.
+    /// Return a iterator for results, process input stream when poll next result.
+    #[inline(always)]
+    pub fn detect_for_video<InputVideoData: VideoData>(
+        &mut self,
+        video_data: InputVideoData,
+    ) -> Result<VideoResultsIter<'_, '_, Self, InputVideoData>, Error> {
+        Ok(VideoResultsIter::new(self, video_data))
+    }
+}
+
+impl<'model> super::TaskSession for PoseLandmarkerSession<'model> {
+    type Result = PoseLandmarkResults;
+
+    #[inline]
+    fn process_next(
+        &mut self,
+        process_options: &super::ImageProcessingOptions,
+        video_data: &mut impl VideoData,
+    ) -> Result<Option<Self::Result>, Error> {
+        // todo: video track optimize
+        if let Some(frame) = video_data.next_frame()? {
+            return self.detect_with_options(&frame, process_options).map(Some);
+        }
+        Ok(None)
+    }
+}
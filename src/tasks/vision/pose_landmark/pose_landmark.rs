@@ -0,0 +1,128 @@
+use std::fmt::{Display, Formatter};
+
+/// The 33 pose landmarks.
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone)]
+#[repr(C)]
+pub enum PoseLandmark {
+    Nose = 0,
+    LeftEyeInner = 1,
+    LeftEye = 2,
+    LeftEyeOuter = 3,
+    RightEyeInner = 4,
+    RightEye = 5,
+    RightEyeOuter = 6,
+    LeftEar = 7,
+    RightEar = 8,
+    MouthLeft = 9,
+    MouthRight = 10,
+    LeftShoulder = 11,
+    RightShoulder = 12,
+    LeftElbow = 13,
+    RightElbow = 14,
+    LeftWrist = 15,
+    RightWrist = 16,
+    LeftPinky = 17,
+    RightPinky = 18,
+    LeftIndex = 19,
+    RightIndex = 20,
+    LeftThumb = 21,
+    RightThumb = 22,
+    LeftHip = 23,
+    RightHip = 24,
+    LeftKnee = 25,
+    RightKnee = 26,
+    LeftAnkle = 27,
+    RightAnkle = 28,
+    LeftHeel = 29,
+    RightHeel = 30,
+    LeftFootIndex = 31,
+    RightFootIndex = 32,
+}
+
+impl PoseLandmark {
+    pub const NAMES: &'static [&'static str] = &[
+        "NOSE",
+        "LEFT_EYE_INNER",
+        "LEFT_EYE",
+        "LEFT_EYE_OUTER",
+        "RIGHT_EYE_INNER",
+        "RIGHT_EYE",
+        "RIGHT_EYE_OUTER",
+        "LEFT_EAR",
+        "RIGHT_EAR",
+        "MOUTH_LEFT",
+        "MOUTH_RIGHT",
+        "LEFT_SHOULDER",
+        "RIGHT_SHOULDER",
+        "LEFT_ELBOW",
+        "RIGHT_ELBOW",
+        "LEFT_WRIST",
+        "RIGHT_WRIST",
+        "LEFT_PINKY",
+        "RIGHT_PINKY",
+        "LEFT_INDEX",
+        "RIGHT_INDEX",
+        "LEFT_THUMB",
+        "RIGHT_THUMB",
+        "LEFT_HIP",
+        "RIGHT_HIP",
+        "LEFT_KNEE",
+        "RIGHT_KNEE",
+        "LEFT_ANKLE",
+        "RIGHT_ANKLE",
+        "LEFT_HEEL",
+        "RIGHT_HEEL",
+        "LEFT_FOOT_INDEX",
+        "RIGHT_FOOT_INDEX",
+    ];
+
+    // reference: https://developers.google.com/mediapipe/solutions/vision/pose_landmarker
+    pub const CONNECTIONS: &'static [(usize, usize)] = &[
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 7),
+        (0, 4),
+        (4, 5),
+        (5, 6),
+        (6, 8),
+        (9, 10),
+        (11, 12),
+        (11, 13),
+        (13, 15),
+        (15, 17),
+        (15, 19),
+        (15, 21),
+        (17, 19),
+        (12, 14),
+        (14, 16),
+        (16, 18),
+        (16, 20),
+        (16, 22),
+        (18, 20),
+        (11, 23),
+        (12, 24),
+        (23, 24),
+        (23, 25),
+        (24, 26),
+        (25, 27),
+        (26, 28),
+        (27, 29),
+        (28, 30),
+        (29, 31),
+        (30, 32),
+        (27, 31),
+        (28, 32),
+    ];
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        Self::NAMES[(*self) as u32 as usize]
+    }
+}
+
+impl Display for PoseLandmark {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::postprocess::{ImageConfidenceMask, NormalizedRect};
+use crate::preprocess::vision::ImageTransform;
+use crate::tasks::vision::ImageProcessingOptions;
+use image::imageops;
+
+/// Project the segmentation mask of the region-of-interest back to the full image.
+///
+/// The mapping is the inverse of the region-of-interest image to tensor, the pixels outside the
+/// region-of-interest are set to 0.
+pub(super) fn projection_segmentation_mask(
+    roi_mask: &ImageConfidenceMask,
+    normalized_rect: &NormalizedRect,
+    img_w: u32,
+    img_h: u32,
+) -> ImageConfidenceMask {
+    // the same transform as the region-of-interest image to tensor
+    let transform = ImageTransform::new(
+        (img_w, img_h),
+        roi_mask.dimensions(),
+        &ImageProcessingOptions::from_normalized_rect(normalized_rect),
+    );
+    let mut res = ImageConfidenceMask::new(img_w, img_h);
+    for (x, y, p) in res.enumerate_pixels_mut() {
+        let (u, v) = transform.to_tensor(
+            (x as f32 + 0.5) / img_w as f32,
+            (y as f32 + 0.5) / img_h as f32,
+        );
+        if let Some(s) = imageops::sample_bilinear(roi_mask, u, v) {
+            *p = s;
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_projection_segmentation_mask() {
+        // left half of the mask is 1
+        let mut roi_mask = ImageConfidenceMask::new(4, 4);
+        for (x, _, p) in roi_mask.enumerate_pixels_mut() {
+            p.0[0] = if x < 2 { 1. } else { 0. };
+        }
+
+        // the roi is the right half of the image
+        let rect = NormalizedRect {
+            x_center: 0.75,
+            y_center: 0.5,
+            width: 0.5,
+            height: 1.,
+            rotation: None,
+            rect_id: None,
+        };
+        let mask = projection_segmentation_mask(&roi_mask, &rect, 8, 4);
+        assert_eq!(mask.dimensions(), (8, 4));
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(mask.get_pixel(x, y).0[0], 0.);
+            }
+            assert_eq!(mask.get_pixel(4, y).0[0], 1.);
+            assert_eq!(mask.get_pixel(7, y).0[0], 0.);
+        }
+
+        // rotate the roi 90 degrees clockwise, the left half of mask is the top half of roi
+        let rect = NormalizedRect {
+            x_center: 0.5,
+            y_center: 0.5,
+            width: 1.,
+            height: 1.,
+            rotation: Some(std::f32::consts::FRAC_PI_2),
+            rect_id: None,
+        };
+        let mask = projection_segmentation_mask(&roi_mask, &rect, 4, 4);
+        for x in 0..4 {
+            assert!((mask.get_pixel(x, 0).0[0] - 1.).abs() < 1e-4);
+            assert!(mask.get_pixel(x, 3).0[0].abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use mediapipe_rs::preprocess::audio::SymphoniaAudioData;
+use mediapipe_rs::tasks::audio::AudioEmbedderBuilder;
+use symphonia::core::io::MediaSourceStream;
+
+const MODEL_1: &'static str = "assets/models/audio_embedding/yamnet_embedder.tflite";
+
+const AUDIO_PATH: &'static str = "assets/testdata/audio/speech_16000_hz_mono.wav";
+
+fn read_audio(audio_path: &str) -> SymphoniaAudioData {
+    let file = std::fs::File::open(audio_path).unwrap();
+    let probed = symphonia::default::get_probe()
+        .format(
+            &Default::default(),
+            MediaSourceStream::new(Box::new(file), Default::default()),
+            &Default::default(),
+            &Default::default(),
+        )
+        .unwrap();
+    let codec_params = &probed.format.default_track().unwrap().codec_params;
+    let decoder = symphonia::default::get_codecs()
+        .make(codec_params, &Default::default())
+        .unwrap();
+    SymphoniaAudioData::new(probed.format, decoder)
+}
+
+#[test]
+fn test_audio_embedding() {
+    let audio_embedder = AudioEmbedderBuilder::new()
+        .cpu()
+        .l2_normalize(true)
+        .build_from_file(MODEL_1)
+        .unwrap();
+    let embedding_results = audio_embedder.embed(read_audio(AUDIO_PATH)).unwrap();
+    assert_eq!(embedding_results.len(), 5);
+
+    let mut last_timestamp_ms = None;
+    for r in embedding_results.iter() {
+        assert_eq!(r.embeddings.len(), 1);
+        assert_ne!(r.embeddings[0].float_embedding.len(), 0);
+        assert!(r.timestamp_ms.is_some());
+        assert!(r.timestamp_ms > last_timestamp_ms);
+        last_timestamp_ms = r.timestamp_ms;
+    }
+
+    // the same audio window has the same embedding
+    let mut session = audio_embedder.new_session().unwrap();
+    let first = session
+        .embed(read_audio(AUDIO_PATH))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    let similarity = first.embeddings[0]
+        .cosine_similarity(&embedding_results[0].embeddings[0])
+        .unwrap();
+    assert!((similarity - 1.).abs() < 1e-4);
+}
@@ -1,5 +1,6 @@
 use mediapipe_rs::postprocess::utils::DrawLandmarksOptions;
 use mediapipe_rs::tasks::vision::results::FaceLandmarkResults;
+use mediapipe_rs::tasks::vision::FaceLandmarkBlendshapes;
 use mediapipe_rs::tasks::vision::FaceLandmarkConnections;
 use mediapipe_rs::tasks::vision::FaceLandmarkerBuilder;
 
@@ -41,6 +42,59 @@ fn test_face_landmark() {
     }
 }
 
+#[test]
+fn test_face_blendshapes() {
+    let img = image::open(FACE_1).unwrap();
+    let face_landmark_results = FaceLandmarkerBuilder::new()
+        .cpu()
+        .num_faces(1)
+        .output_face_blendshapes(true)
+        .build_from_file(MODEL_PATH)
+        .unwrap()
+        .detect(&img)
+        .unwrap();
+
+    assert_eq!(face_landmark_results.len(), 1);
+    let r = &face_landmark_results[0];
+    assert_eq!(r.face_landmarks.len(), 478);
+    let blendshapes = r.face_blendshapes.as_ref().unwrap();
+    assert_eq!(
+        blendshapes.categories.len(),
+        FaceLandmarkBlendshapes::NAMES.len()
+    );
+    let jaw_open = &blendshapes.categories[FaceLandmarkBlendshapes::JawOpen as usize];
+    assert_eq!(
+        jaw_open.category_name.as_deref(),
+        Some(FaceLandmarkBlendshapes::JawOpen.name())
+    );
+    eprintln!("{}", face_landmark_results);
+}
+
+#[test]
+fn test_facial_transformation_matrix() {
+    let img = image::open(FACE_1).unwrap();
+    let face_landmark_results = FaceLandmarkerBuilder::new()
+        .cpu()
+        .num_faces(1)
+        .output_facial_transformation_matrixes(true)
+        .build_from_file(MODEL_PATH)
+        .unwrap()
+        .detect(&img)
+        .unwrap();
+
+    assert_eq!(face_landmark_results.len(), 1);
+    let r = &face_landmark_results[0];
+    let matrix = r.facial_transformation_matrix.unwrap();
+    assert_eq!(matrix[3], [0., 0., 0., 1.]);
+    // the face is in front of the camera
+    assert!(matrix[2][3] < 0.);
+    let mesh = r.face_mesh.as_ref().unwrap();
+    assert_eq!(mesh.vertices.len(), 468);
+    assert_eq!(mesh.texture_coords.len(), mesh.vertices.len());
+    assert_eq!(mesh.indices.len() % 3, 0);
+    eprintln!("{}", face_landmark_results);
+}
+
 fn draw_face_landmarks(
     mut img: image::DynamicImage,
     face_landmark_results: FaceLandmarkResults,
@@ -0,0 +1,40 @@
+use mediapipe_rs::tasks::vision::FaceStylizerBuilder;
+
+const MODEL_PATH: &'static str = "assets/models/face_stylization/face_stylizer_color_sketch.task";
+const FACE_1: &'static str = "assets/testdata/img/face.jpg";
+
+#[test]
+fn test_face_stylization() {
+    let img = image::open(FACE_1).unwrap();
+    let face_stylizer = FaceStylizerBuilder::new()
+        .cpu()
+        .build_from_file(MODEL_PATH)
+        .unwrap();
+    let result = face_stylizer.stylize(&img).unwrap().unwrap();
+    assert!(result.stylized_image.width() > 0);
+    assert!(result.stylized_image.height() > 0);
+
+    let mut out_img = img.to_rgb8();
+    result.paste_to(&mut out_img);
+    assert_eq!(out_img.dimensions(), (img.width(), img.height()));
+
+    let draw = false;
+    if draw {
+        result
+            .stylized_image
+            .save("./target/face_stylized.jpg")
+            .unwrap();
+        out_img.save("./target/face_stylized_pasted.jpg").unwrap();
+    }
+}
+
+#[test]
+fn test_face_stylization_no_face() {
+    let img = image::RgbImage::new(256, 256);
+    let result = FaceStylizerBuilder::new()
+        .build_from_file(MODEL_PATH)
+        .unwrap()
+        .stylize(&img)
+        .unwrap();
+    assert!(result.is_none());
+}
@@ -0,0 +1,40 @@
+use mediapipe_rs::tasks::vision::HolisticLandmarkerBuilder;
+
+const MODEL_PATH: &'static str = "assets/models/holistic_landmark/holistic_landmarker.task";
+const POSE_1: &'static str = "assets/testdata/img/pose.jpg";
+
+#[test]
+fn test_holistic_landmark() {
+    let img = image::open(POSE_1).unwrap();
+    let holistic_landmark_results = HolisticLandmarkerBuilder::new()
+        .cpu()
+        .output_face_blendshapes(true)
+        .output_pose_segmentation_masks(true)
+        .build_from_file(MODEL_PATH)
+        .unwrap()
+        .detect(&img)
+        .unwrap();
+    assert_eq!(holistic_landmark_results.len(), 1);
+    let r = &holistic_landmark_results[0];
+    assert_eq!(r.pose_landmarks.len(), 33);
+    assert!(r.pose_segmentation_mask.is_some());
+    let face_landmarks = r.face_landmarks.as_ref().unwrap();
+    assert!(face_landmarks.len() >= 468);
+    assert!(r.face_blendshapes.is_some());
+    for hand in [&r.left_hand_landmarks, &r.right_hand_landmarks]
+        .into_iter()
+        .flatten()
+    {
+        assert_eq!(hand.len(), 21);
+    }
+    eprintln!("{}", holistic_landmark_results);
+
+    let draw = false;
+    if draw {
+        let mut img = img;
+        for r in holistic_landmark_results.iter() {
+            r.draw(&mut img);
+        }
+        img.save("./target/holistic_landmark_test.jpg").unwrap();
+    }
+}
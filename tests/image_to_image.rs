@@ -0,0 +1,30 @@
+use mediapipe_rs::tasks::vision::ImageToImageBuilder;
+
+const MODEL_PATH: &'static str = "assets/models/face_stylization/face_stylizer.tflite";
+const FACE_1: &'static str = "assets/testdata/img/face.jpg";
+
+#[test]
+fn test_image_to_image() {
+    let img = image::open(FACE_1).unwrap();
+    let image_to_image = ImageToImageBuilder::new()
+        .output_float_range(-1., 1.)
+        .build_from_file(MODEL_PATH)
+        .unwrap();
+    let mut session = image_to_image.new_session().unwrap();
+    let out_img = session.transform(&img).unwrap();
+    assert!(out_img.width() > 0);
+    assert!(out_img.height() > 0);
+
+    let draw = false;
+    if draw {
+        out_img.save("./target/image_to_image.jpg").unwrap();
+    }
+}
+
+#[test]
+fn test_image_to_image_options_check() {
+    assert!(ImageToImageBuilder::new()
+        .output_float_range(1., 1.)
+        .build_from_file(MODEL_PATH)
+        .is_err());
+}
@@ -0,0 +1,65 @@
+use mediapipe_rs::postprocess::NormalizedKeypoint;
+use mediapipe_rs::tasks::vision::{InteractiveSegmenterBuilder, RegionOfInterest};
+
+const MODEL_PATH: &'static str = "assets/models/interactive_segmentation/magic_touch.tflite";
+const IMG_1: &'static str = "assets/testdata/img/cat_and_dog.jpg";
+
+fn key_point(x: f32, y: f32) -> NormalizedKeypoint {
+    NormalizedKeypoint {
+        x,
+        y,
+        label: None,
+        score: None,
+    }
+}
+
+#[test]
+fn test_interactive_segmentation_key_point() {
+    let img = image::open(IMG_1).unwrap();
+    let segmentation_res = InteractiveSegmenterBuilder::new()
+        .output_confidence_masks(true)
+        .output_category_mask(true)
+        .build_from_file(MODEL_PATH)
+        .unwrap()
+        .segment(&img, &RegionOfInterest::KeyPoint(key_point(0.44, 0.7)))
+        .unwrap();
+
+    let category_mask = segmentation_res.category_mask.as_ref().unwrap();
+    assert_eq!(category_mask.width(), img.width());
+    assert_eq!(category_mask.height(), img.height());
+    // the pixel under the key point belongs to the object of interest
+    let (x, y) = (
+        (img.width() as f32 * 0.44) as u32,
+        (img.height() as f32 * 0.7) as u32,
+    );
+    assert_eq!(category_mask.get_pixel(x, y).0[0], 1);
+
+    let confidence_masks = segmentation_res.confidence_masks.as_ref().unwrap();
+    assert_eq!(confidence_masks.len(), 1);
+    assert_eq!(confidence_masks[0].width(), img.width());
+    assert_eq!(confidence_masks[0].height(), img.height());
+}
+
+#[test]
+fn test_interactive_segmentation_scribble() {
+    let img = image::open(IMG_1).unwrap();
+    let segmenter = InteractiveSegmenterBuilder::new()
+        .build_from_file(MODEL_PATH)
+        .unwrap();
+    let mut session = segmenter.new_session().unwrap();
+    let segmentation_res = session
+        .segment(
+            &img,
+            &vec![
+                key_point(0.4, 0.6),
+                key_point(0.44, 0.7),
+                key_point(0.5, 0.75),
+            ]
+            .into(),
+        )
+        .unwrap();
+    assert!(segmentation_res.confidence_masks.is_none());
+    let category_mask = segmentation_res.category_mask.as_ref().unwrap();
+    assert_eq!(category_mask.width(), img.width());
+    assert_eq!(category_mask.height(), img.height());
+}
@@ -0,0 +1,50 @@
+use mediapipe_rs::tasks::text::LanguageDetectorBuilder;
+
+const MODEL_PATH: &'static str = "assets/models/language_detection/language_detector.tflite";
+
+#[test]
+fn test_language_detection() {
+    let detector = LanguageDetectorBuilder::new()
+        .max_results(1)
+        .build_from_file(MODEL_PATH)
+        .unwrap();
+    let mut session = detector.new_session().unwrap();
+
+    let texts = [
+        ("To be, or not to be, that is the question", "en"),
+        (
+            "Il y a beaucoup de bouches qui parlent et fort peu de têtes qui pensent.",
+            "fr",
+        ),
+        ("分久必合合久必分", "zh"),
+    ];
+    for (text, language_code) in texts {
+        let result = session.detect(&text).unwrap();
+        eprintln!("`{}` --- {}", text, result);
+        assert_eq!(result.languages.len(), 1);
+        assert_eq!(result.languages[0].language_code, language_code);
+        assert!(result.languages[0].probability > 0.5);
+    }
+}
+
+/// The tiny model takes a string tensor, and its output is a constant tensor: `[en: 0.1, fr: 0.7, zh: 0.2]`.
+#[cfg(feature = "native-tflite")]
+#[test]
+fn test_language_detection_native_backend() {
+    use mediapipe_rs::inference::TfLiteNativeBackend;
+
+    let detector = LanguageDetectorBuilder::new()
+        .max_results(2)
+        .backend(TfLiteNativeBackend)
+        .build_from_file("assets/testdata/language_detection/model.tflite")
+        .unwrap();
+    let mut session = detector.new_session().unwrap();
+    // the string tensor length changes with the input text
+    for text in ["", "hi", "To be, or not to be, that is the question"] {
+        let result = session.detect(&text).unwrap();
+        assert_eq!(result.languages.len(), 2);
+        assert_eq!(result.languages[0].language_code, "fr");
+        assert!((result.languages[0].probability - 0.7).abs() < 1e-6);
+        assert_eq!(result.languages[1].language_code, "zh");
+    }
+}
@@ -0,0 +1,41 @@
+use mediapipe_rs::inference::{GraphEncoding, TensorType};
+use mediapipe_rs::model::ModelInfo;
+
+const MODEL_1: &'static str = "assets/models/image_classification/efficientnet_lite0_uint8.tflite";
+const MODEL_2: &'static str = "assets/models/gesture_recognition/gesture_recognizer.task";
+
+#[test]
+fn test_model_info_tflite() {
+    let info = ModelInfo::from_file(MODEL_1).unwrap();
+    assert!(!info.is_bundle());
+    assert_eq!(info.model_backend(), Some(GraphEncoding::TensorflowLite));
+    assert_eq!(info.input_count(), 1);
+    assert_eq!(info.output_count(), 1);
+
+    let input = info.input(0).unwrap();
+    assert_eq!(input.tensor_type, TensorType::U8);
+    assert_eq!(input.shape, &[1, 224, 224, 3]);
+    let image_info = info.to_tensor_info(0).unwrap().try_to_image().unwrap();
+    assert_eq!(image_info.width(), 224);
+
+    let output = info.output(0).unwrap();
+    assert_eq!(output.shape, &[1, 1000]);
+    assert!(output.quantization_parameters.is_some());
+    assert_eq!(info.output_index(output.name), Some(0));
+
+    assert_eq!(info.associated_files(), vec!["labels.txt"]);
+    assert_eq!(info.output_labels(0, None).unwrap().len(), 1000);
+    assert!(info.output_labels(0, Some("zh")).is_none());
+}
+
+#[test]
+fn test_model_info_task_bundle() {
+    let info = ModelInfo::from_file(MODEL_2).unwrap();
+    assert!(info.is_bundle());
+    assert_eq!(info.model_backend(), None);
+    assert!(!info.associated_files().is_empty());
+    for (name, sub_model) in info.sub_models() {
+        eprintln!("{}: {:?}", name, sub_model.associated_files());
+        assert!(sub_model.is_bundle() || sub_model.input_count() > 0);
+    }
+}
@@ -0,0 +1,68 @@
+use mediapipe_rs::tasks::vision::PoseLandmarkerBuilder;
+
+const MODEL_PATH: &'static str = "assets/models/pose_landmark/pose_landmarker_lite.task";
+const POSE_1: &'static str = "assets/testdata/img/pose.jpg";
+
+#[test]
+fn test_pose_detection() {
+    let pose_landmarker = PoseLandmarkerBuilder::new()
+        .num_poses(1)
+        .build_from_file(MODEL_PATH)
+        .unwrap();
+    let pose_detector = pose_landmarker.subtask_pose_detector();
+    let img = image::open(POSE_1).unwrap();
+    let detection_result = pose_detector.detect(&img).unwrap();
+    assert_eq!(detection_result.detections.len(), 1);
+    eprintln!("{}", detection_result);
+}
+
+#[test]
+fn test_pose_landmark() {
+    let img = image::open(POSE_1).unwrap();
+    let pose_landmark_results = PoseLandmarkerBuilder::new()
+        .cpu()
+        .num_poses(1)
+        .output_segmentation_masks(true)
+        .build_from_file(MODEL_PATH)
+        .unwrap()
+        .detect(&img)
+        .unwrap();
+    assert_eq!(pose_landmark_results.len(), 1);
+    let r = &pose_landmark_results[0];
+    assert_eq!(r.pose_landmarks.len(), 33);
+    assert_eq!(r.pose_world_landmarks.len(), 33);
+    for l in r.pose_landmarks.iter() {
+        assert!(l.visibility.is_some());
+        assert!(l.presence.is_some());
+    }
+    let mask = r.segmentation_mask.as_ref().unwrap();
+    assert_eq!(mask.dimensions(), (img.width(), img.height()));
+    eprintln!("{}", pose_landmark_results);
+
+    let draw = false;
+    if draw {
+        draw_pose_landmarks(
+            img,
+            pose_landmark_results,
+            "./target/pose_landmark_test.jpg",
+        );
+    }
+}
+
+#[allow(unused)]
+fn draw_pose_landmarks(
+    mut img: image::DynamicImage,
+    pose_landmark_results: mediapipe_rs::tasks::vision::results::PoseLandmarkResults,
+    path: &str,
+) {
+    let options = mediapipe_rs::postprocess::utils::DrawLandmarksOptions::default()
+        .connections(mediapipe_rs::tasks::vision::PoseLandmark::CONNECTIONS);
+    for r in pose_landmark_results.iter() {
+        mediapipe_rs::postprocess::utils::draw_landmarks_with_options(
+            &mut img,
+            &r.pose_landmarks,
+            &options,
+        );
+    }
+    img.save(path).unwrap();
+}
@@ -0,0 +1,154 @@
+//! Golden tests of the task sessions, which run through the [`ReplayBackend`].
+//!
+//! The recorded directories are in `assets/testdata/replay`. Each directory has a small model with metadata but
+//! without operators, and the tensors recorded by [`mediapipe_rs::inference::RecordBackend`]. The output tensors
+//! were written by hand, so the expected results are known. The input tensors check the pre-process, and the
+//! results check the post-process (`TensorsToDetection`, `TensorsToLandmarks` and `TensorsToSegmentation`).
+
+use mediapipe_rs::inference::ReplayBackend;
+use mediapipe_rs::postprocess::{Landmark, Rect};
+use mediapipe_rs::tasks::vision::{
+    FaceDetectorBuilder, HandLandmarkerBuilder, ImageSegmenterBuilder,
+};
+
+const REPLAY_DIR: &str = "assets/testdata/replay";
+
+fn replay_backend(task: &str) -> ReplayBackend {
+    ReplayBackend::new(format!("{}/{}", REPLAY_DIR, task)).check_inputs(true)
+}
+
+fn model_path(task: &str, file: &str) -> String {
+    format!("{}/{}/{}", REPLAY_DIR, task, file)
+}
+
+fn test_image() -> image::RgbImage {
+    image::RgbImage::from_fn(32, 24, |x, y| {
+        image::Rgb([(x * 8) as u8, (y * 10) as u8, ((x + y) * 4) as u8])
+    })
+}
+
+fn assert_near(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-5, "`{}` is not near `{}`", a, b);
+}
+
+fn assert_rect_near(a: &Rect<f32>, b: [f32; 4]) {
+    assert_near(a.left, b[0]);
+    assert_near(a.top, b[1]);
+    assert_near(a.right, b[2]);
+    assert_near(a.bottom, b[3]);
+}
+
+fn assert_landmark_near(a: &Landmark, b: [f32; 3]) {
+    assert_near(a.x, b[0]);
+    assert_near(a.y, b[1]);
+    assert_near(a.z, b[2]);
+}
+
+#[test]
+fn test_face_detection_replay() {
+    let task = "face_detection";
+    let result = FaceDetectorBuilder::new()
+        .backend(replay_backend(task))
+        .build_from_file(model_path(task, "model.tflite"))
+        .unwrap()
+        .detect(&test_image())
+        .unwrap();
+    assert_eq!(result.detections.len(), 2);
+
+    let d = &result.detections[0];
+    assert_eq!(d.categories[0].category_name.as_deref(), Some("Face"));
+    assert_near(d.categories[0].score, 0.95257413);
+    assert_rect_near(&d.bounding_box, [0.0625, -0.03125, 0.5625, 0.46875]);
+    let key_points = d.key_points.as_ref().unwrap();
+    assert_eq!(key_points.len(), 6);
+    assert_near(key_points[0].x, 0.125);
+    assert_near(key_points[0].y, 0.125);
+    assert_near(key_points[3].x, 0.25);
+    assert_near(key_points[3].y, 0.4375);
+
+    let d = &result.detections[1];
+    assert_near(d.categories[0].score, 0.880797);
+    assert_rect_near(&d.bounding_box, [0.34375, 0.375, 0.71875, 0.6875]);
+    let key_points = d.key_points.as_ref().unwrap();
+    assert_near(key_points[5].x, 0.625);
+    assert_near(key_points[5].y, 0.5);
+}
+
+#[test]
+fn test_hand_landmark_replay() {
+    let task = "hand_landmark";
+    let results = HandLandmarkerBuilder::new()
+        .backend(replay_backend(task))
+        .build_from_file(model_path(task, "model.task"))
+        .unwrap()
+        .detect(&test_image())
+        .unwrap();
+    assert_eq!(results.len(), 1);
+
+    let r = &results[0];
+    assert_eq!(r.handedness.index, 0);
+    assert_eq!(r.handedness.category_name.as_deref(), Some("Left"));
+    assert_near(r.handedness.score, 0.8);
+    assert_eq!(r.hand_landmarks.len(), 21);
+    assert_landmark_near(&r.hand_landmarks[0], [0.2125, 0.875, 0.]);
+    assert_landmark_near(&r.hand_landmarks[10], [0.55, 0.5, 0.40625]);
+    assert_landmark_near(&r.hand_landmarks[20], [0.8875, 0.125, 0.8125]);
+    assert_eq!(r.hand_world_landmarks.len(), 21);
+    assert_landmark_near(&r.hand_world_landmarks[10], [0.1, -0.1, 0.05]);
+    assert_landmark_near(&r.hand_world_landmarks[20], [0.2, -0.2, 0.1]);
+}
+
+#[test]
+fn test_image_segmentation_replay() {
+    let task = "image_segmentation";
+    let result = ImageSegmenterBuilder::new()
+        .output_category_mask(true)
+        .output_confidence_masks(true)
+        .backend(replay_backend(task))
+        .build_from_file(model_path(task, "model.tflite"))
+        .unwrap()
+        .segment(&test_image())
+        .unwrap();
+    // the first column of the `object` category in every row
+    const OBJECT_START: [u32; 24] = [
+        20, 20, 20, 20, 20, 20, 20, 20, 19, 17, 16, 16, 16, 16, 15, 13, 12, 12, 12, 12, 12, 12, 12,
+        12,
+    ];
+    let category_mask = result.category_mask.unwrap();
+    assert_eq!(category_mask.dimensions(), (32, 24));
+    for (y, start) in OBJECT_START.iter().enumerate() {
+        for x in 0..32 {
+            let expect = if x < *start { 0 } else { 1 };
+            assert_eq!(category_mask.get_pixel(x, y as u32).0[0], expect);
+        }
+    }
+
+    let confidence_masks = result.confidence_masks.unwrap();
+    assert_eq!(confidence_masks.len(), 2);
+    for (x, y, background, object) in [
+        (0, 0, 1., 0.),
+        (21, 0, 0., 0.05),
+        (25, 0, 0., 0.85),
+        (15, 12, 0.05, 0.),
+        (16, 12, 0., 0.15),
+        (31, 23, 0., 1.),
+    ] {
+        assert_near(confidence_masks[0].get_pixel(x, y).0[0], background);
+        assert_near(confidence_masks[1].get_pixel(x, y).0[0], object);
+    }
+}
+
+#[test]
+fn test_replay_input_mismatch() {
+    let task = "face_detection";
+    let detector = FaceDetectorBuilder::new()
+        .backend(replay_backend(task))
+        .build_from_file(model_path(task, "model.tflite"))
+        .unwrap();
+    // a different image makes a different input tensor
+    let img = image::RgbImage::new(32, 24);
+    let err = detector.detect(&img).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("different from the recorded tensor"));
+}
@@ -10,8 +10,7 @@ fn test_text_embedding_model_1() {
 
 #[test]
 fn test_text_embedding_model_2() {
-    // todo: add universal_sentence_encoder input support.
-    // text_embedding_tasks_run(MODEL_2)
+    text_embedding_tasks_run(MODEL_2)
 }
 
 fn text_embedding_tasks_run(model_asset: &str) {
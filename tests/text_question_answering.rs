@@ -0,0 +1,50 @@
+use mediapipe_rs::tasks::text::TextQuestionAnswererBuilder;
+
+const MODEL_PATH: &'static str =
+    "assets/models/text_question_answering/mobilebert_with_metadata.tflite";
+
+const CONTEXT: &'static str =
+    "Nikola Tesla (7/10 July 1856 - 7 January 1943) was a Serbian-American \
+inventor, electrical engineer, mechanical engineer, physicist, and futurist best known for his \
+contributions to the design of the modern alternating current (AC) electricity supply system.";
+
+#[test]
+fn test_text_question_answering() {
+    let question_answerer = TextQuestionAnswererBuilder::new()
+        .max_results(3)
+        .build_from_file(MODEL_PATH)
+        .unwrap();
+    let result = question_answerer
+        .answer(CONTEXT, "When was Nikola Tesla born?")
+        .unwrap();
+    eprintln!("{}", result);
+
+    assert!(!result.answers.is_empty());
+    assert!(result.answers.len() <= 3);
+    let answer = &result.answers[0];
+    assert_eq!(&CONTEXT[answer.start..answer.end], answer.text);
+    assert!(answer.text.contains("1856"));
+    for w in result.answers.windows(2) {
+        assert!(w[0].score >= w[1].score);
+    }
+}
+
+#[test]
+fn test_text_question_answering_long_context() {
+    // make the context longer than the model input, the answer is in the last window
+    let filler = "This sentence is only used to make the context longer. ".repeat(40);
+    let context = format!("{}{}", filler, CONTEXT);
+    let question_answerer = TextQuestionAnswererBuilder::new()
+        .doc_stride(64)
+        .build_from_file(MODEL_PATH)
+        .unwrap();
+    let mut session = question_answerer.new_session().unwrap();
+    let result = session
+        .answer(&context, "When was Nikola Tesla born?")
+        .unwrap();
+    eprintln!("{}", result);
+
+    let answer = &result.answers[0];
+    assert!(answer.start >= filler.len());
+    assert!(answer.text.contains("1856"));
+}
@@ -0,0 +1,45 @@
+use mediapipe_rs::tasks::text::TokenClassifierBuilder;
+
+/// A tiny BERT NER model: the logits of every token are looked up from its vocab id.
+/// The labels are `O, B-PER, I-PER, B-LOC, I-LOC`, and the vocab is
+/// `ada: B-PER, love: I-PER, ##lace: O, new: B-LOC, york: I-LOC, zürich: B-LOC`, others are `O`.
+const MODEL_PATH: &'static str = "assets/testdata/token_classification/model.tflite";
+
+#[test]
+fn test_token_classification() {
+    let token_classifier = TokenClassifierBuilder::new()
+        .build_from_file(MODEL_PATH)
+        .unwrap();
+    assert_eq!(
+        token_classifier.labels(),
+        &["O", "B-PER", "I-PER", "B-LOC", "I-LOC"]
+    );
+
+    let text = "Ada Lovelace lives in New York, not Zürich";
+    let result = token_classifier.classify(text).unwrap();
+    eprintln!("{}", result);
+    let entities: Vec<(&str, &str, usize, usize)> = result
+        .entities
+        .iter()
+        .map(|e| (e.label.as_str(), e.text.as_str(), e.start, e.end))
+        .collect();
+    assert_eq!(
+        entities,
+        vec![
+            ("PER", "Ada Lovelace", 0, 12),
+            ("LOC", "New York", 22, 30),
+            ("LOC", "Zürich", 36, 43),
+        ]
+    );
+    // softmax of the logits `[4, 0, 0, 0, 0]`
+    let score = 4f32.exp() / (4f32.exp() + 4.);
+    for e in result.entities.iter() {
+        assert!((e.score - score).abs() < 1e-5);
+    }
+
+    // the text is truncated to the max sequence length
+    let result = token_classifier
+        .classify(&"Ada lives in New York. ".repeat(4))
+        .unwrap();
+    assert_eq!(result.entities.len(), 5);
+}